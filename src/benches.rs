@@ -0,0 +1,162 @@
+use crate::code::{build_connect, build_connection_alias, struct_fields};
+use crate::parser::{snake_case_identifier, ParsedTableMacro, FILE_SIGNATURE};
+use crate::{GeneratedStruct, GenerationConfig, Target};
+
+/// the benchmarks of a table's `read`, `paginate` and `create` functions, run on the table's first row (tables
+/// without rows are skipped)
+fn build_table_benches(table: &ParsedTableMacro, config: &GenerationConfig) -> String {
+    let table_options = config.table(&table.name);
+    let table_name = &table.name;
+    let struct_name = &table.struct_name;
+    let [(_, read_fields), (create_struct_name, create_fields), _] =
+        &struct_fields(table, config)[..]
+    else {
+        unreachable!("struct_fields returns the Read, Create and Update structs");
+    };
+
+    let mut benches = String::new();
+
+    // primary key columns are always part of Read structs
+    if !table.primary_key_columns.is_empty() {
+        let key_args = table
+            .primary_key_columns
+            .iter()
+            .filter_map(|pk| read_fields.iter().find(|f| &f.column_name == pk))
+            .map(|f| format!(", row.{}.clone()", f.name))
+            .collect::<String>();
+
+        benches.push_str(&format!(
+            r##"
+    c.bench_function("{table_name}/read", |b| {{
+        b.iter(|| {struct_name}::read(&mut db{key_args}).unwrap())
+    }});
+"##
+        ));
+    }
+
+    benches.push_str(&format!(
+        r##"
+    c.bench_function("{table_name}/paginate", |b| {{
+        b.iter(|| {struct_name}::paginate(&mut db, 0, 20, None).unwrap())
+    }});
+"##
+    ));
+
+    // created rows copy the first row (with a counter appended to strings, which are likely to be unique), and are
+    // rolled back; Create structs with fields the Read struct doesn't have can't be filled in
+    let create_values = create_fields
+        .iter()
+        .map(|f| {
+            let read_field = read_fields
+                .iter()
+                .find(|r| r.column_name == f.column_name)?;
+            let column = table.column(&f.column_name)?;
+            let value = match column.ty.as_str() {
+                "String" if f.base_type == "String" => {
+                    format!("format!(\"{{}} {{i}}\", row.{})", read_field.name)
+                }
+                "String" if f.base_type == "Option<String>" => format!(
+                    "row.{}.as_ref().map(|value| format!(\"{{value}} {{i}}\"))",
+                    read_field.name
+                ),
+                _ => format!("row.{}.clone()", read_field.name),
+            };
+
+            Some(format!("{}: {value}", f.name))
+        })
+        .collect::<Option<Vec<String>>>();
+    let is_writable = !table_options.get_materialized_view()
+        && table_options.generates_struct(GeneratedStruct::Create);
+    if let (true, Some(create_values)) = (is_writable, create_values) {
+        let create = match create_fields.is_empty() {
+            true => format!("{struct_name}::create(db)"),
+            false => format!(
+                "{struct_name}::create(db, &{create_struct_name} {{ {} }})",
+                create_values.join(", ")
+            ),
+        };
+        let (counter, count) = match create_values.iter().any(|v| v.contains("{i}")) {
+            true => ("    let mut i = 0;\n", "            i += 1;\n"),
+            false => ("", ""),
+        };
+
+        benches.push_str(&format!(
+            r##"
+{counter}    c.bench_function("{table_name}/create", |b| {{
+        b.iter(|| {{
+{count}            let _ = db.transaction::<(), diesel::result::Error, _>(|db| {{
+                {create}?;
+                Err(diesel::result::Error::RollbackTransaction)
+            }});
+        }})
+    }});
+"##
+        ));
+    }
+
+    format!(
+        r##"
+fn bench_{fn_name}(c: &mut Criterion) {{
+    let mut db = connect();
+    let Some(row) = {struct_name}::paginate(&mut db, 0, 1, None).unwrap().items.pop() else {{
+        eprintln!("skipping the `{table_name}` benchmarks: the table is empty");
+        return;
+    }};
+{benches}}}
+"##,
+        fn_name = snake_case_identifier(&table.name)
+    )
+}
+
+/// the criterion benchmarks (`benches/models.rs`) of a standalone models crate, for the diesel models of every table
+pub fn render(crate_name: &str, tables: &[ParsedTableMacro], config: &GenerationConfig) -> String {
+    let crate_name = crate_name.replace('-', "_");
+    let tables = tables
+        .iter()
+        .filter(|t| config.table(&t.name).get_target() == Target::Diesel)
+        .collect::<Vec<&ParsedTableMacro>>();
+
+    let imports = tables
+        .iter()
+        .map(|table| {
+            let module = match table.group(config) {
+                Some(group) => format!("{group}::{}", table.module_name(config)),
+                None => table.module_name(config),
+            };
+
+            format!("use {crate_name}::models::{module}::*;\n")
+        })
+        .collect::<String>();
+    let benches = tables
+        .iter()
+        .map(|table| build_table_benches(table, config))
+        .collect::<String>();
+    let targets = tables
+        .iter()
+        .map(|table| format!("bench_{}", snake_case_identifier(&table.name)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!(
+        r##"{FILE_SIGNATURE}
+
+//! Benchmarks of the generated `read`, `paginate` and `create` functions against the database at `DATABASE_URL`, run
+//! on the first row of each table (created rows are rolled back). Run them with `cargo bench`.
+
+use criterion::{{criterion_group, criterion_main, Criterion}};
+use diesel::Connection as _;
+{imports}
+{connection_alias}
+
+fn connect() -> Connection {{
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set to run the benchmarks");
+    {connect}
+}}
+{benches}
+criterion_group!(benches, {targets});
+criterion_main!(benches);
+"##,
+        connection_alias = build_connection_alias(config),
+        connect = build_connect(config)
+    )
+}
@@ -1,31 +1,469 @@
-use dsync::{GenerationConfig, TableOptions};
+use anyhow::{bail, Context};
+use dsync::{
+    BinaryFormat, DiagramFormat, FieldOrder, FileNaming, GeneratedStruct, GenerationConfig,
+    GenerationReport, ImportStyle, MultiConnection, ProtoConfig, SerdeDerives, SortDirection,
+    SqlFunction, StructNameCollisions, TableOptions, Target, TypeMapper, TypeMapping, TypeRule,
+    UuidVersion, WriteResult,
+};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use structopt::clap::Shell;
 use structopt::StructOpt;
 
 const DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
 
+// the command is parsed once, so the size of the generate arguments doesn't matter
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, StructOpt)]
+#[structopt(about = DESCRIPTION)]
+enum Command {
+    /// Generates the models (the default when dsync is run without a subcommand)
+    Generate(GenerateArgs),
+
+    /// Checks that the models of the schema can be generated and that the options only refer to tables and columns of
+    /// the schema, without writing anything
+    Check(CheckArgs),
+
+    /// Deletes the generated models of the tables which are no longer in the schema, without regenerating the others
+    Prune(PruneArgs),
+
+    /// Prints the changes of the schema since a snapshot (as kept by `generate --snapshot`), without updating it
+    Diff(DiffArgs),
+
+    /// Writes the parsed schema (tables, columns, types, keys, foreign keys and applied options) as JSON
+    ExportIr(ExportIrArgs),
+
+    /// Explains what a table option does, with an example of the code it changes (or lists the table options)
+    Explain(ExplainArgs),
+
+    /// Prints a completion script of the CLI for the given shell (for example: `dsync completions bash >
+    /// /etc/bash_completion.d/dsync`)
+    Completions(CompletionsArgs),
+}
+
+/// names of the subcommands, see [`args`]
+const SUBCOMMANDS: &[&str] = &[
+    "generate",
+    "check",
+    "prune",
+    "diff",
+    "export-ir",
+    "explain",
+    "completions",
+    "help",
+];
+
 #[allow(dead_code)]
 #[derive(Debug, StructOpt, Clone)]
-#[structopt(about = DESCRIPTION)]
-struct Args {
+struct GenerateArgs {
     /// Input file
     #[structopt(
         short = "i",
         long = "input",
-        help = "Required; rust file to read diesel schema information from",
-        required = true
+        help = "Required (unless `--import-ir` or `--models-input` is used); rust file to read diesel schema information from (`-` reads it from stdin, with `--stdout`)",
+        required_unless_one = &["import-ir", "models-input"]
     )]
-    input: PathBuf,
+    input: Option<PathBuf>,
+
+    #[structopt(
+        long = "import-ir",
+        parse(from_os_str),
+        conflicts_with = "input",
+        help = "Optional; generates the models from a schema IR document (as written by `--export-ir`, or hand-written) instead of a diesel schema file"
+    )]
+    import_ir: Option<PathBuf>,
+
+    #[structopt(
+        long = "models-input",
+        parse(from_os_str),
+        conflicts_with_all = &["input", "import-ir"],
+        help = "Optional; reverse mode: reads model structs annotated with `#[diesel(table_name = ...)]` (a rust file, or a directory of them) and writes the diesel schema they describe to the `--output` file"
+    )]
+    models_input: Option<PathBuf>,
+
+    #[structopt(
+        long = "migration",
+        parse(from_os_str),
+        requires = "models-input",
+        help = "Optional; with `--models-input`, also writes a postgres migration creating the tables (`up.sql`) and dropping them (`down.sql`) to the given directory"
+    )]
+    migration: Option<PathBuf>,
 
     /// Output file, stdout if not present
     #[structopt(
         parse(from_os_str),
         short = "o",
         long = "output",
-        help = "Required; directory to write generated code to"
+        required_unless = "stdout",
+        help = "Required (unless `--stdout` is used); directory to write generated code to (or the schema file to write with `--models-input`)"
     )]
-    output: PathBuf,
+    output: Option<PathBuf>,
+
+    #[structopt(
+        long = "table",
+        requires = "stdout",
+        help = "Optional; with `--stdout`, the table whose generated code is printed"
+    )]
+    table: Option<String>,
+
+    #[structopt(
+        long = "stdout",
+        requires_all = &["table", "input"],
+        help = "Optional; prints the generated code of the `--table` to stdout instead of writing any files (for quick inspection and editor integrations)"
+    )]
+    stdout: bool,
+
+    #[structopt(
+        long = "strict",
+        requires = "input",
+        help = "Optional; fails if the options refer to tables or columns which aren't in the schema (for example: an `-g` column which no table has)"
+    )]
+    strict: bool,
+
+    #[structopt(
+        long = "deny-warnings",
+        help = "Optional; fails if the generation reports warnings (like tables without primary keys or foreign keys to tables which aren't generated), for CI"
+    )]
+    deny_warnings: bool,
+
+    #[structopt(
+        long = "crate-name",
+        requires = "input",
+        help = "Optional; generates a standalone crate with this name in the output directory (Cargo.toml, src/lib.rs, src/schema.rs and src/models) instead of just the models"
+    )]
+    crate_name: Option<String>,
+
+    #[structopt(
+        long = "generate-benches",
+        requires = "crate-name",
+        help = "Optional; also generates criterion benchmarks of the `read`, `paginate` and `create` functions of every table in `benches/models.rs` of the `--crate-name` crate, which run against the database at `DATABASE_URL`"
+    )]
+    generate_benches: bool,
+
+    #[structopt(
+        long = "test-support",
+        requires = "crate-name",
+        help = "Optional; also generates integration tests of the models in `tests/models.rs` of the `--crate-name` crate, with a `tests/support` module running them against a postgres testcontainer migrated with the migrations in this directory (relative to the crate, for example: `../migrations`)"
+    )]
+    test_support: Option<String>,
+
+    #[structopt(
+        long = "diagram",
+        requires = "input",
+        number_of_values = 2,
+        value_names = &["mermaid|dot", "path"],
+        help = "Optional; also writes an entity-relationship diagram of the schema (tables, columns and foreign keys) in the given format (`mermaid` or `dot`) to the given path"
+    )]
+    diagram: Option<Vec<String>>,
+
+    #[structopt(
+        long = "docs",
+        requires = "input",
+        parse(from_os_str),
+        help = "Optional; also writes markdown documentation of the schema (an index and a page per table with its columns, types, nullability, keys and relations) to the given directory"
+    )]
+    docs: Option<PathBuf>,
+
+    #[structopt(
+        long = "export-ir",
+        requires = "input",
+        parse(from_os_str),
+        help = "Optional; also writes the parsed schema (tables, columns, types, keys, foreign keys and applied options) as JSON to the given path"
+    )]
+    export_ir: Option<PathBuf>,
+
+    #[structopt(
+        long = "proto",
+        parse(from_os_str),
+        requires = "input",
+        help = "Optional; also writes protobuf messages mirroring the generated Read/Create/Update structs to the given `.proto` file"
+    )]
+    proto: Option<PathBuf>,
+
+    #[structopt(
+        long = "proto-package",
+        default_value = "models",
+        help = "Optional; protobuf package of the messages written with `--proto`"
+    )]
+    proto_package: String,
+
+    #[structopt(
+        long = "proto-type",
+        parse(try_from_str = parse_proto_type),
+        help = "Optional; list of protobuf types overriding the default mapping for `--proto`, as `<sql type>=<protobuf type>` (for example: `Int8=sint64`)"
+    )]
+    proto_types: Option<Vec<(String, String)>>,
+
+    #[structopt(
+        long = "zod",
+        parse(from_os_str),
+        requires = "input",
+        help = "Optional; also writes zod schemas for the generated Create/Update structs to the given typescript file"
+    )]
+    zod: Option<PathBuf>,
+
+    #[structopt(
+        long = "openapi",
+        parse(from_os_str),
+        requires = "input",
+        help = "Optional; also writes an OpenAPI document describing CRUD endpoints over the generated structs to the given yaml file"
+    )]
+    openapi: Option<PathBuf>,
+
+    #[structopt(
+        long = "snapshot",
+        parse(from_os_str),
+        requires = "input",
+        help = "Optional; keeps a snapshot of the parsed schema (as JSON) at the given path, updated on every run"
+    )]
+    snapshot: Option<PathBuf>,
+
+    #[structopt(
+        long = "migrations-dir",
+        parse(from_os_str),
+        requires = "snapshot",
+        help = "Optional; when the schema differs from the `--snapshot`, writes a diesel migration scaffold (up/down SQL adding, altering and dropping tables and columns) to a new directory in the given migrations directory"
+    )]
+    migrations_dir: Option<PathBuf>,
+
+    #[structopt(
+        long = "changelog",
+        parse(from_os_str),
+        requires = "snapshot",
+        help = "Optional; when the schema differs from the `--snapshot`, adds the changes to the top of the given markdown file (under the current date)"
+    )]
+    changelog: Option<PathBuf>,
+
+    #[structopt(
+        long = "timings",
+        help = "Optional; prints how long parsing, rendering and writing took to stderr, along with how long rendering each table took (slowest first)"
+    )]
+    timings: bool,
+
+    #[structopt(
+        long = "cache",
+        parse(from_os_str),
+        help = "Optional; keeps hashes of every table's definition, options and generated code in this file (for example: `.dsync-cache.json`), so later runs skip rendering the tables which didn't change since"
+    )]
+    cache: Option<PathBuf>,
+
+    #[structopt(
+        long = "report",
+        possible_values = &["json"],
+        help = "Optional; prints a machine-readable summary of the run (tables parsed, files written/updated/skipped/pruned, warnings and timing) to stdout"
+    )]
+    report: Option<String>,
+
+    #[structopt(flatten)]
+    config: ConfigArgs,
+}
+
+/// The options of the generated models, shared by the subcommands
+#[allow(dead_code)]
+#[derive(Debug, StructOpt, Clone)]
+struct ConfigArgs {
+    #[structopt(
+        short = "v",
+        long = "verbose",
+        parse(from_occurrences),
+        help = "Optional; logs what the generator does to stderr: the parsed tables with `-v`, and also the options applied to them and why structs derive or functions were left out with `-vv`"
+    )]
+    verbose: u8,
+
+    #[structopt(
+        long = "schema-module",
+        help = "Optional; module containing the diesel schema, for example `my_db::schema` (defaults to `crate::schema`)"
+    )]
+    schema_module: Option<String>,
+
+    #[structopt(
+        long = "models-module",
+        help = "Optional; module containing the generated models, used to import the structs of referenced tables (defaults to `crate::models`)"
+    )]
+    models_module: Option<String>,
+
+    #[structopt(
+        long = "file-attribute",
+        help = "Optional; inner attributes (without `#![...]`) added to the top of every generated file, for example: `allow(clippy::all)`"
+    )]
+    file_attributes: Option<Vec<String>>,
+
+    #[structopt(
+        long = "no-identifiable",
+        help = "Optional; stops deriving `Identifiable` on read structs (by default it's derived for every table with a primary key)"
+    )]
+    no_identifiable: bool,
+
+    #[structopt(
+        long = "dangerous-helpers",
+        help = "Optional; generates `delete_all` (and `truncate` for postgres connections) functions which wipe whole tables"
+    )]
+    dangerous_helpers: bool,
+
+    #[structopt(
+        long = "save",
+        help = "Optional; generates `save` functions on Read structs (`todo.save(db)`), writing every field of the row back with diesel's `save_changes`, as an alternative to `update` with Update structs"
+    )]
+    save: bool,
+
+    #[structopt(
+        long = "async-wrappers",
+        help = "Optional; generates async wrappers of the CRUD functions (`create_async`, `read_async`, `paginate_async`, `update_async` and `delete_async`) taking an r2d2 pool, which run the blocking calls on a pooled connection with `tokio::task::spawn_blocking`"
+    )]
+    async_wrappers: bool,
+
+    #[structopt(
+        long = "services",
+        help = "Optional; generates a service struct per table (for example: `TodoService::new(pool)`) owning an r2d2 pool, with async `create`, `read`, `paginate`, `update` and `delete` methods delegating to the async wrappers (implies `--async-wrappers`)"
+    )]
+    services: bool,
+
+    #[structopt(
+        long = "mocks",
+        help = "Optional; generates a repository trait per table (for example: `TodoRepository`) implemented by the service, from which mockall generates `MockTodoRepository` behind the `mock` cargo feature, with `reading(row)` and `not_found()` constructors for common expectations (implies `--services`)"
+    )]
+    mocks: bool,
+
+    #[structopt(
+        long = "proptest",
+        help = "Optional; generates proptest strategies (for example: `CreateTodo::strategy()`) and `Arbitrary` impls for the Create and Update structs behind the `proptest` cargo feature, generating values which fit the columns' types and max lengths"
+    )]
+    proptest: bool,
+
+    #[structopt(
+        long = "form-structs",
+        help = "Optional; generates Form structs (for example: `TodoForm`) with required primary key fields and optional fields for the other columns which aren't autogenerated, converting into the Create struct (`TryFrom`) and the Update struct (`From`), for HTML forms and `PATCH` requests"
+    )]
+    form_structs: bool,
+
+    #[structopt(
+        long = "import-export",
+        help = "Optional; generates `export_csv(db, writer)` and `export_json_lines(db, writer)` functions dumping whole tables, and `import_csv(db, reader)` and `import_json_lines(db, reader)` functions loading rows through the Create structs (the models crate needs `csv` and `serde_json`)"
+    )]
+    import_export: bool,
+
+    #[structopt(
+        long = "seed",
+        help = "Optional; generates `seed.rs` next to the models, with a `seed(db, dir)` function inserting the JSON fixture files in `dir` (`<table>.json`, an array of the table's Create structs) in foreign key order"
+    )]
+    seed: bool,
+
+    #[structopt(
+        long = "file-naming",
+        possible_values = &["table", "singular", "pascal"],
+        help = "Optional; how the directories (and modules) of the generated models are named: `table` (the table name, the default), `singular` (for example: `todo_item` for `todo_items`) or `pascal` (for example: `TodoItem`)"
+    )]
+    file_naming: Option<FileNaming>,
+
+    #[structopt(
+        long = "structs",
+        help = "Optional; list of the structs generated for tables as `<table>=<struct>,<struct>,...` with `read`, `create`, `update` and `form` structs (for example: `events=read,create` for an append-only table); left out Create and Update structs take the functions using them along, and the Read struct is always generated"
+    )]
+    structs: Option<Vec<String>>,
+
+    #[structopt(
+        long = "derives",
+        help = "Optional; list of derives forced or suppressed (with a `no-` prefix) per table as `<table>=<derive>,<derive>,...` with `identifiable`, `associations` and `aschangeset` derives (for example: `todos=no-associations,aschangeset`), overriding the defaults for tables where they don't compile"
+    )]
+    derives: Option<Vec<String>>,
+
+    #[structopt(
+        long = "legacy-derives",
+        help = "Optional; derives `Queryable`, `Insertable` and `AsChangeset` on every struct like older versions did, instead of `Queryable` on Read structs, `Insertable` on Create structs and `AsChangeset` on Update structs only"
+    )]
+    legacy_derives: bool,
+
+    #[structopt(
+        long = "group",
+        help = "Optional; list of groups as `<group>=<table>,<table>,...` (for example: `billing=invoices,payments`), whose models are written to a subdirectory (and module) of the output directory named after the group, for example to group the models of a domain or (postgres) schema"
+    )]
+    groups: Option<Vec<String>>,
+
+    #[structopt(
+        long = "materialized-views",
+        help = "Optional; list of tables which are (postgres) materialized views: they only get reading functions (no Create and Update structs), plus `refresh` and `refresh_concurrently`"
+    )]
+    materialized_views: Option<Vec<String>>,
+
+    #[structopt(
+        long = "queryable-by-name",
+        help = "Optional; derives `QueryableByName` on the Read structs (with a `#[diesel(sql_type = ...)]` attribute on every field), so they can be loaded with `diesel::sql_query`"
+    )]
+    queryable_by_name: bool,
+
+    #[structopt(
+        long = "raw-queries",
+        help = "Optional; generates `query_raw` functions loading Read structs with hand-written `diesel::sql_query`s (implies `--queryable-by-name`)"
+    )]
+    raw_queries: bool,
+
+    #[structopt(
+        long = "fully-qualified-paths",
+        help = "Optional; spells out the full paths of tables and columns in generated queries (for example: `crate::schema::todos::id`) instead of importing the table's module into each function"
+    )]
+    fully_qualified_paths: bool,
+
+    #[structopt(
+        long = "newtype-ids",
+        help = "Optional; wraps primary keys in per-table newtypes (for example: `TodoId(pub i32)`) used by the key fields and function signatures, so keys of different tables can't be mixed up"
+    )]
+    newtype_ids: bool,
+
+    #[structopt(
+        long = "uuid-primary-keys",
+        possible_values = &["v4", "v7"],
+        help = "Optional; for single uuid primary keys which aren't autogenerated, `create` generates keys of this uuid version (so they're left out of the Create structs); `read`, `update` and `delete` accept `impl Into<Uuid>` keys"
+    )]
+    uuid_primary_keys: Option<UuidVersion>,
+
+    #[structopt(
+        long = "id-generator",
+        help = "Optional; an expression generating primary keys (for example: `ulid::Ulid::new().to_string()`), which `create` uses for single primary keys which aren't autogenerated (they're left out of the Create structs)"
+    )]
+    id_generator: Option<String>,
+
+    #[structopt(
+        long = "function-prologue",
+        help = "Optional; statements run at the start of every generated function querying the database (for example: `diesel::sql_query(\"SET LOCAL statement_timeout = 500\").execute(db)?;`), with the function's connection as `db`"
+    )]
+    function_prologue: Option<String>,
+
+    #[structopt(
+        long = "binary-format",
+        possible_values = &["array", "base64"],
+        help = "Optional; how binary columns (`Vec<u8>` fields) are serialized: `array` (arrays of numbers, the default) or `base64` (base64 strings, through a generated serde module)"
+    )]
+    binary_format: Option<BinaryFormat>,
+
+    #[structopt(
+        long = "field-order",
+        possible_values = &["schema", "alphabetical", "primary-key-first"],
+        help = "Optional; how the fields of the generated Read, Create and Update structs are ordered: `schema` (the order of the columns, the default), `alphabetical` or `primary-key-first`"
+    )]
+    field_order: Option<FieldOrder>,
+
+    #[structopt(
+        long = "write-result",
+        possible_values = &["row", "count", "nothing"],
+        help = "Optional; what the generated `create`, `update` and `update_where` functions return: `row` (the written rows, the default), `count` (the number of affected rows, without a `RETURNING` clause, for MySQL and hot paths) or `nothing`"
+    )]
+    write_result: Option<WriteResult>,
+
+    #[structopt(
+        long = "target",
+        possible_values = &["diesel", "seaorm", "sqlx"],
+        help = "Optional; library the models are generated for: `diesel` (the default), `seaorm` (SeaORM entities with `Model`, `ActiveModel` and `Relation`) or `sqlx` (`sqlx::FromRow` structs with constants for the CRUD statements)"
+    )]
+    target: Option<Target>,
+
+    #[structopt(
+        long = "import-style",
+        possible_values = &["glob", "explicit", "prelude"],
+        help = "Optional; how the generated diesel models import diesel, the schema and serde: `glob` (`use crate::diesel::*;` and `use crate::schema::*;`, the default), `explicit` (only the items the models use, by name) or `prelude` (`use diesel::prelude::*;` and direct paths, for crates which don't re-export diesel at their root)"
+    )]
+    import_style: Option<ImportStyle>,
 
     #[cfg(feature = "tsync")]
     #[structopt(
@@ -42,36 +480,1347 @@ struct Args {
     autogenerated_columns: Option<Vec<String>>,
 
     #[structopt(
-        short = "c",
-        long = "connection-type",
-        help = "Required: rust type which describes a connection, for example: `diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>`"
+        long = "infer-autogenerated-columns",
+        help = "Optional; also treats columns as autogenerated when the database likely generates them: single integer primary keys, `id` uuid primary keys and `created_at`/`updated_at` timestamps (and identity columns or columns defaulting to `now()`, `nextval(...)` or `gen_random_uuid()` in IR documents)"
     )]
-    connection_type: String,
-}
+    infer_autogenerated_columns: bool,
 
-fn main() {
-    let args: Args = Args::from_args();
-    let cols = args.autogenerated_columns.unwrap_or_default();
-    let mut default_table_options = TableOptions::default().autogenerated_columns(
-            cols
-            .iter()
-            .map(|t| t.as_str())
-            .collect::<Vec<&str>>(),
-    );
+    #[structopt(
+        long = "type-override",
+        parse(try_from_str = parse_type_override),
+        help = "Optional; list of rust types overriding the default mapping of sql types, as `<sql type>=<rust type>` (for example: `Inet=ipnet::IpNet`, or `MoodEnum=crate::models::Mood` for custom types)"
+    )]
+    type_overrides: Option<Vec<(String, String)>>,
 
-    #[cfg(feature = "tsync")]
-    if args.tsync {
-        default_table_options = default_table_options.tsync();
-    }
+    #[structopt(
+        long = "type-mapping",
+        parse(try_from_str = parse_type_mapping),
+        help = "Optional; list of rust types of sql types along with the imports and serde attributes they need, as `<sql type>=<rust type>` followed by `;use <path>` and `;#[serde(...)]` parts (for example: `MoodEnum=Mood;use crate::types::Mood;#[serde(with = \"crate::types::mood\")]`); type overrides take precedence"
+    )]
+    type_mappings: Option<Vec<TypeRule>>,
 
+    #[structopt(
+        long = "immutable-columns",
+        help = "Optional; List of write-once columns, which are set when creating rows but left out of the Update structs (for example: `owner_id`)"
+    )]
+    immutable_columns: Option<Vec<String>>,
 
-    dsync::generate_files(
-        args.input,
-        args.output,
-        GenerationConfig {
-            default_table_options,
-            table_options: HashMap::from([]),
-            connection_type: args.connection_type,
-        },
-    );
+    #[structopt(
+        long = "sensitive-columns",
+        help = "Optional; List of columns holding secrets (for example: `password_hash`), which are redacted in `Debug` output and skipped when serializing Read structs"
+    )]
+    sensitive_columns: Option<Vec<String>>,
+
+    #[structopt(
+        long = "case-insensitive-columns",
+        help = "Optional; List of text columns compared case-insensitively (for example: `email`), which get `find_by_*` functions matching on `lower(...)`; `citext` columns always get them"
+    )]
+    case_insensitive_columns: Option<Vec<String>>,
+
+    #[structopt(
+        long = "exclude-from-read",
+        help = "Optional; List of columns left out of the Read structs, which are also what gets serialized (for example: `password_hash`); primary and foreign keys are always kept"
+    )]
+    exclude_from_read: Option<Vec<String>>,
+
+    #[structopt(
+        long = "exclude-from-create",
+        help = "Optional; List of columns left out of the Create structs (they get their database defaults)"
+    )]
+    exclude_from_create: Option<Vec<String>>,
+
+    #[structopt(
+        long = "exclude-from-update",
+        help = "Optional; List of columns left out of the Update structs, for columns which never change (for example: `created_by`)"
+    )]
+    exclude_from_update: Option<Vec<String>>,
+
+    #[structopt(
+        long = "serde-derives",
+        help = "Optional; List of the serde traits derived per struct as `<struct>=<derives>` with `read`, `create`, `update` and `form` structs and `both` (the default), `serialize`, `deserialize` or `none` derives (for example: `read=serialize create=deserialize update=deserialize`)"
+    )]
+    serde_derives: Option<Vec<String>>,
+
+    #[structopt(
+        long = "default-page-size",
+        help = "Optional; page size used by the generated pagination functions when the requested page size is less than 1 (defaults to 1)"
+    )]
+    default_page_size: Option<i64>,
+
+    #[structopt(
+        long = "max-page-size",
+        help = "Optional; maximum page size for the generated pagination functions (larger page sizes are clamped)"
+    )]
+    max_page_size: Option<i64>,
+
+    #[structopt(
+        long = "default-order",
+        parse(try_from_str = parse_order),
+        help = "Optional; ordering used by the generated `paginate` function when none is given, as `<column> [ASC|DESC]` (for example: `created_at DESC`); tables without this column are ordered by primary key"
+    )]
+    default_order: Option<(String, SortDirection)>,
+
+    #[structopt(
+        long = "full-text-search-column",
+        help = "Optional; name of a tsvector column; tables which have it get a generated `search()` function (requires `diesel_full_text_search`)"
+    )]
+    full_text_search_column: Option<String>,
+
+    #[structopt(
+        long = "sql-function",
+        help = "Optional; list of database functions getting typed wrappers in `sql_functions.rs` next to the models, declared as `<name>(<arg>: <sql type>, ...) -> <sql type>` (for example: `next_invoice_number(customer_id: Int4) -> Int8`); procedures leave out the return type and are invoked with `CALL`"
+    )]
+    sql_functions: Option<Vec<String>>,
+
+    #[structopt(
+        long = "embed-migrations",
+        help = "Optional; generates a `migrations` module next to the models, embedding the migrations in this directory (relative to the crate, for example: `migrations`) with a `run_pending_migrations(db)` function"
+    )]
+    embed_migrations: Option<String>,
+
+    #[structopt(
+        long = "db-module",
+        help = "Optional; generates a `db` module next to the models with `establish_connection()` and (for pooled connections) `create_pool()` functions connecting to the database at `DATABASE_URL`, and a `ping(db)` health check"
+    )]
+    db_module: bool,
+
+    #[structopt(
+        long = "struct-name-collisions",
+        default_value = "warn",
+        help = "Optional; what happens when tables get the same struct name (like `user_role` and `user_roles`): `warn`, `error`, or `suffix=<suffix>`, appending the suffix to the struct names of the tables coming later in the schema (for example: `suffix=Link` names the struct of `user_roles` `UserRoleLink`)"
+    )]
+    struct_name_collisions: String,
+
+    #[structopt(
+        short = "c",
+        long = "connection-type",
+        help = "Required for generating models: rust type which describes a connection, for example: `diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>`"
+    )]
+    connection_type: Option<String>,
+
+    #[structopt(
+        long = "connection-feature",
+        parse(try_from_str = parse_connection_feature),
+        help = "Optional; list of connection types replacing the `--connection-type` when a cargo feature is enabled, as `<feature>=<connection type>` (for example: `sqlite=diesel::SqliteConnection`); the first one with an enabled feature wins"
+    )]
+    connection_features: Option<Vec<(String, String)>>,
+
+    #[structopt(
+        long = "read-connection-type",
+        help = "Optional; rust type of the connections of the functions which only read (for example, pooled connections to a read replica, with the same backend as the `--connection-type`), aliased as `ReadConnection` next to the `WriteConnection` of the functions which write; async wrappers and services read through a `ReadPool`"
+    )]
+    read_connection_type: Option<String>,
+
+    #[structopt(
+        long = "multi-connection",
+        help = "Optional; generates models for a connection enum deriving diesel's `MultiConnection` (the `--connection-type`, for example: `crate::db::AnyConnection` over postgres and sqlite connections), with queries working on each backend; postgres-only functions take a `diesel::PgConnection` behind the `--postgres-feature`"
+    )]
+    multi_connection: bool,
+
+    #[structopt(
+        long = "postgres-feature",
+        default_value = "postgres",
+        help = "Optional; cargo feature gating the postgres-only functions of `--multi-connection` models (full-text search, refreshing materialized views and `truncate`)"
+    )]
+    postgres_feature: String,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct CheckArgs {
+    #[structopt(
+        short = "i",
+        long = "input",
+        parse(from_os_str),
+        help = "Required; rust file to read diesel schema information from (`-` reads it from stdin)"
+    )]
+    input: PathBuf,
+
+    #[structopt(
+        long = "deny-warnings",
+        help = "Optional; fails if the schema has warnings (like tables without primary keys or foreign keys to tables which aren't generated)"
+    )]
+    deny_warnings: bool,
+
+    #[structopt(flatten)]
+    config: ConfigArgs,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct PruneArgs {
+    #[structopt(
+        short = "i",
+        long = "input",
+        parse(from_os_str),
+        help = "Required; rust file to read diesel schema information from"
+    )]
+    input: PathBuf,
+
+    #[structopt(
+        parse(from_os_str),
+        short = "o",
+        long = "output",
+        help = "Required; directory of the generated models"
+    )]
+    output: PathBuf,
+
+    #[structopt(
+        long = "report",
+        possible_values = &["json"],
+        help = "Optional; prints a machine-readable summary of the run (tables parsed, files deleted and timing) to stdout"
+    )]
+    report: Option<String>,
+
+    #[structopt(flatten)]
+    config: ConfigArgs,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct DiffArgs {
+    #[structopt(
+        short = "i",
+        long = "input",
+        parse(from_os_str),
+        help = "Required; rust file to read diesel schema information from"
+    )]
+    input: PathBuf,
+
+    #[structopt(
+        long = "snapshot",
+        parse(from_os_str),
+        help = "Required; the snapshot of the schema to compare the schema with"
+    )]
+    snapshot: PathBuf,
+
+    #[structopt(
+        long = "report",
+        possible_values = &["json"],
+        help = "Optional; prints a machine-readable summary of the changes (and warnings) to stdout"
+    )]
+    report: Option<String>,
+
+    #[structopt(flatten)]
+    config: ConfigArgs,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct ExportIrArgs {
+    #[structopt(
+        short = "i",
+        long = "input",
+        parse(from_os_str),
+        help = "Required; rust file to read diesel schema information from (`-` reads it from stdin)"
+    )]
+    input: PathBuf,
+
+    #[structopt(
+        parse(from_os_str),
+        short = "o",
+        long = "output",
+        help = "Optional; JSON file to write the parsed schema to (stdout if not present)"
+    )]
+    output: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    config: ConfigArgs,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct ExplainArgs {
+    #[structopt(
+        help = "Optional; the table option to explain, for example: `save` (lists the table options if not present)"
+    )]
+    option: Option<String>,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+struct CompletionsArgs {
+    #[structopt(possible_values = &Shell::variants(), case_insensitive = true)]
+    shell: Shell,
+}
+
+/// the schema whose tables show what the table options change, see [`explain`]
+const EXAMPLE_SCHEMA: &str = r#"diesel::table! {
+    users (id) {
+        id -> Uuid,
+        team_id -> Int4,
+        email -> Varchar,
+        password_hash -> Text,
+        avatar -> Nullable<Bytea>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use diesel_full_text_search::Tsvector;
+
+    teams (id) {
+        id -> Int4,
+        name -> Text,
+        search -> Tsvector,
+    }
+}
+
+diesel::joinable!(users -> teams (team_id));
+"#;
+
+/// the table options: their name, the table of the [`EXAMPLE_SCHEMA`] showing what they change, the arguments
+/// setting them, and what they do
+const EXPLANATIONS: &[(&str, &str, &[&str], &str)] = &[
+    (
+        "autogenerated-columns",
+        "users",
+        &["-g", "created_at"],
+        "Columns which the database generates (besides primary keys), which are left out of the Create and Update structs.",
+    ),
+    (
+        "infer-autogenerated-columns",
+        "users",
+        &["--infer-autogenerated-columns"],
+        "Also treats columns as autogenerated when the database likely generates them: single integer primary keys, `id` uuid primary keys and `created_at`/`updated_at` timestamps.",
+    ),
+    (
+        "type-override",
+        "users",
+        &["--type-override", "Varchar=crate::Email"],
+        "Replaces the rust type of an sql type, for example for custom types or newtypes with diesel impls.",
+    ),
+    (
+        "immutable-columns",
+        "users",
+        &["--immutable-columns", "team_id"],
+        "Write-once columns, which are set when creating rows but left out of the Update structs.",
+    ),
+    (
+        "sensitive-columns",
+        "users",
+        &["--sensitive-columns", "password_hash"],
+        "Columns holding secrets, which are redacted in `Debug` output and skipped when serializing Read structs.",
+    ),
+    (
+        "case-insensitive-columns",
+        "users",
+        &["--case-insensitive-columns", "email"],
+        "Text columns compared case-insensitively, which get `find_by_*` functions matching on `lower(...)`.",
+    ),
+    (
+        "exclude-from-read",
+        "users",
+        &["--exclude-from-read", "password_hash"],
+        "Columns left out of the Read structs (and so of what gets serialized); primary and foreign keys are always kept.",
+    ),
+    (
+        "exclude-from-create",
+        "users",
+        &["--exclude-from-create", "created_at"],
+        "Columns left out of the Create structs, which get their database defaults.",
+    ),
+    (
+        "exclude-from-update",
+        "users",
+        &["--exclude-from-update", "email"],
+        "Columns left out of the Update structs.",
+    ),
+    (
+        "serde-derives",
+        "users",
+        &["--serde-derives", "read=serialize"],
+        "The serde traits derived per struct: `both` (the default), `serialize`, `deserialize` or `none`.",
+    ),
+    (
+        "default-page-size",
+        "users",
+        &["--default-page-size", "20"],
+        "The page size `paginate` uses when the requested one is less than 1.",
+    ),
+    (
+        "max-page-size",
+        "users",
+        &["--max-page-size", "100"],
+        "The largest page size `paginate` returns; larger page sizes are clamped.",
+    ),
+    (
+        "default-order",
+        "users",
+        &["--default-order", "created_at DESC"],
+        "The ordering `paginate` uses when none is given (tables without the column are ordered by primary key).",
+    ),
+    (
+        "full-text-search-column",
+        "teams",
+        &["--full-text-search-column", "search"],
+        "A tsvector column; tables which have it get a `search()` function.",
+    ),
+    (
+        "schema-module",
+        "users",
+        &["--schema-module", "my_db::schema"],
+        "The module the diesel schema is imported from.",
+    ),
+    (
+        "models-module",
+        "users",
+        &["--models-module", "my_db::models"],
+        "The module the structs of referenced tables are imported from.",
+    ),
+    (
+        "file-attribute",
+        "users",
+        &["--file-attribute", "allow(clippy::all)"],
+        "Inner attributes added to the top of every generated file.",
+    ),
+    (
+        "no-identifiable",
+        "users",
+        &["--no-identifiable"],
+        "Stops deriving `Identifiable` on the Read structs.",
+    ),
+    (
+        "derives",
+        "users",
+        &["--derives", "users=no-associations"],
+        "Forces or suppresses (with a `no-` prefix) the `identifiable`, `associations` and `aschangeset` derives of a table.",
+    ),
+    (
+        "legacy-derives",
+        "users",
+        &["--legacy-derives"],
+        "Derives `Queryable`, `Insertable` and `AsChangeset` on every struct, like older versions did.",
+    ),
+    (
+        "structs",
+        "users",
+        &["--structs", "users=read,create"],
+        "The structs generated for a table; left out Create and Update structs take the functions using them along.",
+    ),
+    (
+        "dangerous-helpers",
+        "users",
+        &["--dangerous-helpers"],
+        "Generates `delete_all` (and `truncate` for postgres connections) functions wiping whole tables.",
+    ),
+    (
+        "save",
+        "users",
+        &["--save"],
+        "Generates `save` functions on the Read structs, writing every field of a row back.",
+    ),
+    (
+        "async-wrappers",
+        "users",
+        &["--async-wrappers"],
+        "Generates async wrappers of the CRUD functions, which take an r2d2 pool and run the blocking calls with `tokio::task::spawn_blocking`.",
+    ),
+    (
+        "services",
+        "users",
+        &["--services"],
+        "Generates a service struct per table owning an r2d2 pool, with async CRUD methods (implies `--async-wrappers`).",
+    ),
+    (
+        "mocks",
+        "users",
+        &["--mocks"],
+        "Generates a repository trait per table, implemented by the service and mocked with mockall behind the `mock` cargo feature (implies `--services`).",
+    ),
+    (
+        "proptest",
+        "users",
+        &["--proptest"],
+        "Generates proptest strategies and `Arbitrary` impls for the Create and Update structs behind the `proptest` cargo feature.",
+    ),
+    (
+        "form-structs",
+        "users",
+        &["--form-structs"],
+        "Generates Form structs (for HTML forms and `PATCH` requests) converting into the Create and Update structs.",
+    ),
+    (
+        "import-export",
+        "users",
+        &["--import-export"],
+        "Generates functions exporting whole tables to CSV and JSON lines, and importing rows from them.",
+    ),
+    (
+        "seed",
+        "users",
+        &["--seed"],
+        "Generates `seed.rs` next to the models, inserting JSON fixture files through the Create structs.",
+    ),
+    (
+        "file-naming",
+        "users",
+        &["--file-naming", "pascal"],
+        "How the directories (and modules) of the models are named: `table`, `singular` or `pascal`.",
+    ),
+    (
+        "group",
+        "users",
+        &["--group", "accounts=users"],
+        "Writes the models of a group of tables to a subdirectory (and module) named after the group.",
+    ),
+    (
+        "materialized-views",
+        "users",
+        &["--materialized-views", "users"],
+        "Tables which are materialized views: they only get reading functions, plus `refresh` and `refresh_concurrently`.",
+    ),
+    (
+        "queryable-by-name",
+        "users",
+        &["--queryable-by-name"],
+        "Derives `QueryableByName` on the Read structs, so they can be loaded with `diesel::sql_query`.",
+    ),
+    (
+        "raw-queries",
+        "users",
+        &["--raw-queries"],
+        "Generates `query_raw` functions loading Read structs with hand-written sql (implies `--queryable-by-name`).",
+    ),
+    (
+        "fully-qualified-paths",
+        "users",
+        &["--fully-qualified-paths"],
+        "Spells out the full paths of tables and columns in queries instead of importing the table's module.",
+    ),
+    (
+        "newtype-ids",
+        "users",
+        &["--newtype-ids"],
+        "Wraps primary keys in per-table newtypes, so keys of different tables can't be mixed up.",
+    ),
+    (
+        "uuid-primary-keys",
+        "users",
+        &["--uuid-primary-keys", "v7"],
+        "`create` generates uuid primary keys of this version, so they're left out of the Create structs.",
+    ),
+    (
+        "id-generator",
+        "users",
+        &["--id-generator", "uuid::Uuid::now_v7()"],
+        "An expression `create` generates primary keys with, so they're left out of the Create structs.",
+    ),
+    (
+        "function-prologue",
+        "users",
+        &[
+            "--function-prologue",
+            "diesel::sql_query(\"SET LOCAL statement_timeout = 500\").execute(db)?;",
+        ],
+        "Statements every generated function querying the database starts with, like setting a statement timeout.",
+    ),
+    (
+        "binary-format",
+        "users",
+        &["--binary-format", "base64"],
+        "How binary columns are serialized: `array` (the default) or `base64`.",
+    ),
+    (
+        "field-order",
+        "users",
+        &["--field-order", "alphabetical"],
+        "How the fields of the structs are ordered: `schema` (the default), `alphabetical` or `primary-key-first`.",
+    ),
+    (
+        "write-result",
+        "users",
+        &["--write-result", "count"],
+        "What `create`, `update` and `update_where` return: `row` (the default), `count` or `nothing`.",
+    ),
+    (
+        "target",
+        "users",
+        &["--target", "sqlx"],
+        "The library the models are generated for: `diesel` (the default), `seaorm` or `sqlx`.",
+    ),
+    (
+        "import-style",
+        "users",
+        &["--import-style", "explicit"],
+        "How the models import diesel, the schema and serde: `glob` (the default), `explicit` (only what they use) or `prelude` (diesel's prelude, without `crate::diesel`).",
+    ),
+    (
+        "tsync",
+        "users",
+        &["--tsync"],
+        "Adds the `#[tsync]` attribute to the structs, for generating typescript types with tsync.",
+    ),
+];
+
+fn parse_order(order: &str) -> Result<(String, SortDirection), String> {
+    let mut parts = order.split_whitespace();
+    let column = parts.next().ok_or("expected a column name")?;
+    let direction = match parts.next().map(|d| d.to_lowercase()).as_deref() {
+        None | Some("asc") => SortDirection::Asc,
+        Some("desc") => SortDirection::Desc,
+        Some(other) => {
+            return Err(format!(
+                "unknown sort direction '{other}', expected ASC or DESC"
+            ))
+        }
+    };
+    if parts.next().is_some() {
+        return Err("expected `<column> [ASC|DESC]`".to_string());
+    }
+
+    Ok((column.to_string(), direction))
+}
+
+fn parse_proto_type(mapping: &str) -> Result<(String, String), String> {
+    match mapping.split_once('=') {
+        Some((sql_type, proto_type)) if !sql_type.is_empty() && !proto_type.is_empty() => {
+            Ok((sql_type.to_string(), proto_type.to_string()))
+        }
+        _ => Err("expected `<sql type>=<protobuf type>`".to_string()),
+    }
+}
+
+fn parse_type_override(mapping: &str) -> Result<(String, String), String> {
+    match mapping.split_once('=') {
+        Some((sql_type, rust_type)) if !sql_type.is_empty() && !rust_type.is_empty() => {
+            Ok((sql_type.to_string(), rust_type.to_string()))
+        }
+        _ => Err("expected `<sql type>=<rust type>`".to_string()),
+    }
+}
+
+fn parse_type_mapping(mapping: &str) -> Result<TypeRule, String> {
+    let mut parts = mapping.split(';').map(str::trim);
+    let (sql_type, rust_type) = match parts.next().and_then(|p| p.split_once('=')) {
+        Some((sql_type, rust_type)) if !sql_type.is_empty() && !rust_type.is_empty() => {
+            (sql_type.trim(), rust_type.trim())
+        }
+        _ => return Err("expected `<sql type>=<rust type>`".to_string()),
+    };
+
+    let mut type_mapping = TypeMapping::new(rust_type);
+    for part in parts.filter(|p| !p.is_empty()) {
+        type_mapping = match part.strip_prefix("use ") {
+            Some(path) => type_mapping.import(path.trim()),
+            None if part.starts_with("#[") => type_mapping.serde_attribute(part),
+            None => return Err(format!("expected `use <path>` or `#[...]`, got `{part}`")),
+        };
+    }
+
+    Ok(TypeRule::new(sql_type, type_mapping))
+}
+
+fn parse_connection_feature(mapping: &str) -> Result<(String, String), String> {
+    match mapping.split_once('=') {
+        Some((feature, connection_type)) if !feature.is_empty() && !connection_type.is_empty() => {
+            Ok((feature.to_string(), connection_type.to_string()))
+        }
+        _ => Err("expected `<feature>=<connection type>`".to_string()),
+    }
+}
+
+fn parse_struct_name_collisions(mode: &str) -> Result<StructNameCollisions<'_>, String> {
+    match mode.split_once('=') {
+        None if mode == "warn" => Ok(StructNameCollisions::Warn),
+        None if mode == "error" => Ok(StructNameCollisions::Error),
+        Some(("suffix", suffix))
+            if !suffix.is_empty() && suffix.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+        {
+            Ok(StructNameCollisions::Suffix(suffix))
+        }
+        _ => Err(format!(
+            "unknown struct name collisions '{mode}', expected 'warn', 'error' or 'suffix=<suffix>'"
+        )),
+    }
+}
+
+/// the generation config of the options shared by the subcommands (generating models also needs a connection type)
+fn load_config(args: &ConfigArgs) -> anyhow::Result<GenerationConfig<'_>> {
+    let mut default_table_options = TableOptions::default().autogenerated_columns(
+        args.autogenerated_columns
+            .iter()
+            .flatten()
+            .map(|t| t.as_str())
+            .collect::<Vec<&str>>(),
+    );
+
+    if args.infer_autogenerated_columns {
+        default_table_options = default_table_options.infer_autogenerated_columns(true);
+    }
+
+    for (sql_type, rust_type) in args.type_overrides.iter().flatten() {
+        default_table_options = default_table_options.type_override(sql_type, rust_type);
+    }
+
+    if let Some(cols) = &args.immutable_columns {
+        default_table_options = default_table_options
+            .immutable_columns(cols.iter().map(|t| t.as_str()).collect::<Vec<&str>>());
+    }
+
+    if let Some(cols) = &args.sensitive_columns {
+        default_table_options = default_table_options
+            .sensitive_columns(cols.iter().map(|t| t.as_str()).collect::<Vec<&str>>());
+    }
+
+    if let Some(cols) = &args.case_insensitive_columns {
+        default_table_options = default_table_options
+            .case_insensitive_columns(cols.iter().map(|t| t.as_str()).collect::<Vec<&str>>());
+    }
+
+    let excluded_columns = [
+        (&args.exclude_from_read, GeneratedStruct::Read),
+        (&args.exclude_from_create, GeneratedStruct::Create),
+        (&args.exclude_from_update, GeneratedStruct::Update),
+    ];
+    for (columns, from) in excluded_columns {
+        for column in columns.iter().flatten() {
+            default_table_options = default_table_options.exclude_column(column, from);
+        }
+    }
+
+    for serde_derives in args.serde_derives.iter().flatten() {
+        let Some((generated_struct, derives)) = serde_derives.split_once('=') else {
+            bail!("Invalid serde derives '{serde_derives}', expected `<struct>=<derives>`");
+        };
+        default_table_options = default_table_options.serde_derives(
+            generated_struct
+                .trim()
+                .parse::<GeneratedStruct>()
+                .map_err(anyhow::Error::msg)?,
+            derives
+                .trim()
+                .parse::<SerdeDerives>()
+                .map_err(anyhow::Error::msg)?,
+        );
+    }
+
+    if let Some(page_size) = args.default_page_size {
+        default_table_options = default_table_options.default_page_size(page_size);
+    }
+
+    if let Some(page_size) = args.max_page_size {
+        default_table_options = default_table_options.max_page_size(page_size);
+    }
+
+    if let Some((column, direction)) = &args.default_order {
+        default_table_options = default_table_options.default_order(column, *direction);
+    }
+
+    if let Some(column) = args.full_text_search_column.as_deref() {
+        default_table_options = default_table_options.full_text_search_column(column);
+    }
+
+    if let Some(module) = args.schema_module.as_deref() {
+        default_table_options = default_table_options.schema_module(module);
+    }
+
+    if let Some(module) = args.models_module.as_deref() {
+        default_table_options = default_table_options.models_module(module);
+    }
+
+    if let Some(file_attributes) = args.file_attributes.as_ref().filter(|a| !a.is_empty()) {
+        default_table_options = default_table_options
+            .file_attributes(file_attributes.iter().map(|a| a.as_str()).collect());
+    }
+
+    if args.no_identifiable {
+        default_table_options = default_table_options.identifiable(false);
+    }
+
+    if args.dangerous_helpers {
+        default_table_options = default_table_options.dangerous_helpers();
+    }
+
+    if args.save {
+        default_table_options = default_table_options.save();
+    }
+
+    if args.async_wrappers {
+        default_table_options = default_table_options.async_wrappers();
+    }
+
+    if args.services {
+        default_table_options = default_table_options.service();
+    }
+
+    if args.mocks {
+        default_table_options = default_table_options.mock();
+    }
+
+    if args.proptest {
+        default_table_options = default_table_options.proptest();
+    }
+
+    if args.legacy_derives {
+        default_table_options = default_table_options.legacy_derives();
+    }
+
+    if args.form_structs {
+        default_table_options = default_table_options.form();
+    }
+
+    if args.import_export {
+        default_table_options = default_table_options.import_export();
+    }
+
+    if args.seed {
+        default_table_options = default_table_options.seed();
+    }
+
+    if args.queryable_by_name {
+        default_table_options = default_table_options.queryable_by_name();
+    }
+
+    if args.raw_queries {
+        default_table_options = default_table_options.raw_queries();
+    }
+
+    if args.fully_qualified_paths {
+        default_table_options = default_table_options.fully_qualified_paths();
+    }
+
+    if args.newtype_ids {
+        default_table_options = default_table_options.newtype_ids(true);
+    }
+
+    if let Some(version) = args.uuid_primary_keys {
+        default_table_options = default_table_options.uuid_primary_key(version);
+    }
+
+    if let Some(expression) = &args.id_generator {
+        default_table_options = default_table_options.id_generator(expression);
+    }
+
+    if let Some(statements) = &args.function_prologue {
+        default_table_options = default_table_options.function_prologue(statements);
+    }
+
+    if let Some(format) = args.binary_format {
+        default_table_options = default_table_options.binary_format(format);
+    }
+
+    if let Some(naming) = args.file_naming {
+        default_table_options = default_table_options.file_naming(naming);
+    }
+
+    if let Some(order) = args.field_order {
+        default_table_options = default_table_options.field_order(order);
+    }
+
+    if let Some(write_result) = args.write_result {
+        default_table_options = default_table_options.write_result(write_result);
+    }
+
+    if let Some(target) = args.target {
+        default_table_options = default_table_options.target(target);
+    }
+
+    if let Some(style) = args.import_style {
+        default_table_options = default_table_options.import_style(style);
+    }
+
+    #[cfg(feature = "tsync")]
+    if args.tsync {
+        default_table_options = default_table_options.tsync();
+    }
+
+    let mut table_options: HashMap<&str, TableOptions> = HashMap::new();
+    for view in args.materialized_views.iter().flatten() {
+        let options = table_options.entry(view.as_str()).or_default();
+        *options = std::mem::take(options).materialized_view();
+    }
+    for structs in args.structs.iter().flatten() {
+        let Some((table, structs)) = structs.split_once('=') else {
+            bail!("Invalid structs '{structs}', expected `<table>=<struct>,<struct>,...`");
+        };
+        let structs = structs
+            .split(',')
+            .map(|s| s.trim().parse::<GeneratedStruct>())
+            .collect::<Result<Vec<GeneratedStruct>, String>>()
+            .map_err(anyhow::Error::msg)?;
+        let options = table_options.entry(table.trim()).or_default();
+        *options = std::mem::take(options).structs(structs);
+    }
+    for derives in args.derives.iter().flatten() {
+        let Some((table, derives)) = derives.split_once('=') else {
+            bail!("Invalid derives '{derives}', expected `<table>=<derive>,<derive>,...`");
+        };
+        let options = table_options.entry(table.trim()).or_default();
+        for derive in derives.split(',').map(|d| d.trim()) {
+            let (derive, enabled) = match derive.strip_prefix("no-") {
+                Some(derive) => (derive, false),
+                None => (derive, true),
+            };
+            *options = match derive {
+                "identifiable" => std::mem::take(options).identifiable(enabled),
+                "associations" => std::mem::take(options).associations(enabled),
+                "aschangeset" => std::mem::take(options).aschangeset(enabled),
+                _ => bail!("Unknown derive '{derive}', expected 'identifiable', 'associations' or 'aschangeset' (optionally prefixed with 'no-')"),
+            };
+        }
+    }
+    for group in args.groups.iter().flatten() {
+        let Some((name, tables)) = group.split_once('=') else {
+            bail!("Invalid group '{group}', expected `<group>=<table>,<table>,...`");
+        };
+        for table in tables
+            .split(',')
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+        {
+            let options = table_options.entry(table).or_default();
+            *options = std::mem::take(options).group(name.trim());
+        }
+    }
+
+    Ok(GenerationConfig {
+        default_table_options,
+        table_options,
+        connection_type: args.connection_type.clone().unwrap_or_default(),
+        connection_features: args.connection_features.clone().unwrap_or_default(),
+        read_connection_type: args.read_connection_type.clone(),
+        benches: false,
+        test_support: None,
+        cache: None,
+        migrations_dir: args.embed_migrations.as_deref(),
+        db_module: args.db_module,
+        multi_connection: args.multi_connection.then_some(MultiConnection {
+            postgres_feature: &args.postgres_feature,
+        }),
+        type_mappers: args
+            .type_mappings
+            .iter()
+            .flatten()
+            .map(|rule| Arc::new(rule.clone()) as Arc<dyn TypeMapper>)
+            .collect(),
+        struct_name_collisions: parse_struct_name_collisions(&args.struct_name_collisions)
+            .map_err(anyhow::Error::msg)?,
+        functions: args
+            .sql_functions
+            .iter()
+            .flatten()
+            .map(|declaration| SqlFunction::parse(declaration))
+            .collect::<Result<Vec<SqlFunction>, String>>()
+            .map_err(anyhow::Error::msg)?,
+    })
+}
+
+fn generate(args: GenerateArgs) -> anyhow::Result<()> {
+    if let Some(models_input) = args.models_input {
+        let output = args
+            .output
+            .expect("an output is required unless `--stdout` is used");
+        let report = dsync::generate_schema_from_models(models_input, output, args.migration)?;
+
+        print_report(&report, args.report.as_deref());
+
+        return Ok(());
+    }
+
+    if args.config.connection_type.is_none() {
+        bail!("A connection type (`-c`) is required to generate models");
+    }
+    let mut config = load_config(&args.config)?;
+    config.benches = args.generate_benches;
+    config.test_support = args.test_support.as_deref();
+    config.cache = args.cache.as_deref();
+
+    // only the code of the table is printed, nothing else is generated
+    if let (true, Some(table), Some(input)) = (args.stdout, &args.table, &args.input) {
+        let schema = read_schema(input)?;
+        if args.strict {
+            dsync::validate_config(schema.clone(), config.clone())
+                .with_context(|| format!("Invalid options for '{}'", input.display()))?;
+        }
+        if args.deny_warnings {
+            deny_warnings(&dsync::schema_warnings(schema.clone(), config.clone())?)?;
+        }
+        let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+        return match dsync::write_table_code(schema, table, config, &mut stdout) {
+            // the reader went away (for example, piped into `head`), so there's nobody left to print the code to
+            Err(error) if is_broken_pipe(&error) => Ok(()),
+            result => result,
+        };
+    }
+
+    let output = args
+        .output
+        .expect("an output is required unless `--stdout` is used");
+    if args.input.as_deref() == Some(Path::new("-")) {
+        bail!("The schema can only be read from stdin (`-i -`) with `--stdout`");
+    }
+
+    let Some(input) = args.input else {
+        let ir_file = args
+            .import_ir
+            .expect("either an input or an IR file is required");
+        let report = dsync::generate_files_from_ir(ir_file, output, config)?;
+
+        print_report(&report, args.report.as_deref());
+        if args.timings {
+            print_timings(&report);
+        }
+        if args.deny_warnings {
+            deny_warnings(&report.warnings)?;
+        }
+
+        return Ok(());
+    };
+
+    if args.strict {
+        dsync::validate_config(std::fs::read_to_string(&input)?, config.clone())
+            .with_context(|| format!("Invalid options for '{}'", input.display()))?;
+    }
+    // fail before writing anything, warnings of the snapshot are checked after generating
+    if args.deny_warnings {
+        deny_warnings(&dsync::schema_warnings(
+            std::fs::read_to_string(&input)?,
+            config.clone(),
+        )?)?;
+    }
+
+    if let Some([format, path]) = args.diagram.as_deref() {
+        let format = format
+            .parse::<DiagramFormat>()
+            .map_err(anyhow::Error::msg)?;
+        let diagram =
+            dsync::generate_diagram(std::fs::read_to_string(&input)?, format, config.clone())?;
+        std::fs::write(path, diagram)?;
+    }
+
+    if let Some(docs_dir) = args.docs.clone() {
+        dsync::generate_docs(input.clone(), docs_dir, config.clone())?;
+    }
+
+    if let Some(path) = &args.export_ir {
+        let ir = dsync::export_ir(std::fs::read_to_string(&input)?, config.clone())?;
+        std::fs::write(path, ir)?;
+    }
+
+    if let Some(path) = &args.proto {
+        let proto_types = args.proto_types.unwrap_or_default();
+        let proto = dsync::generate_proto(
+            std::fs::read_to_string(&input)?,
+            config.clone(),
+            ProtoConfig {
+                package: &args.proto_package,
+                type_overrides: proto_types
+                    .iter()
+                    .map(|(sql_type, proto_type)| (sql_type.as_str(), proto_type.as_str()))
+                    .collect(),
+            },
+        )?;
+        std::fs::write(path, proto)?;
+    }
+
+    if let Some(path) = &args.zod {
+        let zod = dsync::generate_zod(std::fs::read_to_string(&input)?, config.clone())?;
+        std::fs::write(path, zod)?;
+    }
+
+    if let Some(path) = &args.openapi {
+        let openapi = dsync::generate_openapi(std::fs::read_to_string(&input)?, config.clone())?;
+        std::fs::write(path, openapi)?;
+    }
+
+    let snapshot_report = match args.snapshot.clone() {
+        Some(snapshot) => Some(dsync::sync_snapshot(
+            input.clone(),
+            snapshot,
+            args.migrations_dir.clone(),
+            args.changelog.clone(),
+            config.clone(),
+        )?),
+        None => None,
+    };
+
+    let mut report = match args.crate_name.as_deref() {
+        Some(crate_name) => dsync::generate_crate(input, output, crate_name, config)?,
+        None => dsync::generate_files(input, output, config)?,
+    };
+    if let Some(snapshot_report) = snapshot_report {
+        report.files.extend(snapshot_report.files);
+        report.schema_changes = snapshot_report.schema_changes;
+        report.warnings.extend(snapshot_report.warnings);
+    }
+
+    print_report(&report, args.report.as_deref());
+    if args.timings {
+        print_timings(&report);
+    }
+    if args.deny_warnings {
+        deny_warnings(&report.warnings)?;
+    }
+
+    Ok(())
+}
+
+/// reads a schema file, or the schema from stdin for `-`
+fn read_schema(input: &Path) -> anyhow::Result<String> {
+    match input == Path::new("-") {
+        true => std::io::read_to_string(std::io::stdin())
+            .context("Could not read the schema from stdin"),
+        false => std::fs::read_to_string(input)
+            .with_context(|| format!("Could not read '{}'", input.display())),
+    }
+}
+
+/// prints the schema changes and warnings of a report, or the whole report as JSON (with `--report json`)
+fn print_report(report: &GenerationReport, format: Option<&str>) {
+    match format {
+        Some("json") => println!("{}", report.to_json()),
+        _ => {
+            if !report.schema_changes.is_empty() {
+                println!("Schema changes since the last snapshot:");
+                for change in report.schema_changes.iter() {
+                    println!("- {change}");
+                }
+            }
+            if !report.skipped_tables.is_empty() {
+                println!(
+                    "Skipped the unchanged tables: {}",
+                    report.skipped_tables.join(", ")
+                );
+            }
+            for warning in report.warnings.iter() {
+                eprintln!("warning: {warning}");
+            }
+        }
+    }
+}
+
+/// prints the durations of the phases and of rendering the tables (with `--timings`)
+fn print_timings(report: &GenerationReport) {
+    let Some(timings) = &report.timings else {
+        return;
+    };
+    let width = timings
+        .tables
+        .iter()
+        .map(|t| t.table.len())
+        .chain(["render".len()])
+        .max()
+        .unwrap_or_default();
+
+    eprintln!("timings (ms):");
+    for (phase, duration) in [
+        ("parse", timings.parse_ms),
+        ("render", timings.render_ms),
+        ("write", timings.write_ms),
+        ("total", report.duration_ms as f64),
+    ] {
+        eprintln!("  {phase:<width$}  {duration:>10.3}");
+    }
+    eprintln!("rendering the tables (ms, slowest first):");
+    for table in timings.tables.iter() {
+        eprintln!("  {:<width$}  {:>10.3}", table.table, table.render_ms);
+    }
+}
+
+/// fails if there are warnings (with `--deny-warnings`)
+fn deny_warnings(warnings: &[String]) -> anyhow::Result<()> {
+    if !warnings.is_empty() {
+        bail!(
+            "Denied {} warning(s) (`--deny-warnings`):\n{}",
+            warnings.len(),
+            warnings
+                .iter()
+                .map(|w| format!("- {w}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+fn check(args: CheckArgs) -> anyhow::Result<()> {
+    let config = load_config(&args.config)?;
+    let schema = read_schema(&args.input)?;
+
+    dsync::validate_config(schema.clone(), config.clone())
+        .with_context(|| format!("Invalid options for '{}'", args.input.display()))?;
+    let warnings = dsync::schema_warnings(schema.clone(), config.clone())?;
+    if args.deny_warnings {
+        deny_warnings(&warnings)?;
+    }
+    for warning in warnings.iter() {
+        eprintln!("warning: {warning}");
+    }
+    dsync::generate_code(schema, config).with_context(|| {
+        format!(
+            "Could not generate the models of '{}'",
+            args.input.display()
+        )
+    })?;
+
+    Ok(())
+}
+
+fn prune(args: PruneArgs) -> anyhow::Result<()> {
+    let report = dsync::prune_files(args.input, args.output, load_config(&args.config)?)?;
+    print_report(&report, args.report.as_deref());
+
+    Ok(())
+}
+
+fn diff(args: DiffArgs) -> anyhow::Result<()> {
+    let report = dsync::diff_snapshot(args.input, args.snapshot, load_config(&args.config)?)?;
+    print_report(&report, args.report.as_deref());
+
+    Ok(())
+}
+
+fn export_ir(args: ExportIrArgs) -> anyhow::Result<()> {
+    let ir = dsync::export_ir(read_schema(&args.input)?, load_config(&args.config)?)?;
+
+    match &args.output {
+        Some(path) => std::fs::write(path, ir)
+            .with_context(|| format!("Could not write '{}'", path.display()))?,
+        None => println!("{ir}"),
+    }
+
+    Ok(())
+}
+
+/// the generation config of the [`EXAMPLE_SCHEMA`] with the given arguments
+fn example_config(args: &[&str]) -> anyhow::Result<ConfigArgs> {
+    Ok(ConfigArgs::from_iter_safe(
+        ["dsync", "-c", "diesel::PgConnection"]
+            .iter()
+            .chain(args.iter()),
+    )?)
+}
+
+fn explain(args: ExplainArgs) -> anyhow::Result<()> {
+    let Some(option) = args.option.as_deref() else {
+        println!("Table options (see `dsync explain <option>`):\n");
+        for (option, _, _, description) in EXPLANATIONS.iter() {
+            println!(
+                "  --{option:<30}{}",
+                description.split(". ").next().unwrap_or_default()
+            );
+        }
+
+        return Ok(());
+    };
+
+    let option = option.trim_start_matches('-');
+    let Some((option, table, example_args, description)) =
+        EXPLANATIONS.iter().find(|(name, _, _, _)| *name == option)
+    else {
+        bail!("Unknown table option '{option}', see `dsync explain` for the table options");
+    };
+
+    let default_config = example_config(&[])?;
+    let option_config =
+        example_config(example_args).with_context(|| format!("Could not explain `--{option}`"))?;
+    let diff = dsync::diff_table_code(
+        EXAMPLE_SCHEMA.to_string(),
+        table,
+        load_config(&default_config)?,
+        load_config(&option_config)?,
+    )?;
+    let example_args = example_args
+        .iter()
+        .map(|arg| match arg.contains(' ') {
+            true => format!("\"{arg}\""),
+            false => arg.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    println!("--{option}: {description}\n");
+    println!("For example, with this schema:\n\n{EXAMPLE_SCHEMA}");
+    match diff.is_empty() {
+        true => {
+            println!("`{example_args}` doesn't change the generated code of the `{table}` table.")
+        }
+        false => println!(
+            "`{example_args}` changes the generated code of the `{table}` table like this:\n\n{diff}"
+        ),
+    }
+
+    Ok(())
+}
+
+fn completions(args: CompletionsArgs) -> anyhow::Result<()> {
+    Command::clap().gen_completions_to("dsync", args.shell, &mut std::io::stdout());
+
+    Ok(())
+}
+
+/// logs the messages of the generator to stderr (see `-v`)
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level() && metadata.target().starts_with("dsync")
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "{}: {}",
+                record.level().as_str().to_lowercase(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// logs the messages of the generator up to the level of the verbosity (warnings only without `-v`)
+fn init_logger(verbosity: u8) {
+    log::set_max_level(match verbosity {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    });
+    log::set_logger(&StderrLogger).expect("Could not set the logger");
+}
+
+/// the command line arguments, with the `generate` subcommand inserted when there's no subcommand (dsync used to only
+/// generate models, and most scripts call it that way); `--explain <option>` is the `explain` subcommand
+fn args() -> Vec<OsString> {
+    let mut args = std::env::args_os().collect::<Vec<OsString>>();
+    let has_subcommand = args.get(1).and_then(|arg| arg.to_str()).is_some_and(|arg| {
+        SUBCOMMANDS.contains(&arg) || ["-h", "--help", "-V", "--version"].contains(&arg)
+    });
+    if args.get(1).is_some_and(|arg| arg == "--explain") {
+        args[1] = "explain".into();
+    } else if args.len() > 1 && !has_subcommand {
+        args.insert(1, "generate".into());
+    }
+
+    args
+}
+
+/// whether writing failed because the reading end of the pipe (stdout) was closed
+fn is_broken_pipe(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|error| error.kind() == std::io::ErrorKind::BrokenPipe)
+    })
+}
+
+fn main() -> anyhow::Result<()> {
+    let command = Command::from_iter(args());
+    init_logger(match &command {
+        Command::Generate(args) => args.config.verbose,
+        Command::Check(args) => args.config.verbose,
+        Command::Prune(args) => args.config.verbose,
+        Command::Diff(args) => args.config.verbose,
+        Command::ExportIr(args) => args.config.verbose,
+        Command::Explain(_) | Command::Completions(_) => 0,
+    });
+
+    match command {
+        Command::Generate(args) => generate(args),
+        Command::Check(args) => check(args),
+        Command::Prune(args) => prune(args),
+        Command::Diff(args) => diff(args),
+        Command::ExportIr(args) => export_ir(args),
+        Command::Explain(args) => explain(args),
+        Command::Completions(args) => completions(args),
+    }
 }
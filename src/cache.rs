@@ -0,0 +1,138 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::ir::TableIr;
+use crate::parser::{self, ParsedTableMacro};
+use crate::{GenerationConfig, GenerationReport};
+
+/// the generation cache (see `GenerationConfig::cache`), with the hashes of the inputs and of the generated code of
+/// every table of the run which saved it
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cache {
+    tables: BTreeMap<String, CachedTable>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedTable {
+    input: String,
+    output: String,
+}
+
+fn hash(value: &impl Hash) -> String {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+
+    format!("{:016x}", hasher.finish())
+}
+
+/// hash of everything a table's generated code depends on: the dsync version, the connection types, the table's
+/// definition and options, and the definitions and options of the other tables (only their primary key columns,
+/// which is what foreign keys refer to, unless their columns are joined by projections of the table)
+fn input_hash(
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> String {
+    let table_options = config.table(&table.name);
+    let joined_tables = table_options
+        .get_projections()
+        .iter()
+        .flat_map(|p| p.joined_columns.iter().map(|(table_name, _)| *table_name))
+        .collect::<Vec<&str>>();
+
+    let tables_ir = tables
+        .iter()
+        .map(|other| {
+            let mut ir = TableIr::new(other, config);
+            if other.name != table.name && !joined_tables.contains(&other.name.as_str()) {
+                ir.columns
+                    .retain(|c| other.primary_key_columns.contains(&c.name));
+            }
+
+            serde_json::to_string(&ir).expect("Could not serialize the table IR")
+        })
+        .collect::<Vec<String>>();
+
+    hash(&(
+        env!("CARGO_PKG_VERSION"),
+        &table.name,
+        tables_ir,
+        &config.connection_type,
+        &config.connection_features,
+        &config.read_connection_type,
+        config.multi_connection.map(|m| m.postgres_feature),
+    ))
+}
+
+/// renders the code of the tables (see `parser::generate_tables_with`), reusing the code in the `generated.rs` files of the
+/// tables whose inputs didn't change since the cache was saved (and whose `generated.rs` wasn't changed since), which
+/// are reported as skipped; a missing or unreadable cache file renders every table
+pub fn generate_tables(
+    tables: Vec<ParsedTableMacro>,
+    output_dir: &Path,
+    cache_file: &Path,
+    config: &GenerationConfig,
+    report: &mut GenerationReport,
+) -> anyhow::Result<Vec<(ParsedTableMacro, Duration)>> {
+    let cache = std::fs::read_to_string(cache_file)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Cache>(&contents).ok())
+        .unwrap_or_default();
+
+    let mut generated_code = HashMap::new();
+    for table in tables.iter() {
+        let Some(cached) = cache.tables.get(&table.name) else {
+            continue;
+        };
+        if cached.input != input_hash(table, &tables, config) {
+            continue;
+        }
+        let generated_rs = table.model_dir(output_dir, config).join("generated.rs");
+        let Ok(code) = std::fs::read_to_string(generated_rs) else {
+            continue;
+        };
+
+        if hash(&code) == cached.output {
+            log::debug!(
+                "table `{}`: unchanged since the last run, reusing its generated code",
+                table.name
+            );
+            report.skipped_tables.push(table.name.clone());
+            generated_code.insert(table.name.clone(), code);
+        }
+    }
+
+    parser::generate_tables_with(tables, generated_code, config)
+}
+
+/// saves the hashes of the inputs and the generated code of the tables
+pub fn save(
+    cache_file: &Path,
+    generated: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> anyhow::Result<()> {
+    let cache = Cache {
+        tables: generated
+            .iter()
+            .map(|table| {
+                let cached = CachedTable {
+                    input: input_hash(table, generated, config),
+                    output: hash(&table.generated_code),
+                };
+
+                (table.name.clone(), cached)
+            })
+            .collect(),
+    };
+
+    std::fs::write(
+        cache_file,
+        serde_json::to_string_pretty(&cache).expect("Could not serialize the cache"),
+    )
+    .with_context(|| format!("Could not write the cache to '{}'", cache_file.display()))
+}
@@ -1,8 +1,16 @@
 use indoc::indoc;
 use inflector::Inflector;
+use std::io::{self, Write};
 
-use crate::parser::{ParsedTableMacro, FILE_SIGNATURE};
-use crate::{GenerationConfig, TableOptions};
+use crate::imports;
+use crate::parser::{
+    module_name, snake_case_identifier, transliterate, ParsedColumnMacro, ParsedTableMacro,
+    FILE_SIGNATURE,
+};
+use crate::{
+    BinaryFormat, FieldOrder, FileNaming, GeneratedStruct, GenerationConfig, ImportStyle,
+    SerdeDerives, SortDirection, TableOptions, Target, UuidVersion, WriteResult,
+};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum StructType {
@@ -33,6 +41,16 @@ impl StructType {
         }
     }
 
+    /// the struct as named in the table options
+    pub fn generated_struct(&self) -> Option<GeneratedStruct> {
+        match self {
+            StructType::Read => Some(GeneratedStruct::Read),
+            StructType::Form => Some(GeneratedStruct::Form),
+            StructType::Update => Some(GeneratedStruct::Update),
+            StructType::Create => Some(GeneratedStruct::Create),
+        }
+    }
+
     /// returns a struct name for this struct type given a base name
     pub fn format(&self, name: &'_ str) -> String {
         format!(
@@ -49,7 +67,9 @@ struct Struct<'a> {
     ty: StructType,
     table: &'a ParsedTableMacro,
     opts: TableOptions<'a>,
-    config: &'a GenerationConfig<'a>,
+    foreign_structs: &'a [ForeignStruct],
+    /// newtypes of the table's key columns as `(column, type)` (see [`key_types`])
+    key_types: &'a [(String, String)],
     rendered_code: Option<String>,
     has_fields: Option<bool>, // note: this is only correctly set after a call to render() which gets called in Struct::new()
 }
@@ -57,7 +77,11 @@ struct Struct<'a> {
 #[derive(Debug, Clone)]
 pub struct StructField {
     pub name: String,
+    /// identifier of the column this field maps to in the schema (usually the same as `name`)
+    pub column_name: String,
     pub base_type: String,
+    /// lines of doc comments carried over from the schema
+    pub doc_comments: Vec<String>,
 
     pub is_optional: bool,
 }
@@ -67,13 +91,16 @@ impl<'a> Struct<'a> {
         ty: StructType,
         table: &'a ParsedTableMacro,
         config: &'a GenerationConfig<'_>,
+        foreign_structs: &'a [ForeignStruct],
+        key_types: &'a [(String, String)],
     ) -> Self {
         let mut obj = Self {
             identifier: ty.format(table.struct_name.as_str()),
-            opts: config.table(&table.name.to_string()),
+            opts: config.table(&table.name),
+            foreign_structs,
+            key_types,
             table,
             ty,
-            config,
             rendered_code: None,
             has_fields: None,
         };
@@ -89,6 +116,21 @@ impl<'a> Struct<'a> {
         self.has_fields.unwrap()
     }
 
+    /// why the struct has no fields, for logging why the functions taking it aren't generated
+    fn no_fields_reason(&self) -> &'static str {
+        if self.opts.get_materialized_view() {
+            "the table is a materialized view"
+        } else if self
+            .ty
+            .generated_struct()
+            .is_some_and(|s| !self.opts.generates_struct(s))
+        {
+            "the struct isn't generated (see `--structs`)"
+        } else {
+            "every column is a primary key, or autogenerated, immutable or excluded from the struct"
+        }
+    }
+
     fn attr_tsync(&self) -> &'static str {
         #[cfg(feature = "tsync")]
         match self.opts.get_tsync() {
@@ -100,139 +142,322 @@ impl<'a> Struct<'a> {
     }
 
     fn attr_derive(&self) -> String {
-        format!("#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Insertable{derive_aschangeset}{derive_identifiable}{derive_associations}{derive_selectable})]",
+        // materialized views are only read
+        let is_view = self.opts.get_materialized_view();
+
+        let derive_serde = match self.serde_derives() {
+            SerdeDerives::Both => "Serialize, Deserialize, ",
+            SerdeDerives::Serialize => "Serialize, ",
+            SerdeDerives::Deserialize => "Deserialize, ",
+            SerdeDerives::None => "",
+        };
+
+        // Form structs aren't passed to diesel, they're converted into Create and Update structs
+        if self.ty == StructType::Form {
+            return format!(
+                "#[derive({derive_debug}{derive_serde}Clone)]",
+                derive_debug = if self.sensitive_fields().is_empty() {
+                    "Debug, "
+                } else {
+                    ""
+                },
+            );
+        }
+
+        // each struct derives what it's used for: Read structs are loaded, Create structs inserted and Update structs
+        // applied as changesets (legacy derives put `Queryable`, `Insertable` and `AsChangeset` on every struct)
+        let is_legacy = self.opts.get_legacy_derives();
+
+        format!("#[derive({derive_debug}{derive_serde}Clone{derive_queryable}{derive_insertable}{derive_aschangeset}{derive_identifiable}{derive_associations}{derive_selectable}{derive_queryable_by_name})]",
+                // structs with sensitive fields get a redacting `Debug` impl instead
+                derive_debug = if self.sensitive_fields().is_empty() { "Debug, " } else { "" },
                 derive_selectable = match self.ty {
                     StructType::Read => { ", Selectable" }
                     _ => { "" }
                 },
-                derive_associations = match self.ty {
-                    StructType::Read => {
-                        if self.table.foreign_keys.len() > 0 { ", Associations" } else { "" }
-                    }
+                derive_queryable_by_name = match self.ty {
+                    StructType::Read if self.opts.get_queryable_by_name() => { ", QueryableByName" }
                     _ => { "" }
                 },
-                derive_identifiable = match self.ty {
-                    StructType::Read => {
-                        if self.table.foreign_keys.len() > 0 { ", Identifiable" } else { "" }
-                    }
+                derive_associations = match self.ty {
+                    StructType::Read if self.opts.get_associations().unwrap_or(!self.table.foreign_keys.is_empty()) => { ", Associations" }
                     _ => { "" }
                 },
-                derive_aschangeset = match self.ty {
-                    _ => if self.fields().iter().all(|f| self.table.primary_key_column_names().contains(&f.name)) {""} else { ", AsChangeset" }
-                }
+                derive_identifiable = if self.derives_identifiable() { ", Identifiable" } else { "" },
+                derive_queryable = if self.ty == StructType::Read || is_legacy { ", Queryable" } else { "" },
+                derive_insertable = if !is_view && (self.ty == StructType::Create || is_legacy) { ", Insertable" } else { "" },
+                derive_aschangeset = if self.derives_aschangeset() { ", AsChangeset" } else { "" }
+        )
+    }
+
+    /// the serde traits the struct derives
+    pub fn serde_derives(&self) -> SerdeDerives {
+        self.ty
+            .generated_struct()
+            .map(|generated_struct| self.opts.get_serde_derives(generated_struct))
+            .unwrap_or_default()
+    }
+
+    /// whether the struct derives `Identifiable` (Read structs of tables with a primary key)
+    fn derives_identifiable(&self) -> bool {
+        self.ty == StructType::Read
+            && self.opts.get_identifiable()
+            && !self.table.primary_key_columns.is_empty()
+    }
+
+    /// whether the struct derives `AsChangeset`: Update structs (and Read structs with `save`, or any struct with
+    /// legacy derives) with fields besides the primary key, except for views, unless the table options force or
+    /// suppress it for Read and Create structs
+    fn derives_aschangeset(&self) -> bool {
+        let primary_key_columns = self.table.primary_key_column_names();
+        let by_columns = !self.opts.get_materialized_view()
+            && !self
+                .fields()
+                .iter()
+                .all(|f| primary_key_columns.contains(&f.column_name));
+
+        match self.ty {
+            StructType::Form => false,
+            StructType::Update => by_columns,
+            StructType::Read | StructType::Create => {
+                let is_needed = self.opts.get_legacy_derives()
+                    || (self.ty == StructType::Read && self.opts.get_save());
+
+                self.opts
+                    .get_aschangeset()
+                    .unwrap_or(by_columns && is_needed)
+            }
+        }
+    }
+
+    /// whether the table options exclude the column from this struct (Read structs always keep key columns)
+    fn is_excluded(&self, column: &ParsedColumnMacro) -> bool {
+        let Some(generated_struct) = self.ty.generated_struct() else {
+            return false;
+        };
+        let is_key = self.table.primary_key_columns.contains(&column.name)
+            || self
+                .table
+                .foreign_keys
+                .iter()
+                .any(|(_, c)| c == &column.name);
+
+        !(self.ty == StructType::Read && is_key)
+            && self
+                .opts
+                .get_excluded_columns(generated_struct)
+                .contains(&column.name.as_str())
+    }
+
+    /// fields of sensitive columns, which are redacted from `Debug` output (and skipped when serializing Read structs)
+    fn sensitive_fields(&self) -> Vec<StructField> {
+        self.fields()
+            .into_iter()
+            .filter(|f| {
+                self.opts
+                    .get_sensitive_columns()
+                    .contains(&f.column_name.as_str())
+            })
+            .collect()
+    }
+
+    /// a `Debug` impl printing `[redacted]` in place of the values of sensitive fields
+    fn debug_impl(&self, fields: &[StructField]) -> String {
+        let sensitive_fields = self.sensitive_fields();
+        let debug_fields = fields
+            .iter()
+            .map(|f| {
+                let is_sensitive = sensitive_fields.iter().any(|s| s.name == f.name);
+                let is_option = f.is_optional || f.base_type.starts_with("Option<");
+                let value = match (is_sensitive, is_option) {
+                    (false, _) => format!("&self.{}", f.name),
+                    (true, false) => r#"&"[redacted]""#.to_string(),
+                    (true, true) => format!(r#"&self.{}.as_ref().map(|_| "[redacted]")"#, f.name),
+                };
+
+                format!(
+                    "            .field(\"{name}\", {value})\n",
+                    name = f.name.trim_start_matches("r#")
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            indoc! {r#"
+
+            impl std::fmt::Debug for {struct_name} {{
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+                    f.debug_struct("{struct_name}")
+            {debug_fields}            .finish()
+                }}
+            }}
+        "#},
+            struct_name = self.identifier,
+            debug_fields = debug_fields,
         )
     }
 
     fn fields(&self) -> Vec<StructField> {
-        self.table
+        // materialized views can't be written to
+        if self.opts.get_materialized_view()
+            && matches!(self.ty, StructType::Create | StructType::Update)
+        {
+            return vec![];
+        }
+        if self
+            .ty
+            .generated_struct()
+            .is_some_and(|s| !self.opts.generates_struct(s))
+        {
+            return vec![];
+        }
+
+        let mut fields = self
+            .table
             .columns
             .iter()
+            .filter(|c| !c.is_tsvector())
+            .filter(|c| !self.is_excluded(c))
             .filter(|c| {
-                let is_autogenerated = self
-                    .opts
-                    .autogenerated_columns
-                    .as_deref()
-                    .unwrap_or_default()
-                    .contains(&c.name.to_string().as_str());
+                let is_autogenerated = is_autogenerated(self.table, c, &self.opts);
 
                 match self.ty {
                     StructType::Read => true,
-                    StructType::Form => true,
+                    StructType::Form => {
+                        let is_pk = self.table.primary_key_columns.contains(&c.name);
+
+                        is_pk || !is_autogenerated
+                    }
                     StructType::Update => {
                         let is_pk = self.table.primary_key_columns.contains(&c.name);
+                        let is_immutable =
+                            self.opts.get_immutable_columns().contains(&c.name.as_str());
+
+                        !is_pk && !is_immutable
+                    }
+                    StructType::Create => {
+                        let is_generated_key = generated_key(self.table, &self.opts)
+                            .is_some_and(|(pk, _)| pk.name == c.name);
 
-                        !is_pk
+                        !is_autogenerated && !is_generated_key
                     }
-                    StructType::Create => !is_autogenerated,
                 }
             })
             .map(|c| {
-                let name = c.name.to_string();
-                let base_type = if c.is_nullable {
-                    format!("Option<{}>", c.ty)
-                } else if c.is_unsigned {
-                    c.ty.replace('i', "u")
-                } else {
-                    c.ty.clone()
-                };
+                let name = c.field_name();
+                let base_type = key_column_type(c, self.key_types);
                 let mut is_optional = false;
 
-                let is_pk = self
-                    .table
-                    .primary_key_columns
-                    .iter()
-                    .any(|pk| pk.to_string().eq(name.as_str()));
-                let is_autogenerated = self
-                    .opts
-                    .autogenerated_columns
-                    .as_deref()
-                    .unwrap_or_default()
-                    .contains(&c.name.to_string().as_str());
+                let is_pk = self.table.primary_key_columns.contains(&c.name);
+                let is_autogenerated = is_autogenerated(self.table, c, &self.opts);
                 // let is_fk = table.foreign_keys.iter().any(|fk| fk.1.to_string().eq(field_name.as_str()));
 
                 match self.ty {
                     StructType::Read => {}
-                    StructType::Form => {}
+                    StructType::Form => {
+                        // rows are identified by their keys, everything else may be left out
+                        is_optional = !is_pk;
+                    }
                     StructType::Update => {
                         // all non-key fields should be optional in Form structs (to allow partial updates)
-                        is_optional = !is_pk || (is_pk && is_autogenerated);
+                        is_optional = !is_pk || is_autogenerated;
                     }
                     StructType::Create => {}
                 }
 
                 StructField {
                     name,
+                    column_name: c.name.clone(),
                     base_type,
+                    doc_comments: c.doc_comments().iter().map(|d| d.to_string()).collect(),
                     is_optional,
                 }
             })
-            .collect()
+            .collect::<Vec<StructField>>();
+
+        // (stable) sorts keep the schema order among the primary key columns and among the other columns
+        match self.opts.get_field_order() {
+            FieldOrder::Schema => {}
+            FieldOrder::Alphabetical => fields.sort_by(|a, b| {
+                a.name
+                    .trim_start_matches("r#")
+                    .cmp(b.name.trim_start_matches("r#"))
+            }),
+            FieldOrder::PrimaryKeyFirst => {
+                fields.sort_by_key(|f| !self.table.primary_key_columns.contains(&f.column_name))
+            }
+        }
+
+        fields
     }
 
     fn render(&mut self) {
         let ty = self.ty;
         let table = &self.table;
-        let opts = self.config.table(table.name.to_string().as_str());
 
-        let primary_keys: Vec<String> = table.primary_key_column_names();
+        // derives refer to schema columns (which are matched against the fields' `column_name`)
+        let primary_keys: Vec<String> = table.primary_key_columns.clone();
 
         let belongs_to = table
             .foreign_keys
             .iter()
             .map(|fk| {
                 format!(
-                    ", belongs_to({foreign_table_name}, foreign_key={join_column})",
-                    foreign_table_name = fk.0.to_string().to_pascal_case().to_singular(),
+                    ", belongs_to({foreign_struct_name}, foreign_key={join_column})",
+                    foreign_struct_name = self
+                        .foreign_structs
+                        .iter()
+                        .find(|f| f.table_name == fk.0)
+                        .map(|f| f.reference.as_str())
+                        .unwrap_or_default(),
                     join_column = fk.1
                 )
             })
             .collect::<Vec<String>>()
             .join(" ");
 
+        let doc_comments = match ty {
+            StructType::Read => table
+                .doc_comments()
+                .iter()
+                .map(|d| format!("///{d}\n"))
+                .collect::<String>(),
+            _ => "".to_string(),
+        };
+
         let struct_code = format!(
             indoc! {r#"
-            {tsync_attr}{derive_attr}
-            #[diesel(table_name={table_name}{primary_key}{belongs_to})]
+            {doc_comments}{tsync_attr}{derive_attr}{diesel_attr}
             pub struct {struct_name} {{
             $COLUMNS$
             }}
         "#},
+            doc_comments = doc_comments,
             tsync_attr = self.attr_tsync(),
             derive_attr = self.attr_derive(),
-            table_name = table.name,
-            struct_name = ty.format(table.struct_name.as_str()),
-            primary_key = if ty != StructType::Read {
-                "".to_string()
-            } else {
-                format!(", primary_key({})", primary_keys.join(","))
+            diesel_attr = match ty {
+                StructType::Form => "".to_string(),
+                _ => format!(
+                    "\n#[diesel(table_name={table_name}{primary_key}{belongs_to})]",
+                    table_name = table.name,
+                    primary_key = if ty != StructType::Read {
+                        "".to_string()
+                    } else {
+                        format!(", primary_key({})", primary_keys.join(","))
+                    },
+                    belongs_to = if ty != StructType::Read {
+                        "".to_string()
+                    } else {
+                        belongs_to
+                    }
+                ),
             },
-            belongs_to = if ty != StructType::Read {
-                "".to_string()
-            } else {
-                belongs_to
-            }
+            struct_name = ty.format(table.struct_name.as_str()),
         );
 
+        // serde attributes need one of the serde derives
+        let has_serde = self.serde_derives() != SerdeDerives::None;
+
         let fields = self.fields();
         let mut lines = vec![];
         for f in fields.iter() {
@@ -243,232 +468,2709 @@ impl<'a> Struct<'a> {
                 f.base_type.clone()
             };
 
+            for doc_comment in f.doc_comments.iter() {
+                lines.push(format!(r#"    ///{doc_comment}"#));
+            }
+            if f.name != f.column_name && ty != StructType::Form {
+                lines.push(format!(
+                    r#"    #[diesel(column_name = {column_name})]"#,
+                    column_name = f.column_name
+                ));
+            }
+            if let Some(name) = self
+                .table
+                .column(&f.column_name)
+                .and_then(|c| c.serde_rename())
+                .filter(|_| has_serde)
+            {
+                lines.push(format!(r#"    #[serde(rename = "{name}")]"#));
+            }
+            // `QueryableByName` can't tell the types of the columns of hand-written queries
+            if ty == StructType::Read && self.opts.get_queryable_by_name() {
+                lines.push(format!(
+                    r#"    #[diesel(sql_type = diesel::dsl::SqlTypeOf<{schema_module}::{table_name}::{column_name}>)]"#,
+                    schema_module = self.opts.get_schema_module(),
+                    table_name = table.name,
+                    column_name = f.column_name
+                ));
+            }
+            if let Some(column) = self.table.column(&f.column_name).filter(|_| has_serde) {
+                for attribute in serde_attributes(column, f.is_optional, &self.opts) {
+                    lines.push(format!("    {attribute}"));
+                }
+            }
+            let is_sensitive = self
+                .opts
+                .get_sensitive_columns()
+                .contains(&f.column_name.as_str());
+            if ty == StructType::Read && is_sensitive && has_serde {
+                lines.push(r#"    #[serde(skip_serializing)]"#.to_string());
+            }
             lines.push(format!(r#"    pub {field_name}: {field_type},"#));
         }
 
+        let debug_impl = match self.sensitive_fields().is_empty() {
+            true => "".to_string(),
+            false => self.debug_impl(&fields),
+        };
+
         if fields.is_empty() {
             self.has_fields = Some(false);
             self.rendered_code = Some("".to_string());
         } else {
             self.has_fields = Some(true);
-            self.rendered_code = Some(struct_code.replace("$COLUMNS$", &lines.join("\n")));
+            self.rendered_code = Some(format!(
+                "{}{debug_impl}",
+                struct_code.replace("$COLUMNS$", &lines.join("\n"))
+            ));
         }
     }
 }
 
-fn build_table_fns(
+/// conversions of a Form struct into the Create struct (which fails when required fields are missing) and into the
+/// Update struct (which leaves out the keys, and columns which can't be updated)
+fn build_form_conversions(
     table: &ParsedTableMacro,
-    config: &GenerationConfig,
-    create_struct: Struct,
-    update_struct: Struct,
+    form_struct: &Struct,
+    create_struct: &Struct,
+    update_struct: &Struct,
 ) -> String {
-    let table_options = config.table(&table.name.to_string());
-
-    let primary_column_name_and_type: Vec<(String, String)> = table
-        .primary_key_columns
-        .iter()
-        .map(|pk| {
-            let col = table
-                .columns
-                .iter()
-                .find(|it| it.name.to_string().eq(pk.to_string().as_str()))
-                .expect("Primary key column doesn't exist in table");
-
-            (col.name.to_string(), col.ty.to_string())
-        })
-        .collect();
-
-    let item_id_params = primary_column_name_and_type
-        .iter()
-        .map(|name_and_type| {
-            format!(
-                "param_{name}: {ty}",
-                name = name_and_type.0,
-                ty = name_and_type.1
-            )
-        })
-        .collect::<Vec<String>>()
-        .join(", ");
-    let item_id_filters = primary_column_name_and_type
-        .iter()
-        .map(|name_and_type| {
-            format!(
-                "filter({name}.eq(param_{name}))",
-                name = name_and_type.0.to_string()
-            )
-        })
-        .collect::<Vec<String>>()
-        .join(".");
-
-    // template variables
-    let table_name = table.name.to_string();
-    #[cfg(feature = "tsync")]
-    let tsync = match table_options.get_tsync() {
-        true => "#[tsync::tsync]",
-        false => "",
-    };
-    #[cfg(not(feature = "tsync"))]
-    let tsync = "";
-    let struct_name = &table.struct_name;
-    let create_struct_identifier = &create_struct.identifier;
-    let update_struct_identifier = &update_struct.identifier;
-    let item_id_params = item_id_params;
-    let item_id_filters = item_id_filters;
-
+    let form_fields = form_struct.fields();
+    let form_struct_name = &form_struct.identifier;
     let mut buffer = String::new();
 
-    buffer.push_str(&format!(
-        r##"{tsync}
-#[derive(Debug, Serialize)]
-pub struct PaginationResult<T> {{
-    pub items: Vec<T>,
-    pub total_items: i64,
-    /// 0-based index
-    pub page: i64,
-    pub page_size: i64,
-    pub num_pages: i64,
-}}
-"##
-    ));
+    if create_struct.has_fields() {
+        let fields = create_struct
+            .fields()
+            .iter()
+            .map(|f| {
+                let name = &f.name;
+                let value = match form_fields.iter().find(|ff| ff.name == f.name) {
+                    Some(ff) if !ff.is_optional => format!("form.{name}"),
+                    // left out nullable fields are `NULL`
+                    Some(_) if table.column(&f.column_name).is_some_and(|c| c.is_nullable) => {
+                        format!("form.{name}.flatten()")
+                    }
+                    Some(_) => format!(
+                        "form.{name}.ok_or(\"the `{}` field is required\")?",
+                        f.name.trim_start_matches("r#")
+                    ),
+                    None => "Default::default()".to_string(),
+                };
 
-    buffer.push_str(&format!(
-        r##"
-impl {struct_name} {{
-"##
-    ));
+                format!("            {name}: {value},\n")
+            })
+            .collect::<String>();
 
-    if create_struct.has_fields() {
         buffer.push_str(&format!(
             r##"
-    pub fn create(db: &mut Connection, item: &{create_struct_identifier}) -> QueryResult<Self> {{
-        use crate::schema::{table_name}::dsl::*;
+impl TryFrom<{form_struct_name}> for {create_struct_name} {{
+    type Error = &'static str;
 
-        insert_into({table_name}).values(item).get_result::<Self>(db)
+    fn try_from(form: {form_struct_name}) -> Result<Self, Self::Error> {{
+        Ok(Self {{
+{fields}        }})
     }}
-"##
+}}
+"##,
+            create_struct_name = create_struct.identifier
         ));
-    } else {
+    }
+
+    if update_struct.has_fields() {
+        let fields = update_struct
+            .fields()
+            .iter()
+            .map(|f| {
+                let value = match form_fields.iter().any(|ff| ff.name == f.name) {
+                    true => format!("form.{}", f.name),
+                    // autogenerated columns aren't part of forms
+                    false => "None".to_string(),
+                };
+
+                format!("            {}: {value},\n", f.name)
+            })
+            .collect::<String>();
+
         buffer.push_str(&format!(
             r##"
-    pub fn create(db: &mut Connection) -> QueryResult<Self> {{
-        use crate::schema::{table_name}::dsl::*;
-
-        insert_into({table_name}).default_values().get_result::<Self>(db)
+impl From<{form_struct_name}> for {update_struct_name} {{
+    fn from(form: {form_struct_name}) -> Self {{
+        Self {{
+{fields}        }}
     }}
-"##
+}}
+"##,
+            update_struct_name = update_struct.identifier
         ));
     }
 
-    buffer.push_str(&format!(
-        r##"
-    pub fn read(db: &mut Connection, {item_id_params}) -> QueryResult<Self> {{
-        use crate::schema::{table_name}::dsl::*;
+    buffer
+}
+
+/// `is_empty` on the Update struct, as updates without changes would have an empty (invalid) `SET` clause
+fn build_update_is_empty(update_struct: &Struct) -> String {
+    if !update_struct.has_fields() {
+        return "".to_string();
+    }
 
-        {table_name}.{item_id_filters}.first::<Self>(db)
+    let conditions = update_struct
+        .fields()
+        .iter()
+        .map(|f| format!("self.{}.is_none()", f.name))
+        .collect::<Vec<String>>()
+        .join("\n            && ");
+
+    format!(
+        r##"
+impl {update_struct_name} {{
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {{
+        {conditions}
     }}
-"##
-    ));
+}}
+"##,
+        update_struct_name = update_struct.identifier
+    )
+}
 
-    buffer.push_str(&format!(r##"
-    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page)
-    pub fn paginate(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<PaginationResult<Self>> {{
-        use crate::schema::{table_name}::dsl::*;
+/// the proptest strategy generating values of a rust type, keeping strings within `max_length` characters (and free
+/// of the control characters databases reject, like NUL); types without a known strategy need an `Arbitrary` impl
+fn proptest_strategy(ty: &str, max_length: Option<&str>) -> String {
+    if let Some(inner) = ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+        return format!(
+            "proptest::option::of({})",
+            proptest_strategy(inner, max_length)
+        );
+    }
+    if ty == "Vec<u8>" {
+        return "proptest::collection::vec(any::<u8>(), 0..64)".to_string();
+    }
+    if let Some(inner) = ty.strip_prefix("Vec<").and_then(|t| t.strip_suffix('>')) {
+        return format!(
+            "proptest::collection::vec({}, 0..8)",
+            proptest_strategy(inner, max_length)
+        );
+    }
 
-        let page_size = if page_size < 1 {{ 1 }} else {{ page_size }};
-        let total_items = {table_name}.count().get_result(db)?;
-        let items = {table_name}.limit(page_size).offset(page * page_size).load::<Self>(db)?;
+    // timestamps are kept between 1970 and 2100
+    let timestamp =
+        "(0i64..4_102_444_800).prop_map(|secs| chrono::DateTime::from_timestamp(secs, 0).unwrap())";
+    match ty {
+        "String" => match max_length {
+            Some(max_length) => format!("\"\\\\PC{{0,{max_length}}}\""),
+            None => "\"\\\\PC*\"".to_string(),
+        },
+        "uuid::Uuid" => "any::<u128>().prop_map(uuid::Uuid::from_u128)".to_string(),
+        "chrono::DateTime<chrono::Utc>" => timestamp.to_string(),
+        "chrono::NaiveDateTime" => format!("{timestamp}.prop_map(|t| t.naive_utc())"),
+        "chrono::NaiveDate" => format!("{timestamp}.prop_map(|t| t.date_naive())"),
+        "chrono::NaiveTime" => "(0u32..86_400).prop_map(|secs| chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs, 0).unwrap())".to_string(),
+        "serde_json::Value" => "\"\\\\PC*\".prop_map(serde_json::Value::String)".to_string(),
+        _ => format!("any::<{ty}>()"),
+    }
+}
 
-        Ok(PaginationResult {{
-            items,
-            total_items,
-            page,
-            page_size,
-            /* ceiling division of integers */
-            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
-        }})
-    }}
-"##));
+/// proptest strategies (and `Arbitrary` impls) for the Create and Update structs, behind the `proptest` feature
+fn build_proptest_strategies(
+    table: &ParsedTableMacro,
+    key_types: &[(String, String)],
+    structs: [&Struct; 2],
+) -> String {
+    let mut buffer = String::new();
 
-    // TODO: If primary key columns are attached to the form struct (not optionally)
-    // then don't require item_id_params (otherwise it'll be duplicated)
+    for s in structs.into_iter().filter(|s| s.has_fields()) {
+        let fields = s.fields();
+        let strategies = fields
+            .iter()
+            .map(|f| {
+                let column = table
+                    .column(&f.column_name)
+                    .expect("Struct field doesn't map to a column");
+                let max_length = column
+                    .attribute("max_length")
+                    .and_then(|a| a.value.as_deref());
 
-    // if has_update_struct {
-    if update_struct.has_fields() {
-        // It's possible we have a form struct with all primary keys (for example, for a join table).
-        // In this scenario, we also have to check whether there are any updatable columns for which
-        // we should generate an update() method.
+                // newtype keys wrap the strategy of their column's type
+                let strategy = match key_types.iter().find(|(c, _)| c == &column.name) {
+                    Some((_, ty)) if column.is_nullable => format!(
+                        "proptest::option::of({}.prop_map({ty}))",
+                        proptest_strategy(&column.ty, max_length)
+                    ),
+                    Some((_, ty)) => format!(
+                        "{}.prop_map({ty})",
+                        proptest_strategy(&column.ty, max_length)
+                    ),
+                    None => proptest_strategy(&f.base_type, max_length),
+                };
 
-        buffer.push_str(&format!(r##"
-    pub fn update(db: &mut Connection, {item_id_params}, item: &{update_struct_identifier}) -> QueryResult<Self> {{
-        use crate::schema::{table_name}::dsl::*;
+                match f.is_optional {
+                    true => format!("proptest::option::of({strategy})"),
+                    false => strategy,
+                }
+            })
+            .collect::<Vec<String>>();
+        let names = fields
+            .iter()
+            .map(|f| f.name.clone())
+            .collect::<Vec<String>>();
 
-        diesel::update({table_name}.{item_id_filters}).set(item).get_result(db)
-    }}
-"##));
-    }
+        let tuple = |items: &[String]| match items {
+            [item] => format!("({item},)"),
+            items => format!("({})", items.join(", ")),
+        };
 
-    buffer.push_str(&format!(
-        r##"
-    pub fn delete(db: &mut Connection, {item_id_params}) -> QueryResult<usize> {{
-        use crate::schema::{table_name}::dsl::*;
+        // proptest implements `Strategy` for tuples of up to 12 strategies, so larger structs nest them
+        let (strategies, pattern) = match fields.len() {
+            0..=12 => (strategies, tuple(&names)),
+            _ => (
+                strategies.chunks(12).map(tuple).collect(),
+                tuple(&names.chunks(12).map(tuple).collect::<Vec<String>>()),
+            ),
+        };
+        let strategies = strategies
+            .iter()
+            .map(|strategy| format!("            {strategy},\n"))
+            .collect::<String>();
+
+        buffer.push_str(&format!(
+            r##"
+#[cfg(feature = "proptest")]
+impl {struct_name} {{
+    /// A proptest strategy generating `{struct_name}`s with values fitting the columns' types (and max lengths)
+    pub fn strategy() -> impl proptest::strategy::Strategy<Value = Self> {{
+        use proptest::prelude::*;
 
-        diesel::delete({table_name}.{item_id_filters}).execute(db)
+        (
+{strategies}        )
+            .prop_map(|{pattern}| Self {{ {fields} }})
     }}
-"##
-    ));
+}}
 
-    buffer.push_str(&format!(
-        r##"
-}}"##
-    ));
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for {struct_name} {{
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {{
+        proptest::strategy::Strategy::boxed(Self::strategy())
+    }}
+}}
+"##,
+            struct_name = s.identifier,
+            fields = names.join(", ")
+        ));
+    }
 
     buffer
 }
 
-fn build_imports(table: &ParsedTableMacro, config: &GenerationConfig) -> String {
-    let belongs_imports = table
-        .foreign_keys
-        .iter()
-        .map(|fk| {
-            format!(
-                "use crate::models::{foreign_table_name_model}::{singular_struct_name};",
-                foreign_table_name_model = fk.0.to_string().to_snake_case().to_lowercase(),
-                singular_struct_name = fk.0.to_string().to_pascal_case().to_singular()
-            )
-        })
+/// names and fields of the Read, Create and Update structs generated for a table (in that order)
+pub fn struct_fields(
+    table: &ParsedTableMacro,
+    config: &GenerationConfig,
+) -> Vec<(String, Vec<StructField>)> {
+    [StructType::Read, StructType::Create, StructType::Update]
+        .into_iter()
+        .map(|ty| {
+            let s = Struct::new(ty, table, config, &[], &[]);
+            (s.identifier.clone(), s.fields())
+        })
+        .collect()
+}
+
+/// the table's single uuid primary key, if the table's options enable uuid primary keys
+fn uuid_key<'a>(
+    table: &'a ParsedTableMacro,
+    opts: &TableOptions,
+) -> Option<(&'a ParsedColumnMacro, UuidVersion)> {
+    let version = opts.get_uuid_primary_key()?;
+    let [pk] = table.primary_key_columns.as_slice() else {
+        return None;
+    };
+
+    table
+        .column(pk)
+        .filter(|c| c.ty == "uuid::Uuid" && !c.is_nullable)
+        .map(|c| (c, version))
+}
+
+/// the primary key which `create` generates values for (it's left out of the Create struct) along with the expression
+/// generating them: the configured id generator, or a new uuid for uuid primary keys; keys autogenerated by the
+/// database are left alone
+fn generated_key<'a>(
+    table: &'a ParsedTableMacro,
+    opts: &TableOptions,
+) -> Option<(&'a ParsedColumnMacro, String)> {
+    let [pk] = table.primary_key_columns.as_slice() else {
+        return None;
+    };
+    let pk = table.column(pk)?;
+    if is_autogenerated(table, pk, opts) {
+        return None;
+    }
+
+    match opts.get_id_generator() {
+        Some(expression) => Some((pk, expression.to_string())),
+        None => uuid_key(table, opts).map(|(pk, version)| {
+            let constructor = match version {
+                UuidVersion::V4 => "new_v4",
+                UuidVersion::V7 => "now_v7",
+            };
+
+            (pk, format!("uuid::Uuid::{constructor}()"))
+        }),
+    }
+}
+
+/// database defaults which generate values (compared case-insensitively)
+const GENERATING_DEFAULTS: &[&str] = &[
+    "nextval(",
+    "now()",
+    "current_timestamp",
+    "gen_random_uuid()",
+    "uuid_generate_v4()",
+];
+
+/// whether the database generates the column's values: identity columns and columns with generating defaults (both
+/// only known from introspected IR attributes), single integer or `id` uuid primary keys, and `created_at`/`updated_at`
+/// timestamps
+fn is_inferred_autogenerated(table: &ParsedTableMacro, column: &ParsedColumnMacro) -> bool {
+    if column.attribute("identity").is_some() {
+        return true;
+    }
+    if let Some(default) = column.attribute("default").and_then(|a| a.value.as_deref()) {
+        let default = default.to_lowercase();
+        if GENERATING_DEFAULTS.iter().any(|d| default.contains(d)) {
+            return true;
+        }
+    }
+
+    let sql_type = column.sql_type.as_str();
+    let is_single_pk =
+        table.primary_key_columns.len() == 1 && table.primary_key_columns[0] == column.name;
+    if is_single_pk {
+        let is_integer =
+            ["Int2", "Int4", "Int8", "SmallInt", "Integer", "BigInt"].contains(&sql_type);
+        if is_integer || (sql_type == "Uuid" && column.name == "id") {
+            return true;
+        }
+    }
+
+    let is_timestamp = ["Timestamp", "Timestamptz", "Datetime"].contains(&sql_type);
+    is_timestamp && ["created_at", "updated_at"].contains(&column.name.to_snake_case().as_str())
+}
+
+/// whether a column is left out of Create structs: it's listed in the autogenerated columns, or inferred to be
+/// autogenerated (see [`is_inferred_autogenerated`]) when inference is enabled
+fn is_autogenerated(
+    table: &ParsedTableMacro,
+    column: &ParsedColumnMacro,
+    opts: &TableOptions,
+) -> bool {
+    opts.get_autogenerated_columns()
+        .contains(&column.name.as_str())
+        || (opts.get_infer_autogenerated_columns() && is_inferred_autogenerated(table, column))
+}
+
+/// path of a table's read struct as seen from other models (for imports and `belongs_to`)
+fn struct_path(table_name: &str, tables: &[ParsedTableMacro], config: &GenerationConfig) -> String {
+    let table_options = config.table(table_name);
+
+    match table_options.get_struct_path() {
+        Some(path) => path.to_string(),
+        None => format!(
+            "{models_module}::{model}::{struct_name}",
+            models_module = table_options.get_models_module(),
+            model = model_module(table_name, tables, config),
+            struct_name = transliterate(table_name).to_pascal_case().to_singular()
+        ),
+    }
+}
+
+/// path of a table's module inside the models module (grouped tables live in the module of their group, see
+/// `ParsedTableMacro::group`)
+pub fn model_module(
+    table_name: &str,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> String {
+    let table_options = config.table(table_name);
+    let model = match table_options.get_file_naming() {
+        FileNaming::Table => snake_case_identifier(table_name).to_lowercase(),
+        naming => module_name(table_name, naming),
+    };
+
+    let group = match tables.iter().find(|t| t.name == table_name) {
+        Some(table) => table.group(config),
+        None => table_options.get_group(),
+    };
+    match group {
+        Some(group) => format!("{group}::{model}"),
+        None => model,
+    }
+}
+
+/// the name a struct is imported as (the last segment of its path)
+fn struct_path_name(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// How a model refers to the struct of a table it has a foreign key to
+#[derive(Debug, Clone)]
+struct ForeignStruct {
+    table_name: String,
+    /// the struct's name if it's imported, otherwise its fully-qualified path
+    reference: String,
+    /// path for the model's `use` statement (if the struct is imported)
+    import: Option<String>,
+}
+
+/// whether `to` can be reached from `from` by following foreign keys
+fn references_table(from: &str, to: &str, tables: &[ParsedTableMacro]) -> bool {
+    let mut visited = vec![from];
+    let mut pending = vec![from];
+
+    while let Some(name) = pending.pop() {
+        let Some(table) = tables.iter().find(|t| t.name == name) else {
+            continue;
+        };
+
+        for (foreign_table_name, _) in table.foreign_keys.iter() {
+            if foreign_table_name == to {
+                return true;
+            }
+            if !visited.contains(&foreign_table_name.as_str()) {
+                visited.push(foreign_table_name);
+                pending.push(foreign_table_name);
+            }
+        }
+    }
+
+    false
+}
+
+/// Resolves how `table` refers to the structs of the tables it references. Structs are imported by name unless the
+/// reference is part of a foreign key cycle or the name clashes with something else in the model, in which case the
+/// fully-qualified path is used instead (references to the table itself use the local struct).
+fn foreign_structs(
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> Vec<ForeignStruct> {
+    let struct_name = &table.struct_name;
+    let mut local_names = vec![
+        struct_name.clone(),
+        StructType::Create.format(struct_name),
+        StructType::Update.format(struct_name),
+        format!("{struct_name}Filter"),
+        format!("{struct_name}FilterExpression"),
+        format!("{struct_name}Column"),
+        format!("{struct_name}Query"),
+        format!("{struct_name}QueryExt"),
+        "PaginationResult".to_string(),
+        "FastPaginationResult".to_string(),
+        "SortDirection".to_string(),
+        "Connection".to_string(),
+        "ReadConnection".to_string(),
+        "WriteConnection".to_string(),
+    ];
+    local_names.extend(
+        config
+            .table(&table.name)
+            .get_projections()
+            .iter()
+            .map(|p| p.name.to_string()),
+    );
+
+    let mut foreign_structs: Vec<ForeignStruct> = vec![];
+    for (foreign_table_name, _) in table.foreign_keys.iter() {
+        if foreign_structs
+            .iter()
+            .any(|f| &f.table_name == foreign_table_name)
+        {
+            continue;
+        }
+
+        if foreign_table_name == &table.name {
+            foreign_structs.push(ForeignStruct {
+                table_name: foreign_table_name.clone(),
+                reference: struct_name.clone(),
+                import: None,
+            });
+            continue;
+        }
+
+        let path = struct_path(foreign_table_name, tables, config);
+        let name = struct_path_name(&path).to_string();
+        let is_cyclic = references_table(foreign_table_name, &table.name, tables);
+        let is_clashing = local_names.contains(&name);
+
+        if is_cyclic || is_clashing {
+            foreign_structs.push(ForeignStruct {
+                table_name: foreign_table_name.clone(),
+                reference: path,
+                import: None,
+            });
+        } else {
+            local_names.push(name.clone());
+            foreign_structs.push(ForeignStruct {
+                table_name: foreign_table_name.clone(),
+                reference: name,
+                import: Some(path),
+            });
+        }
+    }
+
+    foreign_structs
+}
+
+/// rust types which can be wrapped by key newtypes (they're `Eq` and `Hash`, as `Identifiable` requires)
+const NEWTYPE_ID_TYPES: &[&str] = &["i16", "i32", "i64", "String", "uuid::Uuid"];
+
+/// the newtype wrapping a table's primary key as `(name, key column)`, if the table's options enable newtype ids and the
+/// table has a single primary key of a supported type (diesel models only)
+fn id_newtype<'a>(
+    table: &'a ParsedTableMacro,
+    config: &GenerationConfig,
+) -> Option<(String, &'a ParsedColumnMacro)> {
+    let table_options = config.table(&table.name);
+    if !table_options.get_newtype_ids() || table_options.get_target() != Target::Diesel {
+        return None;
+    }
+
+    let [pk] = table.primary_key_columns.as_slice() else {
+        return None;
+    };
+    let column = table.column(pk)?;
+    let is_supported = !column.is_nullable
+        && !column.is_unsigned
+        && NEWTYPE_ID_TYPES.contains(&column.ty.as_str());
+
+    is_supported.then(|| (format!("{}Id", table.struct_name), column))
+}
+
+/// the newtypes of a table's key columns as `(column, type)`: the table's own primary key and the foreign keys to tables
+/// with newtype ids (when the foreign key has the referenced key's sql type); newtypes of other tables are referred to by
+/// their full path (the referenced struct's path with an `Id` suffix)
+fn key_types(
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> Vec<(String, String)> {
+    let mut key_types = vec![];
+    if let Some((name, column)) = id_newtype(table, config) {
+        key_types.push((column.name.clone(), name));
+    }
+
+    for (foreign_table_name, join_column) in table.foreign_keys.iter() {
+        let Some(foreign_table) = tables.iter().find(|t| &t.name == foreign_table_name) else {
+            continue;
+        };
+        let Some((name, foreign_key)) = id_newtype(foreign_table, config) else {
+            continue;
+        };
+        let is_matching = table
+            .column(join_column)
+            .is_some_and(|c| c.sql_type == foreign_key.sql_type);
+        if !is_matching || key_types.iter().any(|(c, _)| c == join_column) {
+            continue;
+        }
+
+        let ty = match foreign_table_name == &table.name {
+            true => name,
+            false => format!("{}Id", struct_path(foreign_table_name, tables, config)),
+        };
+        key_types.push((join_column.clone(), ty));
+    }
+
+    key_types
+}
+
+/// the rust type of a column, using its key newtype (if there is one)
+fn key_column_type(column: &ParsedColumnMacro, key_types: &[(String, String)]) -> String {
+    match key_types.iter().find(|(c, _)| c == &column.name) {
+        Some((_, ty)) if column.is_nullable => format!("Option<{ty}>"),
+        Some((_, ty)) => ty.clone(),
+        None => column_type(column),
+    }
+}
+
+/// the newtype of the table's primary key along with its diesel, serde, `Display` and conversion impls
+fn build_id_newtype(table: &ParsedTableMacro, config: &GenerationConfig) -> String {
+    let Some((name, column)) = id_newtype(table, config) else {
+        return "".to_string();
+    };
+    let inner = &column.ty;
+    let sql_type = match column.sql_type.contains("::") {
+        true => column.sql_type.clone(),
+        false => format!("diesel::sql_types::{}", column.sql_type),
+    };
+    let copy = if inner == "String" { "" } else { ", Copy" };
+
+    format!(
+        r##"/// The primary key of `{table_name}` rows, which can't be mixed up with keys of other tables
+#[derive(Debug, Clone{copy}, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, diesel::AsExpression, diesel::FromSqlRow)]
+#[diesel(sql_type = {sql_type})]
+#[serde(transparent)]
+pub struct {name}(pub {inner});
+
+impl<DB: diesel::backend::Backend> diesel::serialize::ToSql<{sql_type}, DB> for {name}
+where
+    {inner}: diesel::serialize::ToSql<{sql_type}, DB>,
+{{
+    fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, DB>) -> diesel::serialize::Result {{
+        <{inner} as diesel::serialize::ToSql<{sql_type}, DB>>::to_sql(&self.0, out)
+    }}
+}}
+
+impl<DB: diesel::backend::Backend> diesel::deserialize::FromSql<{sql_type}, DB> for {name}
+where
+    {inner}: diesel::deserialize::FromSql<{sql_type}, DB>,
+{{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {{
+        <{inner} as diesel::deserialize::FromSql<{sql_type}, DB>>::from_sql(bytes).map(Self)
+    }}
+}}
+
+impl std::fmt::Display for {name} {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        std::fmt::Display::fmt(&self.0, f)
+    }}
+}}
+
+impl From<{inner}> for {name} {{
+    fn from(id: {inner}) -> Self {{
+        Self(id)
+    }}
+}}
+
+impl From<{name}> for {inner} {{
+    fn from(id: {name}) -> Self {{
+        id.0
+    }}
+}}
+
+"##,
+        table_name = table.name,
+    )
+}
+
+/// diesel types without serde impls, which the models (de)serialize through the generated `pg_serde` module
+const PG_SERDE_TYPES: &[&str] = &[
+    "diesel::data_types::PgInterval",
+    "diesel::data_types::PgMoney",
+];
+
+/// the serde attributes of fields holding values of the column's type: the attributes of its type mapping, and the
+/// `with` attribute if the type needs `pg_serde` (or `base64_serde`, for binary columns of tables serializing them as
+/// base64); fields of `Option` types default to `None`, like plain `Option` fields do
+fn serde_attributes(
+    column: &ParsedColumnMacro,
+    is_optional: bool,
+    table_options: &TableOptions,
+) -> Vec<String> {
+    let mut attributes = column.serde_attributes.clone();
+    let module = if PG_SERDE_TYPES.contains(&column.ty.as_str()) {
+        "pg_serde"
+    } else if column.ty == "Vec<u8>" && table_options.get_binary_format() == BinaryFormat::Base64 {
+        "base64_serde"
+    } else {
+        return attributes;
+    };
+
+    attributes.push(match is_optional || column.is_nullable {
+        true => format!(r#"#[serde(default, with = "{module}")]"#),
+        false => format!(r#"#[serde(with = "{module}")]"#),
+    });
+
+    attributes
+}
+/// the modules (de)serializing values of the table's fields which serde can't handle as we'd like (see
+/// `serde_attributes`), if the table or its projections use them
+fn build_serde_modules(
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> String {
+    let table_options = config.table(&table.name);
+    let joined_columns = table_options
+        .get_projections()
+        .iter()
+        .flat_map(|p| p.joined_columns.clone())
+        .filter_map(|(joined_table_name, column_name)| {
+            tables
+                .iter()
+                .find(|t| t.name == joined_table_name)
+                .and_then(|t| t.column(column_name))
+                .map(|c| (c, config.table(joined_table_name)))
+        })
+        .collect::<Vec<(&ParsedColumnMacro, TableOptions)>>();
+    let modules = table
+        .columns
+        .iter()
+        .map(|c| (c, table_options.clone()))
+        .chain(joined_columns)
+        .flat_map(|(c, options)| serde_attributes(c, false, &options))
+        .collect::<Vec<String>>();
+
+    let mut buffer = String::new();
+    if modules.iter().any(|m| m.contains("pg_serde")) {
+        buffer.push_str(PG_SERDE_MODULE);
+    }
+    if modules.iter().any(|m| m.contains("base64_serde")) {
+        buffer.push_str(BASE64_SERDE_MODULE);
+    }
+
+    buffer
+}
+
+const PG_SERDE_MODULE: &str = r##"/// (de)serializes diesel's `PgMoney` (as its amount in the smallest currency unit) and `PgInterval` (as
+/// `[microseconds, days, months]`) values, which don't implement serde's traits
+mod pg_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub trait Repr: Sized {
+        type Repr: Serialize + for<'de> Deserialize<'de>;
+
+        fn to_repr(&self) -> Self::Repr;
+        fn from_repr(repr: Self::Repr) -> Self;
+    }
+
+    impl Repr for diesel::data_types::PgMoney {
+        type Repr = i64;
+
+        fn to_repr(&self) -> Self::Repr {
+            self.0
+        }
+
+        fn from_repr(repr: Self::Repr) -> Self {
+            Self(repr)
+        }
+    }
+
+    impl Repr for diesel::data_types::PgInterval {
+        type Repr = (i64, i32, i32);
+
+        fn to_repr(&self) -> Self::Repr {
+            (self.microseconds, self.days, self.months)
+        }
+
+        fn from_repr((microseconds, days, months): Self::Repr) -> Self {
+            Self { microseconds, days, months }
+        }
+    }
+
+    impl<T: Repr> Repr for Option<T> {
+        type Repr = Option<T::Repr>;
+
+        fn to_repr(&self) -> Self::Repr {
+            self.as_ref().map(T::to_repr)
+        }
+
+        fn from_repr(repr: Self::Repr) -> Self {
+            repr.map(T::from_repr)
+        }
+    }
+
+    pub fn serialize<T: Repr, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.to_repr().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Repr, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::Repr::deserialize(deserializer).map(T::from_repr)
+    }
+}
+
+"##;
+
+const BASE64_SERDE_MODULE: &str = r##"/// (de)serializes binary values as base64 strings, rather than as arrays of numbers
+mod base64_serde {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub trait Base64: Sized {
+        type Repr: Serialize + for<'de> Deserialize<'de>;
+
+        fn encode(&self) -> Self::Repr;
+        fn decode(repr: Self::Repr) -> Result<Self, base64::DecodeError>;
+    }
+
+    impl Base64 for Vec<u8> {
+        type Repr = String;
+
+        fn encode(&self) -> Self::Repr {
+            STANDARD.encode(self)
+        }
+
+        fn decode(repr: Self::Repr) -> Result<Self, base64::DecodeError> {
+            STANDARD.decode(repr)
+        }
+    }
+
+    impl<T: Base64> Base64 for Option<T> {
+        type Repr = Option<T::Repr>;
+
+        fn encode(&self) -> Self::Repr {
+            self.as_ref().map(T::encode)
+        }
+
+        fn decode(repr: Self::Repr) -> Result<Self, base64::DecodeError> {
+            repr.map(T::decode).transpose()
+        }
+    }
+
+    pub fn serialize<T: Base64, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.encode().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Base64, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::decode(T::Repr::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+"##;
+
+/// the rust type of a column, taking nullability and signedness into account
+pub fn column_type(column: &ParsedColumnMacro) -> String {
+    if column.is_nullable {
+        format!("Option<{}>", column.ty)
+    } else if column.is_unsigned {
+        column.ty.replace('i', "u")
+    } else {
+        column.ty.clone()
+    }
+}
+
+/// how generated queries refer to the table's module as `(path, import)`: by its name after importing it into each
+/// function, or by its full path (with fully qualified paths)
+fn table_module(
+    table: &ParsedTableMacro,
+    table_options: &TableOptions,
+) -> (String, Option<String>) {
+    let schema_module = table_options.get_schema_module();
+
+    match table_options.get_fully_qualified_paths() {
+        true => (format!("{schema_module}::{}", table.name), None),
+        false => (
+            table.name.clone(),
+            Some(format!("use {schema_module}::{};", table.name)),
+        ),
+    }
+}
+
+fn build_filter(
+    table: &ParsedTableMacro,
+    config: &GenerationConfig,
+    key_types: &[(String, String)],
+) -> String {
+    let table_options = config.table(&table.name);
+    let schema_module = table_options.get_schema_module();
+    let table_name = &table.name;
+    let struct_name = &table.struct_name;
+    let (table_path, table_import) = table_module(table, &table_options);
+    let table_import = table_import
+        .map(|import| format!("        {import}\n\n"))
+        .unwrap_or_default();
+    #[cfg(feature = "tsync")]
+    let tsync = match table_options.get_tsync() {
+        true => "#[tsync::tsync]\n",
+        false => "",
+    };
+    #[cfg(not(feature = "tsync"))]
+    let tsync = "";
+
+    // tsvector columns can't be compared for equality, and sensitive columns are left out so that their values can't
+    // end up in the filter's `Debug` output
+    let columns: Vec<&ParsedColumnMacro> = table
+        .columns
+        .iter()
+        .filter(|c| !c.is_tsvector())
+        .filter(|c| {
+            !table_options
+                .get_sensitive_columns()
+                .contains(&c.name.as_str())
+        })
+        .collect();
+
+    let fields = columns
+        .iter()
+        .map(|column| {
+            format!(
+                "{rename}{attributes}    pub {name}: Option<{ty}>,\n",
+                rename = column
+                    .serde_rename()
+                    .map(|name| format!("    #[serde(rename = \"{name}\")]\n"))
+                    .unwrap_or_default(),
+                attributes = serde_attributes(column, true, &table_options)
+                    .iter()
+                    .map(|a| format!("    {a}\n"))
+                    .collect::<String>(),
+                name = table.field_name(&column.name),
+                ty = key_column_type(column, key_types)
+            )
+        })
+        .collect::<String>();
+
+    // nullable columns are filtered with `Some(None)` for `IS NULL` and `Some(Some(value))` for equality
+    let conditions = columns
+        .iter()
+        .map(|column| {
+            let name = table.field_name(&column.name);
+            let column_name = &column.name;
+
+            if column.is_nullable {
+                format!(
+                    r##"
+        match &self.{name} {{
+            Some(Some(value)) => expression = Box::new(expression.and({table_path}::{column_name}.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and({table_path}::{column_name}.is_null())),
+            None => {{}}
+        }}"##
+                )
+            } else {
+                format!(
+                    r##"
+        if let Some(value) = &self.{name} {{
+            expression = Box::new(expression.and({table_path}::{column_name}.eq(value.clone())));
+        }}"##
+                )
+            }
+        })
+        .collect::<String>();
+    let is_empty = match columns.is_empty() {
+        true => "true".to_string(),
+        false => columns
+            .iter()
+            .map(|column| format!("self.{}.is_none()", table.field_name(&column.name)))
+            .collect::<Vec<String>>()
+            .join(" && "),
+    };
+
+    format!(
+        r##"
+{tsync}/// Conditions on `{table_name}` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct {struct_name}Filter {{
+{fields}}}
+
+pub type {struct_name}FilterExpression = Box<dyn BoxableExpression<{schema_module}::{table_name}::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl {struct_name}Filter {{
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {{
+        {is_empty}
+    }}
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> {struct_name}FilterExpression {{
+{table_import}        let mut expression: {struct_name}FilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));{conditions}
+
+        expression
+    }}
+}}
+"##
+    )
+}
+
+fn build_projections(
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+    key_types: &[(String, String)],
+) -> String {
+    let table_options = config.table(&table.name);
+    let schema_module = table_options.get_schema_module();
+    let prologue = function_prologue(&table_options);
+    let table_name = &table.name;
+    #[cfg(feature = "tsync")]
+    let tsync = match table_options.get_tsync() {
+        true => "#[tsync::tsync]\n",
+        false => "",
+    };
+    #[cfg(not(feature = "tsync"))]
+    let tsync = "";
+
+    let (read_connection, _) = connection_types(config);
+    let mut buffer = String::new();
+
+    for projection in table_options.get_projections() {
+        let struct_name = projection.name;
+        let mut fields = vec![];
+        let mut selection = vec![];
+
+        for column_name in projection.columns.iter() {
+            let column = table
+                .column(column_name)
+                .expect("the columns of projections are validated before rendering");
+
+            fields.extend(
+                serde_attributes(column, false, &table_options)
+                    .iter()
+                    .map(|a| format!("    {a}")),
+            );
+            fields.push(format!(
+                "    pub {name}: {ty},",
+                name = column.field_name(),
+                ty = key_column_type(column, key_types)
+            ));
+            selection.push(format!("{table_name}::{}", column.name));
+        }
+
+        let mut joined_tables: Vec<&str> = vec![];
+        for (joined_table_name, column_name) in projection.joined_columns.iter() {
+            let column = tables
+                .iter()
+                .find(|t| t.name == *joined_table_name)
+                .and_then(|t| t.column(column_name))
+                .expect("the joined columns of projections are validated before rendering");
+
+            fields.extend(
+                serde_attributes(column, false, &config.table(joined_table_name))
+                    .iter()
+                    .map(|a| format!("    {a}")),
+            );
+            fields.push(format!(
+                "    pub {prefix}_{name}: {ty},",
+                prefix = snake_case_identifier(joined_table_name).to_singular(),
+                name = column.field_name().trim_start_matches("r#"),
+                ty = column_type(column)
+            ));
+            selection.push(format!("{joined_table_name}::{}", column.name));
+
+            if !joined_tables.contains(joined_table_name) {
+                joined_tables.push(joined_table_name);
+            }
+        }
+
+        // struct fields from one table can be selected with `Selectable`; for joins we rely on the selection order
+        let (derive_selectable, table_name_attr) = if joined_tables.is_empty() {
+            (
+                ", Selectable",
+                format!("#[diesel(table_name={table_name})]\n"),
+            )
+        } else {
+            ("", "".to_string())
+        };
+        let schema_imports = match joined_tables.is_empty() {
+            true => table_name.to_string(),
+            false => format!("{{{table_name}, {}}}", joined_tables.join(", ")),
+        };
+        let joins = joined_tables
+            .iter()
+            .map(|t| format!(".inner_join({t}::table)"))
+            .collect::<String>();
+        let fields = fields.join("\n");
+        let selection = match selection.len() {
+            1 => format!("{},", selection[0]),
+            _ => selection.join(", "),
+        };
+
+        buffer.push_str(&format!(
+            r##"
+
+{tsync}#[derive(Debug, Serialize, Deserialize, Clone, Queryable{derive_selectable})]
+{table_name_attr}pub struct {struct_name} {{
+{fields}
+}}
+
+impl {struct_name} {{
+    pub fn load(db: &mut {read_connection}) -> QueryResult<Vec<Self>> {{
+        use {schema_module}::{schema_imports};
+
+{prologue}        {table_name}::table{joins}.select(({selection})).load::<Self>(db)
+    }}
+}}"##
+        ));
+    }
+
+    buffer
+}
+
+fn build_query_ext(table: &ParsedTableMacro, config: &GenerationConfig) -> String {
+    let table_options = config.table(&table.name);
+    let schema_module = table_options.get_schema_module();
+    let scopes = table_options.get_scopes();
+
+    if scopes.is_empty() {
+        return "".to_string();
+    }
+
+    let table_name = &table.name;
+    let struct_name = &table.struct_name;
+    let trait_name = format!("{struct_name}QueryExt");
+
+    let scope_params = |params: &[(&str, &str)]| {
+        params
+            .iter()
+            .map(|(name, ty)| format!(", {name}: {ty}"))
+            .collect::<String>()
+    };
+
+    let signatures = scopes
+        .iter()
+        .map(|scope| {
+            format!(
+                "    fn {name}(self{params}) -> Self;",
+                name = scope.name,
+                params = scope_params(&scope.params)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let implementations = scopes
+        .iter()
+        .map(|scope| {
+            format!(
+                r##"
+    fn {name}(self{params}) -> Self {{
+        use {schema_module}::{table_name}::dsl::*;
+
+        self.{expression}
+    }}
+"##,
+                name = scope.name,
+                params = scope_params(&scope.params),
+                expression = scope.expression
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r##"
+
+pub type {struct_name}Query<'a> = {schema_module}::{table_name}::BoxedQuery<'a, <Connection as diesel::Connection>::Backend>;
+
+/// Chainable scopes for queries on `{table_name}` (start with `{struct_name}::query()`)
+pub trait {trait_name}: Sized {{
+{signatures}
+}}
+
+impl<'a> {trait_name} for {struct_name}Query<'a> {{
+{implementations}
+}}
+
+impl {struct_name} {{
+    pub fn query<'a>() -> {struct_name}Query<'a> {{
+        {schema_module}::{table_name}::table.into_boxed()
+    }}
+}}"##
+    )
+}
+
+fn build_table_fns(
+    table: &ParsedTableMacro,
+    config: &GenerationConfig,
+    foreign_structs: &[ForeignStruct],
+    read_struct: Struct,
+    create_struct: Struct,
+    update_struct: Struct,
+) -> String {
+    let table_options = config.table(&table.name);
+    let schema_module = table_options.get_schema_module();
+    let prologue = function_prologue(&table_options);
+    let (table_path, table_module_import) = table_module(table, &table_options);
+    let table_use = table_module_import
+        .as_ref()
+        .map(|import| format!("        {import}\n"))
+        .unwrap_or_default();
+    let table_import = table_module_import
+        .map(|import| format!("        {import}\n\n"))
+        .unwrap_or_default();
+
+    // page sizes below 1 fall back to the default, and are clamped to the maximum (if there is one)
+    let page_size = format!(
+        "if page_size < 1 {{ {default} }} else {{ page_size{max} }}",
+        default = table_options.get_default_page_size(),
+        max = table_options
+            .get_max_page_size()
+            .map(|max| format!(".min({max})"))
+            .unwrap_or_default()
+    );
+
+    // when the read struct doesn't contain every column (in order), queries have to select its fields explicitly
+    // (`MultiConnection` backends can't return `as_returning()` selections, so they return the columns of the fields)
+    let (select, returning) = if read_struct.fields().len() < table.columns.len()
+        || table_options.get_field_order() != FieldOrder::Schema
+    {
+        let returning = match config.multi_connection {
+            Some(_) => format!(
+                ".returning(({},))",
+                read_struct
+                    .fields()
+                    .iter()
+                    .map(|f| format!("{table_path}::{}", f.column_name))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            None => ".returning(Self::as_returning())".to_string(),
+        };
+
+        (".select(Self::as_select())", returning)
+    } else {
+        ("", "".to_string())
+    };
+
+    let (read_connection, write_connection) = connection_types(config);
+    // postgres-only functions of `MultiConnection` models take postgres connections (behind a cargo feature)
+    let (postgres_cfg, postgres_read_connection, postgres_write_connection) =
+        match config.multi_connection {
+            Some(multi_connection) => (
+                format!(
+                    "    #[cfg(feature = \"{}\")]\n",
+                    multi_connection.postgres_feature
+                ),
+                "diesel::PgConnection",
+                "diesel::PgConnection",
+            ),
+            None => ("".to_string(), read_connection, write_connection),
+        };
+
+    // what `create`, `update` and `update_where` return (`write_rows` ends their statements, given the statement's
+    // ending for written rows)
+    let write_result = table_options.get_write_result();
+    let (write_type, write_many_type) = match write_result {
+        WriteResult::Row => ("Self", "Vec<Self>"),
+        WriteResult::Count => ("usize", "usize"),
+        WriteResult::Nothing => ("()", "()"),
+    };
+    let write_rows = |rows: &str| match write_result {
+        WriteResult::Row => format!("{returning}{rows}"),
+        WriteResult::Count => ".execute(db)".to_string(),
+        WriteResult::Nothing => ".execute(db).map(|_| ())".to_string(),
+    };
+
+    let primary_column_name_and_type: Vec<(String, String)> = table
+        .primary_key_columns
+        .iter()
+        .map(|pk| {
+            let col = table
+                .columns
+                .iter()
+                .find(|it| it.name.eq(pk))
+                .expect("Primary key column doesn't exist in table");
+
+            let ty = match read_struct.key_types.iter().find(|(c, _)| c == pk) {
+                Some((_, ty)) => ty.clone(),
+                None => col.ty.clone(),
+            };
+
+            (col.name.clone(), ty)
+        })
+        .collect();
+    // `read`, `update` and `delete` (and the functions built on them) find rows by their primary key
+    let has_primary_key = !primary_column_name_and_type.is_empty();
+    if !has_primary_key {
+        log::debug!(
+            "table `{}`: no `read`, `update` and `delete` functions, because it has no primary key",
+            table.name
+        );
+    }
+
+    // parameters are named after the idiomatic field names (`param_user_id` for a `userId` column); the prefix keeps
+    // them apart from the other locals, and columns are referred to by their paths so they can't shadow either
+    let param_name = |column_name: &str| {
+        format!(
+            "param_{}",
+            table.field_name(column_name).trim_start_matches("r#")
+        )
+    };
+
+    // uuid primary keys are accepted as anything converting into them (converted explicitly, since `eq` is generic)
+    let uuid_key = uuid_key(table, &table_options);
+    let is_uuid_key = |column_name: &str| uuid_key.is_some_and(|(pk, _)| pk.name == column_name);
+
+    let item_id_params = primary_column_name_and_type
+        .iter()
+        .map(|name_and_type| {
+            format!(
+                "{param}: {ty}",
+                param = param_name(&name_and_type.0),
+                ty = match is_uuid_key(&name_and_type.0) {
+                    true => format!("impl Into<{}>", name_and_type.1),
+                    false => name_and_type.1.clone(),
+                }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    let item_id_filters = primary_column_name_and_type
+        .iter()
+        .map(|name_and_type| {
+            let param = param_name(&name_and_type.0);
+
+            format!(
+                "filter({table_path}::{name}.eq({value}))",
+                name = name_and_type.0,
+                value = match is_uuid_key(&name_and_type.0) {
+                    true => format!("Into::<{}>::into({param})", name_and_type.1),
+                    false => param,
+                }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(".");
+
+    // primary keys generated by `create`
+    let generated_key = generated_key(table, &table_options)
+        .map(|(pk, expression)| format!("{table_path}::{pk}.eq({expression})", pk = pk.name));
+
+    // `paginate` orders by the configured default, falling back to the primary key for tables without that column
+    let order_expression = |column: &str, direction: SortDirection| match direction {
+        SortDirection::Asc => format!("{table_path}::{column}.asc()"),
+        SortDirection::Desc => format!("{table_path}::{column}.desc()"),
+    };
+    // the primary key's order, which is stable (so pages neither repeat nor skip rows)
+    let primary_key_orders = primary_column_name_and_type
+        .iter()
+        .map(|(pk, _)| order_expression(pk, SortDirection::Asc))
+        .collect::<Vec<String>>();
+    let primary_key_order = match primary_key_orders.as_slice() {
+        [] => None,
+        [order] => Some(order.clone()),
+        orders => Some(format!("({})", orders.join(", "))),
+    };
+    // orders by a column, then by the other primary key columns, so rows with equal values keep their order across
+    // pages
+    let order_with_tiebreaker = |column: &str, direction: SortDirection| {
+        let mut expressions = vec![order_expression(column, direction)];
+        expressions.extend(
+            primary_column_name_and_type
+                .iter()
+                .filter(|(pk, _)| pk != column)
+                .map(|(pk, _)| order_expression(pk, SortDirection::Asc)),
+        );
+
+        match expressions.as_slice() {
+            [expression] => expression.clone(),
+            expressions => format!("({})", expressions.join(", ")),
+        }
+    };
+    let default_order = match table_options
+        .get_default_order()
+        .filter(|(column, _)| table.column(column).is_some())
+    {
+        Some((column, direction)) => Some(order_with_tiebreaker(column, direction)),
+        None => primary_key_order.clone(),
+    };
+    let fast_order = primary_key_order
+        .as_ref()
+        .map(|order| format!(".order({order})"))
+        .unwrap_or_default();
+
+    // tsvector columns have no meaningful order
+    let column_enum = format!("{}Column", table.struct_name);
+    let orderable_columns: Vec<(String, &str)> = table
+        .columns
+        .iter()
+        .filter(|c| !c.is_tsvector())
+        .map(|c| {
+            (
+                table
+                    .field_name(&c.name)
+                    .trim_start_matches("r#")
+                    .to_pascal_case(),
+                c.name.as_str(),
+            )
+        })
+        .collect();
+    let column_variants = orderable_columns
+        .iter()
+        .map(|(variant, _)| format!("    {variant},\n"))
+        .collect::<String>();
+    let order_arms = orderable_columns
+        .iter()
+        .flat_map(|(variant, column)| {
+            [("Asc", SortDirection::Asc), ("Desc", SortDirection::Desc)].map(|(name, direction)| {
+                format!(
+                    "            Some(({column_enum}::{variant}, SortDirection::{name})) => query.order({order}),\n",
+                    order = order_with_tiebreaker(column, direction)
+                )
+            })
+        })
+        .collect::<String>();
+    let (default_order_arm, default_order_doc) = match &default_order {
+        Some(order) => (
+            format!("            None => query.order({order}),"),
+            format!("(`{order}` if no ordering is given)"),
+        ),
+        None => (
+            "            None => query,".to_string(),
+            "(unordered if no ordering is given)".to_string(),
+        ),
+    };
+
+    // template variables
+    let table_name = table.name.clone();
+    #[cfg(feature = "tsync")]
+    let tsync = match table_options.get_tsync() {
+        true => "#[tsync::tsync]",
+        false => "",
+    };
+    #[cfg(not(feature = "tsync"))]
+    let tsync = "";
+    let struct_name = &table.struct_name;
+    let create_struct_identifier = &create_struct.identifier;
+    let update_struct_identifier = &update_struct.identifier;
+
+    let mut buffer = String::new();
+
+    buffer.push_str(&format!(
+        r##"{tsync}
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {{
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}}
+{tsync}
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {{
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}}
+{tsync}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {{
+    Asc,
+    Desc,
+}}
+{tsync}
+/// Columns of `{table_name}` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum {column_enum} {{
+{column_variants}}}
+"##
+    ));
+
+    // composite primary keys are passed around as a single struct, rather than as positional arguments
+    let key_struct = match primary_column_name_and_type.len() > 1 {
+        true => Some((
+            format!("{struct_name}Key"),
+            primary_column_name_and_type
+                .iter()
+                .map(|(column, ty)| (column.as_str(), ty.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+        )),
+        false => None,
+    };
+    if let Some((key_struct_name, key_fields)) = &key_struct {
+        let fields = key_fields
+            .iter()
+            .map(|(column, ty)| {
+                let serde_rename = table
+                    .column(column)
+                    .and_then(|c| c.serde_rename())
+                    .map(|name| format!("    #[serde(rename = \"{name}\")]\n"))
+                    .unwrap_or_default();
+
+                format!(
+                    "{serde_rename}    pub {field}: {ty},\n",
+                    field = table.field_name(column)
+                )
+            })
+            .collect::<String>();
+
+        buffer.push_str(&format!(
+            r##"{tsync}
+/// The composite primary key of `{table_name}` rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct {key_struct_name} {{
+{fields}}}
+"##
+        ));
+    }
+
+    buffer.push_str(&format!(
+        r##"
+impl {struct_name} {{
+"##
+    ));
+
+    if table_options.get_materialized_view() {
+        log::debug!(
+            "table `{}`: no `create`, `update` and `delete` functions, because the table is a materialized view",
+            table.name
+        );
+        let sql_view_name = table.sql_name().unwrap_or(&table.name);
+
+        buffer.push_str(&format!(
+            r##"
+    /// Recomputes the materialized view with `REFRESH MATERIALIZED VIEW`, which blocks reads of the view until it's done
+{postgres_cfg}    pub fn refresh(db: &mut {postgres_write_connection}) -> QueryResult<usize> {{
+{prologue}        diesel::sql_query("REFRESH MATERIALIZED VIEW \"{sql_view_name}\"").execute(db)
+    }}
+
+    /// Like `refresh` but without blocking reads (`REFRESH MATERIALIZED VIEW CONCURRENTLY`), which needs a unique index
+    /// on the view
+{postgres_cfg}    pub fn refresh_concurrently(db: &mut {postgres_write_connection}) -> QueryResult<usize> {{
+{prologue}        diesel::sql_query("REFRESH MATERIALIZED VIEW CONCURRENTLY \"{sql_view_name}\"").execute(db)
+    }}
+"##
+        ));
+    } else if create_struct.has_fields() {
+        let values = match &generated_key {
+            Some(generated_key) => format!("({generated_key}, item)"),
+            None => "item".to_string(),
+        };
+
+        buffer.push_str(&format!(
+            r##"
+    pub fn create(db: &mut {write_connection}, item: &{create_struct_identifier}) -> QueryResult<{write_type}> {{
+{table_import}{prologue}        insert_into({table_path}::table).values({values}){written}
+    }}
+"##,
+            written = write_rows(".get_result::<Self>(db)")
+        ));
+    } else if table_options.generates_struct(GeneratedStruct::Create) {
+        buffer.push_str(&format!(
+            r##"
+    pub fn create(db: &mut {write_connection}) -> QueryResult<{write_type}> {{
+{table_import}{prologue}        insert_into({table_path}::table).{values}{written}
+    }}
+"##,
+            written = write_rows(".get_result::<Self>(db)"),
+            values = match &generated_key {
+                Some(generated_key) => format!("values({generated_key})"),
+                None => "default_values()".to_string(),
+            }
+        ));
+    } else {
+        log::debug!(
+            "table `{}`: no `create` function, because the Create struct isn't generated (see `--structs`)",
+            table.name
+        );
+    }
+
+    if has_primary_key {
+        buffer.push_str(&format!(
+            r##"
+    pub fn read(db: &mut {read_connection}, {item_id_params}) -> QueryResult<Self> {{
+{table_import}{prologue}        {table_path}::table.{item_id_filters}{select}.first::<Self>(db)
+    }}
+"##
+        ));
+    }
+
+    if table_options.get_raw_queries() {
+        buffer.push_str(&format!(
+            r##"
+    /// Loads rows with a hand-written query selecting every field of the struct by name, for example:
+    /// `diesel::sql_query("SELECT * FROM {table_name} WHERE ...").bind::<diesel::sql_types::Text, _>(value)`
+    pub fn query_raw<'query, Q>(db: &mut {read_connection}, query: Q) -> QueryResult<Vec<Self>>
+    where
+        Q: diesel::query_dsl::LoadQuery<'query, {read_connection}, Self>,
+    {{
+{prologue}        query.load::<Self>(db)
+    }}
+"##
+        ));
+    }
+
+    buffer.push_str(&format!(r##"
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// {default_order_doc}
+    pub fn paginate(db: &mut {read_connection}, page: i64, page_size: i64, order: Option<({column_enum}, SortDirection)>) -> QueryResult<PaginationResult<Self>> {{
+{table_import}{prologue}        let page_size = {page_size};
+        let total_items = {table_path}::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {{page}} of {{page_size}} rows is out of range").into()))?;
+        let query = {table_path}::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {{
+{order_arms}{default_order_arm}
+        }};
+        let items = query{select}.load::<Self>(db)?;
+
+        Ok(PaginationResult {{
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        }})
+    }}
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut {read_connection}, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {{
+{table_import}{prologue}        let page_size = {page_size};
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {{page}} of {{page_size}} rows is out of range").into()))?;
+        let mut items = {table_path}::table{fast_order}.limit(page_size.saturating_add(1)).offset(offset){select}.load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {{
+            items,
+            page,
+            page_size,
+            has_next,
+        }})
+    }}
+"##));
+
+    if let Some(order) = &primary_key_order {
+        // keyset pagination: every chunk continues after the last key of the previous one; composite keys compare as
+        // rows (`(a, b) > (last.a, last.b)`), spelled out as `a > last.a OR (a = last.a AND b > last.b)`
+        let after_last = primary_column_name_and_type
+            .iter()
+            .rev()
+            .fold(None, |after: Option<String>, (pk, _)| {
+                let field = table.field_name(pk);
+                let greater = format!("{table_path}::{pk}.gt(&last.{field})");
+
+                Some(match after {
+                    Some(after) => {
+                        format!("{greater}.or({table_path}::{pk}.eq(&last.{field}).and({after}))")
+                    }
+                    None => greater,
+                })
+            })
+            .unwrap_or_default();
+
+        buffer.push_str(&format!(r##"
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut {read_connection}, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {{
+{table_import}{prologue}        let chunk_size = if chunk_size < 1 {{ 1 }} else {{ chunk_size }};
+        let mut last: Option<Self> = None;
+        loop {{
+            let mut query = {table_path}::table.order({order}).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {{
+                query = query.filter({after_last});
+            }}
+            let items = query{select}.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {{
+                f(items)?;
+            }}
+            if is_last_chunk {{
+                return Ok(());
+            }}
+        }}
+    }}
+"##));
+    }
+
+    if let Some(search_column) = table_options
+        .get_full_text_search_column()
+        .and_then(|c| table.column(c))
+        .filter(|c| c.is_tsvector())
+    {
+        let search_column = &search_column.name;
+        let rank =
+            format!("ts_rank({table_path}::{search_column}, websearch_to_tsquery(query)).desc()");
+        let search_order = match primary_key_orders.is_empty() {
+            true => rank,
+            false => format!("({rank}, {})", primary_key_orders.join(", ")),
+        };
+        let select = match select.is_empty() {
+            true => "".to_string(),
+            false => format!("\n            {select}"),
+        };
+
+        buffer.push_str(&format!(r##"
+    /// Full-text search on `{search_column}` using `websearch_to_tsquery`, ordered by rank (page is a 0-based index)
+{postgres_cfg}    pub fn search(db: &mut {postgres_read_connection}, query: &str, page: i64, page_size: i64) -> QueryResult<Vec<Self>> {{
+{table_use}        use diesel_full_text_search::{{ts_rank, websearch_to_tsquery, TsVectorExtensions}};
+
+{prologue}        let page_size = {page_size};
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {{page}} of {{page_size}} rows is out of range").into()))?;
+        {table_path}::table
+            .filter({table_path}::{search_column}.matches(websearch_to_tsquery(query)))
+            .order({search_order})
+            .limit(page_size)
+            .offset(offset){select}
+            .load::<Self>(db)
+    }}
+"##));
+    }
+
+    // citext columns compare case-insensitively on their own, other text columns are compared by `lower(...)` (which
+    // is what functional indexes like `create unique index on users (lower(email))` cover)
+    for column in table.columns.iter().filter(|c| {
+        c.ty == "String"
+            && (c.is_citext()
+                || table_options
+                    .get_case_insensitive_columns()
+                    .contains(&c.name.as_str()))
+    }) {
+        let field_name = table.field_name(&column.name);
+        let (doc, filter) = match column.is_citext() {
+            true => (
+                format!("Finds the row whose `{field_name}` equals `value` ignoring case (`{field_name}` is a citext column)"),
+                format!("{table_path}::{}.eq(value)", column.name),
+            ),
+            false => {
+                let sql_name = column
+                    .sql_name()
+                    .unwrap_or_else(|| column.name.trim_start_matches("r#"));
+
+                (
+                    format!("Finds the row whose `{field_name}` equals `value` ignoring case (`lower({sql_name}) = lower(value)`)"),
+                    format!(r##"diesel::dsl::sql::<diesel::sql_types::Bool>(r#"lower("{sql_name}") = lower("#).bind::<diesel::sql_types::Text, _>(value).sql(")")"##),
+                )
+            }
+        };
+
+        buffer.push_str(&format!(
+            r##"
+    /// {doc},
+    /// which is meant for unique columns like emails and usernames
+    pub fn find_by_{fn_name}(db: &mut {read_connection}, value: &str) -> QueryResult<Option<Self>> {{
+{table_import}{prologue}        {table_path}::table.filter({filter}){select}.first::<Self>(db).optional()
+    }}
+"##,
+            fn_name = field_name.trim_start_matches("r#")
+        ));
+    }
+
+    // TODO: If primary key columns are attached to the form struct (not optionally)
+    // then don't require item_id_params (otherwise it'll be duplicated)
+
+    // if has_update_struct {
+    if update_struct.has_fields() {
+        // It's possible we have a form struct with all primary keys (for example, for a join table).
+        // In this scenario, we also have to check whether there are any updatable columns for which
+        // we should generate an update() method.
+
+        // empty Update structs don't change anything, so there's nothing to write
+        // filters without conditions update nothing (rather than every row)
+        let (update_doc, unchanged, update_where_doc, unchanged_where, untouched) = match write_result {
+            WriteResult::Row => (
+                "returning the updated row (or the row as it is, if `item` is empty)",
+                // `Self::read`'s query, on the write connection
+                format!("{table_path}::table.{item_id_filters}{select}.first::<Self>(db)"),
+                "returning the updated rows (or the\n    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows",
+                format!("{table_path}::table.filter(filter.expression()){select}.load::<Self>(db)"),
+                "Ok(vec![])",
+            ),
+            WriteResult::Count => (
+                "returning the number of updated rows (0 if `item` is empty)",
+                "Ok(0)".to_string(),
+                "returning the number of updated\n    /// rows (0 if `changes` or `filter` is empty)",
+                "Ok(0)".to_string(),
+                "Ok(0)",
+            ),
+            WriteResult::Nothing => (
+                "unless `item` is empty",
+                "Ok(())".to_string(),
+                "unless `changes` or `filter` is\n    /// empty",
+                "Ok(())".to_string(),
+                "Ok(())",
+            ),
+        };
+
+        if has_primary_key {
+            buffer.push_str(&format!(r##"
+    /// Applies `item` to the row, {update_doc}
+    pub fn update(db: &mut {write_connection}, {item_id_params}, item: &{update_struct_identifier}) -> QueryResult<{write_type}> {{
+{table_import}{prologue}        if item.is_empty() {{
+            return {unchanged};
+        }}
+
+        diesel::update({table_path}::table.{item_id_filters}).set(item){written}
+    }}
+"##, written = write_rows(".get_result(db)")));
+        }
+
+        buffer.push_str(&format!(r##"
+    /// Applies `changes` to every row matching `filter` in a single statement, {update_where_doc}
+    pub fn update_where(db: &mut {write_connection}, filter: &{struct_name}Filter, changes: &{update_struct_identifier}) -> QueryResult<{write_many_type}> {{
+{table_import}{prologue}        if filter.is_empty() {{
+            return {untouched};
+        }}
+        if changes.is_empty() {{
+            return {unchanged_where};
+        }}
+
+        diesel::update({table_path}::table.filter(filter.expression())).set(changes){written}
+    }}
+"##, written = write_rows(".get_results(db)")));
+    } else if !table_options.get_materialized_view() {
+        log::debug!(
+            "table `{}`: no `update` and `update_where` functions, because the Update struct has no fields: {}",
+            table.name,
+            update_struct.no_fields_reason()
+        );
+    }
+
+    // `save_changes` loads the row with the table's default selection, so it needs every column in schema order
+    let save_skipped_reason = if !read_struct.derives_identifiable() {
+        Some("the Read struct doesn't derive `Identifiable`")
+    } else if !read_struct.derives_aschangeset() {
+        Some("the Read struct doesn't derive `AsChangeset` (it has no fields besides the primary key, or the table is a materialized view)")
+    } else if !select.is_empty() {
+        Some("the Read struct doesn't have every column in schema order")
+    } else {
+        None
+    };
+    if let (true, Some(reason)) = (table_options.get_save(), save_skipped_reason) {
+        log::debug!(
+            "table `{}`: no `save` function, because {reason}",
+            table.name
+        );
+    }
+    if table_options.get_save() && save_skipped_reason.is_none() {
+        buffer.push_str(&format!(
+            r##"
+    /// Writes every field of the row (except for the primary key) back with diesel's `save_changes`, returning the
+    /// row as it's stored; `None` fields of nullable columns are left unchanged rather than set to `NULL`
+    pub fn save(&self, db: &mut {write_connection}) -> QueryResult<Self> {{
+{prologue}        diesel::SaveChangesDsl::save_changes::<Self>(self, db)
+    }}
+"##,
+        ));
+    }
+
+    if !table_options.get_materialized_view() {
+        let delete_all_doc = match table_options.get_dangerous_helpers() {
+            true => " (`delete_all` deletes every row)",
+            false => "",
+        };
+        if has_primary_key {
+            buffer.push_str(&format!(
+                r##"
+    pub fn delete(db: &mut {write_connection}, {item_id_params}) -> QueryResult<usize> {{
+{table_import}{prologue}        diesel::delete({table_path}::table.{item_id_filters}).execute(db)
+    }}
+"##
+            ));
+        }
+        buffer.push_str(&format!(
+            r##"
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows{delete_all_doc}
+    pub fn delete_where(db: &mut {write_connection}, filter: &{struct_name}Filter) -> QueryResult<usize> {{
+{table_import}{prologue}        if filter.is_empty() {{
+            return Ok(0);
+        }}
+
+        diesel::delete({table_path}::table.filter(filter.expression())).execute(db)
+    }}
+"##
+        ));
+
+        if table_options.get_dangerous_helpers() {
+            buffer.push_str(&format!(
+                r##"
+    /// Deletes every row of the table, returning the number of deleted rows
+    pub fn delete_all(db: &mut {write_connection}) -> QueryResult<usize> {{
+{table_import}{prologue}        diesel::delete({table_path}::table).execute(db)
+    }}
+"##
+            ));
+
+            if config.connection_type.contains("PgConnection") || config.multi_connection.is_some()
+            {
+                let sql_table_name = table.sql_name().unwrap_or(&table.name);
+
+                buffer.push_str(&format!(
+                    r##"
+    /// Empties the table with `TRUNCATE`, which is faster than `delete_all` but doesn't fire `ON DELETE` triggers
+{postgres_cfg}    pub fn truncate(db: &mut {postgres_write_connection}) -> QueryResult<usize> {{
+{prologue}        diesel::sql_query("TRUNCATE TABLE \"{sql_table_name}\"").execute(db)
+    }}
+"##
+                ));
+            }
+        }
+    }
+
+    if let Some((key_struct_name, key_fields)) = &key_struct {
+        let key_args = key_fields
+            .iter()
+            .map(|(column, _)| format!("key.{}.clone()", table.field_name(column)))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        // the read struct may leave out key columns (or rename their fields)
+        let key_values = key_fields
+            .iter()
+            .map(|(column, _)| {
+                read_struct
+                    .fields()
+                    .iter()
+                    .find(|f| f.column_name == *column)
+                    .map(|f| format!("{}: self.{}.clone()", table.field_name(column), f.name))
+            })
+            .collect::<Option<Vec<String>>>();
+        if let Some(key_values) = key_values {
+            buffer.push_str(&format!(
+                r##"
+    /// The composite primary key of this row
+    pub fn key(&self) -> {key_struct_name} {{
+        {key_struct_name} {{ {key_values} }}
+    }}
+"##,
+                key_values = key_values.join(", ")
+            ));
+        }
+
+        buffer.push_str(&format!(
+            r##"
+    pub fn read_by_key(db: &mut {read_connection}, key: &{key_struct_name}) -> QueryResult<Self> {{
+        Self::read(db, {key_args})
+    }}
+"##
+        ));
+
+        if update_struct.has_fields() {
+            buffer.push_str(&format!(
+                r##"
+    pub fn update_by_key(db: &mut {write_connection}, key: &{key_struct_name}, item: &{update_struct_identifier}) -> QueryResult<{write_type}> {{
+        Self::update(db, {key_args}, item)
+    }}
+"##
+            ));
+        }
+
+        if !table_options.get_materialized_view() {
+            buffer.push_str(&format!(
+                r##"
+    pub fn delete_by_key(db: &mut {write_connection}, key: &{key_struct_name}) -> QueryResult<usize> {{
+        Self::delete(db, {key_args})
+    }}
+"##
+            ));
+        }
+    }
+
+    if table_options.get_import_export() && read_struct.serde_derives().serialize() {
+        buffer.push_str(&format!(
+            r##"
+    /// Writes every row of the table to `writer` as CSV (with a header row), returning the number of written rows
+    pub fn export_csv<W: std::io::Write>(db: &mut {read_connection}, writer: W) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {{
+{prologue}        let rows = {schema_module}::{table_name}::table{select}.load::<Self>(db)?;
+        let mut writer = csv::Writer::from_writer(writer);
+        for row in rows.iter() {{
+            writer.serialize(row)?;
+        }}
+        writer.flush()?;
+
+        Ok(rows.len())
+    }}
+
+    /// Writes every row of the table to `writer` as JSON lines (a JSON object per line), returning the number of
+    /// written rows
+    pub fn export_json_lines<W: std::io::Write>(db: &mut {read_connection}, mut writer: W) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {{
+{prologue}        let rows = {schema_module}::{table_name}::table{select}.load::<Self>(db)?;
+        for row in rows.iter() {{
+            serde_json::to_writer(&mut writer, row)?;
+            writer.write_all(b"\n")?;
+        }}
+        writer.flush()?;
+
+        Ok(rows.len())
+    }}
+"##
+        ));
+    }
+
+    if table_options.get_import_export()
+        && create_struct.serde_derives().deserialize()
+        && !table_options.get_materialized_view()
+        && create_struct.has_fields()
+    {
+        buffer.push_str(&format!(
+                r##"
+    /// Inserts the rows of the CSV in `reader` (with a header row naming the fields of `{create_struct_identifier}`) in a
+    /// single transaction, returning the number of inserted rows
+    pub fn import_csv<R: std::io::Read>(db: &mut {write_connection}, reader: R) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {{
+        let mut reader = csv::Reader::from_reader(reader);
+        diesel::Connection::transaction(db, |db| {{
+            let mut count = 0;
+            for item in reader.deserialize::<{create_struct_identifier}>() {{
+                Self::create(db, &item?)?;
+                count += 1;
+            }}
+
+            Ok(count)
+        }})
+    }}
+
+    /// Inserts the rows of the JSON lines in `reader` (a `{create_struct_identifier}` object per line, blank lines are
+    /// skipped) in a single transaction, returning the number of inserted rows
+    pub fn import_json_lines<R: std::io::BufRead>(db: &mut {write_connection}, reader: R) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {{
+        diesel::Connection::transaction(db, |db| {{
+            let mut count = 0;
+            for line in reader.lines() {{
+                let line = line?;
+                if line.trim().is_empty() {{
+                    continue;
+                }}
+                Self::create(db, &serde_json::from_str::<{create_struct_identifier}>(&line)?)?;
+                count += 1;
+            }}
+
+            Ok(count)
+        }})
+    }}
+"##
+        ));
+    }
+
+    // services delegate to the async wrappers, which return the same types (and repository traits declare the
+    // service methods as `(method, parameters, arguments, return type)`)
+    let mut service_methods = String::new();
+    let mut repository_fns: Vec<(&str, String, String, String)> = vec![];
+    if table_options.get_async_wrappers() {
+        // the arguments move into the blocking closure, so they're owned (uuid keys are taken as uuids)
+        let async_id_params = primary_column_name_and_type
+            .iter()
+            .map(|(column, ty)| format!(", {}: {ty}", param_name(column)))
+            .collect::<String>();
+        let async_id_args = primary_column_name_and_type
+            .iter()
+            .map(|(column, _)| format!(", {}", param_name(column)))
+            .collect::<String>();
+
+        // (function, parameters, arguments, the struct `item` taken after them, return type)
+        let mut wrappers: Vec<(&str, String, String, Option<&str>, String)> = vec![];
+        if !table_options.get_materialized_view() && create_struct.has_fields() {
+            wrappers.push((
+                "create",
+                "".to_string(),
+                "".to_string(),
+                Some(create_struct_identifier),
+                write_type.to_string(),
+            ));
+        } else if !table_options.get_materialized_view()
+            && table_options.generates_struct(GeneratedStruct::Create)
+        {
+            wrappers.push((
+                "create",
+                "".to_string(),
+                "".to_string(),
+                None,
+                write_type.to_string(),
+            ));
+        }
+        if has_primary_key {
+            wrappers.push((
+                "read",
+                async_id_params.clone(),
+                async_id_args.clone(),
+                None,
+                "Self".to_string(),
+            ));
+        }
+        wrappers.push((
+            "paginate",
+            format!(", page: i64, page_size: i64, order: Option<({column_enum}, SortDirection)>"),
+            ", page, page_size, order".to_string(),
+            None,
+            "PaginationResult<Self>".to_string(),
+        ));
+        if has_primary_key && update_struct.has_fields() {
+            wrappers.push((
+                "update",
+                async_id_params.clone(),
+                async_id_args.clone(),
+                Some(update_struct_identifier),
+                write_type.to_string(),
+            ));
+        }
+        if has_primary_key && !table_options.get_materialized_view() {
+            wrappers.push((
+                "delete",
+                async_id_params,
+                async_id_args,
+                None,
+                "usize".to_string(),
+            ));
+        }
+
+        for (name, params, args, item, ty) in wrappers {
+            let (item_param, item_ref, item_arg) = match item {
+                Some(item) => (format!(", item: {item}"), ", &item", ", item"),
+                None => ("".to_string(), "", ""),
+            };
+            // with a read connection type, reads get their connections from a pool of their own
+            let (pool_type, pool_field) = match (config.read_connection_type.is_some(), name) {
+                (true, "read" | "paginate") => ("ReadPool", "read_pool"),
+                _ => ("Pool", "pool"),
+            };
+
+            buffer.push_str(&format!(
+                r##"
+    /// Runs `{name}` on a connection of `pool`, in tokio's blocking thread pool
+    pub async fn {name}_async(pool: &{pool_type}{params}{item_param}) -> Result<{ty}, Box<dyn std::error::Error + Send + Sync>> {{
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {{
+            let mut db = pool.get()?;
+            Ok(Self::{name}(&mut db{args}{item_ref})?)
+        }})
+        .await?
+    }}
+"##
+            ));
+
+            service_methods.push_str(&format!(
+                r##"
+    pub async fn {name}(&self{params}{item_param}) -> Result<{ty}, Box<dyn std::error::Error + Send + Sync>> {{
+        {struct_name}::{name}_async(&self.{pool_field}{args}{item_arg}).await
+    }}
+"##,
+                ty = ty.replace("Self", struct_name)
+            ));
+
+            repository_fns.push((
+                name,
+                format!("{params}{item_param}"),
+                format!("{args}{item_arg}"),
+                ty.replace("Self", struct_name),
+            ));
+        }
+    }
+
+    for (foreign_table_name, join_column) in table.joinable_foreign_keys() {
+        let foreign_struct_name = foreign_structs
+            .iter()
+            .find(|f| &f.table_name == foreign_table_name)
+            .map(|f| f.reference.as_str())
+            .unwrap_or_default();
+        let fn_name = snake_case_identifier(foreign_table_name).to_singular();
+
+        buffer.push_str(&format!(r##"
+    /// Loads rows along with their associated `{foreign_struct_name}` (inner join on `{join_column}`)
+    pub fn with_{fn_name}(db: &mut {read_connection}) -> QueryResult<Vec<(Self, {foreign_struct_name})>> {{
+        use {schema_module}::{{{table_name}, {foreign_table_name}}};
+
+{prologue}        {table_path}::table.inner_join({foreign_table_name}::table).select((Self::as_select(), {foreign_struct_name}::as_select())).load::<(Self, {foreign_struct_name})>(db)
+    }}
+"##));
+    }
+
+    buffer.push_str(
+        r##"
+}"##,
+    );
+
+    if table_options.get_service() {
+        let service = match config.read_connection_type {
+            Some(_) => format!(
+                r##"
+
+/// Async CRUD on `{table_name}` over connection pools (reading through `read_pool`), so handlers can hold a single
+/// (cloneable) service per table
+#[derive(Clone)]
+pub struct {struct_name}Service {{
+    pool: Pool,
+    read_pool: ReadPool,
+}}
+
+impl {struct_name}Service {{
+    pub fn new(pool: Pool, read_pool: ReadPool) -> Self {{
+        Self {{ pool, read_pool }}
+    }}
+{service_methods}}}"##
+            ),
+            None => format!(
+                r##"
+
+/// Async CRUD on `{table_name}` over a connection pool, so handlers can hold a single (cloneable) service per table
+#[derive(Clone)]
+pub struct {struct_name}Service {{
+    pool: Pool,
+}}
+
+impl {struct_name}Service {{
+    pub fn new(pool: Pool) -> Self {{
+        Self {{ pool }}
+    }}
+{service_methods}}}"##
+            ),
+        };
+        buffer.push_str(&service);
+    }
+
+    if table_options.get_mock() {
+        let result = |ty: &str| {
+            format!("impl std::future::Future<Output = Result<{ty}, Box<dyn std::error::Error + Send + Sync>>> + Send")
+        };
+        let signatures = repository_fns
+            .iter()
+            .map(|(name, params, _, ty)| {
+                format!("    fn {name}(&self{params}) -> {};\n", result(ty))
+            })
+            .collect::<String>();
+        let implementations = repository_fns
+            .iter()
+            .map(|(name, params, args, ty)| {
+                format!(
+                    r##"
+    fn {name}(&self{params}) -> {result} {{
+        {struct_name}Service::{name}(self{args})
+    }}
+"##,
+                    result = result(ty)
+                )
+            })
+            .collect::<String>();
+
+        // mocked async methods return boxed futures (their closures take an argument per parameter)
+        let placeholders = |args: &str| vec!["_"; args.matches(", ").count()].join(", ");
+        let not_found = repository_fns
+            .iter()
+            .filter(|(name, _, _, _)| ["read", "update", "delete"].contains(name))
+            .map(|(name, _, args, _)| {
+                format!(
+                    "        mock.expect_{name}().returning(|{params}| Box::pin(async {{ Err(diesel::result::Error::NotFound.into()) }}));\n",
+                    params = placeholders(args)
+                )
+            })
+            .collect::<String>();
+        let read_arity = repository_fns
+            .iter()
+            .find(|(name, _, _, _)| *name == "read")
+            .map(|(_, _, args, _)| placeholders(args))
+            .unwrap_or_default();
+        // tables without primary keys have no `read`, `update` and `delete` to mock
+        let mock_helpers = match has_primary_key {
+            true => format!(
+                r##"
+
+#[cfg(feature = "mock")]
+impl Mock{struct_name}Repository {{
+    /// A mock whose `read` returns (a clone of) `row`
+    pub fn reading(row: {struct_name}) -> Self {{
+        let mut mock = Self::new();
+        mock.expect_read().returning(move |{read_arity}| {{
+            let row = row.clone();
+            Box::pin(async move {{ Ok(row) }})
+        }});
+        mock
+    }}
+
+    /// A mock whose `read`, `update` and `delete` fail with diesel's `NotFound` error
+    pub fn not_found() -> Self {{
+        let mut mock = Self::new();
+{not_found}        mock
+    }}
+}}"##
+            ),
+            false => "".to_string(),
+        };
+
+        buffer.push_str(&format!(
+            r##"
+
+/// The async CRUD of `{table_name}`, implemented by `{struct_name}Service`; with the `mock` feature, mockall generates
+/// `Mock{struct_name}Repository` to unit test handlers without a database
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait {struct_name}Repository: Send + Sync {{
+{signatures}}}
+
+impl {struct_name}Repository for {struct_name}Service {{{implementations}}}{mock_helpers}"##
+        ));
+    }
+
+    buffer
+}
+
+/// the imports and type aliases of a table's file; the imports of diesel, the schema and serde are globs, or
+/// (given the rendered `models` of the explicit and prelude import styles) only the items the models use
+fn build_imports(
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+    foreign_structs: &[ForeignStruct],
+    models: Option<&str>,
+) -> String {
+    let schema_module = config.table(&table.name).get_schema_module();
+    let common_imports = match models {
+        None => format!(
+            indoc! {"
+                use crate::diesel::*;
+                use {schema_module}::*;
+                use diesel::QueryResult;
+                use serde::{{Deserialize, Serialize}};"
+            },
+            schema_module = schema_module
+        ),
+        Some(models) => imports::explicit_imports(
+            models,
+            config.table(&table.name).get_import_style(),
+            schema_module,
+            tables,
+        )
+        .join("\n"),
+    };
+
+    let mut paths: Vec<String> = vec![];
+    for path in foreign_structs
+        .iter()
+        .filter_map(|f| f.import.clone())
+        .chain(type_imports(table, tables, config))
+    {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    let belongs_imports = paths
+        .iter()
+        .map(|path| format!("use {path};"))
         .collect::<Vec<String>>()
         .join("\n");
 
     format!(
         indoc! {"
-        use crate::diesel::*;
-        use crate::schema::*;
-        use diesel::QueryResult;
-        use serde::{{Deserialize, Serialize}};
+        {common_imports}
         {belongs_imports}
 
-        type Connection = {connection_type};
+        {connection_alias}{read_alias}{pool_alias}
     "},
-        connection_type = config.connection_type,
+        common_imports = common_imports,
+        connection_alias = build_connection_alias(config),
+        read_alias = match &config.read_connection_type {
+            Some(read_connection_type) => format!(
+                "\ntype WriteConnection = Connection;\ntype ReadConnection = {read_connection_type};"
+            ),
+            None => "".to_string(),
+        },
+        pool_alias = match (
+            config.table(&table.name).get_async_wrappers(),
+            &config.read_connection_type
+        ) {
+            (true, Some(read_connection_type)) => format!(
+                "\ntype Pool = {};\ntype ReadPool = {};",
+                pool_type(config),
+                pool_of(read_connection_type, "ReadConnection")
+            ),
+            (true, None) => format!("\ntype Pool = {};", pool_type(config)),
+            (false, _) => "".to_string(),
+        },
         belongs_imports = belongs_imports,
     )
 }
 
-pub fn generate_for_table(table: ParsedTableMacro, config: &GenerationConfig) -> String {
+/// the paths the type mappings of the table's columns (and of the columns joined by its projections) need, sorted and
+/// without duplicates
+pub fn type_imports(
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> Vec<String> {
+    let joined_columns = config
+        .table(&table.name)
+        .get_projections()
+        .iter()
+        .flat_map(|p| p.joined_columns.iter())
+        .filter_map(|(joined_table_name, column_name)| {
+            tables
+                .iter()
+                .find(|t| t.name == *joined_table_name)
+                .and_then(|t| t.column(column_name))
+        })
+        .collect::<Vec<&ParsedColumnMacro>>();
+
+    let mut imports = table
+        .columns
+        .iter()
+        .chain(joined_columns)
+        .flat_map(|c| c.type_imports.iter().cloned())
+        .collect::<Vec<String>>();
+    imports.sort();
+    imports.dedup();
+
+    imports
+}
+
+/// the `Connection` type alias of generated files; with connection features, one alias per feature (gated on the
+/// feature and on the features before it not being enabled) and the connection type for when none is enabled
+pub fn build_connection_alias(config: &GenerationConfig) -> String {
+    let mut alias = String::new();
+    let mut previous_features = vec![];
+    for (feature, connection_type) in config.connection_features.iter() {
+        let cfg = match previous_features.is_empty() {
+            true => format!("feature = \"{feature}\""),
+            false => format!(
+                "all(feature = \"{feature}\", not(any({})))",
+                previous_features.join(", ")
+            ),
+        };
+        alias.push_str(&format!(
+            "#[cfg({cfg})]\ntype Connection = {connection_type};\n"
+        ));
+        previous_features.push(format!("feature = \"{feature}\""));
+    }
+    if !previous_features.is_empty() {
+        alias.push_str(&format!(
+            "#[cfg(not(any({})))]\n",
+            previous_features.join(", ")
+        ));
+    }
+    alias.push_str(&format!("type Connection = {};", config.connection_type));
+
+    alias
+}
+
+/// how generated benchmarks and tests connect to the database at `database_url`: pooled connection types through a
+/// single-connection pool, other connection types directly
+pub fn build_connect(config: &GenerationConfig) -> &'static str {
+    match config
+        .connection_type
+        .starts_with("diesel::r2d2::PooledConnection<")
+    {
+        true => {
+            r##"diesel::r2d2::Pool::builder()
+        .max_size(1)
+        .build(diesel::r2d2::ConnectionManager::new(database_url))
+        .expect("Could not connect to the database")
+        .get()
+        .expect("Could not connect to the database")"##
+        }
+        false => {
+            r##"<Connection as diesel::Connection>::establish(&database_url)
+        .expect("Could not connect to the database")"##
+        }
+    }
+}
+
+/// the r2d2 pool handing out the connections of async wrappers: the pool of pooled connection types, or a pool of
+/// (possibly feature-switched) `Connection`s, whose pooled connections deref into them
+pub fn pool_type(config: &GenerationConfig) -> String {
+    pool_of(&config.connection_type, "Connection")
+}
+
+/// the r2d2 pool of a connection type: the pool of pooled connection types, or a pool of the connections (named by
+/// their type alias)
+fn pool_of(connection_type: &str, alias: &str) -> String {
+    match connection_type
+        .strip_prefix("diesel::r2d2::PooledConnection<")
+        .and_then(|manager| manager.strip_suffix('>'))
+    {
+        Some(manager) => format!("diesel::r2d2::Pool<{manager}>"),
+        None => format!("diesel::r2d2::Pool<diesel::r2d2::ConnectionManager<{alias}>>"),
+    }
+}
+
+/// the connection types of the functions which only read and of those which write: `ReadConnection` and
+/// `WriteConnection` with a read connection type (see `GenerationConfig::read_connection_type`), `Connection` for both
+/// otherwise
+pub fn connection_types(config: &GenerationConfig) -> (&'static str, &'static str) {
+    match config.read_connection_type {
+        Some(_) => ("ReadConnection", "WriteConnection"),
+        None => ("Connection", "Connection"),
+    }
+}
+
+/// the statements of the table's function prologue (see `TableOptions::function_prologue`), indented for function
+/// bodies and followed by a blank line (nothing without a prologue)
+fn function_prologue(table_options: &TableOptions) -> String {
+    table_options
+        .get_function_prologue()
+        .map(|statements| {
+            let statements = statements
+                .trim()
+                .lines()
+                .map(|line| format!("        {}", line.trim()))
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!("{statements}\n\n")
+        })
+        .unwrap_or_default()
+}
+
+/// `//!` docs summarizing the table: its keys and a markdown table of its columns
+pub fn build_module_docs(table: &ParsedTableMacro) -> String {
+    // doc comments and sql names may contain characters with a meaning in markdown tables
+    let cell = |text: &str| text.trim().replace('|', "\\|");
+
+    let sql_name = match table.sql_name() {
+        Some(sql_name) => format!(" (`{sql_name}` in the database)"),
+        None => "".to_string(),
+    };
+    let mut lines = vec![
+        format!("Model for the `{}` table{sql_name}.", table.name),
+        "".to_string(),
+    ];
+
+    if table.primary_key_columns.is_empty() {
+        lines.push("- primary key: none".to_string());
+    } else {
+        lines.push(format!(
+            "- primary key: {}",
+            table
+                .primary_key_columns
+                .iter()
+                .map(|pk| format!("`{pk}`"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    for (foreign_table_name, join_column) in table.foreign_keys.iter() {
+        lines.push(format!(
+            "- foreign key: `{join_column}` references `{foreign_table_name}`"
+        ));
+    }
+
+    lines.push("".to_string());
+    lines.push("| column | sql type | rust type | description |".to_string());
+    lines.push("|--------|----------|-----------|-------------|".to_string());
+    for column in table.columns.iter() {
+        let sql_type = match column.is_nullable {
+            true => format!("Nullable<{}>", column.sql_type),
+            false => column.sql_type.clone(),
+        };
+
+        lines.push(format!(
+            "| `{name}` | `{sql_type}` | `{rust_type}` | {description} |",
+            name = cell(&column.name),
+            rust_type = column_type(column),
+            description = cell(&column.doc_comments().join(" "))
+        ));
+    }
+
+    lines
+        .iter()
+        .map(|line| match line.is_empty() {
+            true => "//!\n".to_string(),
+            false => format!("//! {line}\n"),
+        })
+        .collect::<String>()
+        + "\n"
+}
+
+/// inner attributes at the top of the generated file (the feature gate and configured lint attributes)
+pub fn build_file_attributes(table: &ParsedTableMacro, config: &GenerationConfig) -> String {
+    let table_options = config.table(&table.name);
+
+    let mut attributes = vec![];
+    if let Some(feature) = table_options.get_cfg_feature() {
+        attributes.push(format!("#![cfg(feature = \"{feature}\")]"));
+    }
+    for attribute in table_options.get_file_attributes() {
+        attributes.push(format!("#![{attribute}]"));
+    }
+
+    match attributes.is_empty() {
+        true => "".to_string(),
+        false => format!("{}\n\n", attributes.join("\n")),
+    }
+}
+
+/// renders the diesel models of a table into `out`, piece by piece; with explicit imports, the models are rendered
+/// before the imports (which are computed from them)
+pub fn generate_for_table(
+    out: &mut impl Write,
+    table: ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> io::Result<()> {
+    let foreign_structs = foreign_structs(&table, tables, config);
+    let write_header = |out: &mut dyn Write, models: Option<&str>| {
+        write!(
+            out,
+            "{FILE_SIGNATURE}\n\n{}{}{}\n",
+            build_module_docs(&table),
+            build_file_attributes(&table, config),
+            build_imports(&table, tables, config, &foreign_structs, models)
+        )
+    };
+
+    match config.table(&table.name).get_import_style() {
+        ImportStyle::Glob => {
+            write_header(out, None)?;
+            write_models(out, &table, tables, config, &foreign_structs)
+        }
+        ImportStyle::Explicit | ImportStyle::Prelude => {
+            let mut models = vec![];
+            write_models(&mut models, &table, tables, config, &foreign_structs)?;
+            write_header(out, Some(&String::from_utf8_lossy(&models)))?;
+            out.write_all(&models)
+        }
+    }
+}
+
+/// renders everything of a table's file after the imports: the structs and their functions
+fn write_models(
+    out: &mut impl Write,
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+    foreign_structs: &[ForeignStruct],
+) -> io::Result<()> {
+    let key_types = key_types(table, tables, config);
+
     // first, we generate struct code
-    let read_struct = Struct::new(StructType::Read, &table, config);
-    let update_struct = Struct::new(StructType::Update, &table, config);
-    let create_struct = Struct::new(StructType::Create, &table, config);
+    let read_struct = Struct::new(StructType::Read, table, config, foreign_structs, &key_types);
+    let update_struct = Struct::new(
+        StructType::Update,
+        table,
+        config,
+        foreign_structs,
+        &key_types,
+    );
+    let create_struct = Struct::new(
+        StructType::Create,
+        table,
+        config,
+        foreign_structs,
+        &key_types,
+    );
 
-    let mut structs = String::new();
-    structs.push_str(&read_struct.code());
-    structs.push('\n');
-    structs.push_str(&create_struct.code());
-    structs.push('\n');
-    structs.push_str(&update_struct.code());
+    if !read_struct.derives_identifiable() && config.table(&table.name).get_identifiable() {
+        log::debug!(
+            "table `{}`: the Read struct doesn't derive `Identifiable`, because the table has no primary key",
+            table.name
+        );
+    }
 
-    let functions = build_table_fns(&table, config, create_struct, update_struct);
-    let imports = build_imports(&table, config);
+    let table_options = config.table(&table.name);
+    out.write_all(build_serde_modules(table, tables, config).as_bytes())?;
+    out.write_all(build_id_newtype(table, config).as_bytes())?;
+    writeln!(out, "{}", read_struct.code())?;
+    writeln!(out, "{}", create_struct.code())?;
+    out.write_all(update_struct.code().as_bytes())?;
+    out.write_all(build_update_is_empty(&update_struct).as_bytes())?;
+    if table_options.get_proptest() {
+        out.write_all(
+            build_proptest_strategies(table, &key_types, [&create_struct, &update_struct])
+                .as_bytes(),
+        )?;
+    }
+    if table_options.generates_struct(GeneratedStruct::Form)
+        && !table_options.get_materialized_view()
+    {
+        let form_struct = Struct::new(StructType::Form, table, config, foreign_structs, &key_types);
+        write!(out, "\n{}", form_struct.code())?;
+        out.write_all(
+            build_form_conversions(table, &form_struct, &create_struct, &update_struct).as_bytes(),
+        )?;
+    }
+    writeln!(out, "{}", build_filter(table, config, &key_types))?;
 
-    format!("{FILE_SIGNATURE}\n\n{imports}\n{structs}\n{functions}")
+    out.write_all(
+        build_table_fns(
+            table,
+            config,
+            foreign_structs,
+            read_struct,
+            create_struct,
+            update_struct,
+        )
+        .as_bytes(),
+    )?;
+    out.write_all(build_projections(table, tables, config, &key_types).as_bytes())?;
+    out.write_all(build_query_ext(table, config).as_bytes())
 }
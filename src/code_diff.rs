@@ -0,0 +1,77 @@
+/// number of unchanged lines shown around changed lines
+const CONTEXT_LINES: usize = 2;
+
+/// a line of the diff between two versions of a file
+enum Line<'a> {
+    Unchanged(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// the lines of `old` and `new`, matched by their longest common subsequence
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = match old[i] == new[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(Line::Unchanged(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+
+    lines
+}
+
+/// renders the lines changed between `old` and `new` (prefixed with `-` and `+`) with a few unchanged lines around
+/// them; lines which aren't shown are replaced with `...`
+pub fn render(old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<&str>>();
+    let new = new.lines().collect::<Vec<&str>>();
+    let lines = diff_lines(&old, &new);
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Line::Unchanged(_)))
+        .map(|(index, _)| index)
+        .collect::<Vec<usize>>();
+
+    let mut output = String::new();
+    let mut last_shown: Option<usize> = None;
+    for (index, line) in lines.iter().enumerate() {
+        if !changed.iter().any(|c| c.abs_diff(index) <= CONTEXT_LINES) {
+            continue;
+        }
+        if last_shown.is_some_and(|last| last + 1 < index) {
+            output.push_str("...\n");
+        }
+        last_shown = Some(index);
+
+        let (prefix, text) = match line {
+            Line::Unchanged(text) => (' ', text),
+            Line::Removed(text) => ('-', text),
+            Line::Added(text) => ('+', text),
+        };
+        output.push_str(format!("{prefix} {text}").trim_end());
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,80 @@
+use crate::code::{build_connection_alias, pool_type};
+use crate::imports;
+use crate::parser::{ParsedTableMacro, FILE_SIGNATURE};
+use crate::GenerationConfig;
+
+/// the module connecting to the database configured by environment variables (`DATABASE_URL`, and
+/// `DATABASE_POOL_SIZE` for pools), with a health check
+pub fn render(tables: &[ParsedTableMacro], config: &GenerationConfig) -> Option<String> {
+    if !config.db_module {
+        return None;
+    }
+
+    // pooled connection types are established as the connections they pool
+    let pooled_connection = config
+        .connection_type
+        .strip_prefix("diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<")
+        .and_then(|connection| connection.strip_suffix(">>"));
+    let (connection_alias, connection) = match pooled_connection {
+        Some(connection) => ("".to_string(), connection),
+        None => (
+            format!("\n{}\n", build_connection_alias(config)),
+            "Connection",
+        ),
+    };
+
+    // pools are only used by pooled connection types and async wrappers
+    let is_pooled = pooled_connection.is_some()
+        || tables
+            .iter()
+            .any(|t| config.table(&t.name).get_async_wrappers());
+    let pool = match is_pooled {
+        true => format!(
+            r##"
+pub type Pool = {pool_type};
+
+/// Creates a connection pool for the database at `DATABASE_URL`, with up to `DATABASE_POOL_SIZE` connections (r2d2's
+/// default of 10 if it isn't set)
+pub fn create_pool() -> Result<Pool, DbError> {{
+    let mut builder = Pool::builder();
+    if let Ok(pool_size) = std::env::var("DATABASE_POOL_SIZE") {{
+        builder = builder.max_size(pool_size.parse()?);
+    }}
+
+    Ok(builder.build(diesel::r2d2::ConnectionManager::new(database_url()?))?)
+}}
+"##,
+            pool_type = pool_type(config)
+        ),
+        false => "".to_string(),
+    };
+
+    let diesel_import =
+        imports::diesel_glob_import(config.default_table_options.get_import_style());
+    Some(format!(
+        r##"{FILE_SIGNATURE}
+
+//! Connects to the database configured by the `DATABASE_URL` environment variable.
+
+{diesel_import}
+use diesel::QueryResult;
+{connection_alias}
+pub type DbError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The url of the database, from the `DATABASE_URL` environment variable
+pub fn database_url() -> Result<String, DbError> {{
+    std::env::var("DATABASE_URL").map_err(|_| "DATABASE_URL must be set".into())
+}}
+
+/// Connects to the database at `DATABASE_URL`
+pub fn establish_connection() -> Result<{connection}, DbError> {{
+    Ok(<{connection} as diesel::Connection>::establish(&database_url()?)?)
+}}
+{pool}
+/// Checks that the database answers queries (pooled connections can be passed too), for health check endpoints
+pub fn ping(db: &mut {connection}) -> QueryResult<()> {{
+    diesel::sql_query("SELECT 1").execute(db).map(|_| ())
+}}
+"##
+    ))
+}
@@ -0,0 +1,158 @@
+use std::str::FromStr;
+
+use crate::parser::ParsedTableMacro;
+
+/// Formats for the entity-relationship diagram of a schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramFormat {
+    /// a mermaid `erDiagram` (see https://mermaid.js.org/syntax/entityRelationshipDiagram.html)
+    Mermaid,
+    /// a graphviz digraph (see https://graphviz.org/doc/info/lang.html)
+    Dot,
+}
+
+impl FromStr for DiagramFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mermaid" => Ok(Self::Mermaid),
+            "dot" => Ok(Self::Dot),
+            _ => Err(format!(
+                "unknown diagram format '{s}', expected 'mermaid' or 'dot'"
+            )),
+        }
+    }
+}
+
+pub fn render(tables: &[ParsedTableMacro], format: DiagramFormat) -> String {
+    match format {
+        DiagramFormat::Mermaid => render_mermaid(tables),
+        DiagramFormat::Dot => render_dot(tables),
+    }
+}
+
+/// column names without the raw identifier prefix (`r#type` is `type` in the database)
+fn column_name(name: &str) -> &str {
+    name.trim_start_matches("r#")
+}
+
+fn is_foreign_key(table: &ParsedTableMacro, column: &str) -> bool {
+    table.foreign_keys.iter().any(|(_, c)| c == column)
+}
+
+fn render_mermaid(tables: &[ParsedTableMacro]) -> String {
+    let mut buffer = String::from("erDiagram\n");
+
+    for table in tables.iter() {
+        buffer.push_str(&format!("    {} {{\n", table.name));
+
+        for column in table.columns.iter() {
+            let mut keys = vec![];
+            if table.primary_key_columns.contains(&column.name) {
+                keys.push("PK");
+            }
+            if is_foreign_key(table, &column.name) {
+                keys.push("FK");
+            }
+
+            buffer.push_str(&format!(
+                "        {sql_type} {name}{keys}{comment}\n",
+                sql_type = column.sql_type,
+                name = column_name(&column.name),
+                keys = match keys.is_empty() {
+                    true => "".to_string(),
+                    false => format!(" {}", keys.join(", ")),
+                },
+                comment = match column.is_nullable {
+                    true => " \"nullable\"",
+                    false => "",
+                },
+            ));
+        }
+
+        buffer.push_str("    }\n");
+    }
+
+    for table in tables.iter() {
+        for (foreign_table_name, join_column) in table.foreign_keys.iter() {
+            // a nullable foreign key means rows don't have to reference anything
+            let is_optional = table
+                .column(join_column)
+                .map(|c| c.is_nullable)
+                .unwrap_or_default();
+
+            buffer.push_str(&format!(
+                "    {foreign_table_name} {parent}--o{{ {table_name} : {join_column}\n",
+                parent = if is_optional { "|o" } else { "||" },
+                table_name = table.name,
+                join_column = column_name(join_column),
+            ));
+        }
+    }
+
+    buffer
+}
+
+/// escapes characters with a meaning in graphviz record labels
+fn dot_escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '<' | '>' | '{' | '}' | '|' | '"' | '\\' => format!("\\{c}"),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn render_dot(tables: &[ParsedTableMacro]) -> String {
+    let mut buffer = String::from("digraph schema {\n    rankdir=LR;\n    node [shape=record];\n");
+
+    for table in tables.iter() {
+        let fields = table
+            .columns
+            .iter()
+            .map(|column| {
+                let mut label = format!(
+                    "{name}: {sql_type}",
+                    name = column_name(&column.name),
+                    sql_type = match column.is_nullable {
+                        true => format!("Nullable<{}>", column.sql_type),
+                        false => column.sql_type.clone(),
+                    }
+                );
+                if table.primary_key_columns.contains(&column.name) {
+                    label.push_str(" (PK)");
+                }
+                if is_foreign_key(table, &column.name) {
+                    label.push_str(" (FK)");
+                }
+
+                format!(
+                    "<{port}> {label}\\l",
+                    port = column_name(&column.name),
+                    label = dot_escape(&label)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("|");
+
+        buffer.push_str(&format!(
+            "    \"{name}\" [label=\"{{{name}|{fields}}}\"];\n",
+            name = table.name
+        ));
+    }
+
+    for table in tables.iter() {
+        for (foreign_table_name, join_column) in table.foreign_keys.iter() {
+            buffer.push_str(&format!(
+                "    \"{table_name}\":\"{join_column}\" -> \"{foreign_table_name}\";\n",
+                table_name = table.name,
+                join_column = column_name(join_column),
+            ));
+        }
+    }
+
+    buffer.push_str("}\n");
+
+    buffer
+}
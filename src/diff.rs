@@ -0,0 +1,275 @@
+use crate::parser::{ParsedColumnMacro, ParsedTableMacro};
+use crate::{GeneratedStruct, GenerationConfig};
+
+/// Changes between two versions of a schema (tables and columns are matched by name)
+pub struct SchemaDiff<'a> {
+    pub added_tables: Vec<&'a ParsedTableMacro>,
+    pub dropped_tables: Vec<&'a ParsedTableMacro>,
+    pub changed_tables: Vec<TableDiff<'a>>,
+}
+
+/// Changes to the columns of a table present in both versions of a schema
+pub struct TableDiff<'a> {
+    pub old: &'a ParsedTableMacro,
+    pub new: &'a ParsedTableMacro,
+    pub added_columns: Vec<&'a ParsedColumnMacro>,
+    pub dropped_columns: Vec<&'a ParsedColumnMacro>,
+    /// columns whose type or nullability changed, as `(old, new)`
+    pub changed_columns: Vec<(&'a ParsedColumnMacro, &'a ParsedColumnMacro)>,
+    /// likely renames as `(old, new)`: a removed and an added column with the same type at the same position
+    pub renamed_columns: Vec<(&'a ParsedColumnMacro, &'a ParsedColumnMacro)>,
+}
+
+impl SchemaDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty()
+            && self.dropped_tables.is_empty()
+            && self.changed_tables.is_empty()
+    }
+}
+
+fn is_changed(old: &ParsedColumnMacro, new: &ParsedColumnMacro) -> bool {
+    let max_length =
+        |c: &ParsedColumnMacro| c.attribute("max_length").and_then(|a| a.value.clone());
+
+    old.sql_type != new.sql_type
+        || old.is_nullable != new.is_nullable
+        || old.is_unsigned != new.is_unsigned
+        || max_length(old) != max_length(new)
+}
+
+pub fn diff<'a>(old: &'a [ParsedTableMacro], new: &'a [ParsedTableMacro]) -> SchemaDiff<'a> {
+    let find = |tables: &'a [ParsedTableMacro], name: &str| tables.iter().find(|t| t.name == name);
+
+    let mut changed_tables = vec![];
+    for new_table in new.iter() {
+        let Some(old_table) = find(old, &new_table.name) else {
+            continue;
+        };
+
+        let mut table_diff = TableDiff {
+            old: old_table,
+            new: new_table,
+            added_columns: new_table
+                .columns
+                .iter()
+                .filter(|c| old_table.column(&c.name).is_none())
+                .collect(),
+            dropped_columns: old_table
+                .columns
+                .iter()
+                .filter(|c| new_table.column(&c.name).is_none())
+                .collect(),
+            changed_columns: new_table
+                .columns
+                .iter()
+                .filter_map(|c| old_table.column(&c.name).map(|old_column| (old_column, c)))
+                .filter(|(old_column, new_column)| is_changed(old_column, new_column))
+                .collect(),
+            renamed_columns: vec![],
+        };
+
+        let position = |table: &ParsedTableMacro, column: &ParsedColumnMacro| {
+            table.columns.iter().position(|c| c.name == column.name)
+        };
+        for dropped in table_diff.dropped_columns.clone() {
+            let renamed = table_diff.added_columns.iter().position(|added| {
+                !is_changed(dropped, added)
+                    && position(old_table, dropped) == position(new_table, added)
+            });
+
+            if let Some(index) = renamed {
+                let added = table_diff.added_columns.remove(index);
+                table_diff
+                    .dropped_columns
+                    .retain(|c| c.name != dropped.name);
+                table_diff.renamed_columns.push((dropped, added));
+            }
+        }
+
+        if !table_diff.added_columns.is_empty()
+            || !table_diff.dropped_columns.is_empty()
+            || !table_diff.changed_columns.is_empty()
+            || !table_diff.renamed_columns.is_empty()
+        {
+            changed_tables.push(table_diff);
+        }
+    }
+
+    SchemaDiff {
+        added_tables: new
+            .iter()
+            .filter(|t| find(old, &t.name).is_none())
+            .collect(),
+        dropped_tables: old
+            .iter()
+            .filter(|t| find(new, &t.name).is_none())
+            .collect(),
+        changed_tables,
+    }
+}
+
+/// a column's type as written in schemas, with its maximum length
+fn describe_type(column: &ParsedColumnMacro) -> String {
+    let mut ty = match column.is_nullable {
+        true => format!("`Nullable<{}>`", column.sql_type),
+        false => format!("`{}`", column.sql_type),
+    };
+    if let Some(max_length) = column
+        .attribute("max_length")
+        .and_then(|a| a.value.as_deref())
+    {
+        ty.push_str(&format!(" (max length {max_length})"));
+    }
+
+    ty
+}
+
+/// a line per change, for reviewers (for example: "changed column `users.age` from `Int4` to `Nullable<Int8>`")
+pub fn summary(diff: &SchemaDiff) -> Vec<String> {
+    let mut lines = vec![];
+
+    for table in diff.added_tables.iter() {
+        lines.push(format!("added table `{}`", table.name));
+    }
+    for table in diff.dropped_tables.iter() {
+        lines.push(format!("removed table `{}`", table.name));
+    }
+    for table_diff in diff.changed_tables.iter() {
+        let table_name = &table_diff.new.name;
+
+        for column in table_diff.added_columns.iter() {
+            lines.push(format!(
+                "added column `{table_name}.{}` ({})",
+                column.name,
+                describe_type(column)
+            ));
+        }
+        for column in table_diff.dropped_columns.iter() {
+            lines.push(format!(
+                "removed column `{table_name}.{}` ({})",
+                column.name,
+                describe_type(column)
+            ));
+        }
+        for (old_column, new_column) in table_diff.renamed_columns.iter() {
+            lines.push(format!(
+                "renamed column `{table_name}.{}` to `{}` (likely: same type and position)",
+                old_column.name, new_column.name
+            ));
+        }
+        for (old_column, new_column) in table_diff.changed_columns.iter() {
+            lines.push(format!(
+                "changed column `{table_name}.{}` from {} to {}",
+                new_column.name,
+                describe_type(old_column),
+                describe_type(new_column)
+            ));
+        }
+    }
+
+    lines
+}
+
+/// warnings about options of tables which still refer to columns by their name before a (likely) rename
+pub fn renamed_column_warnings(diff: &SchemaDiff, config: &GenerationConfig) -> Vec<String> {
+    let mut warnings = vec![];
+
+    for table_diff in diff.changed_tables.iter() {
+        let table_name = &table_diff.new.name;
+        let table_options = config.table(table_name);
+
+        for (old_column, new_column) in table_diff.renamed_columns.iter() {
+            let refers_to = |column: &str| column == old_column.name;
+
+            let mut options = vec![];
+            if table_options
+                .get_autogenerated_columns()
+                .iter()
+                .any(|c| refers_to(c))
+            {
+                options.push("autogenerated columns");
+            }
+            if table_options
+                .get_immutable_columns()
+                .iter()
+                .any(|c| refers_to(c))
+            {
+                options.push("immutable columns");
+            }
+            if table_options
+                .get_sensitive_columns()
+                .iter()
+                .any(|c| refers_to(c))
+            {
+                options.push("sensitive columns");
+            }
+            if table_options
+                .get_case_insensitive_columns()
+                .iter()
+                .any(|c| refers_to(c))
+            {
+                options.push("case-insensitive columns");
+            }
+            if [
+                GeneratedStruct::Read,
+                GeneratedStruct::Create,
+                GeneratedStruct::Update,
+                GeneratedStruct::Form,
+            ]
+            .into_iter()
+            .any(|s| {
+                table_options
+                    .get_excluded_columns(s)
+                    .iter()
+                    .any(|c| refers_to(c))
+            }) {
+                options.push("excluded columns");
+            }
+            if table_options
+                .get_default_order()
+                .is_some_and(|(c, _)| refers_to(c))
+            {
+                options.push("default order");
+            }
+            if table_options
+                .get_full_text_search_column()
+                .is_some_and(refers_to)
+            {
+                options.push("full-text search column");
+            }
+            if table_options
+                .get_projections()
+                .iter()
+                .any(|p| p.columns.iter().any(|c| refers_to(c)))
+            {
+                options.push("projections");
+            }
+            // projections of other tables can join this table's columns too
+            if diff.changed_tables.iter().any(|other| {
+                config
+                    .table(&other.new.name)
+                    .get_projections()
+                    .iter()
+                    .any(|p| {
+                        p.joined_columns
+                            .iter()
+                            .any(|(t, c)| t == table_name && refers_to(c))
+                    })
+            }) {
+                options.push("joined columns of projections");
+            }
+
+            if !options.is_empty() {
+                warnings.push(format!(
+                    "column `{table_name}.{old}` looks renamed to `{new}`, but the {options} still refer to `{old}`; replace it with `{new}`",
+                    old = old_column.name,
+                    new = new_column.name,
+                    options = options.join(", ")
+                ));
+            }
+        }
+    }
+
+    warnings
+}
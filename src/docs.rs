@@ -0,0 +1,110 @@
+use crate::code::column_type;
+use crate::parser::ParsedTableMacro;
+
+/// marks markdown files written by dsync (so they can be updated and pruned safely)
+pub const DOCS_SIGNATURE: &str = "<!-- This file is generated and managed by dsync -->";
+
+/// escapes characters with a meaning in markdown tables
+fn cell(text: &str) -> String {
+    text.trim().replace('|', "\\|")
+}
+
+/// the index listing every table
+pub fn index_md(tables: &[ParsedTableMacro]) -> String {
+    let mut buffer = format!(
+        "{DOCS_SIGNATURE}\n\n# Schema\n\n| table | description |\n|-------|-------------|\n"
+    );
+
+    for table in tables.iter() {
+        buffer.push_str(&format!(
+            "| [`{name}`]({name}.md) | {description} |\n",
+            name = table.name,
+            description = cell(&table.doc_comments().join(" "))
+        ));
+    }
+
+    buffer
+}
+
+/// the documentation page of a table: its description, keys, relations and columns
+pub fn table_md(table: &ParsedTableMacro, tables: &[ParsedTableMacro]) -> String {
+    let mut buffer = format!("{DOCS_SIGNATURE}\n\n# `{}`\n\n", table.name);
+
+    let description = table
+        .doc_comments()
+        .iter()
+        .map(|d| d.trim())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    if !description.is_empty() {
+        buffer.push_str(&format!("{description}\n\n"));
+    }
+    if let Some(sql_name) = table.sql_name() {
+        buffer.push_str(&format!("Named `{sql_name}` in the database.\n\n"));
+    }
+
+    buffer.push_str("## Keys\n\n");
+    buffer.push_str(&match table.primary_key_columns.is_empty() {
+        true => "- primary key: none\n".to_string(),
+        false => format!(
+            "- primary key: {}\n",
+            table
+                .primary_key_columns
+                .iter()
+                .map(|pk| format!("`{pk}`"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    });
+
+    let references = table
+        .foreign_keys
+        .iter()
+        .map(|(foreign_table_name, join_column)| {
+            format!(
+                "- `{join_column}` references [`{foreign_table_name}`]({foreign_table_name}.md)\n"
+            )
+        })
+        .collect::<String>();
+    let referenced_by = tables
+        .iter()
+        .flat_map(|other| {
+            other
+                .foreign_keys
+                .iter()
+                .filter(|(foreign_table_name, _)| foreign_table_name == &table.name)
+                .map(|(_, join_column)| {
+                    format!(
+                        "- referenced by [`{other_name}`]({other_name}.md) through `{join_column}`\n",
+                        other_name = other.name
+                    )
+                })
+        })
+        .collect::<String>();
+    if !references.is_empty() || !referenced_by.is_empty() {
+        buffer.push_str(&format!("\n## Relations\n\n{references}{referenced_by}"));
+    }
+
+    buffer.push_str("\n## Columns\n\n| column | sql type | rust type | nullable | key | description |\n|--------|----------|-----------|----------|-----|-------------|\n");
+    for column in table.columns.iter() {
+        let mut keys = vec![];
+        if table.primary_key_columns.contains(&column.name) {
+            keys.push("primary");
+        }
+        if table.foreign_keys.iter().any(|(_, c)| c == &column.name) {
+            keys.push("foreign");
+        }
+
+        buffer.push_str(&format!(
+            "| `{name}` | `{sql_type}` | `{rust_type}` | {nullable} | {keys} | {description} |\n",
+            name = cell(&column.name),
+            sql_type = column.sql_type,
+            rust_type = column_type(column),
+            nullable = if column.is_nullable { "yes" } else { "no" },
+            keys = keys.join(", "),
+            description = cell(&column.doc_comments().join(" "))
+        ));
+    }
+
+    buffer
+}
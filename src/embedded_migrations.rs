@@ -0,0 +1,35 @@
+use crate::code::build_connection_alias;
+use crate::parser::FILE_SIGNATURE;
+use crate::GenerationConfig;
+
+/// the module embedding the migrations of the configured directory, if migrations are embedded
+pub fn render(config: &GenerationConfig) -> Option<String> {
+    let migrations_dir = config.migrations_dir?;
+
+    Some(format!(
+        r##"{FILE_SIGNATURE}
+
+//! Embeds the migrations in `{migrations_dir}` into the crate, so databases can be migrated (for example, on startup)
+//! without the diesel CLI.
+
+use diesel_migrations::{{embed_migrations, EmbeddedMigrations, MigrationHarness}};
+
+{connection_alias}
+
+/// The migrations in `{migrations_dir}` (relative to the crate), read at compile time
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("{migrations_dir}");
+
+pub type MigrationError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Runs the migrations which haven't been applied yet, returning the versions of the migrations it ran
+pub fn run_pending_migrations(db: &mut Connection) -> Result<Vec<String>, MigrationError> {{
+    Ok(db
+        .run_pending_migrations(MIGRATIONS)?
+        .iter()
+        .map(|version| version.to_string())
+        .collect())
+}}
+"##,
+        connection_alias = build_connection_alias(config)
+    ))
+}
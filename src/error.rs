@@ -0,0 +1,89 @@
+use proc_macro2::Span;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// An error found while parsing the diesel schema file, pointing at the offending location
+#[derive(Debug, Clone)]
+pub struct SchemaError {
+    pub message: String,
+    pub file: Option<PathBuf>,
+    /// 1-based line of the offending tokens
+    pub line: usize,
+    /// 1-based column of the offending tokens
+    pub column: usize,
+    /// number of characters to underline (at least 1)
+    len: usize,
+    /// the source line containing the offending tokens (only available after `with_source`)
+    snippet: Option<String>,
+}
+
+impl SchemaError {
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        let start = span.start();
+        let end = span.end();
+
+        Self {
+            message: message.into(),
+            file: None,
+            line: start.line,
+            column: start.column + 1,
+            len: if end.line == start.line && end.column > start.column {
+                end.column - start.column
+            } else {
+                1
+            },
+            snippet: None,
+        }
+    }
+
+    /// attaches the offending source line so it can be shown in the error
+    pub fn with_source(self, source: &str) -> Self {
+        Self {
+            snippet: source
+                .lines()
+                .nth(self.line.saturating_sub(1))
+                .map(|l| l.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_file(self, file: &Path) -> Self {
+        Self {
+            file: Some(file.to_path_buf()),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.message)?;
+
+        let file = self
+            .file
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "<schema>".to_string());
+        write!(
+            f,
+            "  --> {file}:{line}:{column}",
+            line = self.line,
+            column = self.column
+        )?;
+
+        if let Some(snippet) = &self.snippet {
+            let gutter = " ".repeat(self.line.to_string().len());
+            write!(
+                f,
+                "\n{gutter} |\n{line} | {snippet}\n{gutter} | {pad}{marker}",
+                line = self.line,
+                pad = " ".repeat(self.column - 1),
+                marker = "^".repeat(self.len),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaError {}
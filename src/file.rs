@@ -1,22 +1,30 @@
 use std::path::PathBuf;
 
+use crate::report::FileStatus;
+
 pub struct MarkedFile {
     pub file_contents: String,
     pub path: PathBuf,
+    /// contents of the file before any changes were made (`None` if the file didn't exist)
+    original_contents: Option<String>,
 }
 
 impl MarkedFile {
     pub fn new(path: PathBuf) -> MarkedFile {
-        MarkedFile {
-            path: path.clone(),
-            file_contents: if !path.exists() {
-                std::fs::write(&path, "")
-                    .unwrap_or_else(|_| panic!("Could not write to '{path:#?}'"));
-                "".to_string()
-            } else {
+        let original_contents = if !path.exists() {
+            std::fs::write(&path, "").unwrap_or_else(|_| panic!("Could not write to '{path:#?}'"));
+            None
+        } else {
+            Some(
                 std::fs::read_to_string(&path)
-                    .unwrap_or_else(|_| panic!("Could not read '{path:#?}'"))
-            },
+                    .unwrap_or_else(|_| panic!("Could not read '{path:#?}'")),
+            )
+        };
+
+        MarkedFile {
+            path,
+            file_contents: original_contents.clone().unwrap_or_default(),
+            original_contents,
         }
     }
 
@@ -58,6 +66,9 @@ impl MarkedFile {
     }
 
     pub fn remove_mod_stmt(&mut self, mod_name: &str) {
+        // feature gates belong to the statement, so they're removed with it
+        self.set_mod_stmt_cfg_feature(mod_name, None);
+
         let content_to_remove = &format!("pub mod {mod_name};");
         if self.file_contents.contains(content_to_remove) {
             self.file_contents = self
@@ -80,6 +91,29 @@ impl MarkedFile {
         }
     }
 
+    /// adds, replaces or removes the `#[cfg(feature = "...")]` attribute on the line before a mod statement
+    pub fn set_mod_stmt_cfg_feature(&mut self, mod_name: &str, cfg_feature: Option<&str>) {
+        let mod_stmt = format!("pub mod {mod_name};");
+        let mut lines: Vec<String> = self.file_contents.lines().map(|l| l.to_string()).collect();
+        let Some(index) = lines.iter().position(|l| l.trim() == mod_stmt) else {
+            return;
+        };
+
+        let has_cfg = index > 0 && lines[index - 1].trim().starts_with("#[cfg(feature");
+        match (has_cfg, cfg_feature) {
+            (true, None) => {
+                lines.remove(index - 1);
+            }
+            (true, Some(feature)) => lines[index - 1] = format!("#[cfg(feature = \"{feature}\")]"),
+            (false, Some(feature)) => {
+                lines.insert(index, format!("#[cfg(feature = \"{feature}\")]"))
+            }
+            (false, None) => return,
+        }
+
+        self.file_contents = lines.join("\n") + "\n";
+    }
+
     pub fn has_file_signature(&self) -> bool {
         // the reason we consider filelength=0 as having a file signature is because
         // the whole purpose of file signatures is to prevent writing to files which aren't generated
@@ -97,13 +131,32 @@ impl MarkedFile {
         }
     }
 
-    pub fn write(&self) {
-        std::fs::write(&self.path, &self.file_contents)
+    /// writes the file contents to disk, skipping the write if the contents didn't change
+    pub fn write(&self) -> FileStatus {
+        self.write_contents(&self.file_contents)
+    }
+
+    /// like `write`, but writes `contents` instead of the file contents (so code which is already rendered doesn't
+    /// need to be copied into the file first)
+    pub fn write_contents(&self, contents: &str) -> FileStatus {
+        let status = match &self.original_contents {
+            None => FileStatus::Created,
+            Some(original_contents) if original_contents == contents => {
+                return FileStatus::Unchanged
+            }
+            Some(_) => FileStatus::Updated,
+        };
+
+        std::fs::write(&self.path, contents)
             .unwrap_or_else(|_| panic!("Could not write to file '{:#?}'", self.path));
+
+        status
     }
 
-    pub fn delete(self) {
+    pub fn delete(self) -> FileStatus {
         std::fs::remove_file(&self.path)
             .unwrap_or_else(|_| panic!("Could not delete redundant file '{:#?}'", self.path));
+
+        FileStatus::Deleted
     }
 }
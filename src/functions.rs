@@ -0,0 +1,148 @@
+use anyhow::bail;
+
+use crate::code::build_connection_alias;
+use crate::imports;
+use crate::parser::{column_type_mapping, FILE_SIGNATURE};
+use crate::{GenerationConfig, SqlFunction};
+
+/// the path of a diesel sql type (types which aren't paths already are taken from `diesel::sql_types`)
+fn sql_type_path(sql_type: &str) -> String {
+    match sql_type
+        .strip_prefix("Nullable<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        Some(inner) => format!(
+            "diesel::sql_types::Nullable<{}>",
+            sql_type_path(inner.trim())
+        ),
+        None if sql_type.contains("::") => sql_type.to_string(),
+        None => format!("diesel::sql_types::{sql_type}"),
+    }
+}
+
+/// the rust type of values of a sql type (`Nullable<...>` types map to options)
+fn rust_type(sql_type: &str, config: &GenerationConfig) -> Option<String> {
+    match sql_type
+        .strip_prefix("Nullable<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        Some(inner) => rust_type(inner.trim(), config).map(|ty| format!("Option<{ty}>")),
+        None => column_type_mapping(
+            sql_type.rsplit("::").next().unwrap_or(sql_type),
+            &config.default_table_options,
+            config,
+        )
+        .map(|mapping| mapping.rust_type),
+    }
+}
+
+/// arguments are borrowed where that's what callers usually have at hand (`&str` rather than `String`)
+fn arg_type(rust_type: &str) -> String {
+    match rust_type {
+        "String" => "&str".to_string(),
+        "Option<String>" => "Option<&str>".to_string(),
+        "Vec<u8>" => "&[u8]".to_string(),
+        "Option<Vec<u8>>" => "Option<&[u8]>".to_string(),
+        ty => ty.to_string(),
+    }
+}
+
+fn build_function(function: &SqlFunction, config: &GenerationConfig) -> anyhow::Result<String> {
+    let name = function.name;
+    let fn_name = name.rsplit('.').next().unwrap_or(name);
+    let is_postgres = config.connection_type.contains("PgConnection");
+
+    let mut params = vec!["db: &mut Connection".to_string()];
+    let mut binds = vec![];
+    for (index, (arg_name, sql_type)) in function.args.iter().enumerate() {
+        let Some(ty) = rust_type(sql_type, config) else {
+            bail!(
+                "Unknown sql type '{sql_type}' of argument '{arg_name}' of sql function '{name}'"
+            );
+        };
+
+        params.push(format!("{arg_name}: {}", arg_type(&ty)));
+        binds.push((
+            format!(
+                ".bind::<{sql_type}, _>({arg_name})",
+                sql_type = sql_type_path(sql_type)
+            ),
+            match is_postgres {
+                true => format!("${}", index + 1),
+                false => "?".to_string(),
+            },
+        ));
+    }
+    let params = params.join(", ");
+
+    let Some(return_type) = function.return_type else {
+        let placeholders = binds
+            .iter()
+            .map(|(_, placeholder)| placeholder.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        let binds = binds
+            .iter()
+            .map(|(bind, _)| bind.as_str())
+            .collect::<String>();
+
+        return Ok(format!(
+            r##"
+/// Invokes the `{name}` procedure (with `CALL`)
+pub fn {fn_name}({params}) -> QueryResult<usize> {{
+    diesel::sql_query("CALL {name}({placeholders})"){binds}.execute(db)
+}}
+"##
+        ));
+    };
+
+    let Some(return_rust_type) = rust_type(return_type, config) else {
+        bail!("Unknown return type '{return_type}' of sql function '{name}'");
+    };
+    // the arguments are bound in between the pieces of the call
+    let call = match binds.is_empty() {
+        true => format!(r#"("{name}()")"#),
+        false => format!(
+            r#"("{name}("){binds}.sql(")")"#,
+            binds = binds
+                .iter()
+                .map(|(bind, _)| bind.as_str())
+                .collect::<Vec<&str>>()
+                .join(r#".sql(", ")"#)
+        ),
+    };
+
+    Ok(format!(
+        r##"
+/// Calls the `{name}` database function
+pub fn {fn_name}({params}) -> QueryResult<{return_rust_type}> {{
+    diesel::select(diesel::dsl::sql::<{return_sql_type}>{call}).get_result::<{return_rust_type}>(db)
+}}
+"##,
+        return_sql_type = sql_type_path(return_type)
+    ))
+}
+
+/// the module of typed wrappers around the configured database functions and procedures
+pub fn render(config: &GenerationConfig) -> anyhow::Result<String> {
+    let diesel_import =
+        imports::diesel_glob_import(config.default_table_options.get_import_style());
+    let mut buffer = format!(
+        r##"{FILE_SIGNATURE}
+
+//! Typed wrappers for database functions and procedures.
+
+{diesel_import}
+use diesel::QueryResult;
+
+{connection_alias}
+"##,
+        connection_alias = build_connection_alias(config)
+    );
+
+    for function in config.functions.iter() {
+        buffer.push_str(&build_function(function, config)?);
+    }
+
+    Ok(buffer)
+}
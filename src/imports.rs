@@ -0,0 +1,314 @@
+use crate::parser::ParsedTableMacro;
+use crate::ImportStyle;
+
+/// the functions of diesel building statements which generated code calls directly (diesel's prelude doesn't have
+/// them)
+const DIESEL_FUNCTIONS: &[&str] = &["delete", "insert_into", "select", "sql_query", "update"];
+
+/// the items of diesel's prelude which generated code names directly: the derive macros and `BoxableExpression`
+const DIESEL_PRELUDE_ITEMS: &[&str] = &[
+    "AsChangeset",
+    "Associations",
+    "BoxableExpression",
+    "Identifiable",
+    "Insertable",
+    "Queryable",
+    "QueryableByName",
+    "Selectable",
+];
+
+/// the traits of diesel's prelude whose methods generated code calls, with those methods
+const DIESEL_TRAITS: &[(&str, &[&str])] = &[
+    ("BoolExpressionMethods", &["and", "or"]),
+    (
+        "ExpressionMethods",
+        &[
+            "asc",
+            "between",
+            "desc",
+            "eq",
+            "eq_any",
+            "ge",
+            "gt",
+            "is_not_null",
+            "is_null",
+            "le",
+            "lt",
+            "ne",
+            "ne_all",
+            "not_between",
+        ],
+    ),
+    (
+        "NullableExpressionMethods",
+        &["assume_not_null", "nullable"],
+    ),
+    ("OptionalExtension", &["optional"]),
+    ("PgTextExpressionMethods", &["ilike", "not_ilike"]),
+    (
+        "QueryDsl",
+        &[
+            "count",
+            "distinct",
+            "filter",
+            "find",
+            "group_by",
+            "inner_join",
+            "into_boxed",
+            "left_join",
+            "left_outer_join",
+            "limit",
+            "offset",
+            "or_filter",
+            "order",
+            "order_by",
+            "select",
+            "then_order_by",
+        ],
+    ),
+    (
+        "RunQueryDsl",
+        &[
+            "execute",
+            "first",
+            "get_result",
+            "get_results",
+            "load",
+            "load_iter",
+        ],
+    ),
+    ("SelectableHelper", &["as_returning", "as_select"]),
+    ("TextExpressionMethods", &["concat", "like", "not_like"]),
+];
+
+/// the traits of diesel's prelude whose functions generated code calls through paths (like `Self::as_select()`)
+const DIESEL_TRAIT_FUNCTIONS: &[(&str, &[&str])] =
+    &[("SelectableHelper", &["as_returning", "as_select"])];
+
+/// keywords after which an identifier is being defined (rather than used)
+const DEFINING_KEYWORDS: &[&str] = &[
+    "const", "enum", "fn", "let", "mod", "mut", "static", "struct", "trait", "type",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Usage {
+    /// called as a method (`.name`)
+    Method,
+    /// a segment of a path after the first (`path::name`)
+    PathSegment,
+    /// defined (`fn name`, `let name`), a field or parameter (`name: Type`), or imported (`use path::name;`)
+    Definition,
+    /// used by itself, but imported into the enclosing block (like the table modules imported by the functions)
+    LocallyImported,
+    /// used by itself, so it has to be in scope (`name(…)`, `#[derive(Name)]`, `name::table`)
+    Free,
+}
+
+/// the number of `#` of the raw string literal starting at `r` (`r"…"`, `r#"…"#`)
+fn raw_string_hashes(code: &[u8]) -> Option<usize> {
+    let hashes = code[1..].iter().take_while(|b| **b == b'#').count();
+
+    (code.get(1 + hashes) == Some(&b'"')).then_some(hashes)
+}
+
+/// the identifiers of rust code and how they're used; comments and the contents of string and character literals are
+/// skipped
+fn identifiers(code: &str) -> Vec<(&str, Usage)> {
+    let bytes = code.as_bytes();
+    let is_identifier_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || !b.is_ascii();
+
+    let mut identifiers = vec![];
+    let mut previous_token = "";
+    // the names imported by the `use` statement being read, and the names imported into the enclosing blocks (with
+    // the depth of their blocks)
+    let mut use_statement: Option<Vec<&str>> = None;
+    let mut local_imports: Vec<(usize, &str)> = vec![];
+    let mut depth: usize = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(b"//") {
+            i += rest.iter().position(|b| *b == b'\n').unwrap_or(rest.len());
+        } else if rest.starts_with(b"/*") {
+            i += rest
+                .windows(2)
+                .position(|w| w == b"*/")
+                .map_or(rest.len(), |p| p + 2);
+        } else if rest[0] == b'"' {
+            let mut j = 1;
+            while j < rest.len() && rest[j] != b'"' {
+                j += if rest[j] == b'\\' { 2 } else { 1 };
+            }
+            i += j + 1;
+            previous_token = "\"";
+        } else if rest[0] == b'r' && raw_string_hashes(rest).is_some() {
+            // raw string literals: `r"…"`, `r#"…"#` (but not raw identifiers, like `r#type`)
+            let hashes = raw_string_hashes(rest).unwrap_or_default();
+            let terminator = [b"\"".as_slice(), &vec![b'#'; hashes]].concat();
+            let start = 2 + hashes;
+            i += rest[start.min(rest.len())..]
+                .windows(terminator.len())
+                .position(|w| w == terminator.as_slice())
+                .map_or(rest.len(), |p| start + p + terminator.len());
+            previous_token = "\"";
+        } else if rest[0] == b'\'' {
+            // character literals (`'a'`, `'\n'`); otherwise, the quote starts a lifetime
+            match (rest.get(1), rest.get(2)) {
+                (Some(b'\\'), _) => {
+                    i += rest[2..]
+                        .iter()
+                        .position(|b| *b == b'\'')
+                        .map_or(rest.len(), |p| p + 3)
+                }
+                (Some(_), Some(b'\'')) => i += 3,
+                _ => i += 1,
+            }
+            previous_token = "'";
+        } else if is_identifier_byte(rest[0]) {
+            let length = rest
+                .iter()
+                .position(|b| !is_identifier_byte(*b))
+                .unwrap_or(rest.len());
+            let identifier = &code[i..i + length];
+            i += length;
+
+            let next = bytes[i..].iter().position(|b| !b.is_ascii_whitespace());
+            let is_annotated = next.is_some_and(|n| {
+                let next = &bytes[i + n..];
+                next.starts_with(b":") && !next.starts_with(b"::")
+            });
+            let usage = match previous_token {
+                _ if use_statement.is_some() => Usage::Definition,
+                "." => Usage::Method,
+                "::" => Usage::PathSegment,
+                keyword if DEFINING_KEYWORDS.contains(&keyword) => Usage::Definition,
+                _ if is_annotated => Usage::Definition,
+                _ if local_imports.iter().any(|(_, name)| *name == identifier) => {
+                    Usage::LocallyImported
+                }
+                _ => Usage::Free,
+            };
+            if let Some(names) = use_statement.as_mut() {
+                // the last segments of the paths (or their aliases) are the imported names
+                let next = next.map(|n| bytes[i + n]);
+                if matches!(next, Some(b',' | b'}' | b';')) && identifier != "self" {
+                    names.push(identifier);
+                }
+            } else if identifier == "use" && !matches!(previous_token, "." | "::") {
+                use_statement = Some(vec![]);
+            }
+            if !identifier.as_bytes()[0].is_ascii_digit() {
+                identifiers.push((identifier, usage));
+            }
+            previous_token = identifier;
+        } else if rest[0].is_ascii_whitespace() {
+            i += 1;
+        } else if rest.starts_with(b"::") {
+            i += 2;
+            previous_token = "::";
+        } else {
+            match rest[0] {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth = depth.saturating_sub(1);
+                    local_imports.retain(|(import_depth, _)| *import_depth <= depth);
+                }
+                b';' => {
+                    if let Some(names) = use_statement.take() {
+                        local_imports.extend(names.into_iter().map(|name| (depth, name)));
+                    }
+                }
+                _ => {}
+            }
+            previous_token = &code[i..i + 1];
+            i += 1;
+        }
+    }
+
+    identifiers
+}
+
+/// `use {path}::{items};` (or `use {path}::{item};` for a single item), with the items sorted like rustfmt sorts them
+/// (lowercase names first); nothing without items
+fn use_line(path: &str, mut items: Vec<&str>) -> Option<String> {
+    items.sort_by_key(|item| (item.starts_with(|c: char| c.is_ascii_uppercase()), *item));
+    items.dedup();
+
+    match items.as_slice() {
+        [] => None,
+        [item] => Some(format!("use {path}::{item};")),
+        items => Some(format!("use {path}::{{{}}};", items.join(", "))),
+    }
+}
+
+/// the glob import of diesel in the files shared by the tables (like the seed and db modules): the re-exported
+/// `crate::diesel`, or diesel's prelude with [`ImportStyle::Prelude`]
+pub fn diesel_glob_import(style: ImportStyle) -> &'static str {
+    match style {
+        ImportStyle::Glob | ImportStyle::Explicit => "use crate::diesel::*;",
+        ImportStyle::Prelude => "use diesel::prelude::*;",
+    }
+}
+
+/// the imports of the diesel items, schema tables and serde traits which the (rendered) code of a table's file uses,
+/// each by name; with [`ImportStyle::Prelude`], diesel's prelude is glob-imported instead, along with the functions it
+/// doesn't have (by their direct paths, like `diesel::insert_into`)
+pub fn explicit_imports(
+    code: &str,
+    style: ImportStyle,
+    schema_module: &str,
+    tables: &[ParsedTableMacro],
+) -> Vec<String> {
+    let identifiers = identifiers(code);
+    let is_used = |name: &str, usage: Usage| identifiers.contains(&(name, usage));
+
+    let diesel_functions = DIESEL_FUNCTIONS
+        .iter()
+        .copied()
+        .filter(|item| is_used(item, Usage::Free))
+        .collect::<Vec<&str>>();
+    let diesel_items = DIESEL_PRELUDE_ITEMS
+        .iter()
+        .copied()
+        .filter(|item| is_used(item, Usage::Free))
+        .chain(diesel_functions.iter().copied())
+        .chain(
+            DIESEL_TRAITS
+                .iter()
+                .filter(|(_, methods)| methods.iter().any(|m| is_used(m, Usage::Method)))
+                .map(|(name, _)| *name),
+        )
+        .chain(
+            DIESEL_TRAIT_FUNCTIONS
+                .iter()
+                .filter(|(_, functions)| functions.iter().any(|f| is_used(f, Usage::PathSegment)))
+                .map(|(name, _)| *name),
+        )
+        .collect();
+    let schema_items = tables
+        .iter()
+        .map(|t| t.name.as_str())
+        .filter(|name| is_used(name, Usage::Free))
+        .collect();
+    let serde_items = ["Deserialize", "Serialize"]
+        .into_iter()
+        .filter(|item| is_used(item, Usage::Free))
+        .collect();
+
+    let mut imports = match style {
+        ImportStyle::Prelude => vec![
+            Some("use diesel::prelude::*;".to_string()),
+            use_line("diesel", diesel_functions),
+            use_line(schema_module, schema_items),
+        ],
+        ImportStyle::Glob | ImportStyle::Explicit => vec![
+            use_line("crate::diesel", diesel_items),
+            use_line(schema_module, schema_items),
+            // diesel's prelude has `QueryResult`
+            is_used("QueryResult", Usage::Free).then(|| "use diesel::QueryResult;".to_string()),
+        ],
+    };
+    imports.push(use_line("serde", serde_items));
+
+    imports.into_iter().flatten().collect()
+}
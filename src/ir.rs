@@ -0,0 +1,238 @@
+use anyhow::{bail, Context};
+use inflector::Inflector;
+use serde::{Deserialize, Serialize};
+
+use crate::parser::{
+    column_type_mapping, disambiguate_struct_name, transliterate, ParsedAttribute,
+    ParsedColumnMacro, ParsedTableMacro,
+};
+use crate::{GenerationConfig, TypeMapping};
+
+/// version of the IR document format (bumped on breaking changes)
+pub const IR_VERSION: u32 = 1;
+
+/// A language-agnostic description of the parsed schema
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaIr {
+    pub version: u32,
+    pub tables: Vec<TableIr>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TableIr {
+    pub name: String,
+    /// name of the generated Read struct (derived from the table name if it's left out)
+    #[serde(default)]
+    pub struct_name: String,
+    pub columns: Vec<ColumnIr>,
+    pub primary_key: Vec<String>,
+    #[serde(default)]
+    pub foreign_keys: Vec<ForeignKeyIr>,
+    /// tables which are allowed to appear in the same query as this one
+    #[serde(default)]
+    pub same_query_tables: Vec<String>,
+    /// attributes of the `table!` entry, including doc comments (`doc`) and `sql_name`
+    #[serde(default)]
+    pub attributes: Vec<AttributeIr>,
+    /// the (postgres) schema of schema-qualified tables, which is their default group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// the generation options applied to the table (informational; they're ignored when importing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ColumnIr {
+    pub name: String,
+    /// the diesel sql type (for example: `Int4`)
+    pub sql_type: String,
+    /// the rust type of the column's values, disregarding nullability and signedness (derived from the sql type if
+    /// it's left out)
+    #[serde(default)]
+    pub rust_type: String,
+    #[serde(default)]
+    pub is_nullable: bool,
+    #[serde(default)]
+    pub is_unsigned: bool,
+    /// paths the models import to use the rust type (only used along with a `rust_type`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<String>,
+    /// serde attributes of fields holding the column's values (only used along with a `rust_type`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub serde_attributes: Vec<String>,
+    #[serde(default)]
+    pub attributes: Vec<AttributeIr>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForeignKeyIr {
+    /// the referenced table
+    pub table: String,
+    /// the column of this table referencing the other table's primary key
+    pub column: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttributeIr {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl From<&ParsedAttribute> for AttributeIr {
+    fn from(attribute: &ParsedAttribute) -> Self {
+        Self {
+            name: attribute.name.clone(),
+            value: attribute.value.clone(),
+        }
+    }
+}
+
+impl From<AttributeIr> for ParsedAttribute {
+    fn from(attribute: AttributeIr) -> Self {
+        Self {
+            name: attribute.name,
+            value: attribute.value,
+        }
+    }
+}
+
+impl From<&ParsedColumnMacro> for ColumnIr {
+    fn from(column: &ParsedColumnMacro) -> Self {
+        Self {
+            name: column.name.clone(),
+            sql_type: column.sql_type.clone(),
+            rust_type: column.ty.clone(),
+            is_nullable: column.is_nullable,
+            is_unsigned: column.is_unsigned,
+            imports: column.type_imports.clone(),
+            serde_attributes: column.serde_attributes.clone(),
+            attributes: column.attributes.iter().map(AttributeIr::from).collect(),
+        }
+    }
+}
+
+impl TableIr {
+    pub fn new(table: &ParsedTableMacro, config: &GenerationConfig) -> Self {
+        Self {
+            name: table.name.clone(),
+            struct_name: table.struct_name.clone(),
+            columns: table.columns.iter().map(ColumnIr::from).collect(),
+            primary_key: table.primary_key_columns.clone(),
+            foreign_keys: table
+                .foreign_keys
+                .iter()
+                .map(|(table, column)| ForeignKeyIr {
+                    table: table.clone(),
+                    column: column.clone(),
+                })
+                .collect(),
+            same_query_tables: table.same_query_tables.clone(),
+            attributes: table.attributes.iter().map(AttributeIr::from).collect(),
+            schema: table.schema.clone(),
+            options: serde_json::to_value(config.table(&table.name)).ok(),
+        }
+    }
+}
+
+/// serializes the parsed tables (along with the options applied to them) as a pretty-printed JSON document
+pub fn export(tables: &[ParsedTableMacro], config: &GenerationConfig) -> String {
+    let schema = SchemaIr {
+        version: IR_VERSION,
+        tables: tables
+            .iter()
+            .map(|table| TableIr::new(table, config))
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&schema).expect("Could not serialize the schema IR")
+}
+
+pub fn import(contents: &str, config: &GenerationConfig) -> anyhow::Result<Vec<ParsedTableMacro>> {
+    let schema: SchemaIr =
+        serde_json::from_str(contents).context("Could not parse the schema IR")?;
+    if schema.version != IR_VERSION {
+        bail!(
+            "Unsupported schema IR version {} (expected {IR_VERSION})",
+            schema.version
+        );
+    }
+
+    let mut tables = vec![];
+    for table in schema.tables {
+        let table_options = config.table(&table.name);
+        if table_options.get_ignore() {
+            continue;
+        }
+
+        let mut columns = vec![];
+        for column in table.columns {
+            let mapping = match column.rust_type.is_empty() {
+                true => column_type_mapping(&column.sql_type, &table_options, config)
+                    .with_context(|| {
+                        format!(
+                            "Unknown sql type '{}' of column '{}.{}' (please specify its `rust_type`)",
+                            column.sql_type, table.name, column.name
+                        )
+                    })?,
+                false => TypeMapping {
+                    rust_type: column.rust_type,
+                    imports: column.imports,
+                    serde_attributes: column.serde_attributes,
+                },
+            };
+
+            columns.push(ParsedColumnMacro {
+                ty: mapping.rust_type,
+                type_imports: mapping.imports,
+                serde_attributes: mapping.serde_attributes,
+                sql_type: column.sql_type,
+                name: column.name,
+                is_nullable: column.is_nullable,
+                is_unsigned: column.is_unsigned,
+                attributes: column
+                    .attributes
+                    .into_iter()
+                    .map(ParsedAttribute::from)
+                    .collect(),
+            });
+        }
+
+        for pk in table.primary_key.iter() {
+            if !columns.iter().any(|c| &c.name == pk) {
+                bail!(
+                    "Primary key column '{pk}' doesn't exist in table '{}'",
+                    table.name
+                );
+            }
+        }
+
+        let mut parsed_table = ParsedTableMacro {
+            struct_name: match table.struct_name.is_empty() {
+                true => transliterate(&table.name).to_pascal_case().to_singular(),
+                false => table.struct_name,
+            },
+            name: table.name,
+            columns,
+            primary_key_columns: table.primary_key,
+            foreign_keys: table
+                .foreign_keys
+                .into_iter()
+                .map(|fk| (fk.table, fk.column))
+                .collect(),
+            same_query_tables: table.same_query_tables,
+            attributes: table
+                .attributes
+                .into_iter()
+                .map(ParsedAttribute::from)
+                .collect(),
+            schema: table.schema,
+            generated_code: String::new(),
+        };
+        disambiguate_struct_name(&tables, &mut parsed_table, config).map_err(anyhow::Error::msg)?;
+        tables.push(parsed_table);
+    }
+
+    Ok(tables)
+}
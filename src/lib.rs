@@ -1,19 +1,642 @@
+mod benches;
+mod cache;
 mod code;
+mod code_diff;
+mod db_module;
+mod diagram;
+mod diff;
+mod docs;
+mod embedded_migrations;
+mod error;
 mod file;
+mod functions;
+mod imports;
+mod ir;
+mod manifest;
+mod migration;
+mod openapi;
 mod parser;
+mod proto;
+mod report;
+mod reverse;
+mod seaorm;
+mod seed;
+mod sqlx;
+mod test_support;
+mod types;
+mod validate;
+mod warnings;
+mod zod;
 
+use anyhow::{bail, Context};
+pub use diagram::DiagramFormat;
+pub use error::SchemaError;
 use file::MarkedFile;
 use parser::ParsedTableMacro;
 pub use parser::FILE_SIGNATURE;
-use std::collections::HashMap;
-use std::path::PathBuf;
+pub use proto::ProtoConfig;
+use rayon::prelude::*;
+pub use report::{FileChange, FileStatus, GenerationReport, TableTiming, Timings};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+pub use types::{DieselTypeMapper, TypeMapper, TypeMapping, TypeRule};
 
-#[derive(Default, Debug, Clone)]
+/// A struct holding a subset of a table's columns (and optionally columns of tables joined with it)
+/// for which a dedicated `Queryable` struct and loader function will be generated.
+#[derive(Debug, Clone, Serialize)]
+pub struct Projection<'a> {
+    /// Name of the generated struct (for example: `TodoSummary`)
+    pub name: &'a str,
+    /// Columns of the table this projection belongs to
+    pub columns: Vec<&'a str>,
+    /// Columns of other tables as `(table, column)`; these tables are inner-joined and must be joinable
+    /// and allowed to appear in the same query as the projection's table.
+    pub joined_columns: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Projection<'a> {
+    pub fn new(name: &'a str, columns: Vec<&'a str>) -> Self {
+        Self {
+            name,
+            columns,
+            joined_columns: vec![],
+        }
+    }
+
+    pub fn joined_column(self, table: &'a str, column: &'a str) -> Self {
+        let mut joined_columns = self.joined_columns;
+        joined_columns.push((table, column));
+
+        Self {
+            joined_columns,
+            ..self
+        }
+    }
+}
+
+/// A named, chainable filter for a table's boxed query, generated as a method of the table's `QueryExt` trait
+#[derive(Debug, Clone, Serialize)]
+pub struct Scope<'a> {
+    /// Name of the generated method (for example: `published`)
+    pub name: &'a str,
+    /// Parameters of the generated method as `(name, rust type)` (for example: `("param_user_id", "i32")`)
+    pub params: Vec<(&'a str, &'a str)>,
+    /// Query method call applied to the boxed query, with the table's dsl in scope
+    /// (for example: `filter(user_id.eq(param_user_id))` or `order(created_at.desc())`)
+    pub expression: &'a str,
+}
+
+impl<'a> Scope<'a> {
+    pub fn new(name: &'a str, expression: &'a str) -> Self {
+        Self {
+            name,
+            params: vec![],
+            expression,
+        }
+    }
+
+    pub fn param(self, name: &'a str, ty: &'a str) -> Self {
+        let mut params = self.params;
+        params.push((name, ty));
+
+        Self { params, ..self }
+    }
+}
+
+/// A database function (or procedure) for which a typed wrapper is generated in the `sql_functions` module of the
+/// models
+#[derive(Debug, Clone, Serialize)]
+pub struct SqlFunction<'a> {
+    /// Name of the function in the database, optionally qualified by its schema (for example: `next_invoice_number` or
+    /// `billing.next_invoice_number`); the wrapper is named after the unqualified name
+    pub name: &'a str,
+    /// Arguments as `(name, diesel sql type)` (for example: `("customer_id", "Int4")` or `("note", "Nullable<Text>")`)
+    pub args: Vec<(&'a str, &'a str)>,
+    /// The diesel sql type of the returned value; procedures don't return anything and are invoked with `CALL`
+    pub return_type: Option<&'a str>,
+}
+
+impl<'a> SqlFunction<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            args: vec![],
+            return_type: None,
+        }
+    }
+
+    pub fn arg(self, name: &'a str, ty: &'a str) -> Self {
+        let mut args = self.args;
+        args.push((name, ty));
+
+        Self { args, ..self }
+    }
+
+    pub fn returns(self, ty: &'a str) -> Self {
+        Self {
+            return_type: Some(ty),
+            ..self
+        }
+    }
+
+    /// Parses a declaration like `next_invoice_number(customer_id: Int4, note: Nullable<Text>) -> Int8` (procedures
+    /// leave out the `-> <return type>`)
+    pub fn parse(declaration: &'a str) -> Result<Self, String> {
+        let invalid = || {
+            format!("invalid sql function '{declaration}', expected `<name>(<arg>: <sql type>, ...) -> <sql type>`")
+        };
+
+        let (name, rest) = declaration.split_once('(').ok_or_else(invalid)?;
+        let (args, return_type) = rest.rsplit_once(')').ok_or_else(invalid)?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut function = Self::new(name);
+        for arg in args.split(',').filter(|a| !a.trim().is_empty()) {
+            match arg.split_once(':') {
+                Some((name, ty)) if !name.trim().is_empty() && !ty.trim().is_empty() => {
+                    function = function.arg(name.trim(), ty.trim());
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        match return_type.trim() {
+            "" => {}
+            return_type => match return_type.strip_prefix("->").map(str::trim) {
+                Some(ty) if !ty.is_empty() => function = function.returns(ty),
+                _ => return Err(invalid()),
+            },
+        }
+
+        Ok(function)
+    }
+}
+
+/// Direction of an ordering used by the generated pagination functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// The generated structs of a table (which can be left out of the generated code, or have columns excluded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeneratedStruct {
+    /// the struct mapping to a row (for example: `Todo`), which is also what gets serialized in responses
+    Read,
+    /// the struct for inserting rows (for example: `CreateTodo`)
+    Create,
+    /// the struct for updating rows (for example: `UpdateTodo`)
+    Update,
+    /// the struct for forms and partial updates (for example: `TodoForm`), which isn't generated by default
+    Form,
+}
+
+impl FromStr for GeneratedStruct {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(Self::Read),
+            "create" => Ok(Self::Create),
+            "update" => Ok(Self::Update),
+            "form" => Ok(Self::Form),
+            _ => Err(format!(
+                "unknown struct '{s}', expected 'read', 'create', 'update' or 'form'"
+            )),
+        }
+    }
+}
+
+/// The serde traits derived by a generated struct
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SerdeDerives {
+    /// `Serialize` and `Deserialize`
+    #[default]
+    Both,
+    /// only `Serialize` (for example: for Read structs which are only sent in responses)
+    Serialize,
+    /// only `Deserialize` (for example: for Create and Update structs which are only received in requests)
+    Deserialize,
+    /// neither
+    None,
+}
+
+impl SerdeDerives {
+    pub fn serialize(self) -> bool {
+        matches!(self, Self::Both | Self::Serialize)
+    }
+
+    pub fn deserialize(self) -> bool {
+        matches!(self, Self::Both | Self::Deserialize)
+    }
+}
+
+impl FromStr for SerdeDerives {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "both" => Ok(Self::Both),
+            "serialize" => Ok(Self::Serialize),
+            "deserialize" => Ok(Self::Deserialize),
+            "none" => Ok(Self::None),
+            _ => Err(format!(
+                "unknown serde derives '{s}', expected 'both', 'serialize', 'deserialize' or 'none'"
+            )),
+        }
+    }
+}
+
+/// Versions of the uuids generated for primary keys by the generated `create` functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UuidVersion {
+    /// random uuids (`uuid::Uuid::new_v4`)
+    V4,
+    /// time-ordered uuids (`uuid::Uuid::now_v7`), which keep indexes compact
+    V7,
+}
+
+impl FromStr for UuidVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "v4" => Ok(Self::V4),
+            "v7" => Ok(Self::V7),
+            _ => Err(format!("unknown uuid version '{s}', expected 'v4' or 'v7'")),
+        }
+    }
+}
+
+/// How the generated structs (de)serialize binary columns (`Bytea`, `Blob`, ...), which are `Vec<u8>` fields either way
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BinaryFormat {
+    /// arrays of numbers (serde's default for `Vec<u8>`)
+    #[default]
+    Array,
+    /// base64 strings (see https://docs.rs/base64)
+    Base64,
+}
+
+impl FromStr for BinaryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "array" => Ok(Self::Array),
+            "base64" => Ok(Self::Base64),
+            _ => Err(format!(
+                "unknown binary format '{s}', expected 'array' or 'base64'"
+            )),
+        }
+    }
+}
+
+/// How the fields of the generated structs are ordered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FieldOrder {
+    /// the order of the columns in the schema
+    #[default]
+    Schema,
+    /// alphabetically, by field name
+    Alphabetical,
+    /// the primary key columns first, then the other columns in schema order
+    PrimaryKeyFirst,
+}
+
+impl FromStr for FieldOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "schema" => Ok(Self::Schema),
+            "alphabetical" => Ok(Self::Alphabetical),
+            "primary-key-first" => Ok(Self::PrimaryKeyFirst),
+            _ => Err(format!(
+                "unknown field order '{s}', expected 'schema', 'alphabetical' or 'primary-key-first'"
+            )),
+        }
+    }
+}
+
+/// What the generated `create`, `update` and `update_where` functions return
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WriteResult {
+    /// the written rows (`RETURNING` them, which MySQL doesn't support)
+    #[default]
+    Row,
+    /// the number of affected rows
+    Count,
+    /// nothing (`()`)
+    Nothing,
+}
+
+impl FromStr for WriteResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "row" => Ok(Self::Row),
+            "count" => Ok(Self::Count),
+            "nothing" => Ok(Self::Nothing),
+            _ => Err(format!(
+                "unknown write result '{s}', expected 'row', 'count' or 'nothing'"
+            )),
+        }
+    }
+}
+
+/// How the files (and modules) of the generated models are named
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileNaming {
+    /// the name of the table (for example: `todo_items`)
+    #[default]
+    Table,
+    /// the singular of the table name (for example: `todo_item`)
+    Singular,
+    /// PascalCase, like the name of the Read struct (for example: `TodoItem`)
+    Pascal,
+}
+
+impl FromStr for FileNaming {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "singular" => Ok(Self::Singular),
+            "pascal" => Ok(Self::Pascal),
+            _ => Err(format!(
+                "unknown file naming '{s}', expected 'table', 'singular' or 'pascal'"
+            )),
+        }
+    }
+}
+
+/// The library the generated models are written for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    /// diesel structs with CRUD functions (see https://diesel.rs)
+    #[default]
+    Diesel,
+    /// SeaORM entities: `Model`, `ActiveModel` and `Relation` (see https://www.sea-ql.org/SeaORM)
+    SeaOrm,
+    /// plain structs deriving `sqlx::FromRow`, with constants for the CRUD statements (see https://github.com/launchbadge/sqlx)
+    Sqlx,
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "diesel" => Ok(Self::Diesel),
+            "seaorm" => Ok(Self::SeaOrm),
+            "sqlx" => Ok(Self::Sqlx),
+            _ => Err(format!(
+                "unknown target '{s}', expected 'diesel', 'seaorm' or 'sqlx'"
+            )),
+        }
+    }
+}
+
+/// How the generated files import diesel's items and the schema's tables
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStyle {
+    /// glob imports of diesel and of the schema module (`use crate::diesel::*;` and `use crate::schema::*;`)
+    #[default]
+    Glob,
+    /// only the items the generated code uses, by name (like `use crate::diesel::{AsChangeset, QueryDsl};` and
+    /// `use crate::schema::todos;`)
+    Explicit,
+    /// diesel's prelude (`use diesel::prelude::*;`) and the other items of diesel by their direct paths (like
+    /// `use diesel::insert_into;`), for crates which don't re-export diesel at their root; the schema's tables are
+    /// imported by name
+    Prelude,
+}
+
+impl FromStr for ImportStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "glob" => Ok(Self::Glob),
+            "explicit" => Ok(Self::Explicit),
+            "prelude" => Ok(Self::Prelude),
+            _ => Err(format!(
+                "unknown import style '{s}', expected 'glob', 'explicit' or 'prelude'"
+            )),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize)]
 pub struct TableOptions<'a> {
     ignore: Option<bool>,
     /// Names used for autogenerated columns which are NOT primary keys (for example: `created_at`, `updated_at`, etc.).
     autogenerated_columns: Option<Vec<&'a str>>,
 
+    /// Also treats columns as autogenerated when the database likely generates their values: single integer primary keys,
+    /// `id` uuid primary keys, `created_at`/`updated_at` timestamps, and (in imported IR documents) columns with an
+    /// `identity` attribute or a `default` attribute like `now()`, `nextval(...)` or `gen_random_uuid()`
+    infer_autogenerated_columns: Option<bool>,
+
+    /// Write-once columns (for example: `owner_id`), which are set by the Create struct but left out of the Update struct
+    immutable_columns: Option<Vec<&'a str>>,
+
+    /// Columns holding secrets (for example: `password_hash`), which are redacted by the `Debug` impls of the generated
+    /// structs and skipped when serializing Read structs
+    sensitive_columns: Option<Vec<&'a str>>,
+
+    /// Text columns compared case-insensitively (for example: `email` or `username`), which get `find_by_*`
+    /// functions matching on `lower(...)`; `citext` columns are always case-insensitive
+    case_insensitive_columns: Option<Vec<&'a str>>,
+
+    /// Columns left out of some of the generated structs as `(column, struct)` (for example: `password_hash` left out
+    /// of the Read struct, or `created_by` left out of the Update struct); primary and foreign key columns are always
+    /// kept in the Read struct, because `Identifiable` and `Associations` need them
+    excluded_columns: Option<Vec<(&'a str, GeneratedStruct)>>,
+
+    /// The serde traits derived by some of the generated structs as `(struct, derives)` (structs which aren't listed
+    /// derive both `Serialize` and `Deserialize`); functions (de)serializing structs without the traits they need, like
+    /// `seed` and the import and export functions, are left out
+    serde_derives: Option<Vec<(GeneratedStruct, SerdeDerives)>>,
+
+    /// Rust types overriding the default mapping of sql types as `(sql type, rust type)` (for example:
+    /// `("Inet", "ipnet::IpNet")`, or `("MoodEnum", "crate::models::Mood")` for custom types)
+    type_overrides: Option<Vec<(&'a str, &'a str)>>,
+
+    /// Projection structs to generate for the table (these are never inherited from the default table options)
+    projections: Vec<Projection<'a>>,
+
+    /// Scopes for the table's generated `QueryExt` trait (these are never inherited from the default table options)
+    scopes: Vec<Scope<'a>>,
+
+    /// Page size used by the generated pagination functions when the requested page size is less than 1
+    default_page_size: Option<i64>,
+
+    /// Upper limit for page sizes in the generated pagination functions (larger page sizes are clamped)
+    max_page_size: Option<i64>,
+
+    /// Ordering used by the generated `paginate` function when no ordering is passed (for example: `created_at DESC`);
+    /// tables without this column are ordered by their primary key instead
+    default_order: Option<(&'a str, SortDirection)>,
+
+    /// A tsvector column used by the generated `search()` function (requires `diesel_full_text_search`)
+    full_text_search_column: Option<&'a str>,
+
+    /// Module containing the diesel schema (defaults to `crate::schema`), for example `my_db::schema`
+    schema_module: Option<&'a str>,
+
+    /// Module containing the generated models, used to import the structs of referenced tables (defaults to `crate::models`)
+    models_module: Option<&'a str>,
+
+    /// Subdirectory (and module) of the output directory holding the table's model, which groups the models of a domain
+    /// or (postgres) schema, for example `billing` for `crate::models::billing::invoices`
+    group: Option<&'a str>,
+
+    /// How the directory (and module) of the table's model is named (defaults to the table name)
+    file_naming: Option<FileNaming>,
+
+    /// Path of the table's Read struct as imported by tables referencing it (defaults to
+    /// `{models_module}::{table}::{Struct}`); use it for renamed structs or models living elsewhere.
+    /// This is never inherited from the default table options.
+    struct_path: Option<&'a str>,
+
+    /// Derives `Identifiable` on the table's Read struct (defaults to true; tables without primary keys never derive it)
+    identifiable: Option<bool>,
+
+    /// Derives `Associations` on the table's Read struct (defaults to deriving it for tables with foreign keys); turning
+    /// it off helps when the referenced models aren't generated (or live elsewhere)
+    associations: Option<bool>,
+
+    /// Derives `AsChangeset` on the table's Read and Create structs (defaults to deriving it on Read structs with `save`,
+    /// and on both with legacy derives, unless the struct only holds primary key columns or the table is a materialized
+    /// view); the Update struct always derives it, since the update functions need it
+    aschangeset: Option<bool>,
+
+    /// Derives `Queryable`, `Insertable` and `AsChangeset` on every struct (where possible), like older versions did,
+    /// instead of only deriving what each struct is used for (`Queryable` on Read structs, `Insertable` on Create
+    /// structs and `AsChangeset` on Update structs)
+    legacy_derives: Option<bool>,
+
+    /// Inner attributes added to the top of the generated file, without `#![...]` (for example: `allow(clippy::all)`)
+    file_attributes: Option<Vec<&'a str>>,
+
+    /// Compiles the table's model only when this cargo feature is enabled (gates its `mod.rs` entry and its generated code);
+    /// models of tables referencing this one should be gated by the same feature
+    cfg_feature: Option<&'a str>,
+
+    /// Generates `delete_all` (and `truncate` for postgres connections) which wipe the whole table
+    dangerous_helpers: Option<bool>,
+
+    /// Generates `save` on the Read struct, which writes every field of the row back (with diesel's `save_changes`);
+    /// this needs a Read struct deriving `Identifiable` (and `AsChangeset`, which it derives by default with `save`) with
+    /// every column in schema order
+    save: Option<bool>,
+
+    /// Generates async wrappers (for example: `read_async(pool, ...)`) of the CRUD functions which run them on a pooled
+    /// connection in tokio's blocking thread pool (with `tokio::task::spawn_blocking`), for async servers on sync diesel
+    async_wrappers: Option<bool>,
+
+    /// Generates a service struct (for example: `TodoService`) owning a connection pool, with async methods delegating
+    /// to the async wrappers (which it implies), so web handlers can hold a single injectable service per table
+    service: Option<bool>,
+
+    /// Generates a repository trait (for example: `TodoRepository`) declaring the service methods, which mockall
+    /// implements as `MockTodoRepository` (with helpers for common expectations) behind the `mock` cargo feature;
+    /// implies `service`
+    mock: Option<bool>,
+
+    /// Generates proptest strategies (`CreateTodo::strategy()`) and `Arbitrary` impls for the Create and Update structs
+    /// behind the `proptest` cargo feature, with values fitting the columns' types and max lengths
+    proptest: Option<bool>,
+
+    /// The structs generated for the table (defaults to the Read, Create and Update structs, plus the Form struct with
+    /// `form`), for example to leave the Update struct (and the update functions) out for append-only tables; the
+    /// generated functions return Read structs, so it's always generated
+    structs: Option<Vec<GeneratedStruct>>,
+
+    /// Generates a Form struct (for example: `TodoForm`) with the primary key fields and optional fields for the other
+    /// columns which aren't autogenerated, for HTML forms and `PATCH` requests; it converts into the Create struct
+    /// (with `TryFrom`, failing on missing required fields) and the Update struct (with `From`)
+    form: Option<bool>,
+
+    /// Generates `export_csv` and `export_json_lines` reading every row, and `import_csv` and `import_json_lines`
+    /// inserting rows through the Create struct
+    import_export: Option<bool>,
+
+    /// Includes the table in the generated `seed` function, which inserts fixture files through the Create structs
+    seed: Option<bool>,
+
+    /// Treats the table as a (postgres) materialized view: it's only read (no Create and Update structs, and no
+    /// functions writing rows), and refreshed by the generated `refresh` and `refresh_concurrently`
+    materialized_view: Option<bool>,
+
+    /// Derives `QueryableByName` on the Read struct, whose fields get `#[diesel(sql_type = ...)]` attributes, so it can
+    /// be loaded with `diesel::sql_query` (diesel models only)
+    queryable_by_name: Option<bool>,
+
+    /// Generates `query_raw` (loading Read structs with hand-written `sql_query`s), which implies `queryable_by_name`
+    raw_queries: Option<bool>,
+
+    /// Spells out the full path of the table's module in generated queries (for example:
+    /// `crate::schema::todos::table.filter(crate::schema::todos::id.eq(...))`) instead of importing the module into each
+    /// function
+    fully_qualified_paths: Option<bool>,
+
+    /// Wraps the table's primary key in a newtype (for example: `pub struct TodoId(pub i32)`), which the generated structs
+    /// and functions use for the primary key and the foreign keys referencing it, so keys of different tables can't be
+    /// mixed up; only single primary keys of integer, `String` or uuid types are wrapped (diesel models only)
+    newtype_ids: Option<bool>,
+
+    /// Generates uuids of this version for the table's primary key in `create` (leaving the key out of the Create struct)
+    /// unless the key is autogenerated, and makes `read`, `update` and `delete` accept `impl Into<Uuid>` keys; this
+    /// only applies to single uuid primary keys
+    uuid_primary_key: Option<UuidVersion>,
+
+    /// An expression generating the table's primary key, which `create` uses instead of taking the key from the Create
+    /// struct (for example: `ulid::Ulid::new().to_string()`); this takes precedence over uuid primary keys and only
+    /// applies to single primary keys which aren't autogenerated
+    id_generator: Option<&'a str>,
+
+    /// How binary columns are (de)serialized (defaults to arrays of numbers)
+    binary_format: Option<BinaryFormat>,
+
+    /// How the fields of the Read, Create and Update structs are ordered (defaults to the order of the schema)
+    field_order: Option<FieldOrder>,
+
+    /// What `create`, `update` and `update_where` return (defaults to the written rows)
+    write_result: Option<WriteResult>,
+
+    /// The library the table's model is generated for (defaults to diesel)
+    target: Option<Target>,
+
+    /// How the table's generated file imports diesel and the schema (defaults to glob imports)
+    import_style: Option<ImportStyle>,
+
+    /// Statements run at the start of every generated function which queries the database itself (for example:
+    /// `diesel::sql_query("SET LOCAL statement_timeout = 500").execute(db)?;`), with the function's connection as `db`;
+    /// functions delegating to other generated functions (like `read_by_key` or `import_csv`) run them through those
+    function_prologue: Option<&'a str>,
+
     #[cfg(feature = "tsync")]
     /// Adds #[tsync] attribute to structs (see https://github.com/Wulf/tsync)
     tsync: Option<bool>,
@@ -29,10 +652,223 @@ impl<'a> TableOptions<'a> {
         self.tsync.unwrap_or_default()
     }
 
+    pub fn get_schema_module(&self) -> &'a str {
+        self.schema_module.unwrap_or("crate::schema")
+    }
+
+    pub fn get_models_module(&self) -> &'a str {
+        self.models_module.unwrap_or("crate::models")
+    }
+
+    pub fn get_group(&self) -> Option<&'a str> {
+        self.group
+    }
+
+    pub fn get_file_naming(&self) -> FileNaming {
+        self.file_naming.unwrap_or_default()
+    }
+
+    pub fn get_struct_path(&self) -> Option<&'a str> {
+        self.struct_path
+    }
+
+    pub fn get_identifiable(&self) -> bool {
+        self.identifiable.unwrap_or(true)
+    }
+
+    /// whether `Associations` is forced (`Some(true)`) or suppressed (`Some(false)`), if it's not up to the foreign keys
+    pub fn get_associations(&self) -> Option<bool> {
+        self.associations
+    }
+
+    /// whether `AsChangeset` is forced (`Some(true)`) or suppressed (`Some(false)`), if it's not up to the columns
+    pub fn get_aschangeset(&self) -> Option<bool> {
+        self.aschangeset
+    }
+
+    pub fn get_legacy_derives(&self) -> bool {
+        self.legacy_derives.unwrap_or_default()
+    }
+
+    pub fn get_file_attributes(&self) -> &[&'a str] {
+        self.file_attributes.as_deref().unwrap_or_default()
+    }
+
+    pub fn get_cfg_feature(&self) -> Option<&'a str> {
+        self.cfg_feature
+    }
+
+    pub fn get_dangerous_helpers(&self) -> bool {
+        self.dangerous_helpers.unwrap_or_default()
+    }
+
+    pub fn get_save(&self) -> bool {
+        self.save.unwrap_or_default()
+    }
+
+    pub fn get_async_wrappers(&self) -> bool {
+        self.async_wrappers.unwrap_or_default() || self.get_service()
+    }
+
+    pub fn get_service(&self) -> bool {
+        self.service.unwrap_or_default() || self.get_mock()
+    }
+
+    pub fn get_mock(&self) -> bool {
+        self.mock.unwrap_or_default()
+    }
+
+    pub fn get_proptest(&self) -> bool {
+        self.proptest.unwrap_or_default()
+    }
+
+    pub fn get_form(&self) -> bool {
+        self.form.unwrap_or_default()
+    }
+
+    /// whether the struct is generated (see `structs` and `form`)
+    pub fn generates_struct(&self, generated_struct: GeneratedStruct) -> bool {
+        match generated_struct {
+            GeneratedStruct::Read => true,
+            GeneratedStruct::Form if self.get_form() => true,
+            _ => match &self.structs {
+                Some(structs) => structs.contains(&generated_struct),
+                None => generated_struct != GeneratedStruct::Form,
+            },
+        }
+    }
+
+    pub fn get_import_export(&self) -> bool {
+        self.import_export.unwrap_or_default()
+    }
+
+    pub fn get_seed(&self) -> bool {
+        self.seed.unwrap_or_default()
+    }
+
+    pub fn get_materialized_view(&self) -> bool {
+        self.materialized_view.unwrap_or_default()
+    }
+
+    pub fn get_queryable_by_name(&self) -> bool {
+        self.queryable_by_name.unwrap_or_default() || self.get_raw_queries()
+    }
+
+    pub fn get_raw_queries(&self) -> bool {
+        self.raw_queries.unwrap_or_default()
+    }
+
+    pub fn get_fully_qualified_paths(&self) -> bool {
+        self.fully_qualified_paths.unwrap_or_default()
+    }
+
+    pub fn get_newtype_ids(&self) -> bool {
+        self.newtype_ids.unwrap_or_default()
+    }
+
+    pub fn get_uuid_primary_key(&self) -> Option<UuidVersion> {
+        self.uuid_primary_key
+    }
+
+    pub fn get_id_generator(&self) -> Option<&'a str> {
+        self.id_generator
+    }
+
+    pub fn get_function_prologue(&self) -> Option<&'a str> {
+        self.function_prologue
+    }
+
+    pub fn get_binary_format(&self) -> BinaryFormat {
+        self.binary_format.unwrap_or_default()
+    }
+
+    pub fn get_field_order(&self) -> FieldOrder {
+        self.field_order.unwrap_or_default()
+    }
+
+    pub fn get_write_result(&self) -> WriteResult {
+        self.write_result.unwrap_or_default()
+    }
+
+    pub fn get_target(&self) -> Target {
+        self.target.unwrap_or_default()
+    }
+
+    pub fn get_import_style(&self) -> ImportStyle {
+        self.import_style.unwrap_or_default()
+    }
+
     pub fn get_autogenerated_columns(&self) -> &[&'_ str] {
         self.autogenerated_columns.as_deref().unwrap_or_default()
     }
 
+    pub fn get_infer_autogenerated_columns(&self) -> bool {
+        self.infer_autogenerated_columns.unwrap_or_default()
+    }
+
+    pub fn get_immutable_columns(&self) -> &[&'a str] {
+        self.immutable_columns.as_deref().unwrap_or_default()
+    }
+
+    pub fn get_sensitive_columns(&self) -> &[&'a str] {
+        self.sensitive_columns.as_deref().unwrap_or_default()
+    }
+
+    pub fn get_case_insensitive_columns(&self) -> &[&'a str] {
+        self.case_insensitive_columns.as_deref().unwrap_or_default()
+    }
+
+    pub fn get_excluded_columns(&self, from: GeneratedStruct) -> Vec<&'a str> {
+        self.excluded_columns
+            .iter()
+            .flatten()
+            .filter(|(_, s)| *s == from)
+            .map(|(column, _)| *column)
+            .collect()
+    }
+
+    pub fn get_serde_derives(&self, generated_struct: GeneratedStruct) -> SerdeDerives {
+        self.serde_derives
+            .iter()
+            .flatten()
+            .rev()
+            .find(|(s, _)| *s == generated_struct)
+            .map(|(_, derives)| *derives)
+            .unwrap_or_default()
+    }
+
+    pub fn get_type_override(&self, sql_type: &str) -> Option<&'a str> {
+        self.type_overrides
+            .iter()
+            .flatten()
+            .find(|(t, _)| *t == sql_type)
+            .map(|(_, rust_type)| *rust_type)
+    }
+
+    pub fn get_default_page_size(&self) -> i64 {
+        self.default_page_size.unwrap_or(1)
+    }
+
+    pub fn get_max_page_size(&self) -> Option<i64> {
+        self.max_page_size
+    }
+
+    pub fn get_default_order(&self) -> Option<(&'a str, SortDirection)> {
+        self.default_order
+    }
+
+    pub fn get_full_text_search_column(&self) -> Option<&'a str> {
+        self.full_text_search_column
+    }
+
+    pub fn get_projections(&self) -> &[Projection<'a>] {
+        &self.projections
+    }
+
+    pub fn get_scopes(&self) -> &[Scope<'a>] {
+        &self.scopes
+    }
+
     pub fn ignore(self) -> Self {
         Self {
             ignore: Some(true),
@@ -48,23 +884,436 @@ impl<'a> TableOptions<'a> {
         }
     }
 
-    pub fn autogenerated_columns(self, cols: Vec<&'a str>) -> Self {
+    pub fn schema_module(self, module: &'a str) -> Self {
+        Self {
+            schema_module: Some(module),
+            ..self
+        }
+    }
+
+    pub fn models_module(self, module: &'a str) -> Self {
+        Self {
+            models_module: Some(module),
+            ..self
+        }
+    }
+
+    pub fn group(self, group: &'a str) -> Self {
+        Self {
+            group: Some(group),
+            ..self
+        }
+    }
+
+    pub fn file_naming(self, naming: FileNaming) -> Self {
+        Self {
+            file_naming: Some(naming),
+            ..self
+        }
+    }
+
+    pub fn struct_path(self, path: &'a str) -> Self {
+        Self {
+            struct_path: Some(path),
+            ..self
+        }
+    }
+
+    pub fn identifiable(self, identifiable: bool) -> Self {
+        Self {
+            identifiable: Some(identifiable),
+            ..self
+        }
+    }
+
+    pub fn associations(self, associations: bool) -> Self {
+        Self {
+            associations: Some(associations),
+            ..self
+        }
+    }
+
+    pub fn aschangeset(self, aschangeset: bool) -> Self {
+        Self {
+            aschangeset: Some(aschangeset),
+            ..self
+        }
+    }
+
+    pub fn legacy_derives(self) -> Self {
+        Self {
+            legacy_derives: Some(true),
+            ..self
+        }
+    }
+
+    pub fn file_attributes(self, attributes: Vec<&'a str>) -> Self {
+        Self {
+            file_attributes: Some(attributes),
+            ..self
+        }
+    }
+
+    pub fn cfg_feature(self, feature: &'a str) -> Self {
+        Self {
+            cfg_feature: Some(feature),
+            ..self
+        }
+    }
+
+    pub fn dangerous_helpers(self) -> Self {
+        Self {
+            dangerous_helpers: Some(true),
+            ..self
+        }
+    }
+
+    pub fn save(self) -> Self {
+        Self {
+            save: Some(true),
+            ..self
+        }
+    }
+
+    pub fn async_wrappers(self) -> Self {
+        Self {
+            async_wrappers: Some(true),
+            ..self
+        }
+    }
+
+    pub fn service(self) -> Self {
+        Self {
+            service: Some(true),
+            ..self
+        }
+    }
+
+    pub fn mock(self) -> Self {
+        Self {
+            mock: Some(true),
+            ..self
+        }
+    }
+
+    pub fn proptest(self) -> Self {
+        Self {
+            proptest: Some(true),
+            ..self
+        }
+    }
+
+    pub fn structs(self, structs: Vec<GeneratedStruct>) -> Self {
+        Self {
+            structs: Some(structs),
+            ..self
+        }
+    }
+
+    pub fn form(self) -> Self {
+        Self {
+            form: Some(true),
+            ..self
+        }
+    }
+
+    pub fn import_export(self) -> Self {
+        Self {
+            import_export: Some(true),
+            ..self
+        }
+    }
+
+    pub fn seed(self) -> Self {
+        Self {
+            seed: Some(true),
+            ..self
+        }
+    }
+
+    pub fn materialized_view(self) -> Self {
+        Self {
+            materialized_view: Some(true),
+            ..self
+        }
+    }
+
+    pub fn queryable_by_name(self) -> Self {
+        Self {
+            queryable_by_name: Some(true),
+            ..self
+        }
+    }
+
+    pub fn raw_queries(self) -> Self {
+        Self {
+            raw_queries: Some(true),
+            ..self
+        }
+    }
+
+    pub fn fully_qualified_paths(self) -> Self {
+        Self {
+            fully_qualified_paths: Some(true),
+            ..self
+        }
+    }
+
+    pub fn newtype_ids(self, newtype_ids: bool) -> Self {
+        Self {
+            newtype_ids: Some(newtype_ids),
+            ..self
+        }
+    }
+
+    pub fn uuid_primary_key(self, version: UuidVersion) -> Self {
+        Self {
+            uuid_primary_key: Some(version),
+            ..self
+        }
+    }
+
+    pub fn id_generator(self, expression: &'a str) -> Self {
+        Self {
+            id_generator: Some(expression),
+            ..self
+        }
+    }
+
+    pub fn function_prologue(self, statements: &'a str) -> Self {
+        Self {
+            function_prologue: Some(statements),
+            ..self
+        }
+    }
+
+    pub fn binary_format(self, format: BinaryFormat) -> Self {
+        Self {
+            binary_format: Some(format),
+            ..self
+        }
+    }
+
+    pub fn field_order(self, order: FieldOrder) -> Self {
+        Self {
+            field_order: Some(order),
+            ..self
+        }
+    }
+
+    pub fn write_result(self, write_result: WriteResult) -> Self {
+        Self {
+            write_result: Some(write_result),
+            ..self
+        }
+    }
+
+    pub fn target(self, target: Target) -> Self {
+        Self {
+            target: Some(target),
+            ..self
+        }
+    }
+
+    pub fn import_style(self, style: ImportStyle) -> Self {
+        Self {
+            import_style: Some(style),
+            ..self
+        }
+    }
+
+    pub fn autogenerated_columns(self, cols: Vec<&'a str>) -> Self {
+        Self {
+            autogenerated_columns: Some(cols.clone()),
+            ..self
+        }
+    }
+
+    pub fn infer_autogenerated_columns(self, infer: bool) -> Self {
+        Self {
+            infer_autogenerated_columns: Some(infer),
+            ..self
+        }
+    }
+
+    pub fn immutable_columns(self, cols: Vec<&'a str>) -> Self {
+        Self {
+            immutable_columns: Some(cols),
+            ..self
+        }
+    }
+
+    pub fn sensitive_columns(self, cols: Vec<&'a str>) -> Self {
+        Self {
+            sensitive_columns: Some(cols),
+            ..self
+        }
+    }
+
+    pub fn case_insensitive_columns(self, cols: Vec<&'a str>) -> Self {
+        Self {
+            case_insensitive_columns: Some(cols),
+            ..self
+        }
+    }
+
+    pub fn exclude_column(self, column: &'a str, from: GeneratedStruct) -> Self {
+        let mut excluded_columns = self.excluded_columns.unwrap_or_default();
+        excluded_columns.push((column, from));
+
+        Self {
+            excluded_columns: Some(excluded_columns),
+            ..self
+        }
+    }
+
+    pub fn serde_derives(self, generated_struct: GeneratedStruct, derives: SerdeDerives) -> Self {
+        let mut serde_derives = self.serde_derives.unwrap_or_default();
+        serde_derives.push((generated_struct, derives));
+
+        Self {
+            serde_derives: Some(serde_derives),
+            ..self
+        }
+    }
+
+    pub fn type_override(self, sql_type: &'a str, rust_type: &'a str) -> Self {
+        let mut type_overrides = self.type_overrides.unwrap_or_default();
+        type_overrides.push((sql_type, rust_type));
+
+        Self {
+            type_overrides: Some(type_overrides),
+            ..self
+        }
+    }
+
+    pub fn default_page_size(self, page_size: i64) -> Self {
+        Self {
+            default_page_size: Some(page_size),
+            ..self
+        }
+    }
+
+    pub fn max_page_size(self, page_size: i64) -> Self {
+        Self {
+            max_page_size: Some(page_size),
+            ..self
+        }
+    }
+
+    pub fn default_order(self, column: &'a str, direction: SortDirection) -> Self {
+        Self {
+            default_order: Some((column, direction)),
+            ..self
+        }
+    }
+
+    pub fn full_text_search_column(self, column: &'a str) -> Self {
+        Self {
+            full_text_search_column: Some(column),
+            ..self
+        }
+    }
+
+    pub fn projection(self, projection: Projection<'a>) -> Self {
+        let mut projections = self.projections;
+        projections.push(projection);
+
         Self {
-            autogenerated_columns: Some(cols.clone()),
+            projections,
             ..self
         }
     }
 
+    pub fn scope(self, scope: Scope<'a>) -> Self {
+        let mut scopes = self.scopes;
+        scopes.push(scope);
+
+        Self { scopes, ..self }
+    }
+
     /// Fills any `None` properties with values from another TableConfig
     pub fn apply_defaults(&self, other: &TableOptions<'a>) -> Self {
         Self {
             ignore: self.ignore.or(other.ignore),
+            projections: self.projections.clone(),
+            scopes: self.scopes.clone(),
+            schema_module: self.schema_module.or(other.schema_module),
+            models_module: self.models_module.or(other.models_module),
+            group: self.group.or(other.group),
+            file_naming: self.file_naming.or(other.file_naming),
+            struct_path: self.struct_path,
+            identifiable: self.identifiable.or(other.identifiable),
+            associations: self.associations.or(other.associations),
+            aschangeset: self.aschangeset.or(other.aschangeset),
+            legacy_derives: self.legacy_derives.or(other.legacy_derives),
+            file_attributes: self
+                .file_attributes
+                .clone()
+                .or_else(|| other.file_attributes.clone()),
+            cfg_feature: self.cfg_feature.or(other.cfg_feature),
+            dangerous_helpers: self.dangerous_helpers.or(other.dangerous_helpers),
+            save: self.save.or(other.save),
+            async_wrappers: self.async_wrappers.or(other.async_wrappers),
+            service: self.service.or(other.service),
+            mock: self.mock.or(other.mock),
+            proptest: self.proptest.or(other.proptest),
+            structs: self.structs.clone().or_else(|| other.structs.clone()),
+            form: self.form.or(other.form),
+            import_export: self.import_export.or(other.import_export),
+            seed: self.seed.or(other.seed),
+            materialized_view: self.materialized_view.or(other.materialized_view),
+            queryable_by_name: self.queryable_by_name.or(other.queryable_by_name),
+            raw_queries: self.raw_queries.or(other.raw_queries),
+            fully_qualified_paths: self.fully_qualified_paths.or(other.fully_qualified_paths),
+            newtype_ids: self.newtype_ids.or(other.newtype_ids),
+            uuid_primary_key: self.uuid_primary_key.or(other.uuid_primary_key),
+            id_generator: self.id_generator.or(other.id_generator),
+            binary_format: self.binary_format.or(other.binary_format),
+            field_order: self.field_order.or(other.field_order),
+            write_result: self.write_result.or(other.write_result),
+            target: self.target.or(other.target),
+            import_style: self.import_style.or(other.import_style),
+            function_prologue: self.function_prologue.or(other.function_prologue),
             #[cfg(feature = "tsync")]
             tsync: self.tsync.or(other.tsync),
             autogenerated_columns: self
                 .autogenerated_columns
                 .clone()
                 .or_else(|| other.autogenerated_columns.clone()),
+            infer_autogenerated_columns: self
+                .infer_autogenerated_columns
+                .or(other.infer_autogenerated_columns),
+            immutable_columns: self
+                .immutable_columns
+                .clone()
+                .or_else(|| other.immutable_columns.clone()),
+            sensitive_columns: self
+                .sensitive_columns
+                .clone()
+                .or_else(|| other.sensitive_columns.clone()),
+            case_insensitive_columns: self
+                .case_insensitive_columns
+                .clone()
+                .or_else(|| other.case_insensitive_columns.clone()),
+            excluded_columns: self
+                .excluded_columns
+                .clone()
+                .or_else(|| other.excluded_columns.clone()),
+            serde_derives: self
+                .serde_derives
+                .clone()
+                .or_else(|| other.serde_derives.clone()),
+            type_overrides: self
+                .type_overrides
+                .clone()
+                .or_else(|| other.type_overrides.clone()),
+            default_page_size: self.default_page_size.or(other.default_page_size),
+            max_page_size: self.max_page_size.or(other.max_page_size),
+            default_order: self.default_order.or(other.default_order),
+            full_text_search_column: self
+                .full_text_search_column
+                .or(other.full_text_search_column),
         }
     }
 }
@@ -74,19 +1323,73 @@ pub struct GenerationConfig<'a> {
     pub table_options: HashMap<&'a str, TableOptions<'a>>,
     pub default_table_options: TableOptions<'a>,
     pub connection_type: String,
+    /// Connection types replacing `connection_type` when a cargo feature is enabled, as `(feature, connection type)`
+    /// pairs (the first pair with an enabled feature wins), for example to run against SQLite in tests
+    pub connection_features: Vec<(String, String)>,
+    /// The connection type of the functions which only read (like connections to a read replica, with the same backend
+    /// as `connection_type`): the generated files alias it as `ReadConnection` and `connection_type` as
+    /// `WriteConnection` (async wrappers and services read through a `ReadPool` of their own); without it, every
+    /// function takes `connection_type` connections
+    pub read_connection_type: Option<String>,
+    /// Generates models for a connection enum deriving diesel's `MultiConnection` (the connection type)
+    pub multi_connection: Option<MultiConnection<'a>>,
+    /// Generates criterion benchmarks of the `read`, `paginate` and `create` functions of every table in standalone
+    /// crates (`benches/models.rs`, see [`generate_crate`]), which run against the database at `DATABASE_URL`
+    pub benches: bool,
+    /// Generates integration tests of the models in standalone crates (`tests/models.rs`, see [`generate_crate`]), with
+    /// a support module running them against a postgres testcontainer migrated with the migrations in this directory
+    /// (relative to the crate)
+    pub test_support: Option<&'a str>,
+    /// Embeds the migrations in this directory (relative to the crate) in a `migrations` module next to the models,
+    /// with a `run_pending_migrations(db)` function
+    pub migrations_dir: Option<&'a str>,
+    /// Generates a `db` module next to the models connecting to the database configured by environment variables
+    /// (`establish_connection()`, `create_pool()` for pooled connections) with a `ping(db)` health check
+    pub db_module: bool,
+    /// Database functions and procedures getting typed wrappers (in `sql_functions.rs`, next to the models)
+    pub functions: Vec<SqlFunction<'a>>,
+    /// Keeps hashes of the inputs and the generated code of every table in this file, so later runs skip rendering the
+    /// tables which didn't change since (and report them as skipped)
+    pub cache: Option<&'a Path>,
+    /// Mappings of sql types to rust types (with the imports and serde attributes they need) taking precedence over
+    /// the default mapping, asked in order (type overrides of the table options take precedence over all of them)
+    pub type_mappers: Vec<Arc<dyn TypeMapper>>,
+    /// What happens when tables get the same struct name (like `user_role` and `user_roles`)
+    pub struct_name_collisions: StructNameCollisions<'a>,
 }
 
 impl GenerationConfig<'_> {
     pub fn table(&self, name: &str) -> TableOptions<'_> {
-        let t = self
-            .table_options
-            .get(name)
-            .unwrap_or(&self.default_table_options);
-
-        t.apply_defaults(&self.default_table_options)
+        match self.table_options.get(name) {
+            Some(t) => t.apply_defaults(&self.default_table_options),
+            None => TableOptions::default().apply_defaults(&self.default_table_options),
+        }
     }
 }
 
+/// What happens when several tables get the same struct name, which they do when their names only differ in plurality
+/// (like `user_role` and `user_roles`), and whose models then can't be imported next to each other
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StructNameCollisions<'a> {
+    /// generates the structs anyway, reporting a warning
+    #[default]
+    Warn,
+    /// fails the generation
+    Error,
+    /// appends the suffix to the struct names of the tables coming later in the schema (and a counter, if that isn't
+    /// enough), like `UserRoleLink` for `user_roles` with the suffix `Link`
+    Suffix(&'a str),
+}
+
+/// Options for models of a connection enum deriving diesel's `MultiConnection`, whose queries have to work on every
+/// backend of the enum
+#[derive(Debug, Clone, Copy)]
+pub struct MultiConnection<'a> {
+    /// Cargo feature gating the postgres-only functions (full-text search, refreshing materialized views and
+    /// `truncate`), which take a `diesel::PgConnection` instead of the connection enum
+    pub postgres_feature: &'a str,
+}
+
 pub fn generate_code(
     diesel_schema_file_contents: String,
     config: GenerationConfig,
@@ -94,22 +1397,781 @@ pub fn generate_code(
     parser::parse_and_generate_code(diesel_schema_file_contents, &config)
 }
 
+/// Generates the code of a single table of a schema (what [`generate_files`] writes to its `generated.rs`), for
+/// printing it without touching the file system
+pub fn generate_table_code(
+    diesel_schema_file_contents: String,
+    table_name: &str,
+    config: GenerationConfig,
+) -> anyhow::Result<String> {
+    let mut code = Vec::new();
+    write_table_code(diesel_schema_file_contents, table_name, config, &mut code)?;
+
+    Ok(String::from_utf8(code)?)
+}
+
+/// Like [`generate_table_code`], but writes the code into `out` as it's rendered (for example, into a buffered and
+/// locked stdout, to pipe it into other tools)
+pub fn write_table_code(
+    diesel_schema_file_contents: String,
+    table_name: &str,
+    config: GenerationConfig,
+    out: &mut impl std::io::Write,
+) -> anyhow::Result<()> {
+    let tables = parser::parse_schema(&diesel_schema_file_contents, &config)?;
+    let Some(table) = tables.iter().find(|t| t.name == table_name) else {
+        bail!("The schema has no table named '{table_name}'");
+    };
+    validate::validate_generation(&tables, &config)?;
+
+    parser::write_table_code(out, table, &tables, &config)?;
+    out.flush()?;
+
+    Ok(())
+}
+
+/// Renders the lines of a table's generated code which change from `config` to `changed_config` (prefixed with `-`
+/// and `+`, with a few unchanged lines around them), for example to show what an option does
+pub fn diff_table_code(
+    diesel_schema_file_contents: String,
+    table_name: &str,
+    config: GenerationConfig,
+    changed_config: GenerationConfig,
+) -> anyhow::Result<String> {
+    let code = generate_table_code(diesel_schema_file_contents.clone(), table_name, config)?;
+    let changed_code =
+        generate_table_code(diesel_schema_file_contents, table_name, changed_config)?;
+
+    Ok(code_diff::render(&code, &changed_code))
+}
+
+/// Fails with a list of the tables and columns which the options refer to but the schema doesn't have (options naming
+/// missing columns are silently ignored by the generators, so typos go unnoticed otherwise). Columns in the default
+/// table options only have to exist in one of the tables.
+pub fn validate_config(
+    diesel_schema_file_contents: String,
+    config: GenerationConfig,
+) -> anyhow::Result<()> {
+    let tables = parser::parse_schema(&diesel_schema_file_contents, &config)?;
+
+    validate::validate(&tables, &config)
+}
+
+/// Lists the parts of a schema which likely generate code that doesn't compile, or which doesn't do what was intended
+/// (tables without primary keys, foreign keys to tables which aren't generated and tables whose structs get the same
+/// name); [`generate_files`] reports them as warnings
+pub fn schema_warnings(
+    diesel_schema_file_contents: String,
+    config: GenerationConfig,
+) -> anyhow::Result<Vec<String>> {
+    let tables = parser::parse_schema(&diesel_schema_file_contents, &config)?;
+
+    Ok(warnings::schema_warnings(&tables, &config))
+}
+
+/// Serializes the parsed tables of a schema (names, columns, types, keys, foreign keys and the options applied to
+/// them) as a JSON document, so code generators for other languages don't have to parse diesel schema files
+pub fn export_ir(
+    diesel_schema_file_contents: String,
+    config: GenerationConfig,
+) -> anyhow::Result<String> {
+    let tables = parser::parse_schema(&diesel_schema_file_contents, &config)?;
+
+    Ok(ir::export(&tables, &config))
+}
+
+/// Renders a `.proto` file with protobuf messages mirroring the Read, Create and Update structs of every table
+pub fn generate_proto(
+    diesel_schema_file_contents: String,
+    config: GenerationConfig,
+    proto_config: ProtoConfig,
+) -> anyhow::Result<String> {
+    let tables = parser::parse_schema(&diesel_schema_file_contents, &config)?;
+
+    Ok(proto::render(&tables, &config, &proto_config))
+}
+
+/// Renders an OpenAPI document describing CRUD endpoints over the generated structs of every table (list and create
+/// on `/{table}`, read, update and delete on `/{table}/{primary key}`), whether or not the handlers are generated
+pub fn generate_openapi(
+    diesel_schema_file_contents: String,
+    config: GenerationConfig,
+) -> anyhow::Result<String> {
+    let tables = parser::parse_schema(&diesel_schema_file_contents, &config)?;
+
+    Ok(openapi::render(&tables, &config))
+}
+
+/// Renders a typescript module with zod schemas for the Create and Update structs of every table
+/// (string lengths come from `#[max_length]` attributes)
+pub fn generate_zod(
+    diesel_schema_file_contents: String,
+    config: GenerationConfig,
+) -> anyhow::Result<String> {
+    let tables = parser::parse_schema(&diesel_schema_file_contents, &config)?;
+
+    Ok(zod::render(&tables, &config))
+}
+
+/// Renders the tables of a schema and the foreign keys between them as an entity-relationship diagram
+pub fn generate_diagram(
+    diesel_schema_file_contents: String,
+    format: DiagramFormat,
+    config: GenerationConfig,
+) -> anyhow::Result<String> {
+    let tables = parser::parse_schema(&diesel_schema_file_contents, &config)?;
+
+    Ok(diagram::render(&tables, format))
+}
+
+/// Writes markdown documentation of the schema into `output_docs_dir`: an `index.md` listing the tables and a
+/// `{table}.md` page per table describing its columns, keys and relations. Pages of removed tables are pruned.
+pub fn generate_docs(
+    input_diesel_schema_file: PathBuf,
+    output_docs_dir: PathBuf,
+    config: GenerationConfig,
+) -> anyhow::Result<GenerationReport> {
+    let start = Instant::now();
+    let input = input_diesel_schema_file;
+    let mut report = GenerationReport::default();
+
+    let tables = parser::parse_schema(
+        &std::fs::read_to_string(&input).expect("Could not read schema file."),
+        &config,
+    )
+    .map_err(|e| e.with_file(&input))?;
+
+    std::fs::create_dir_all(&output_docs_dir)
+        .unwrap_or_else(|_| panic!("Could not create directory '{output_docs_dir:#?}'"));
+
+    let mut pages = vec![("index.md".to_string(), docs::index_md(&tables))];
+    pages.extend(
+        tables
+            .iter()
+            .map(|table| (format!("{}.md", table.name), docs::table_md(table, &tables))),
+    );
+
+    // prune the pages of removed tables (only files we generated, i.e. starting with the signature)
+    for item in std::fs::read_dir(&output_docs_dir)
+        .unwrap_or_else(|_| panic!("Could not read directory '{output_docs_dir:#?}'"))
+    {
+        let path = item
+            .unwrap_or_else(|_| panic!("Could not read item in '{output_docs_dir:#?}'"))
+            .path();
+        let file_name = path
+            .file_name()
+            .and_then(|f| f.to_str())
+            .unwrap_or_default();
+
+        let is_generated_page = path.extension().map(|e| e == "md").unwrap_or_default()
+            && std::fs::read_to_string(&path)
+                .map(|c| c.starts_with(docs::DOCS_SIGNATURE))
+                .unwrap_or_default();
+        if is_generated_page && !pages.iter().any(|(name, _)| name == file_name) {
+            report.files.push(FileChange {
+                status: MarkedFile::new(path.clone()).delete(),
+                path,
+            });
+        }
+    }
+
+    for (file_name, contents) in pages {
+        let mut page = MarkedFile::new(output_docs_dir.join(file_name));
+        if !page.file_contents.is_empty() && !page.file_contents.starts_with(docs::DOCS_SIGNATURE) {
+            panic!("Expected file '{:#?}' to have the docs signature ('{}') -- you might be accidentally overwriting files that weren't generated!", page.path, docs::DOCS_SIGNATURE)
+        }
+        page.file_contents = contents;
+        report.files.push(FileChange {
+            status: page.write(),
+            path: page.path,
+        });
+    }
+
+    report.tables = tables.iter().map(|t| t.name.clone()).collect();
+    report.duration_ms = start.elapsed().as_millis();
+
+    Ok(report)
+}
+
 pub fn generate_files(
     input_diesel_schema_file: PathBuf,
     output_models_dir: PathBuf,
     config: GenerationConfig,
-) {
+) -> anyhow::Result<GenerationReport> {
+    let start = Instant::now();
     let input = input_diesel_schema_file;
     let output_dir = output_models_dir;
+    let mut report = GenerationReport::default();
 
-    let generated = generate_code(
-        std::fs::read_to_string(input).expect("Could not read schema file."),
-        config,
+    let tables = parser::parse_schema(
+        &std::fs::read_to_string(&input).expect("Could not read schema file."),
+        &config,
+    )
+    .map_err(|e| e.with_file(&input))?;
+    let parse_duration = start.elapsed();
+    let generated = generate_tables(tables, &output_dir, &config, &mut report)?;
+
+    let write_start = Instant::now();
+    write_models(&generated, &output_dir, &config, &mut report)?;
+    save_cache(&generated, &config)?;
+    record_phases(&mut report, parse_duration, write_start.elapsed());
+    report.duration_ms = start.elapsed().as_millis();
+
+    Ok(report)
+}
+
+/// Like [`generate_files`], but reads the tables from an IR document (as written by [`export_ir`], or hand-written)
+/// instead of a diesel schema file
+pub fn generate_files_from_ir(
+    input_ir_file: PathBuf,
+    output_models_dir: PathBuf,
+    config: GenerationConfig,
+) -> anyhow::Result<GenerationReport> {
+    let start = Instant::now();
+    let mut report = GenerationReport::default();
+
+    let tables = ir::import(
+        &std::fs::read_to_string(&input_ir_file).expect("Could not read schema IR file."),
+        &config,
+    )
+    .with_context(|| format!("Could not import '{}'", input_ir_file.display()))?;
+    let parse_duration = start.elapsed();
+    let generated = generate_tables(tables, &output_models_dir, &config, &mut report)?;
+
+    let write_start = Instant::now();
+    write_models(&generated, &output_models_dir, &config, &mut report)?;
+    save_cache(&generated, &config)?;
+    record_phases(&mut report, parse_duration, write_start.elapsed());
+    report.duration_ms = start.elapsed().as_millis();
+
+    Ok(report)
+}
+
+/// Generates a standalone crate in `output_crate_dir` containing the schema and the generated models, so the
+/// data layer can live in its own workspace member:
+///
+/// - `Cargo.toml` with the dependencies the models need (only written if it doesn't exist, so it can be customized)
+/// - `src/lib.rs` declaring the `schema` and `models` modules
+/// - `src/schema.rs`, a copy of the diesel schema file
+/// - `src/models`, the same files [`generate_files`] generates
+/// - `benches/models.rs`, criterion benchmarks of the models (with `benches` in the config)
+/// - `tests/models.rs` and `tests/support/mod.rs`, integration tests of the models against a postgres testcontainer
+///   (with `test_support` in the config)
+pub fn generate_crate(
+    input_diesel_schema_file: PathBuf,
+    output_crate_dir: PathBuf,
+    crate_name: &str,
+    config: GenerationConfig,
+) -> anyhow::Result<GenerationReport> {
+    let start = Instant::now();
+    let input = input_diesel_schema_file;
+    let mut report = GenerationReport::default();
+
+    let schema = std::fs::read_to_string(&input).expect("Could not read schema file.");
+    let tables = parser::parse_schema(&schema, &config).map_err(|e| e.with_file(&input))?;
+    let parse_duration = start.elapsed();
+    let src_dir = output_crate_dir.join("src");
+    let generated = generate_tables(tables, &src_dir.join("models"), &config, &mut report)?;
+
+    let write_start = Instant::now();
+    std::fs::create_dir_all(&src_dir)
+        .unwrap_or_else(|_| panic!("Could not create directory '{src_dir:#?}'"));
+
+    let cargo_toml_path = output_crate_dir.join("Cargo.toml");
+    if !cargo_toml_path.exists() {
+        let mut cargo_toml = MarkedFile::new(cargo_toml_path);
+        cargo_toml.file_contents = manifest::cargo_toml(crate_name, &generated, &config);
+        report.files.push(FileChange {
+            status: cargo_toml.write(),
+            path: cargo_toml.path,
+        });
+    }
+
+    for (file_name, contents) in [
+        ("lib.rs", manifest::lib_rs()),
+        ("schema.rs", manifest::schema_rs(&schema)),
+    ] {
+        let mut file = MarkedFile::new(src_dir.join(file_name));
+        file.ensure_file_signature();
+        file.file_contents = contents;
+        report.files.push(FileChange {
+            status: file.write(),
+            path: file.path,
+        });
+    }
+
+    if config.benches {
+        let benches_dir = output_crate_dir.join("benches");
+        std::fs::create_dir_all(&benches_dir)
+            .unwrap_or_else(|_| panic!("Could not create directory '{benches_dir:#?}'"));
+
+        let mut file = MarkedFile::new(benches_dir.join("models.rs"));
+        file.ensure_file_signature();
+        file.file_contents = benches::render(crate_name, &generated, &config);
+        report.files.push(FileChange {
+            status: file.write(),
+            path: file.path,
+        });
+    }
+
+    if let Some(migrations_dir) = config.test_support {
+        if !config.connection_type.contains("PgConnection") {
+            bail!("The generated test support runs postgres testcontainers, which need a postgres connection type");
+        }
+
+        let support_dir = output_crate_dir.join("tests").join("support");
+        std::fs::create_dir_all(&support_dir)
+            .unwrap_or_else(|_| panic!("Could not create directory '{support_dir:#?}'"));
+
+        for (path, contents) in [
+            (
+                support_dir.join("mod.rs"),
+                test_support::render_support(migrations_dir, &config),
+            ),
+            (
+                output_crate_dir.join("tests").join("models.rs"),
+                test_support::render_tests(crate_name, &generated, &config),
+            ),
+        ] {
+            let mut file = MarkedFile::new(path);
+            file.ensure_file_signature();
+            file.file_contents = contents;
+            report.files.push(FileChange {
+                status: file.write(),
+                path: file.path,
+            });
+        }
+    }
+
+    write_models(&generated, &src_dir.join("models"), &config, &mut report)?;
+    save_cache(&generated, &config)?;
+    record_phases(&mut report, parse_duration, write_start.elapsed());
+    report.duration_ms = start.elapsed().as_millis();
+
+    Ok(report)
+}
+
+/// Reverse mode: reads annotated model structs (from a rust file, or every rust file in a directory) and writes the
+/// diesel schema they describe to `output_schema_file`:
+///
+/// - `#[diesel(table_name = ...)]` names the table; the struct deriving `Selectable` (or else the first struct naming
+///   the table) provides its columns
+/// - `#[diesel(primary_key(...))]` gives the primary key (defaults to `id`)
+/// - `#[diesel(belongs_to(Parent, foreign_key = ...))]` gives the foreign keys
+/// - sql types are inferred from field types, unless fields have a `#[diesel(sql_type = ...)]` attribute
+///
+/// If `migration_dir` is given, a postgres migration creating the tables (`up.sql`) and dropping them (`down.sql`) is
+/// written there too.
+pub fn generate_schema_from_models(
+    input_models: PathBuf,
+    output_schema_file: PathBuf,
+    migration_dir: Option<PathBuf>,
+) -> anyhow::Result<GenerationReport> {
+    let start = Instant::now();
+    let mut report = GenerationReport::default();
+
+    let mut model_files = vec![];
+    let mut dirs = vec![input_models.clone()];
+    while let Some(path) = dirs.pop() {
+        if !path.is_dir() {
+            model_files.push(path);
+            continue;
+        }
+
+        for entry in std::fs::read_dir(&path)
+            .with_context(|| format!("Could not read directory '{}'", path.display()))?
+        {
+            let entry_path = entry?.path();
+            if entry_path.is_dir() || entry_path.extension().is_some_and(|e| e == "rs") {
+                dirs.push(entry_path);
+            }
+        }
+    }
+    model_files.sort();
+
+    let contents = model_files
+        .iter()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("Could not read '{}'", path.display()))
+        })
+        .collect::<anyhow::Result<Vec<String>>>()?;
+    let tables = reverse::parse_models(&contents)
+        .with_context(|| format!("Could not read models from '{}'", input_models.display()))?;
+
+    let mut files = vec![(output_schema_file, reverse::schema_rs(&tables))];
+    if let Some(migration_dir) = migration_dir {
+        std::fs::create_dir_all(&migration_dir)
+            .with_context(|| format!("Could not create directory '{}'", migration_dir.display()))?;
+
+        let ordered = migration::creation_order(&tables);
+        let up = ordered
+            .iter()
+            .map(|table| migration::create_table(table, &tables))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let down = ordered
+            .iter()
+            .rev()
+            .map(|table| migration::drop_table(table))
+            .collect::<String>();
+        files.push((
+            migration_dir.join("up.sql"),
+            format!("{FILE_SIGNATURE}\n\n{up}"),
+        ));
+        files.push((
+            migration_dir.join("down.sql"),
+            format!("{FILE_SIGNATURE}\n\n{down}"),
+        ));
+    }
+
+    for (path, contents) in files {
+        let mut file = MarkedFile::new(path);
+        file.ensure_file_signature();
+        file.file_contents = contents;
+        report.files.push(FileChange {
+            status: file.write(),
+            path: file.path,
+        });
+    }
+
+    report.tables = tables.into_iter().map(|t| t.name).collect();
+    report.duration_ms = start.elapsed().as_millis();
+
+    Ok(report)
+}
+
+/// Compares the schema with the snapshot of the last run (an IR document, see [`export_ir`]) and then updates the
+/// snapshot. The first run only writes the snapshot. If the schema changed:
+///
+/// - the report's `schema_changes` lists the tables added or removed and the columns added, removed, renamed or
+///   changed; likely renames (a removed and an added column with the same type at the same position) are warned about
+///   if table options still refer to the old column name
+/// - if `changelog_file` is given, these changes are added to the top of it, under the current date
+/// - if `migrations_dir` is given, a diesel migration scaffold is written to a new directory in it: `up.sql` creates
+///   added tables, drops removed ones and adds, alters or drops columns, and `down.sql` reverts that
+pub fn sync_snapshot(
+    input_diesel_schema_file: PathBuf,
+    snapshot_file: PathBuf,
+    migrations_dir: Option<PathBuf>,
+    changelog_file: Option<PathBuf>,
+    config: GenerationConfig,
+) -> anyhow::Result<GenerationReport> {
+    let start = Instant::now();
+    let mut report = GenerationReport::default();
+
+    let tables = parser::parse_schema(
+        &std::fs::read_to_string(&input_diesel_schema_file).expect("Could not read schema file."),
+        &config,
+    )
+    .map_err(|e| e.with_file(&input_diesel_schema_file))?;
+
+    let previous_snapshot =
+        match snapshot_file.exists() {
+            true => Some(std::fs::read_to_string(&snapshot_file).with_context(|| {
+                format!("Could not read snapshot '{}'", snapshot_file.display())
+            })?),
+            false => None,
+        };
+
+    if let Some(previous_snapshot) = &previous_snapshot {
+        let previous_tables = ir::import(previous_snapshot, &config)
+            .with_context(|| format!("Could not import snapshot '{}'", snapshot_file.display()))?;
+        let diff = diff::diff(&previous_tables, &tables);
+        report.schema_changes = diff::summary(&diff);
+        report
+            .warnings
+            .extend(diff::renamed_column_warnings(&diff, &config));
+
+        if let (false, Some(migrations_dir)) = (diff.is_empty(), migrations_dir) {
+            let migration_dir =
+                migrations_dir.join(migration::migration_name("dsync_schema_changes"));
+            std::fs::create_dir_all(&migration_dir).with_context(|| {
+                format!("Could not create directory '{}'", migration_dir.display())
+            })?;
+
+            let (up, down) = migration::migrate(&diff, &previous_tables, &tables);
+            for (file_name, contents) in [("up.sql", up), ("down.sql", down)] {
+                let path = migration_dir.join(file_name);
+                std::fs::write(&path, contents)
+                    .with_context(|| format!("Could not write '{}'", path.display()))?;
+                report.files.push(FileChange {
+                    path,
+                    status: FileStatus::Created,
+                });
+            }
+        }
+
+        if let (false, Some(changelog_file)) = (diff.is_empty(), changelog_file) {
+            let existed = changelog_file.exists();
+            let changelog = match existed {
+                true => std::fs::read_to_string(&changelog_file)
+                    .with_context(|| format!("Could not read '{}'", changelog_file.display()))?,
+                false => "# Schema changelog\n".to_string(),
+            };
+
+            // the newest changes go right below the title
+            let (title, entries) = changelog.split_once('\n').unwrap_or((&changelog, ""));
+            let [year, month, day, ..] = migration::utc_now();
+            let changes = report
+                .schema_changes
+                .iter()
+                .map(|change| format!("- {change}\n"))
+                .collect::<String>();
+            std::fs::write(
+                &changelog_file,
+                format!(
+                    "{title}\n\n## {year:04}-{month:02}-{day:02}\n\n{changes}{}",
+                    match entries.trim_start() {
+                        "" => "".to_string(),
+                        entries => format!("\n{entries}"),
+                    }
+                ),
+            )
+            .with_context(|| format!("Could not write '{}'", changelog_file.display()))?;
+            report.files.push(FileChange {
+                path: changelog_file,
+                status: match existed {
+                    true => FileStatus::Updated,
+                    false => FileStatus::Created,
+                },
+            });
+        }
+    }
+
+    let snapshot = ir::export(&tables, &config);
+    let status = match &previous_snapshot {
+        None => FileStatus::Created,
+        Some(previous) if previous == &snapshot => FileStatus::Unchanged,
+        Some(_) => FileStatus::Updated,
+    };
+    if status != FileStatus::Unchanged {
+        std::fs::write(&snapshot_file, &snapshot)
+            .with_context(|| format!("Could not write snapshot '{}'", snapshot_file.display()))?;
+    }
+    report.files.push(FileChange {
+        path: snapshot_file,
+        status,
+    });
+
+    report.tables = tables.into_iter().map(|t| t.name).collect();
+    report.duration_ms = start.elapsed().as_millis();
+
+    Ok(report)
+}
+
+/// Compares the schema with a snapshot (as kept by [`sync_snapshot`]) without updating it: the report's
+/// `schema_changes` lists the changes since the snapshot, and likely renames are warned about if table options still
+/// refer to the old column name
+pub fn diff_snapshot(
+    input_diesel_schema_file: PathBuf,
+    snapshot_file: PathBuf,
+    config: GenerationConfig,
+) -> anyhow::Result<GenerationReport> {
+    let start = Instant::now();
+    let mut report = GenerationReport::default();
+
+    let tables = parser::parse_schema(
+        &std::fs::read_to_string(&input_diesel_schema_file).expect("Could not read schema file."),
+        &config,
+    )
+    .map_err(|e| e.with_file(&input_diesel_schema_file))?;
+    let previous_tables = ir::import(
+        &std::fs::read_to_string(&snapshot_file)
+            .with_context(|| format!("Could not read snapshot '{}'", snapshot_file.display()))?,
+        &config,
+    )
+    .with_context(|| format!("Could not import snapshot '{}'", snapshot_file.display()))?;
+
+    let diff = diff::diff(&previous_tables, &tables);
+    report.schema_changes = diff::summary(&diff);
+    report
+        .warnings
+        .extend(diff::renamed_column_warnings(&diff, &config));
+
+    report.tables = tables.into_iter().map(|t| t.name).collect();
+    report.duration_ms = start.elapsed().as_millis();
+
+    Ok(report)
+}
+
+/// Deletes the generated code of the tables which are no longer in the schema from `output_models_dir` (a directory
+/// written by [`generate_files`]), without regenerating the models of the other tables
+pub fn prune_files(
+    input_diesel_schema_file: PathBuf,
+    output_models_dir: PathBuf,
+    config: GenerationConfig,
+) -> anyhow::Result<GenerationReport> {
+    let start = Instant::now();
+    let mut report = GenerationReport::default();
+
+    let tables = parser::parse_schema(
+        &std::fs::read_to_string(&input_diesel_schema_file).expect("Could not read schema file."),
+        &config,
     )
-    .expect("An error occurred.");
+    .map_err(|e| e.with_file(&input_diesel_schema_file))?;
+
+    let mod_rs_path = output_models_dir.join("mod.rs");
+    if !mod_rs_path.is_file() {
+        bail!(
+            "Expected '{}' to be a directory of generated models (it has no `mod.rs`)",
+            output_models_dir.display()
+        );
+    }
+    let mut mod_rs = MarkedFile::new(mod_rs_path);
+    prune_removed_tables(
+        &tables,
+        &output_models_dir,
+        &config,
+        &mut mod_rs,
+        BTreeMap::new(),
+        &mut report,
+    );
+    report.files.push(FileChange {
+        path: mod_rs.path.clone(),
+        status: mod_rs.write(),
+    });
+
+    report.tables = tables.into_iter().map(|t| t.name).collect();
+    report.duration_ms = start.elapsed().as_millis();
+
+    Ok(report)
+}
+
+/// name of the module (next to the models of the tables) holding the wrappers of sql functions
+const FUNCTIONS_MODULE: &str = "sql_functions";
+
+/// name of the module (next to the models of the tables) inserting fixture files
+const SEED_MODULE: &str = "seed";
+
+/// name of the module (next to the models of the tables) embedding the migrations
+const MIGRATIONS_MODULE: &str = "migrations";
+
+/// name of the module (next to the models of the tables) connecting to the database
+const DB_MODULE: &str = "db";
+
+/// allows the PascalCase module names of the pascal file naming (at the top of the `mod.rs` of a model)
+const ALLOW_PASCAL_CASE: &str = "#![allow(non_snake_case)]";
+
+/// renders the code of the tables, reusing the code of the tables which didn't change if there's a cache, and records
+/// how long rendering took
+fn generate_tables(
+    tables: Vec<ParsedTableMacro>,
+    output_dir: &Path,
+    config: &GenerationConfig,
+    report: &mut GenerationReport,
+) -> anyhow::Result<Vec<ParsedTableMacro>> {
+    let start = Instant::now();
+    let generated = match config.cache {
+        Some(cache_file) => cache::generate_tables(tables, output_dir, cache_file, config, report),
+        None => parser::generate_tables_with(tables, HashMap::new(), config),
+    }?;
+
+    let timings = report.timings.get_or_insert_with(Timings::default);
+    timings.render_ms = milliseconds(start.elapsed());
+    timings.tables = generated
+        .iter()
+        .map(|(table, duration)| TableTiming {
+            table: table.name.clone(),
+            render_ms: milliseconds(*duration),
+        })
+        .collect();
+    timings
+        .tables
+        .sort_by(|a, b| b.render_ms.total_cmp(&a.render_ms));
+
+    Ok(generated.into_iter().map(|(table, _)| table).collect())
+}
+
+/// records how long parsing and writing took (rendering is recorded by `generate_tables`)
+fn record_phases(report: &mut GenerationReport, parse: Duration, write: Duration) {
+    let timings = report.timings.get_or_insert_with(Timings::default);
+    timings.parse_ms = milliseconds(parse);
+    timings.write_ms = milliseconds(write);
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn save_cache(generated: &[ParsedTableMacro], config: &GenerationConfig) -> anyhow::Result<()> {
+    match config.cache {
+        Some(cache_file) => cache::save(cache_file, generated, config),
+        None => Ok(()),
+    }
+}
+
+/// writes the generated code of every table (and the modules next to them) into `output_dir` and prunes the code of
+/// removed tables
+fn write_models(
+    generated: &[ParsedTableMacro],
+    output_dir: &Path,
+    config: &GenerationConfig,
+    report: &mut GenerationReport,
+) -> anyhow::Result<()> {
+    // modules next to the models of the tables (which are removed once they aren't generated anymore)
+    let modules = [
+        (
+            FUNCTIONS_MODULE,
+            match config.functions.is_empty() {
+                true => None,
+                false => Some(functions::render(config)?),
+            },
+        ),
+        (SEED_MODULE, seed::render(generated, config)),
+        (MIGRATIONS_MODULE, embedded_migrations::render(config)),
+        (DB_MODULE, db_module::render(generated, config)),
+    ];
+    for (module, contents) in modules.iter() {
+        if contents.is_some() && generated.iter().any(|t| t.module_name(config) == *module) {
+            bail!(
+                "The generated `{module}` module collides with the model of the '{module}' table"
+            );
+        }
+    }
+
+    // file namings (like singular names) can map several tables to the same module
+    let mut table_modules = BTreeMap::new();
+    for table in generated.iter() {
+        let module = (table.group(config), table.module_name(config));
+        if let Some(other) = table_modules.insert(module.clone(), &table.name) {
+            bail!(
+                "The models of the '{other}' and '{}' tables would both be written to the `{}` module",
+                table.name,
+                module.1
+            );
+        }
+    }
+
+    // groups of tables are modules next to the models of the other tables
+    let ungrouped_modules = generated
+        .iter()
+        .filter(|t| t.group(config).is_none())
+        .map(|t| t.module_name(config))
+        .collect::<Vec<String>>();
+    for table in generated.iter() {
+        let Some(group) = table.group(config) else {
+            continue;
+        };
+        if syn::parse_str::<syn::Ident>(group).is_err() {
+            bail!(
+                "The group `{group}` of the '{}' table isn't a valid module name",
+                table.name
+            );
+        }
+        if ungrouped_modules.iter().any(|m| m == group) {
+            bail!("The group `{group}` collides with the model of the '{group}' table");
+        }
+        if modules
+            .iter()
+            .any(|(module, contents)| contents.is_some() && *module == group)
+        {
+            bail!("The group `{group}` collides with the generated `{group}` module");
+        }
+    }
 
     if !output_dir.exists() {
-        std::fs::create_dir(&output_dir)
+        std::fs::create_dir(output_dir)
             .unwrap_or_else(|_| panic!("Could not create directory '{output_dir:#?}'"));
     } else if !output_dir.is_dir() {
         panic!("Expected output argument to be a directory or non-existent.")
@@ -119,38 +2181,208 @@ pub fn generate_files(
     let mut mod_rs = MarkedFile::new(output_dir.join("mod.rs"));
 
     // pass 1: add code for new tables
-    for table in generated.iter() {
-        let table_dir = output_dir.join(table.name.to_string());
+    // (every table owns its own directory, so these can be written concurrently)
+    let table_files = generated
+        .par_iter()
+        .map(|table| {
+            let table_dir = table.model_dir(output_dir, config);
 
-        if !table_dir.exists() {
-            std::fs::create_dir(&table_dir)
-                .unwrap_or_else(|_| panic!("Could not create directory '{table_dir:#?}'"));
-        }
+            if !table_dir.exists() {
+                std::fs::create_dir_all(&table_dir)
+                    .unwrap_or_else(|_| panic!("Could not create directory '{table_dir:#?}'"));
+            }
 
-        if !table_dir.is_dir() {
-            panic!("Expected a directory at '{table_dir:#?}'")
-        }
+            if !table_dir.is_dir() {
+                panic!("Expected a directory at '{table_dir:#?}'")
+            }
+
+            let table_generated_rs = MarkedFile::new(table_dir.join("generated.rs"));
+            let mut table_mod_rs = MarkedFile::new(table_dir.join("mod.rs"));
+
+            table_generated_rs.ensure_file_signature();
+            let generated_rs_status = table_generated_rs.write_contents(&table.generated_code);
+
+            if config.table(&table.name).get_file_naming() == FileNaming::Pascal
+                && !table_mod_rs.file_contents.contains(ALLOW_PASCAL_CASE)
+            {
+                table_mod_rs.file_contents =
+                    format!("{ALLOW_PASCAL_CASE}\n{}", table_mod_rs.file_contents);
+            }
+            table_mod_rs.ensure_mod_stmt("generated");
+            table_mod_rs.ensure_use_stmt("generated::*");
+            let mod_rs_status = table_mod_rs.write();
+
+            [
+                FileChange {
+                    path: table_generated_rs.path,
+                    status: generated_rs_status,
+                },
+                FileChange {
+                    path: table_mod_rs.path,
+                    status: mod_rs_status,
+                },
+            ]
+        })
+        .collect::<Vec<_>>();
 
-        let mut table_generated_rs = MarkedFile::new(table_dir.join("generated.rs"));
-        let mut table_mod_rs = MarkedFile::new(table_dir.join("mod.rs"));
+    report
+        .warnings
+        .extend(warnings::schema_warnings(generated, config));
 
-        table_generated_rs.ensure_file_signature();
-        table_generated_rs.file_contents = table.generated_code.clone();
-        table_generated_rs.write();
+    // the mod.rs files of the groups, which declare the modules of their tables
+    let mut group_mod_rss: BTreeMap<&str, MarkedFile> = BTreeMap::new();
+    for (table, files) in generated.iter().zip(table_files) {
+        report.tables.push(table.name.clone());
+
+        report.files.extend(files);
+        let table_options = config.table(&table.name);
+        let parent_mod_rs = match table.group(config) {
+            Some(group) => group_mod_rss
+                .entry(group)
+                .or_insert_with(|| MarkedFile::new(output_dir.join(group).join("mod.rs"))),
+            None => &mut mod_rs,
+        };
+        parent_mod_rs.ensure_mod_stmt(&table.module_name(config));
+        parent_mod_rs
+            .set_mod_stmt_cfg_feature(&table.module_name(config), table_options.get_cfg_feature());
+    }
+    for group in group_mod_rss.keys() {
+        mod_rs.ensure_mod_stmt(group);
+    }
 
-        table_mod_rs.ensure_mod_stmt("generated");
-        table_mod_rs.ensure_use_stmt("generated::*");
-        table_mod_rs.write();
+    // pass 2: delete code for removed tables (and for tables which moved into or out of a group)
+    prune_removed_tables(
+        generated,
+        output_dir,
+        config,
+        &mut mod_rs,
+        group_mod_rss,
+        report,
+    );
 
-        mod_rs.ensure_mod_stmt(table.name.to_string().as_str());
+    for (module, contents) in modules {
+        let path = output_dir.join(format!("{module}.rs"));
+        match contents {
+            Some(contents) => {
+                let mut file = MarkedFile::new(path);
+                file.ensure_file_signature();
+                file.file_contents = contents;
+                report.files.push(FileChange {
+                    status: file.write(),
+                    path: file.path,
+                });
+                mod_rs.ensure_mod_stmt(module);
+            }
+            None if path.is_file() => {
+                let file = MarkedFile::new(path);
+                if file.has_file_signature() {
+                    report.files.push(FileChange {
+                        path: file.path.clone(),
+                        status: file.delete(),
+                    });
+                    mod_rs.remove_mod_stmt(module);
+                }
+            }
+            None => {}
+        }
     }
 
-    // pass 2: delete code for removed tables
-    for item in std::fs::read_dir(&output_dir)
+    report.files.push(FileChange {
+        path: mod_rs.path.clone(),
+        status: mod_rs.write(),
+    });
+
+    Ok(())
+}
+
+/// deletes the generated code of the tables which aren't in `tables` (or moved into or out of a group) from
+/// `output_dir`, along with groups which have no tables left; `group_mod_rss` are the `mod.rs` files of groups which
+/// were already updated
+fn prune_removed_tables<'a>(
+    tables: &[ParsedTableMacro],
+    output_dir: &Path,
+    config: &'a GenerationConfig,
+    mod_rs: &mut MarkedFile,
+    mut group_mod_rss: BTreeMap<&'a str, MarkedFile>,
+    report: &mut GenerationReport,
+) {
+    let ungrouped_modules = tables
+        .iter()
+        .filter(|t| t.group(config).is_none())
+        .map(|t| t.module_name(config))
+        .collect::<Vec<String>>();
+    prune_models(output_dir, &ungrouped_modules, mod_rs, report);
+
+    // groups are directories without generated code of their own; they're pruned as well, and removed with their
+    // last table
+    for item in std::fs::read_dir(output_dir)
         .unwrap_or_else(|_| panic!("Could not read directory '{output_dir:#?}'"))
     {
         let item = item.unwrap_or_else(|_| panic!("Could not read item in '{output_dir:#?}'"));
+        let group_dir = item.path();
+        if !group_dir.is_dir()
+            || group_dir.join("generated.rs").exists()
+            || !group_dir.join("mod.rs").is_file()
+        {
+            continue;
+        }
+        let Some(group) = item.file_name().to_str().map(|name| name.to_string()) else {
+            continue;
+        };
+
+        let group_modules = tables
+            .iter()
+            .filter(|t| t.group(config) == Some(group.as_str()))
+            .map(|t| t.module_name(config))
+            .collect::<Vec<String>>();
+        let mut group_mod_rs = group_mod_rss
+            .remove(group.as_str())
+            .unwrap_or_else(|| MarkedFile::new(group_dir.join("mod.rs")));
+        let pruned = prune_models(&group_dir, &group_modules, &mut group_mod_rs, report);
+
+        if group_modules.is_empty() && pruned && group_mod_rs.file_contents.trim().is_empty() {
+            report.files.push(FileChange {
+                path: group_mod_rs.path.clone(),
+                status: group_mod_rs.delete(),
+            });
+            let is_empty = group_dir
+                .read_dir()
+                .unwrap_or_else(|_| panic!("Could not read directory {group_dir:#?}"))
+                .next()
+                .is_none();
+            if is_empty {
+                std::fs::remove_dir(&group_dir)
+                    .unwrap_or_else(|_| panic!("Could not delete directory '{group_dir:#?}'"));
+            }
+            mod_rs.remove_mod_stmt(&group);
+        } else if !group_modules.is_empty() || pruned {
+            report.files.push(FileChange {
+                path: group_mod_rs.path.clone(),
+                status: group_mod_rs.write(),
+            });
+        }
+    }
+}
 
+/// deletes the generated code of the table directories in `dir` which aren't one of `modules` (along with their
+/// statements in `mod_rs`), returning whether anything was deleted
+fn prune_models(
+    dir: &Path,
+    modules: &[String],
+    mod_rs: &mut MarkedFile,
+    report: &mut GenerationReport,
+) -> bool {
+    let mut pruned = false;
+
+    let items = std::fs::read_dir(dir)
+        .unwrap_or_else(|_| panic!("Could not read directory '{dir:#?}'"))
+        .map(|item| item.unwrap_or_else(|_| panic!("Could not read item in '{dir:#?}'")))
+        .collect::<Vec<_>>();
+    let item_names = items
+        .iter()
+        .filter_map(|item| item.file_name().to_str().map(|name| name.to_string()))
+        .collect::<Vec<String>>();
+    for item in items {
         // check if item is a directory
         let file_type = item
             .file_type()
@@ -173,33 +2405,46 @@ pub fn generate_files(
         let associated_table_name = file_name
             .to_str()
             .unwrap_or_else(|| panic!("Could not determine name of file '{:#?}'", item.path()));
-        let found = generated.iter().find(|g| {
-            g.name
-                .to_string()
-                .eq_ignore_ascii_case(associated_table_name)
-        });
-        if found.is_some() {
+        // on case-insensitive file systems, the directory of a module can differ in case from its name (when there's
+        // no directory with the exact name)
+        if modules.iter().any(|m| {
+            m == associated_table_name
+                || (m.eq_ignore_ascii_case(associated_table_name) && !item_names.contains(m))
+        }) {
             continue;
         }
+        pruned = true;
 
         // this table was deleted, let's delete the generated code
         std::fs::remove_file(&generated_rs_path)
             .unwrap_or_else(|_| panic!("Could not delete redundant file '{generated_rs_path:#?}'"));
+        report.files.push(FileChange {
+            path: generated_rs_path,
+            status: FileStatus::Deleted,
+        });
 
         // remove the mod.rs file if there isn't anything left in there except the use stmt
         let table_mod_rs_path = item.path().join("mod.rs");
         if table_mod_rs_path.exists() {
-            let mut table_mod_rs = MarkedFile::new(table_mod_rs_path);
+            let mut table_mod_rs = MarkedFile::new(table_mod_rs_path.clone());
 
             table_mod_rs.remove_mod_stmt("generated");
             table_mod_rs.remove_use_stmt("generated::*");
-            table_mod_rs.write();
+            table_mod_rs.file_contents = table_mod_rs
+                .file_contents
+                .replace(ALLOW_PASCAL_CASE, "")
+                .trim()
+                .to_string();
 
-            if table_mod_rs.file_contents.trim().is_empty() {
+            let status = if table_mod_rs.file_contents.trim().is_empty() {
                 table_mod_rs.delete()
             } else {
                 table_mod_rs.write() // write the changes we made above
-            }
+            };
+            report.files.push(FileChange {
+                path: table_mod_rs_path,
+                status,
+            });
         }
 
         // delete the table dir if there's nothing else in there
@@ -218,5 +2463,5 @@ pub fn generate_files(
         mod_rs.remove_mod_stmt(associated_table_name);
     }
 
-    mod_rs.write();
+    pruned
 }
@@ -0,0 +1,261 @@
+use crate::parser::{ParsedTableMacro, FILE_SIGNATURE};
+use crate::{BinaryFormat, GenerationConfig, Target, UuidVersion};
+
+/// Crates used by the generated models as `(crate, diesel feature, dependency spec)`, keyed by a type path prefix
+const TYPE_DEPENDENCIES: &[(&str, Option<&str>, &str)] = &[
+    (
+        "chrono",
+        Some("chrono"),
+        r#"{ version = "0.4", features = ["serde"] }"#,
+    ),
+    ("serde_json", Some("serde_json"), r#""1""#),
+    (
+        "bigdecimal",
+        Some("numeric"),
+        r#"{ version = "0.4", features = ["serde"] }"#,
+    ),
+    (
+        "uuid",
+        Some("uuid"),
+        r#"{ version = "1", features = ["serde"] }"#,
+    ),
+    (
+        "ipnetwork",
+        Some("network-address"),
+        r#"{ version = "0.21", features = ["serde"] }"#,
+    ),
+    ("diesel_full_text_search", None, r#""2""#),
+    (
+        "postgis_diesel",
+        None,
+        r#"{ version = "3", features = ["serde"] }"#,
+    ),
+];
+
+/// the diesel features (backends and connection pools) a connection type needs
+fn connection_diesel_features(connection_type: &str) -> Vec<&'static str> {
+    [
+        ("PgConnection", "postgres"),
+        ("MysqlConnection", "mysql"),
+        ("SqliteConnection", "sqlite"),
+        // the written rows are returned with `RETURNING` clauses
+        ("SqliteConnection", "returning_clauses_for_sqlite_3_35"),
+        ("r2d2", "r2d2"),
+    ]
+    .iter()
+    .filter(|(needle, _)| connection_type.contains(needle))
+    .map(|(_, feature)| *feature)
+    .collect()
+}
+
+/// the manifest of a standalone models crate, with the dependencies (and diesel features) the models need
+pub fn cargo_toml(
+    crate_name: &str,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> String {
+    let mut diesel_features = connection_diesel_features(&config.connection_type);
+
+    // uuid primary keys generated by the models need the features of their uuid versions
+    let mut uuid_features = vec![];
+    for table in tables.iter() {
+        let feature = match config.table(&table.name).get_uuid_primary_key() {
+            Some(UuidVersion::V4) => "v4",
+            Some(UuidVersion::V7) => "v7",
+            None => continue,
+        };
+        if !uuid_features.contains(&feature) {
+            uuid_features.push(feature);
+        }
+    }
+    uuid_features.sort();
+
+    let mut dependencies = vec![];
+    for (krate, diesel_feature, spec) in TYPE_DEPENDENCIES {
+        let is_used = tables
+            .iter()
+            .flat_map(|t| t.columns.iter())
+            .any(|c| c.ty.contains(&format!("{krate}::")))
+            // the generated `seed` function and the JSON lines helpers (de)serialize JSON
+            || (*krate == "serde_json"
+                && tables.iter().any(|t| {
+                    let table_options = config.table(&t.name);
+                    table_options.get_seed() || table_options.get_import_export()
+                }));
+
+        if is_used {
+            diesel_features.extend(diesel_feature);
+            let spec = match *krate == "uuid" && !uuid_features.is_empty() {
+                true => format!(
+                    r#"{{ version = "1", features = ["serde", {}] }}"#,
+                    uuid_features
+                        .iter()
+                        .map(|f| format!("\"{f}\""))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                false => spec.to_string(),
+            };
+            dependencies.push(format!("{krate} = {spec}\n"));
+        }
+    }
+
+    // binary columns serialized as base64 are encoded by the generated `base64_serde` modules
+    if tables.iter().any(|t| {
+        config.table(&t.name).get_binary_format() == BinaryFormat::Base64
+            && t.columns.iter().any(|c| c.ty == "Vec<u8>")
+    }) {
+        dependencies.push("base64 = \"0.22\"\n".to_string());
+    }
+    if tables
+        .iter()
+        .any(|t| config.table(&t.name).get_import_export())
+    {
+        dependencies.push("csv = \"1\"\n".to_string());
+    }
+    if tables
+        .iter()
+        .any(|t| config.table(&t.name).get_target() == Target::SeaOrm)
+    {
+        dependencies.push("sea-orm = { version = \"1\", features = [\"macros\"] }\n".to_string());
+    }
+    if tables
+        .iter()
+        .any(|t| config.table(&t.name).get_target() == Target::Sqlx)
+    {
+        dependencies.push("sqlx = { version = \"0.8\", features = [\"macros\"] }\n".to_string());
+    }
+
+    if config.migrations_dir.is_some() {
+        dependencies.push("diesel_migrations = \"2\"\n".to_string());
+    }
+
+    // async wrappers run the functions on r2d2 pools, in tokio's blocking thread pool
+    if tables
+        .iter()
+        .any(|t| config.table(&t.name).get_async_wrappers())
+    {
+        if !diesel_features.contains(&"r2d2") {
+            diesel_features.push("r2d2");
+        }
+        dependencies.push("tokio = { version = \"1\", features = [\"rt\"] }\n".to_string());
+    }
+
+    #[cfg(feature = "tsync")]
+    if tables.iter().any(|t| config.table(&t.name).get_tsync()) {
+        dependencies.push("tsync = \"2\"\n".to_string());
+    }
+
+    // connection features switch to connection types which may need other diesel features, the postgres-only
+    // functions of `MultiConnection` models take a `PgConnection` behind a feature, and mocks and proptest strategies
+    // are behind features of their own
+    let mut features: Vec<(&str, Vec<String>)> = config
+        .connection_features
+        .iter()
+        .map(|(feature, connection_type)| {
+            (
+                feature.as_str(),
+                connection_diesel_features(connection_type)
+                    .into_iter()
+                    .filter(|f| !diesel_features.contains(f))
+                    .map(|f| format!("diesel/{f}"))
+                    .collect(),
+            )
+        })
+        .collect();
+    if let Some(multi_connection) = &config.multi_connection {
+        let postgres = "diesel/postgres".to_string();
+        match features
+            .iter_mut()
+            .find(|(feature, _)| *feature == multi_connection.postgres_feature)
+        {
+            Some((_, dependency_features)) if dependency_features.contains(&postgres) => {}
+            Some((_, dependency_features)) => dependency_features.push(postgres),
+            None => features.push((multi_connection.postgres_feature, vec![postgres])),
+        }
+    }
+    if tables.iter().any(|t| config.table(&t.name).get_mock()) {
+        dependencies.push("mockall = { version = \"0.13\", optional = true }\n".to_string());
+        features.push(("mock", vec!["dep:mockall".to_string()]));
+    }
+    if tables.iter().any(|t| config.table(&t.name).get_proptest()) {
+        dependencies.push("proptest = { version = \"1\", optional = true }\n".to_string());
+        features.push(("proptest", vec!["dep:proptest".to_string()]));
+    }
+    let features = match features.is_empty() {
+        true => "".to_string(),
+        false => format!(
+            "\n[features]\n{}",
+            features
+                .iter()
+                .map(|(feature, dependency_features)| format!(
+                    "{feature} = [{}]\n",
+                    dependency_features
+                        .iter()
+                        .map(|f| format!("\"{f}\""))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ))
+                .collect::<String>()
+        ),
+    };
+
+    // the benchmarks use criterion's own harness, and the tests run against migrated testcontainers
+    let mut dev_dependencies = vec![];
+    if config.benches {
+        dev_dependencies.push("criterion = \"0.5\"\n");
+    }
+    if config.test_support.is_some() {
+        dev_dependencies.push("diesel_migrations = \"2\"\n");
+        dev_dependencies.push(
+            "testcontainers-modules = { version = \"0.11\", features = [\"postgres\", \"blocking\"] }\n",
+        );
+    }
+    let mut dev_dependencies = match dev_dependencies.is_empty() {
+        true => "".to_string(),
+        false => format!("\n[dev-dependencies]\n{}", dev_dependencies.join("")),
+    };
+    if config.benches {
+        dev_dependencies.push_str("\n[[bench]]\nname = \"models\"\nharness = false\n");
+    }
+
+    format!(
+        r##"# generated by dsync; this file is only written if it doesn't exist, so it's safe to edit
+[package]
+name = "{crate_name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+diesel = {{ version = "2", features = [{diesel_features}] }}
+serde = {{ version = "1", features = ["derive"] }}
+{dependencies}{features}{dev_dependencies}"##,
+        diesel_features = diesel_features
+            .iter()
+            .map(|f| format!("\"{f}\""))
+            .collect::<Vec<String>>()
+            .join(", "),
+        dependencies = dependencies.join("")
+    )
+}
+
+/// the root of a standalone models crate
+pub fn lib_rs() -> String {
+    format!(
+        r##"{FILE_SIGNATURE}
+
+// the generated models refer to diesel as `crate::diesel`
+extern crate diesel;
+
+pub mod models;
+pub mod schema;
+"##
+    )
+}
+
+/// the copy of the diesel schema inside a standalone models crate
+pub fn schema_rs(schema: &str) -> String {
+    let schema = schema.strip_prefix(FILE_SIGNATURE).unwrap_or(schema);
+
+    format!("{FILE_SIGNATURE}\n\n{}\n", schema.trim())
+}
@@ -0,0 +1,316 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::diff::SchemaDiff;
+use crate::parser::{ParsedColumnMacro, ParsedTableMacro};
+
+/// the postgres type of a column's values (diesel sql types mostly share their names with postgres types)
+fn sql_type(column: &ParsedColumnMacro) -> String {
+    let sql_type = column.sql_type.as_str();
+    if let Some(element) = sql_type
+        .strip_prefix("Array<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        let element = element
+            .strip_prefix("Nullable<")
+            .and_then(|t| t.strip_suffix('>'))
+            .unwrap_or(element);
+
+        return format!("{}[]", simple_sql_type(element, None));
+    }
+
+    simple_sql_type(
+        sql_type,
+        column
+            .attribute("max_length")
+            .and_then(|a| a.value.as_deref()),
+    )
+}
+
+fn simple_sql_type(sql_type: &str, max_length: Option<&str>) -> String {
+    match sql_type {
+        "Int2" | "SmallInt" => "SMALLINT".to_string(),
+        "Int4" | "Integer" => "INTEGER".to_string(),
+        "Int8" | "BigInt" => "BIGINT".to_string(),
+        "Float4" | "Float" => "REAL".to_string(),
+        "Float8" | "Double" => "DOUBLE PRECISION".to_string(),
+        "Bool" => "BOOLEAN".to_string(),
+        "Varchar" => match max_length {
+            Some(max_length) => format!("VARCHAR({max_length})"),
+            None => "VARCHAR".to_string(),
+        },
+        "Binary" => "BYTEA".to_string(),
+        other => other.to_uppercase(),
+    }
+}
+
+/// the quoted name of a table in the database (qualified by its schema, if it has one)
+fn table_name(table: &ParsedTableMacro) -> String {
+    let name = table.sql_name().unwrap_or(&table.name);
+    match &table.schema {
+        Some(schema) => format!("\"{schema}\".\"{name}\""),
+        None => format!("\"{name}\""),
+    }
+}
+
+/// the name of a column in the database
+fn column_name(column: &ParsedColumnMacro) -> &str {
+    column
+        .sql_name()
+        .unwrap_or(column.name.trim_start_matches("r#"))
+}
+
+/// the definition of a column in `CREATE TABLE` and `ALTER TABLE ... ADD COLUMN` statements
+fn column_definition(
+    table: &ParsedTableMacro,
+    column: &ParsedColumnMacro,
+    tables: &[ParsedTableMacro],
+) -> String {
+    // dsync treats single integer primary keys as generated by the database
+    let is_serial =
+        table.primary_key_columns.len() == 1 && table.primary_key_columns[0] == column.name;
+    let sql_type = match (column.sql_type.as_str(), is_serial) {
+        ("Int2", true) => "SMALLSERIAL".to_string(),
+        ("Int4", true) => "SERIAL".to_string(),
+        ("Int8", true) => "BIGSERIAL".to_string(),
+        _ => sql_type(column),
+    };
+
+    let mut definition = format!("\"{}\" {sql_type}", column_name(column));
+    if !column.is_nullable {
+        definition.push_str(" NOT NULL");
+    }
+
+    let references = table
+        .foreign_keys
+        .iter()
+        .find(|(_, join_column)| join_column == &column.name)
+        .and_then(|(foreign_table_name, _)| tables.iter().find(|t| &t.name == foreign_table_name));
+    if let Some(foreign_table) = references {
+        if let [pk] = foreign_table.primary_key_columns.as_slice() {
+            let pk = foreign_table
+                .column(pk)
+                .map(column_name)
+                .unwrap_or(pk.as_str());
+            definition.push_str(&format!(
+                " REFERENCES {} (\"{pk}\")",
+                table_name(foreign_table)
+            ));
+        }
+    }
+
+    definition
+}
+
+pub fn create_table(table: &ParsedTableMacro, tables: &[ParsedTableMacro]) -> String {
+    let mut lines = table
+        .columns
+        .iter()
+        .map(|column| format!("    {}", column_definition(table, column, tables)))
+        .collect::<Vec<String>>();
+
+    if !table.primary_key_columns.is_empty() {
+        lines.push(format!(
+            "    PRIMARY KEY ({})",
+            table
+                .primary_key_columns
+                .iter()
+                .map(|pk| {
+                    let name = table.column(pk).map(column_name).unwrap_or(pk.as_str());
+                    format!("\"{name}\"")
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+
+    format!(
+        "CREATE TABLE {} (\n{}\n);\n",
+        table_name(table),
+        lines.join(",\n")
+    )
+}
+
+pub fn drop_table(table: &ParsedTableMacro) -> String {
+    format!("DROP TABLE {};\n", table_name(table))
+}
+
+/// orders tables so referenced tables come before the tables referencing them (tables in cycles keep their order)
+pub fn creation_order(tables: &[ParsedTableMacro]) -> Vec<&ParsedTableMacro> {
+    let mut ordered: Vec<&ParsedTableMacro> = vec![];
+
+    while ordered.len() < tables.len() {
+        let remaining = tables
+            .iter()
+            .filter(|t| !ordered.iter().any(|o| o.name == t.name))
+            .collect::<Vec<&ParsedTableMacro>>();
+
+        let ready = remaining
+            .iter()
+            .filter(|table| {
+                table.foreign_keys.iter().all(|(foreign_table_name, _)| {
+                    foreign_table_name == &table.name
+                        || ordered.iter().any(|o| &o.name == foreign_table_name)
+                        || !tables.iter().any(|t| &t.name == foreign_table_name)
+                })
+            })
+            .copied()
+            .collect::<Vec<&ParsedTableMacro>>();
+
+        match ready.is_empty() {
+            true => ordered.extend(remaining),
+            false => ordered.extend(ready),
+        }
+    }
+
+    ordered
+}
+
+/// statements changing the type and nullability of a column from `from` to `to`
+fn alter_column(
+    table: &ParsedTableMacro,
+    from: &ParsedColumnMacro,
+    to: &ParsedColumnMacro,
+) -> String {
+    let prefix = format!(
+        "ALTER TABLE {} ALTER COLUMN \"{}\"",
+        table_name(table),
+        column_name(to)
+    );
+
+    let mut statements = String::new();
+    if sql_type(from) != sql_type(to) {
+        statements.push_str(&format!("{prefix} TYPE {};\n", sql_type(to)));
+    }
+    match (from.is_nullable, to.is_nullable) {
+        (true, false) => statements.push_str(&format!("{prefix} SET NOT NULL;\n")),
+        (false, true) => statements.push_str(&format!("{prefix} DROP NOT NULL;\n")),
+        _ => {}
+    }
+
+    statements
+}
+
+fn add_column(
+    table: &ParsedTableMacro,
+    column: &ParsedColumnMacro,
+    tables: &[ParsedTableMacro],
+) -> String {
+    let note = match column.is_nullable {
+        true => "",
+        false => "-- existing rows need a value for this column (add a DEFAULT if the table isn't empty)\n",
+    };
+
+    format!(
+        "{note}ALTER TABLE {} ADD COLUMN {};\n",
+        table_name(table),
+        column_definition(table, column, tables)
+    )
+}
+
+fn drop_column(table: &ParsedTableMacro, column: &ParsedColumnMacro) -> String {
+    format!(
+        "ALTER TABLE {} DROP COLUMN \"{}\";\n",
+        table_name(table),
+        column_name(column)
+    )
+}
+
+/// the `up.sql` and `down.sql` of a migration applying a schema diff (and reverting it)
+pub fn migrate(
+    diff: &SchemaDiff,
+    old_tables: &[ParsedTableMacro],
+    new_tables: &[ParsedTableMacro],
+) -> (String, String) {
+    let header = "-- generated by dsync from the schema changes since the last snapshot; review it before running it\n";
+    let mut up = vec![];
+    let mut down = vec![];
+
+    for table in creation_order(new_tables)
+        .into_iter()
+        .filter(|t| diff.added_tables.iter().any(|a| a.name == t.name))
+    {
+        up.push(create_table(table, new_tables));
+        down.insert(0, drop_table(table));
+    }
+
+    for table_diff in diff.changed_tables.iter() {
+        let mut up_statements = String::new();
+        let mut down_statements = String::new();
+
+        for column in table_diff.added_columns.iter() {
+            up_statements.push_str(&add_column(table_diff.new, column, new_tables));
+            down_statements.push_str(&drop_column(table_diff.new, column));
+        }
+        for (old_column, new_column) in table_diff.renamed_columns.iter() {
+            let note = "-- looks like a rename (same type and position); use DROP COLUMN and ADD COLUMN if it isn't\n";
+            up_statements.push_str(&format!(
+                "{note}ALTER TABLE {} RENAME COLUMN \"{}\" TO \"{}\";\n",
+                table_name(table_diff.new),
+                column_name(old_column),
+                column_name(new_column)
+            ));
+            down_statements.push_str(&format!(
+                "ALTER TABLE {} RENAME COLUMN \"{}\" TO \"{}\";\n",
+                table_name(table_diff.old),
+                column_name(new_column),
+                column_name(old_column)
+            ));
+        }
+        for (old_column, new_column) in table_diff.changed_columns.iter() {
+            up_statements.push_str(&alter_column(table_diff.new, old_column, new_column));
+            down_statements.push_str(&alter_column(table_diff.old, new_column, old_column));
+        }
+        for column in table_diff.dropped_columns.iter() {
+            up_statements.push_str(&drop_column(table_diff.old, column));
+            down_statements.push_str(&add_column(table_diff.old, column, old_tables));
+        }
+
+        up.push(up_statements);
+        down.insert(0, down_statements);
+    }
+
+    let dropped = creation_order(old_tables)
+        .into_iter()
+        .filter(|t| diff.dropped_tables.iter().any(|d| d.name == t.name))
+        .collect::<Vec<&ParsedTableMacro>>();
+    for table in dropped.iter().rev() {
+        up.push(drop_table(table));
+    }
+    for table in dropped.iter() {
+        down.insert(0, create_table(table, old_tables));
+    }
+
+    (
+        format!("{header}\n{}", up.join("\n")),
+        format!("{header}\n{}", down.join("\n")),
+    )
+}
+
+/// the current UTC date and time as `[year, month, day, hours, minutes, seconds]`
+pub fn utc_now() -> [i64; 6] {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("The system clock is set before 1970")
+        .as_secs() as i64;
+    let (days, time) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
+
+    // converts days since 1970-01-01 into a civil date (see http://howardhinnant.github.io/date_algorithms.html)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    [year, month, day, time / 3600, time % 3600 / 60, time % 60]
+}
+
+/// the name of a diesel migration directory created now (diesel orders migrations by their `%Y-%m-%d-%H%M%S` prefix)
+pub fn migration_name(name: &str) -> String {
+    let [year, month, day, hours, minutes, seconds] = utc_now();
+
+    format!("{year:04}-{month:02}-{day:02}-{hours:02}{minutes:02}{seconds:02}_{name}")
+}
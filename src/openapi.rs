@@ -0,0 +1,325 @@
+use crate::code::{struct_fields, StructField};
+use crate::parser::{ParsedColumnMacro, ParsedTableMacro};
+use crate::{BinaryFormat, GenerationConfig};
+
+/// quotes arbitrary text as a yaml scalar (JSON strings are valid double-quoted yaml scalars)
+fn quote(text: &str) -> String {
+    serde_json::to_string(text).expect("Could not quote a string")
+}
+
+/// the json schema of a column's values as yaml lines (indented by `indent` spaces)
+fn value_schema(
+    column: &ParsedColumnMacro,
+    binary_format: BinaryFormat,
+    indent: usize,
+) -> Vec<String> {
+    let ty = match column.is_unsigned {
+        true => column.ty.replace('i', "u"),
+        false => column.ty.clone(),
+    };
+    let mut lines: Vec<String> = match ty.as_str() {
+        "bool" => vec!["type: boolean".into()],
+        "i8" | "i16" | "i32" | "u8" | "u16" | "u32" => {
+            vec!["type: integer".into(), "format: int32".into()]
+        }
+        "i64" | "u64" => vec!["type: integer".into(), "format: int64".into()],
+        "f32" => vec!["type: number".into(), "format: float".into()],
+        "f64" => vec!["type: number".into(), "format: double".into()],
+        "Vec<u8>" if binary_format == BinaryFormat::Base64 => {
+            vec!["type: string".into(), "format: byte".into()]
+        }
+        "Vec<u8>" => vec![
+            "type: array".into(),
+            "items:".into(),
+            "  type: integer".into(),
+        ],
+        "uuid::Uuid" => vec!["type: string".into(), "format: uuid".into()],
+        "chrono::NaiveDateTime" | "chrono::DateTime<chrono::Utc>" => {
+            vec!["type: string".into(), "format: date-time".into()]
+        }
+        "chrono::NaiveDate" => vec!["type: string".into(), "format: date".into()],
+        // any json value
+        "serde_json::Value" => vec![],
+        // strings, and types serialized as strings (like times and decimals)
+        _ => vec!["type: string".into()],
+    };
+
+    if let Some(max_length) = column
+        .attribute("max_length")
+        .and_then(|a| a.value.as_deref())
+    {
+        lines.push(format!("maxLength: {max_length}"));
+    }
+    if column.is_nullable {
+        lines.push("nullable: true".into());
+    }
+    let description = column
+        .doc_comments()
+        .iter()
+        .map(|d| d.trim())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    if !description.is_empty() {
+        lines.push(format!("description: {}", quote(&description)));
+    }
+    if lines.is_empty() {
+        lines.push("{}".into());
+    }
+
+    lines
+        .into_iter()
+        .map(|line| format!("{:indent$}{line}", ""))
+        .collect()
+}
+
+/// the component schema of a generated struct
+fn struct_schema(
+    table: &ParsedTableMacro,
+    name: &str,
+    fields: &[StructField],
+    is_read: bool,
+    binary_format: BinaryFormat,
+) -> String {
+    let mut buffer = format!("    {name}:\n      type: object\n");
+
+    // optional fields (and nullable ones, which serde defaults to `None`) can be left out of requests
+    let required = fields
+        .iter()
+        .filter(|field| is_read || !(field.is_optional || column(table, field).is_nullable))
+        .map(|field| format!("        - {}\n", property_name(table, field)))
+        .collect::<String>();
+    if !required.is_empty() {
+        buffer.push_str(&format!("      required:\n{required}"));
+    }
+
+    buffer.push_str("      properties:\n");
+    for field in fields.iter() {
+        buffer.push_str(&format!(
+            "        {}:\n{}\n",
+            property_name(table, field),
+            value_schema(column(table, field), binary_format, 10).join("\n")
+        ));
+    }
+
+    buffer
+}
+
+fn column<'a>(table: &'a ParsedTableMacro, field: &StructField) -> &'a ParsedColumnMacro {
+    table
+        .column(&field.column_name)
+        .expect("Struct field doesn't map to a column")
+}
+
+/// the name a field is (de)serialized as
+fn property_name<'a>(table: &'a ParsedTableMacro, field: &'a StructField) -> &'a str {
+    column(table, field)
+        .serde_rename()
+        .unwrap_or(field.name.trim_start_matches("r#"))
+}
+
+/// a json request or response body referring to a component schema (indented by `indent` spaces)
+fn json_body(schema: &str, indent: usize) -> String {
+    format!(
+        "{0:indent$}content:\n{0:indent$}  application/json:\n{0:indent$}    schema:\n{0:indent$}      $ref: \"#/components/schemas/{schema}\"\n",
+        ""
+    )
+}
+
+/// renders an OpenAPI 3.0 document describing CRUD endpoints over the generated structs of every table
+///
+/// - `GET /{table}` pages through rows (`paginate`), `POST /{table}` creates a row
+/// - `GET`, `PUT` and `DELETE /{table}/{primary key...}` read, update and delete a single row
+pub fn render(tables: &[ParsedTableMacro], config: &GenerationConfig) -> String {
+    let mut paths = String::new();
+    let mut schemas = String::new();
+
+    for table in tables.iter() {
+        let mut structs = struct_fields(table, config).into_iter();
+        let (read_name, mut read_fields) = structs.next().expect("Missing the Read struct");
+        // sensitive fields are skipped when serializing Read structs
+        let sensitive_columns = config.table(&table.name).get_sensitive_columns().to_vec();
+        let binary_format = config.table(&table.name).get_binary_format();
+        read_fields.retain(|f| !sensitive_columns.contains(&f.column_name.as_str()));
+        let (create_name, create_fields) = structs.next().expect("Missing the Create struct");
+        let (update_name, update_fields) = structs.next().expect("Missing the Update struct");
+        let page_name = format!("{read_name}PaginationResult");
+        let tag = &table.name;
+
+        schemas.push_str(&struct_schema(
+            table,
+            &read_name,
+            &read_fields,
+            true,
+            binary_format,
+        ));
+        if !create_fields.is_empty() {
+            schemas.push_str(&struct_schema(
+                table,
+                &create_name,
+                &create_fields,
+                false,
+                binary_format,
+            ));
+        }
+        if !update_fields.is_empty() {
+            schemas.push_str(&struct_schema(
+                table,
+                &update_name,
+                &update_fields,
+                false,
+                binary_format,
+            ));
+        }
+        schemas.push_str(&format!(
+            r##"    {page_name}:
+      type: object
+      required:
+        - items
+        - total_items
+        - page
+        - page_size
+        - num_pages
+      properties:
+        items:
+          type: array
+          items:
+            $ref: "#/components/schemas/{read_name}"
+        total_items:
+          type: integer
+          format: int64
+        page:
+          type: integer
+          format: int64
+          description: 0-based index
+        page_size:
+          type: integer
+          format: int64
+        num_pages:
+          type: integer
+          format: int64
+"##
+        ));
+
+        paths.push_str(&format!(
+            r##"  /{tag}:
+    get:
+      tags:
+        - {tag}
+      operationId: {tag}_paginate
+      parameters:
+        - name: page
+          in: query
+          description: 0-based index
+          schema:
+            type: integer
+            format: int64
+            default: 0
+        - name: page_size
+          in: query
+          schema:
+            type: integer
+            format: int64
+      responses:
+        "200":
+          description: a page of rows
+{page_body}"##,
+            page_body = json_body(&page_name, 10)
+        ));
+        if !create_fields.is_empty() {
+            paths.push_str(&format!(
+                r##"    post:
+      tags:
+        - {tag}
+      operationId: {tag}_create
+      requestBody:
+        required: true
+{create_body}      responses:
+        "201":
+          description: the created row
+{read_body}"##,
+                create_body = json_body(&create_name, 8),
+                read_body = json_body(&read_name, 10)
+            ));
+        }
+
+        if table.primary_key_columns.is_empty() {
+            continue;
+        }
+
+        let pk_names = table
+            .primary_key_columns
+            .iter()
+            .map(|pk| pk.trim_start_matches("r#"))
+            .collect::<Vec<&str>>();
+        let parameters = table
+            .primary_key_columns
+            .iter()
+            .map(|pk| {
+                let column = table.column(pk).expect("Primary key isn't a column");
+                format!(
+                    "      - name: {name}\n        in: path\n        required: true\n        schema:\n{schema}\n",
+                    name = pk.trim_start_matches("r#"),
+                    schema = value_schema(column, binary_format, 10).join("\n")
+                )
+            })
+            .collect::<String>();
+        let not_found = "        \"404\":\n          description: no row has this primary key\n";
+
+        paths.push_str(&format!(
+            r##"  /{tag}/{{{pk_path}}}:
+    parameters:
+{parameters}    get:
+      tags:
+        - {tag}
+      operationId: {tag}_read
+      responses:
+        "200":
+          description: the row
+{read_body}{not_found}"##,
+            pk_path = pk_names.join("}/{"),
+            read_body = json_body(&read_name, 10)
+        ));
+        if !update_fields.is_empty() {
+            paths.push_str(&format!(
+                r##"    put:
+      tags:
+        - {tag}
+      operationId: {tag}_update
+      requestBody:
+        required: true
+{update_body}      responses:
+        "200":
+          description: the updated row
+{read_body}{not_found}"##,
+                update_body = json_body(&update_name, 8),
+                read_body = json_body(&read_name, 10)
+            ));
+        }
+        // materialized views are only read
+        if config.table(&table.name).get_materialized_view() {
+            continue;
+        }
+        paths.push_str(&format!(
+            r##"    delete:
+      tags:
+        - {tag}
+      operationId: {tag}_delete
+      responses:
+        "204":
+          description: the row was deleted
+{not_found}"##
+        ));
+    }
+
+    format!(
+        r##"# This file is generated and managed by dsync
+openapi: 3.0.3
+info:
+  title: CRUD API
+  version: 0.1.0
+paths:
+{paths}components:
+  schemas:
+{schemas}"##
+    )
+}
@@ -1,45 +1,283 @@
+use anyhow::Context;
 use inflector::Inflector;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use syn::Ident;
 use syn::Item::Macro;
 
-use crate::{code, GenerationConfig, TableOptions};
+use crate::error::SchemaError;
+use crate::{
+    code, seaorm, sqlx, validate, DieselTypeMapper, FileNaming, GenerationConfig,
+    StructNameCollisions, TableOptions, Target, TypeMapper, TypeMapping,
+};
 
 pub const FILE_SIGNATURE: &str = "/* This file is generated and managed by dsync */";
 
-// TODO: handle postgres array types
 // TODO: handle postgres tuple/record types
 
+/// An attribute found in a `table!` macro, like `#[sql_name = "..."]`, `#[max_length = 255]`,
+/// or doc comments (which show up as `#[doc = "..."]`)
+#[derive(Debug, Clone)]
+pub struct ParsedAttribute {
+    pub name: String,
+    /// value of a `#[name = value]` (string literals are unquoted) or `#[name(value)]` attribute
+    pub value: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ParsedColumnMacro {
     pub ty: String,
-    pub name: Ident,
+    /// paths the models import to use the rust type (see `TypeMapping`)
+    pub type_imports: Vec<String>,
+    /// serde attributes of fields holding values of the column (see `TypeMapping`)
+    pub serde_attributes: Vec<String>,
+    /// the diesel sql type of the column as written in the schema (for example: `Int4`)
+    pub sql_type: String,
+    pub name: String,
     pub is_nullable: bool,
     pub is_unsigned: bool,
+    pub attributes: Vec<ParsedAttribute>,
+}
+
+impl ParsedColumnMacro {
+    pub fn attribute(&self, name: &str) -> Option<&ParsedAttribute> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    /// name of the column in the database, if it differs from the identifier used in the schema
+    pub fn sql_name(&self) -> Option<&str> {
+        self.attribute("sql_name").and_then(|a| a.value.as_deref())
+    }
+
+    /// An idiomatic rust identifier for struct fields which map to this column.
+    ///
+    /// This is the column's identifier in the schema unless it isn't snake_case (for example `userId`) or isn't
+    /// ascii (for example `prénom`, see `transliterate`), in which case the generated fields need a
+    /// `#[diesel(column_name = ...)]` attribute.
+    pub fn field_name(&self) -> String {
+        if self.name.is_ascii()
+            && (self.name.starts_with("r#") || !self.name.chars().any(|c| c.is_uppercase()))
+        {
+            return self.name.clone();
+        }
+
+        let field_name = snake_case_identifier(&self.name);
+        match syn::parse_str::<Ident>(&field_name) {
+            Ok(_) => field_name,
+            // the snake_case version collides with a keyword
+            Err(_) => format!("r#{field_name}"),
+        }
+    }
+
+    /// the name fields of non-ascii columns keep when they're (de)serialized, as their identifiers are transliterated
+    pub fn serde_rename(&self) -> Option<&str> {
+        match self.name.is_ascii() {
+            true => None,
+            false => Some(&self.name),
+        }
+    }
+
+    /// tsvector columns can't be (de)serialized or inserted, so they never end up in generated structs
+    pub fn is_tsvector(&self) -> bool {
+        self.sql_type.eq_ignore_ascii_case("tsvector")
+    }
+
+    /// citext columns compare case-insensitively in the database
+    pub fn is_citext(&self) -> bool {
+        self.sql_type.eq_ignore_ascii_case("citext")
+    }
+
+    /// lines of the column's doc comments
+    pub fn doc_comments(&self) -> Vec<&str> {
+        doc_comments(&self.attributes)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ParsedTableMacro {
-    pub name: Ident,
+    pub name: String,
     pub struct_name: String,
     pub columns: Vec<ParsedColumnMacro>,
-    pub primary_key_columns: Vec<Ident>,
+    pub primary_key_columns: Vec<String>,
     pub foreign_keys: Vec<(
         ForeignTableName,
         JoinColumn, /* this is the column from this table which maps to the foreign table's primary key*/
     )>,
+    /// tables which are allowed to appear in the same query as this one (see `allow_tables_to_appear_in_same_query!`)
+    pub same_query_tables: Vec<String>,
+    pub attributes: Vec<ParsedAttribute>,
+    /// the (postgres) schema of schema-qualified tables (like `billing` for `billing.invoices`)
+    pub schema: Option<String>,
     pub generated_code: String,
 }
 
 impl ParsedTableMacro {
     pub fn primary_key_column_names(&self) -> Vec<String> {
-        self.primary_key_columns
+        self.primary_key_columns.clone()
+    }
+
+    /// foreign keys whose tables can be joined with this one (i.e. they're allowed to appear in the same query)
+    pub fn joinable_foreign_keys(&self) -> Vec<&(ForeignTableName, JoinColumn)> {
+        self.foreign_keys
             .iter()
-            .map(|i| i.to_string())
+            .filter(|fk| self.same_query_tables.contains(&fk.0))
             .collect()
     }
+
+    pub fn column(&self, name: &str) -> Option<&ParsedColumnMacro> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// name of the module holding the table's model, which is also the name of its directory (see `module_name`)
+    pub fn module_name(&self, config: &GenerationConfig) -> String {
+        module_name(&self.name, config.table(&self.name).get_file_naming())
+    }
+
+    /// the group of the table's model: the group of its options, or its schema (for schema-qualified tables)
+    pub fn group<'a>(&'a self, config: &'a GenerationConfig) -> Option<&'a str> {
+        config
+            .table(&self.name)
+            .get_group()
+            .or(self.schema.as_deref())
+    }
+
+    /// directory of the table's model in the models directory (inside the directory of its group, if it has one)
+    pub fn model_dir(&self, output_dir: &Path, config: &GenerationConfig) -> PathBuf {
+        match self.group(config) {
+            Some(group) => output_dir.join(group).join(self.module_name(config)),
+            None => output_dir.join(self.module_name(config)),
+        }
+    }
+
+    /// the struct field name for a column of this table (see `ParsedColumnMacro::field_name`)
+    pub fn field_name(&self, column_name: &str) -> String {
+        self.column(column_name)
+            .map(|c| c.field_name())
+            .unwrap_or_else(|| column_name.to_string())
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&ParsedAttribute> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    /// name of the table in the database, if it differs from the identifier used in the schema
+    pub fn sql_name(&self) -> Option<&str> {
+        self.attribute("sql_name").and_then(|a| a.value.as_deref())
+    }
+
+    /// lines of the table's doc comments
+    pub fn doc_comments(&self) -> Vec<&str> {
+        doc_comments(&self.attributes)
+    }
 }
 
-type ForeignTableName = Ident;
+/// An ascii version of an identifier: letters lose their diacritics (`é` becomes `e`, `ö` becomes `oe` and `ß`
+/// becomes `ss`), and other non-ascii characters are spelled out as their code points (`名前` becomes `u540d_u524d`).
+///
+/// Rust accepts non-ascii identifiers, but not as names of module files (and they're awkward to type), so the
+/// identifiers dsync derives from non-ascii tables and columns are transliterated.
+pub fn transliterate(identifier: &str) -> String {
+    let mut ascii = String::with_capacity(identifier.len());
+    // code points are separated from the surrounding characters like words
+    let mut separate = false;
+    for c in identifier.chars() {
+        if separate && c != '_' {
+            ascii.push('_');
+        }
+        separate = false;
+
+        if c.is_ascii() {
+            ascii.push(c);
+            continue;
+        }
+
+        let lowercase = c.to_lowercase().next().unwrap_or(c);
+        let letters = match lowercase {
+            'à' | 'á' | 'â' | 'ã' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+            'ä' | 'æ' => "ae",
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+            'ď' | 'đ' | 'ð' => "d",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+            'ĥ' | 'ħ' => "h",
+            'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+            'ĵ' => "j",
+            'ķ' => "k",
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+            'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ō' | 'ŏ' | 'ő' => "o",
+            'ö' | 'ø' | 'œ' => "oe",
+            'ŕ' | 'ŗ' | 'ř' => "r",
+            'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+            'ß' => "ss",
+            'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+            'þ' => "th",
+            'ù' | 'ú' | 'û' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+            'ü' => "ue",
+            'ŵ' => "w",
+            'ý' | 'ÿ' | 'ŷ' => "y",
+            'ź' | 'ż' | 'ž' => "z",
+            _ => {
+                if !ascii.is_empty() && !ascii.ends_with('_') {
+                    ascii.push('_');
+                }
+                ascii.push_str(&format!("u{:x}", c as u32));
+                separate = true;
+                continue;
+            }
+        };
+
+        // uppercase letters stay uppercase, so that word boundaries of camelCase identifiers are kept
+        match c.is_uppercase() {
+            true => {
+                let mut chars = letters.chars();
+                ascii.extend(chars.next().map(|first| first.to_ascii_uppercase()));
+                ascii.push_str(chars.as_str());
+            }
+            false => ascii.push_str(letters),
+        }
+    }
+
+    ascii
+}
+
+/// name of the module holding a table's model, following the file naming; it's also the name of the model's directory
+/// (and so has to be ascii, see `transliterate`)
+pub fn module_name(table_name: &str, naming: FileNaming) -> String {
+    match naming {
+        FileNaming::Table if table_name.is_ascii() => table_name.to_string(),
+        FileNaming::Table => snake_case_identifier(table_name),
+        FileNaming::Singular => snake_case_identifier(table_name).to_singular(),
+        FileNaming::Pascal => transliterate(table_name).to_pascal_case().to_singular(),
+    }
+}
+
+/// the snake_case version of an identifier, which is transliterated if it isn't ascii (see `transliterate`)
+pub fn snake_case_identifier(identifier: &str) -> String {
+    if identifier.is_ascii() {
+        return identifier.to_snake_case();
+    }
+
+    // inflector would split the spelled out code points (`u540d` into `u_54_0d`)
+    let ascii = transliterate(identifier);
+    match ascii.chars().any(|c| c.is_uppercase()) {
+        true => ascii.to_snake_case(),
+        false => ascii,
+    }
+}
+
+fn doc_comments(attributes: &[ParsedAttribute]) -> Vec<&str> {
+    attributes
+        .iter()
+        .filter(|a| a.name == "doc")
+        .filter_map(|a| a.value.as_deref())
+        .collect()
+}
+
+type ForeignTableName = String;
 type JoinColumn = String;
 
 #[derive(Debug, Clone)]
@@ -49,67 +287,246 @@ pub struct ParsedJoinMacro {
     pub table1_columns: String,
 }
 
+/// parses the tables of a schema without generating any code
+pub fn parse_schema(
+    schema_file_contents: &str,
+    config: &GenerationConfig,
+) -> Result<Vec<ParsedTableMacro>, SchemaError> {
+    parse_tables(schema_file_contents, config).map_err(|e| e.with_source(schema_file_contents))
+}
+
 pub fn parse_and_generate_code(
     schema_file_contents: String,
     config: &GenerationConfig,
 ) -> anyhow::Result<Vec<ParsedTableMacro>> {
-    let schema_file = syn::parse_file(&schema_file_contents).unwrap();
+    let tables = parse_schema(&schema_file_contents, config)?;
+
+    generate_tables(tables, config)
+}
+
+/// gives a table whose struct name is already taken by one of the previous tables (table names only differing in
+/// plurality get the same struct names) the suffix of the config, or fails if collisions are errors
+pub fn disambiguate_struct_name(
+    previous_tables: &[ParsedTableMacro],
+    table: &mut ParsedTableMacro,
+    config: &GenerationConfig,
+) -> Result<(), String> {
+    let is_taken = |struct_name: &str| previous_tables.iter().any(|t| t.struct_name == struct_name);
+    let Some(other) = previous_tables
+        .iter()
+        .find(|t| t.struct_name == table.struct_name)
+    else {
+        return Ok(());
+    };
+
+    match config.struct_name_collisions {
+        StructNameCollisions::Warn => {}
+        StructNameCollisions::Error => {
+            return Err(format!(
+                "The '{}' and '{}' tables would both generate structs named `{}`",
+                other.name, table.name, table.struct_name
+            ))
+        }
+        StructNameCollisions::Suffix(suffix) => {
+            let mut struct_name = format!("{}{suffix}", table.struct_name);
+            let mut counter = 2;
+            while is_taken(&struct_name) {
+                struct_name = format!("{}{suffix}{counter}", table.struct_name);
+                counter += 1;
+            }
+            table.struct_name = struct_name;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_tables(
+    schema_file_contents: &str,
+    config: &GenerationConfig,
+) -> Result<Vec<ParsedTableMacro>, SchemaError> {
+    let schema_file = syn::parse_file(schema_file_contents).map_err(|e| {
+        SchemaError::new(
+            format!("Unsupported schema format! (could not parse rust file: {e})"),
+            e.span(),
+        )
+    })?;
 
     let mut tables: Vec<ParsedTableMacro> = vec![];
+    let mut same_query_groups: Vec<Vec<String>> = vec![];
 
     for item in schema_file.items {
         if let Macro(macro_item) = item {
-            let macro_identifier = macro_item
-                .mac
-                .path
-                .segments
-                .last()
-                .expect("could not read identifier for macro")
-                .ident
-                .to_string();
+            let macro_identifier = match macro_item.mac.path.segments.last() {
+                Some(segment) => segment.ident.to_string(),
+                None => {
+                    return Err(SchemaError::new(
+                        "Unsupported schema format! (could not read identifier for macro)",
+                        macro_item.mac.bang_token.span,
+                    ))
+                }
+            };
 
             match macro_identifier.as_str() {
                 "table" => {
-                    let parsed_table = handle_table_macro(macro_item, config);
+                    let macro_span = macro_item.mac.path.segments.last().unwrap().ident.span();
+                    let mut parsed_table = handle_table_macro(macro_item, config)?;
 
                     // make sure the table isn't ignored
-                    let table_options = config.table(parsed_table.name.to_string().as_str());
+                    let table_options = config.table(parsed_table.name.as_str());
                     if !table_options.get_ignore() {
+                        disambiguate_struct_name(&tables, &mut parsed_table, config)
+                            .map_err(|e| SchemaError::new(e, macro_span))?;
                         tables.push(parsed_table);
                     }
                 }
                 "joinable" => {
-                    let parsed_join = handle_joinable_macro(macro_item);
+                    let parsed_join = handle_joinable_macro(macro_item)?;
 
                     for table in tables.iter_mut() {
-                        if parsed_join
-                            .table1
-                            .to_string()
-                            .eq(table.name.to_string().as_str())
-                        {
+                        if parsed_join.table1.to_string().eq(table.name.as_str()) {
                             table.foreign_keys.push((
-                                parsed_join.table2.clone(),
+                                parsed_join.table2.to_string(),
                                 parsed_join.table1_columns.clone(),
                             ));
                             break;
                         }
                     }
                 }
+                "allow_tables_to_appear_in_same_query" => {
+                    same_query_groups.push(handle_allow_tables_macro(macro_item));
+                }
                 _ => {}
             };
         }
     }
 
     for table in tables.iter_mut() {
-        table.generated_code = code::generate_for_table(table.clone(), config);
+        for group in same_query_groups.iter() {
+            if group.contains(&table.name) {
+                table.same_query_tables.extend(
+                    group
+                        .iter()
+                        .filter(|t| **t != table.name && !table.same_query_tables.contains(t))
+                        .cloned()
+                        .collect::<Vec<String>>(),
+                );
+            }
+        }
     }
 
     Ok(tables)
 }
 
-fn handle_joinable_macro(macro_item: syn::ItemMacro) -> ParsedJoinMacro {
+pub fn generate_tables(
+    tables: Vec<ParsedTableMacro>,
+    config: &GenerationConfig,
+) -> anyhow::Result<Vec<ParsedTableMacro>> {
+    Ok(generate_tables_with(tables, HashMap::new(), config)?
+        .into_iter()
+        .map(|(table, _)| table)
+        .collect())
+}
+
+/// like `generate_tables`, but takes the code of the tables in `generated_code` (by table name) from there instead of
+/// rendering it, and returns how long rendering each table took; fails if the options can't be rendered (see
+/// `validate::validate_generation`)
+pub fn generate_tables_with(
+    mut tables: Vec<ParsedTableMacro>,
+    generated_code: HashMap<String, String>,
+    config: &GenerationConfig,
+) -> anyhow::Result<Vec<(ParsedTableMacro, Duration)>> {
+    validate::validate_generation(&tables, config)?;
+
+    for table in tables.iter() {
+        log::info!(
+            "parsed table `{}` ({} columns, primary key: {})",
+            table.name,
+            table.columns.len(),
+            match table.primary_key_columns.is_empty() {
+                true => "none".to_string(),
+                false => table.primary_key_column_names().join(", "),
+            }
+        );
+        log::debug!(
+            "table `{}`: options {}",
+            table.name,
+            applied_options(&config.table(&table.name))
+        );
+    }
+
+    // tables are rendered independently of each other, so we can do this in parallel; the code is buffered (rather
+    // than streamed like `write_table_code` allows) because it's compared with the existing files and cached
+    let parsed_tables = tables.clone();
+    let durations = tables
+        .par_iter_mut()
+        .map(|table| {
+            if let Some(code) = generated_code.get(&table.name) {
+                table.generated_code = code.clone();
+                return Ok(Duration::ZERO);
+            }
+
+            let start = Instant::now();
+            let mut code = Vec::new();
+            write_table_code(&mut code, table, &parsed_tables, config)
+                .with_context(|| format!("Could not render the code of table '{}'", table.name))?;
+            table.generated_code = String::from_utf8(code).with_context(|| {
+                format!(
+                    "The generated code of table '{}' isn't valid utf-8",
+                    table.name
+                )
+            })?;
+            Ok(start.elapsed())
+        })
+        .collect::<anyhow::Result<Vec<Duration>>>()?;
+
+    Ok(tables.into_iter().zip(durations).collect())
+}
+
+/// renders the code of a table (what ends up in its `generated.rs`) into `out`
+pub fn write_table_code(
+    out: &mut impl Write,
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> io::Result<()> {
+    match config.table(&table.name).get_target() {
+        Target::Diesel => code::generate_for_table(out, table.clone(), tables, config),
+        Target::SeaOrm => seaorm::generate_for_table(out, table.clone(), tables, config),
+        Target::Sqlx => sqlx::generate_for_table(out, table.clone(), config),
+    }
+}
+
+/// the options of a table which are set, as `name=value` pairs (for logging)
+fn applied_options(options: &TableOptions) -> String {
+    let Ok(serde_json::Value::Object(options)) = serde_json::to_value(options) else {
+        return "".to_string();
+    };
+
+    options
+        .into_iter()
+        .filter(|(_, value)| !value.is_null() && value != &serde_json::json!([]))
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn handle_allow_tables_macro(macro_item: syn::ItemMacro) -> Vec<String> {
+    macro_item
+        .mac
+        .tokens
+        .into_iter()
+        .filter_map(|item| match item {
+            proc_macro2::TokenTree::Ident(ident) => Some(ident.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn handle_joinable_macro(macro_item: syn::ItemMacro) -> Result<ParsedJoinMacro, SchemaError> {
     // println!("joinable! macro: {:#?}", macro_item);
 
+    let macro_span = macro_item.mac.path.segments.last().unwrap().ident.span();
     let mut table1_name: Option<Ident> = None;
     let mut table2_name: Option<Ident> = None;
     let mut table2_join_column: Option<String> = None;
@@ -125,7 +542,10 @@ fn handle_joinable_macro(macro_item: syn::ItemMacro) -> ParsedJoinMacro {
             }
             proc_macro2::TokenTree::Group(group) => {
                 if table1_name.is_none() || table2_name.is_none() {
-                    panic!("Unsupported schema format! (encountered join column group too early)");
+                    return Err(SchemaError::new(
+                        "Unsupported schema format! (encountered join column group too early)",
+                        group.span(),
+                    ));
                 } else {
                     table2_join_column = Some(group.stream().to_string());
                 }
@@ -134,33 +554,81 @@ fn handle_joinable_macro(macro_item: syn::ItemMacro) -> ParsedJoinMacro {
         }
     }
 
-    ParsedJoinMacro {
-        table1: table1_name
-            .expect("Unsupported schema format! (could not determine first join table name)"),
-        table2: table2_name
-            .expect("Unsupported schema format! (could not determine second join table name)"),
-        table1_columns: table2_join_column
-            .expect("Unsupported schema format! (could not determine join column name)"),
-    }
+    Ok(ParsedJoinMacro {
+        table1: table1_name.ok_or_else(|| {
+            SchemaError::new(
+                "Unsupported schema format! (could not determine first join table name)",
+                macro_span,
+            )
+        })?,
+        table2: table2_name.ok_or_else(|| {
+            SchemaError::new(
+                "Unsupported schema format! (could not determine second join table name)",
+                macro_span,
+            )
+        })?,
+        table1_columns: table2_join_column.ok_or_else(|| {
+            SchemaError::new(
+                "Unsupported schema format! (could not determine join column name)",
+                macro_span,
+            )
+        })?,
+    })
 }
 
-fn handle_table_macro(macro_item: syn::ItemMacro, _config: &GenerationConfig) -> ParsedTableMacro {
+fn handle_table_macro(
+    macro_item: syn::ItemMacro,
+    config: &GenerationConfig,
+) -> Result<ParsedTableMacro, SchemaError> {
+    let macro_span = macro_item.mac.path.segments.last().unwrap().ident.span();
     let mut table_name_ident: Option<Ident> = None;
-    let mut table_primary_key_idents: Vec<Ident> = vec![];
+    let mut table_schema: Option<String> = None;
+    let mut table_primary_key_idents: Vec<String> = vec![];
     let mut table_columns: Vec<ParsedColumnMacro> = vec![];
+    let mut table_attributes: Vec<ParsedAttribute> = vec![];
+    let mut expecting_attribute = false;
+    let mut in_use_stmt = false;
 
     for item in macro_item.mac.tokens.into_iter() {
+        if in_use_stmt {
+            // skip `use diesel::sql_types::*;` and the like until the end of the statement
+            if let proc_macro2::TokenTree::Punct(punct) = &item {
+                in_use_stmt = punct.as_char() != ';';
+            }
+            continue;
+        }
+
         match &item {
+            proc_macro2::TokenTree::Ident(ident) if ident == "use" => {
+                in_use_stmt = true;
+            }
             proc_macro2::TokenTree::Ident(ident) => {
                 table_name_ident = Some(ident.clone());
             }
+            proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                expecting_attribute = true;
+            }
+            // schema-qualified tables (`billing.invoices`): the table's name follows
+            proc_macro2::TokenTree::Punct(punct)
+                if punct.as_char() == '.'
+                    && table_schema.is_none()
+                    && table_name_ident.is_some() =>
+            {
+                table_schema = table_name_ident.take().map(|schema| schema.to_string());
+            }
+            proc_macro2::TokenTree::Group(group)
+                if expecting_attribute && group.delimiter() == proc_macro2::Delimiter::Bracket =>
+            {
+                table_attributes.push(parse_attribute(group)?);
+                expecting_attribute = false;
+            }
             proc_macro2::TokenTree::Group(group) => {
                 if group.delimiter() == proc_macro2::Delimiter::Parenthesis {
                     // primary keys group
                     // println!("GROUP-keys {:#?}", group);
                     for key_token in group.stream().into_iter() {
                         if let proc_macro2::TokenTree::Ident(ident) = key_token {
-                            table_primary_key_idents.push(ident)
+                            table_primary_key_idents.push(ident.to_string())
                         }
                     }
                 } else if group.delimiter() == proc_macro2::Delimiter::Brace {
@@ -171,79 +639,280 @@ fn handle_table_macro(macro_item: syn::ItemMacro, _config: &GenerationConfig) ->
                     let mut column_type: Option<Ident> = None;
                     let mut column_nullable: bool = false;
                     let mut column_unsigned: bool = false;
+                    // `Array`, `Range` and `Multirange` wrapping the column's type (along with the `Nullable`s inside
+                    // of them), outermost first
+                    let mut column_type_wrappers: Vec<Ident> = vec![];
+                    let mut column_attributes: Vec<ParsedAttribute> = vec![];
+                    let mut expecting_column_attribute = false;
 
                     for column_tokens in group.stream().into_iter() {
                         match column_tokens {
+                            proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                                expecting_column_attribute = true;
+                            }
+                            proc_macro2::TokenTree::Group(group) if expecting_column_attribute && group.delimiter() == proc_macro2::Delimiter::Bracket => {
+                                column_attributes.push(parse_attribute(&group)?);
+                                expecting_column_attribute = false;
+                            }
                             proc_macro2::TokenTree::Ident(ident) => {
                                 if column_name.is_none() {
                                     column_name = Some(ident.clone());
                                 } else if ident.to_string().eq_ignore_ascii_case("Nullable") {
-                                    column_nullable = true;
+                                    match column_type_wrappers.is_empty() {
+                                        true => column_nullable = true,
+                                        // nullable elements (like `Array<Nullable<Text>>`)
+                                        false => column_type_wrappers.push(ident.clone()),
+                                    }
                                 } else if ident.to_string().eq_ignore_ascii_case("Unsigned") {
                                     column_unsigned = true;
+                                } else if SQL_TYPE_WRAPPERS.contains(&ident.to_string().as_str()) {
+                                    column_type_wrappers.push(ident.clone());
                                 } else {
                                     column_type = Some(ident.clone());
                                 }
                             }
                             proc_macro2::TokenTree::Punct(punct) => {
                                 let char = punct.as_char();
-                                if char == '-' || char == '>' {
+                                if char == '-' && column_type.is_some() {
+                                    // an arrow after a column's type: the column wasn't followed by `,` (the identifier
+                                    // taken for its type is the name of the next column)
+                                    return Err(SchemaError::new(
+                                        format!(
+                                            "Unsupported schema format! (column '{}' isn't followed by `,`)",
+                                            column_name.map(|name| name.to_string()).unwrap_or_default()
+                                        ),
+                                        column_type.map_or(punct.span(), |ty| ty.span()),
+                                    ));
+                                } else if char == '-' || char == '>' {
                                     // nothing for arrow
                                     continue;
                                 } else if char == ',' && column_name.is_some() && column_type.is_some() {
                                     // end of column def!
-
-                                    // add the column
-                                    table_columns.push(ParsedColumnMacro {
-                                        name: column_name.expect("Unsupported schema format! (Invalid column name syntax)"),
-                                        ty: schema_type_to_rust_type(column_type.expect("Unsupported schema format! (Invalid column type syntax)").to_string()),
-                                        is_nullable: column_nullable,
-                                        is_unsigned: column_unsigned,
-                                    });
+                                    table_columns.push(parse_column(
+                                        column_name.take().unwrap(),
+                                        (column_type.take().unwrap(), std::mem::take(&mut column_type_wrappers)),
+                                        column_nullable,
+                                        column_unsigned,
+                                        std::mem::take(&mut column_attributes),
+                                        table_name_ident.as_ref(),
+                                        config,
+                                    )?);
 
                                     // reset the properties
-                                    column_name = None;
-                                    column_type = None;
                                     column_unsigned = false;
                                     column_nullable = false;
                                 }
                             }
-                            _ => panic!("Unsupported schema format! (Invalid column definition token in diesel table macro)")
+                            other => return Err(SchemaError::new("Unsupported schema format! (Invalid column definition token in diesel table macro)", other.span()))
                         }
                     }
 
-                    if column_name.is_some() || column_type.is_some() || column_nullable || column_unsigned {
-                        // looks like a column was in the middle of being parsed, let's panic!
-                        panic!(
-                            "Unsupported schema format! (It seems a column was partially defined)"
-                        );
+                    if column_name.is_some() && column_type.is_some() {
+                        // the last column doesn't need a trailing `,`
+                        table_columns.push(parse_column(
+                            column_name.take().unwrap(),
+                            (
+                                column_type.take().unwrap(),
+                                std::mem::take(&mut column_type_wrappers),
+                            ),
+                            column_nullable,
+                            column_unsigned,
+                            std::mem::take(&mut column_attributes),
+                            table_name_ident.as_ref(),
+                            config,
+                        )?);
+                        column_unsigned = false;
+                        column_nullable = false;
+                    }
+
+                    if column_name.is_some()
+                        || column_type.is_some()
+                        || column_nullable
+                        || column_unsigned
+                        || !column_type_wrappers.is_empty()
+                        || !column_attributes.is_empty()
+                    {
+                        // looks like a column was in the middle of being parsed
+                        return Err(SchemaError::new(
+                            "Unsupported schema format! (It seems a column was partially defined)",
+                            column_name
+                                .or(column_type)
+                                .map(|i| i.span())
+                                .unwrap_or_else(|| group.span_close()),
+                        ));
                     }
                 } else {
-                    panic!("Unsupported schema format! (Invalid delimiter in diesel table macro group)")
+                    return Err(SchemaError::new(
+                        "Unsupported schema format! (Invalid delimiter in diesel table macro group)",
+                        group.span(),
+                    ));
                 }
             }
-            _ => {
-                panic!("Unsupported schema format! (Invalid token tree item in diesel table macro)")
+            other => {
+                return Err(SchemaError::new(
+                    "Unsupported schema format! (Invalid token tree item in diesel table macro)",
+                    other.span(),
+                ))
             }
         }
     }
 
-    ParsedTableMacro {
-        name: table_name_ident
-            .clone()
-            .expect("Unsupported schema format! (Could not extract table name from schema file)"),
-        struct_name: table_name_ident
-            .unwrap()
-            .to_string()
-            .to_pascal_case()
-            .to_singular(),
+    let table_name = table_name_ident
+        .ok_or_else(|| {
+            SchemaError::new(
+                "Unsupported schema format! (Could not extract table name from schema file)",
+                macro_span,
+            )
+        })?
+        .to_string();
+
+    Ok(ParsedTableMacro {
+        struct_name: transliterate(&table_name).to_pascal_case().to_singular(),
+        name: table_name,
         columns: table_columns,
         primary_key_columns: table_primary_key_idents,
         foreign_keys: vec![],
+        same_query_tables: vec![],
+        attributes: table_attributes,
+        schema: table_schema,
         generated_code: format!(
             "{FILE_SIGNATURE}\n\nFATAL ERROR: nothing was generated; this shouldn't be possible."
         ),
+    })
+}
+
+/// a column of a `table!` macro, with the rust type of its sql type
+fn parse_column(
+    name: Ident,
+    // the type and the wrappers around it, outermost first
+    (ty, wrappers): (Ident, Vec<Ident>),
+    is_nullable: bool,
+    is_unsigned: bool,
+    attributes: Vec<ParsedAttribute>,
+    table_name: Option<&Ident>,
+    config: &GenerationConfig,
+) -> Result<ParsedColumnMacro, SchemaError> {
+    let table_options = config.table(&table_name.map(|t| t.to_string()).unwrap_or_default());
+    // the wrapped type as written in the schema, like `Array<Nullable<Text>>`
+    let sql_type = wrappers
+        .iter()
+        .rev()
+        .fold(ty.to_string(), |inner, wrapper| {
+            format!("{wrapper}<{inner}>")
+        });
+    let mapping = column_type_mapping(&sql_type, &table_options, config).ok_or_else(|| {
+        SchemaError::new(format!("Unsupported schema format! (unknown column type '{sql_type}'; map it to a rust type with a type override, or please report this!)"), ty.span())
+    })?;
+
+    Ok(ParsedColumnMacro {
+        name: name.to_string(),
+        ty: mapping.rust_type,
+        type_imports: mapping.imports,
+        serde_attributes: mapping.serde_attributes,
+        sql_type,
+        is_nullable,
+        is_unsigned,
+        attributes,
+    })
+}
+
+fn parse_attribute(group: &proc_macro2::Group) -> Result<ParsedAttribute, SchemaError> {
+    let mut tokens = group.stream().into_iter();
+
+    let name = match tokens.next() {
+        Some(proc_macro2::TokenTree::Ident(ident)) => ident.to_string(),
+        _ => {
+            return Err(SchemaError::new(
+                "Unsupported schema format! (Invalid attribute in diesel table macro)",
+                group.span(),
+            ))
+        }
+    };
+
+    let value = match tokens.next() {
+        None => None,
+        Some(proc_macro2::TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+            let value = tokens.collect::<proc_macro2::TokenStream>();
+            Some(
+                syn::parse2::<syn::LitStr>(value.clone())
+                    .map(|lit| lit.value())
+                    .unwrap_or_else(|_| value.to_string()),
+            )
+        }
+        Some(proc_macro2::TokenTree::Group(args)) => Some(args.stream().to_string()),
+        // we don't understand this attribute, but it doesn't affect generation, so preserve it as-is
+        Some(other) => Some(
+            std::iter::once(other)
+                .chain(tokens)
+                .collect::<proc_macro2::TokenStream>()
+                .to_string(),
+        ),
+    };
+
+    Ok(ParsedAttribute { name, value })
+}
+
+/// the mapping of a sql type: the table's type override, else the first type mapper knowing it (else the default one)
+pub fn column_type_mapping(
+    sql_type: &str,
+    table_options: &TableOptions,
+    config: &GenerationConfig,
+) -> Option<TypeMapping> {
+    if let Some(rust_type) = table_options.get_type_override(sql_type) {
+        return Some(TypeMapping::new(rust_type));
+    }
+
+    config
+        .type_mappers
+        .iter()
+        .find_map(|mapper| mapper.map(sql_type))
+        .or_else(|| DieselTypeMapper.map(sql_type))
+        .or_else(|| wrapped_type_mapping(sql_type, table_options, config))
+}
+
+/// the sql types wrapping other sql types (see `wrapped_type_mapping`)
+const SQL_TYPE_WRAPPERS: &[&str] = &["Array", "Range", "Multirange"];
+
+/// the mapping of a wrapped sql type (like `Array<Nullable<Text>>`), built from the mapping of the type it wraps:
+/// arrays are vectors, ranges pairs of bounds and multiranges vectors of them; `None` for other types, and for
+/// wrapped types mapped with serde attributes (which don't apply to the wrapping type)
+fn wrapped_type_mapping(
+    sql_type: &str,
+    table_options: &TableOptions,
+    config: &GenerationConfig,
+) -> Option<TypeMapping> {
+    let (wrapper, inner) = sql_type.strip_suffix('>')?.split_once('<')?;
+    let inner_mapping = match inner
+        .strip_prefix("Nullable<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        Some(element) => {
+            let mapping = column_type_mapping(element, table_options, config)?;
+            TypeMapping {
+                rust_type: format!("Option<{}>", mapping.rust_type),
+                ..mapping
+            }
+        }
+        None => column_type_mapping(inner, table_options, config)?,
+    };
+    if !inner_mapping.serde_attributes.is_empty() {
+        return None;
     }
+
+    let inner_type = &inner_mapping.rust_type;
+    let bounds =
+        format!("(std::collections::Bound<{inner_type}>, std::collections::Bound<{inner_type}>)");
+    let rust_type = match wrapper {
+        "Array" => format!("Vec<{inner_type}>"),
+        "Range" => bounds,
+        "Multirange" => format!("Vec<{bounds}>"),
+        _ => return None,
+    };
+
+    Some(TypeMapping {
+        rust_type,
+        ..inner_mapping
+    })
 }
 
 // A function to translate diesel schema types into rust types
@@ -253,12 +922,10 @@ fn handle_table_macro(macro_item: syn::ItemMacro, _config: &GenerationConfig) ->
 //
 // The docs page for sql_types is comprehensive but it hides some alias types like Int4, Float8, etc.:
 // https://docs.rs/diesel/latest/diesel/sql_types/index.html
-fn schema_type_to_rust_type(schema_type: String) -> String {  
-    match schema_type.to_lowercase().as_str() {
-        "unsigned" => panic!("Unsigned types are not yet supported, please open an issue if you need this feature!"), // TODO: deal with this later
-        "inet" => panic!("Unsigned types are not yet supported, please open an issue if you need this feature!"), // TODO: deal with this later
-        "cidr" => panic!("Unsigned types are not yet supported, please open an issue if you need this feature!"), // TODO: deal with this later
-
+//
+// Returns `None` if the type is unknown (wrapped types like `Array<Text>` are mapped by `column_type_mapping`)
+pub fn schema_type_to_rust_type(schema_type: String) -> Option<String> {
+    let rust_type = match schema_type.to_lowercase().as_str() {
         // boolean
         "bool" => "bool",
 
@@ -269,22 +936,26 @@ fn schema_type_to_rust_type(schema_type: String) -> String {
         "int2" => "i16",
         "int4" => "i32",
         "int4range" => "(std::collections::Bound<i32>, std::collections::Bound<i32>)",
+        "int4multirange" => "Vec<(std::collections::Bound<i32>, std::collections::Bound<i32>)>",
         "integer" => "i32",
         "serial" => "i32",
         "bigint" => "i64",
         "bigserial" => "i64",
         "int8" => "i64",
         "int8range" => "(std::collections::Bound<i64>, std::collections::Bound<i64>)",
+        "int8multirange" => "Vec<(std::collections::Bound<i64>, std::collections::Bound<i64>)>",
         "float" => "f32",
         "float4" => "f32",
         "double" => "f64",
         "float8" => "f64",
         "numeric" => "bigdecimal::BigDecimal",
         "numrange" => "(std::collections::Bound<bigdecimal::BigDecimal>, std::collections::Bound<bigdecimal::BigDecimal>)",
+        "nummultirange" => "Vec<(std::collections::Bound<bigdecimal::BigDecimal>, std::collections::Bound<bigdecimal::BigDecimal>)>",
         "decimal" => "bigdecimal::BigDecimal",
 
         // string
         "text" => "String",
+        "citext" => "String",
         "varchar" => "String",
         "bpchar" => "String",
         "char" => "String",
@@ -305,28 +976,47 @@ fn schema_type_to_rust_type(schema_type: String) -> String {
         // date & time
         "date" => "chrono::NaiveDate",
         "daterange" => "(std::collections::Bound<chrono::NaiveDate>, std::collections::Bound<chrono::NaiveDate>)",
+        "datemultirange" => "Vec<(std::collections::Bound<chrono::NaiveDate>, std::collections::Bound<chrono::NaiveDate>)>",
         "datetime" => "chrono::NaiveDateTime",
         "time" => "chrono::NaiveTime",
         "timestamp" => "chrono::NaiveDateTime",
         "tsrange" => "(std::collections::Bound<chrono::NaiveDateTime>, std::collections::Bound<chrono::NaiveDateTime>)",
+        "tsmultirange" => "Vec<(std::collections::Bound<chrono::NaiveDateTime>, std::collections::Bound<chrono::NaiveDateTime>)>",
         "timestamptz" => "chrono::DateTime<chrono::Utc>",
         "timestamptzsqlite" => "chrono::DateTime<chrono::Utc>",
         "tstzrange" => "(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)",
+        "tstzmultirange" => "Vec<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>",
 
         // json
         "json" => "serde::Value",
         "jsonb" => "serde_json::Value",
 
+        // full-text search (see https://github.com/diesel-rs/diesel_full_text_search)
+        // note: these columns can't be serialized or inserted, so they're left out of generated structs
+        "tsvector" => "diesel_full_text_search::PgTsVector",
+
+        // spatial (see https://github.com/vitaly-m/postgis-diesel); the container holds any kind of 2D geometry
+        #[cfg(feature = "postgis")]
+        "geometry" => "postgis_diesel::types::GeometryContainer<postgis_diesel::types::Point>",
+        #[cfg(feature = "postgis")]
+        "geography" => "postgis_diesel::types::GeometryContainer<postgis_diesel::types::Point>",
+
         // misc
         "uuid" => "uuid::Uuid",
-        "interval" => "PgInterval",
+        // these diesel types don't implement serde's traits, so the models serialize them through `pg_serde`
+        "interval" => "diesel::data_types::PgInterval",
+        "money" => "diesel::data_types::PgMoney",
         "oid" => "u32",
-        "money" => "PgMoney",
+
+        // network (`ipnet::IpNet` works too, with diesel's `ipnet-address` feature and a type override)
+        "inet" => "ipnetwork::IpNetwork",
+        "cidr" => "ipnetwork::IpNetwork",
         "macaddr" => "[u8; 6]",
-        // "inet" => "either ipnetwork::IpNetwork or ipnet::IpNet (TODO)",
-        // "cidr" => "either ipnetwork::IpNetwork or ipnet::IpNet (TODO)",
+        "macaddr8" => "[u8; 8]",
+
+        // no type is found (this means generation is broken for this particular schema)
+        _ => return None,
+    };
 
-        // panic if no type is found (this means generation is broken for this particular schema)
-        _ => panic!("Unknown type found '{schema_type}', please report this!")
-    }.to_string()
+    Some(rust_type.to_string())
 }
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use crate::code::struct_fields;
+use crate::parser::{ParsedColumnMacro, ParsedTableMacro};
+use crate::GenerationConfig;
+
+/// Options for the generated `.proto` file
+#[derive(Debug, Clone, Default)]
+pub struct ProtoConfig<'a> {
+    /// the protobuf package of the generated messages (for example: `my_app.models`)
+    pub package: &'a str,
+    /// protobuf types overriding the default mapping, keyed by diesel sql type (for example: `("Int8", "sint64")`)
+    pub type_overrides: HashMap<&'a str, &'a str>,
+}
+
+/// the default protobuf type for values of a rust type
+fn proto_type(rust_type: &str) -> &'static str {
+    match rust_type {
+        "bool" => "bool",
+        "i8" | "i16" | "i32" => "int32",
+        "u8" | "u16" | "u32" => "uint32",
+        "i64" => "int64",
+        "u64" => "uint64",
+        "f32" => "float",
+        "f64" => "double",
+        "Vec<u8>" | "[u8; 6]" => "bytes",
+        "chrono::NaiveDateTime" | "chrono::DateTime<chrono::Utc>" => "google.protobuf.Timestamp",
+        "serde_json::Value" => "google.protobuf.Value",
+        // strings, and types without a protobuf counterpart (like dates, uuids and decimals) in their text form
+        _ => "string",
+    }
+}
+
+/// well-known protobuf types which need to be imported
+const WELL_KNOWN_TYPES: &[(&str, &str)] = &[
+    (
+        "google.protobuf.Timestamp",
+        "google/protobuf/timestamp.proto",
+    ),
+    ("google.protobuf.Value", "google/protobuf/struct.proto"),
+];
+
+/// renders protobuf messages mirroring the Read, Create and Update structs of every table
+pub fn render(
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+    proto_config: &ProtoConfig,
+) -> String {
+    let mut messages = vec![];
+    let mut used_types = vec![];
+
+    for table in tables.iter() {
+        for (message_name, fields) in struct_fields(table, config) {
+            if fields.is_empty() {
+                continue;
+            }
+
+            let mut lines = vec![];
+            for (index, field) in fields.iter().enumerate() {
+                let column = table
+                    .column(&field.column_name)
+                    .expect("Struct field doesn't map to a column");
+                let ty = proto_config
+                    .type_overrides
+                    .get(column.sql_type.as_str())
+                    .copied()
+                    .unwrap_or_else(|| proto_type(&column_type_without_option(column)));
+                if !used_types.contains(&ty) {
+                    used_types.push(ty);
+                }
+
+                for doc_comment in field.doc_comments.iter() {
+                    lines.push(format!("  //{doc_comment}"));
+                }
+                lines.push(format!(
+                    "  {optional}{ty} {name} = {number};",
+                    optional = if field.is_optional || column.is_nullable {
+                        "optional "
+                    } else {
+                        ""
+                    },
+                    name = field.name.trim_start_matches("r#"),
+                    number = index + 1
+                ));
+            }
+
+            messages.push(format!(
+                "message {message_name} {{\n{}\n}}\n",
+                lines.join("\n")
+            ));
+        }
+    }
+
+    let imports = WELL_KNOWN_TYPES
+        .iter()
+        .filter(|(ty, _)| used_types.contains(ty))
+        .map(|(_, file)| format!("import \"{file}\";\n"))
+        .collect::<String>();
+
+    format!(
+        "// This file is generated and managed by dsync\n\nsyntax = \"proto3\";\n\npackage {package};\n\n{imports}{newline}{messages}",
+        package = proto_config.package,
+        newline = if imports.is_empty() { "" } else { "\n" },
+        messages = messages.join("\n")
+    )
+}
+
+/// the rust type of a column's values (nullability is expressed with `optional` instead)
+fn column_type_without_option(column: &ParsedColumnMacro) -> String {
+    match column.is_unsigned {
+        true => column.ty.replace('i', "u"),
+        false => column.ty.clone(),
+    }
+}
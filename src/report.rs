@@ -0,0 +1,95 @@
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// What happened to a file during generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    /// the file didn't exist before and was written
+    Created,
+    /// the file existed and its contents changed
+    Updated,
+    /// the file existed and its contents were already up-to-date (nothing was written)
+    Unchanged,
+    /// the file belonged to a table which no longer exists and was pruned
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub status: FileStatus,
+}
+
+/// Summary of a `generate_files` run, useful for wiring dsync into larger codegen orchestration
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GenerationReport {
+    /// names of the tables which were parsed and generated
+    pub tables: Vec<String>,
+    pub files: Vec<FileChange>,
+    pub warnings: Vec<String>,
+    /// names of the tables whose code was reused from the last run, as they didn't change (see
+    /// `GenerationConfig::cache`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_tables: Vec<String>,
+    /// human-readable changes of the schema since the last snapshot (see `sync_snapshot`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub schema_changes: Vec<String>,
+    pub duration_ms: u128,
+    /// how long the phases of generating the models took (not measured by the other runs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+}
+
+/// Durations of the phases of generating models, in milliseconds
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Timings {
+    /// parsing the schema (or importing the IR)
+    pub parse_ms: f64,
+    /// rendering the code of the tables (in parallel, so the durations of the tables can add up to more)
+    pub render_ms: f64,
+    /// writing the files of the tables and the modules next to them (including pruning removed tables)
+    pub write_ms: f64,
+    /// rendering the code of each table, slowest first (tables whose code was reused from the cache took 0)
+    pub tables: Vec<TableTiming>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TableTiming {
+    pub table: String,
+    pub render_ms: f64,
+}
+
+impl GenerationReport {
+    pub fn count(&self, status: FileStatus) -> usize {
+        self.files.iter().filter(|f| f.status == status).count()
+    }
+
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Summary {
+            created: usize,
+            updated: usize,
+            unchanged: usize,
+            deleted: usize,
+        }
+
+        #[derive(Serialize)]
+        struct Json<'a> {
+            #[serde(flatten)]
+            report: &'a GenerationReport,
+            summary: Summary,
+        }
+
+        serde_json::to_string_pretty(&Json {
+            report: self,
+            summary: Summary {
+                created: self.count(FileStatus::Created),
+                updated: self.count(FileStatus::Updated),
+                unchanged: self.count(FileStatus::Unchanged),
+                deleted: self.count(FileStatus::Deleted),
+            },
+        })
+        .expect("Could not serialize generation report")
+    }
+}
@@ -0,0 +1,480 @@
+use anyhow::{bail, Context};
+use inflector::Inflector;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+
+use crate::parser::{ParsedAttribute, ParsedColumnMacro, ParsedTableMacro, FILE_SIGNATURE};
+
+/// a model struct annotated with `#[diesel(table_name = ...)]`
+struct ModelStruct<'a> {
+    name: String,
+    table_name: String,
+    /// whether the struct derives `Selectable` (which makes it the struct describing all of the table's columns)
+    is_selectable: bool,
+    item: &'a syn::ItemStruct,
+}
+
+/// splits a token stream on top-level commas
+fn split_commas(tokens: TokenStream) -> Vec<TokenStream> {
+    let mut parts = vec![TokenStream::new()];
+    for token in tokens {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => parts.push(TokenStream::new()),
+            _ => parts
+                .last_mut()
+                .expect("there's always a part")
+                .extend([token]),
+        }
+    }
+
+    parts.into_iter().filter(|p| !p.is_empty()).collect()
+}
+
+/// renders tokens without the spaces proc_macro2 puts between them (string literals are unquoted)
+fn token_text(tokens: TokenStream) -> String {
+    syn::parse2::<syn::LitStr>(tokens.clone())
+        .map(|lit| lit.value())
+        .unwrap_or_else(|_| tokens.to_string().replace(' ', ""))
+}
+
+/// the arguments of `#[diesel(...)]` attributes as `(name, values)`: `name = value` has one value, `name(a, b)` has
+/// a value per argument and a bare `name` has none
+fn diesel_args(attributes: &[syn::Attribute]) -> Vec<(String, Vec<String>)> {
+    let mut args = vec![];
+
+    for attribute in attributes.iter().filter(|a| a.path.is_ident("diesel")) {
+        let Some(TokenTree::Group(group)) = attribute.tokens.clone().into_iter().next() else {
+            continue;
+        };
+
+        for arg in split_commas(group.stream()) {
+            let mut tokens = arg.into_iter();
+            let Some(TokenTree::Ident(name)) = tokens.next() else {
+                continue;
+            };
+
+            let values = match tokens.next() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+                    vec![token_text(tokens.collect())]
+                }
+                Some(TokenTree::Group(group)) => split_commas(group.stream())
+                    .into_iter()
+                    .map(token_text)
+                    .collect(),
+                _ => vec![],
+            };
+            args.push((name.to_string(), values));
+        }
+    }
+
+    args
+}
+
+fn derives(attributes: &[syn::Attribute], name: &str) -> bool {
+    attributes
+        .iter()
+        .filter(|a| a.path.is_ident("derive"))
+        .any(|a| {
+            token_text(a.tokens.clone())
+                .trim_matches(|c| c == '(' || c == ')')
+                .split(',')
+                .any(|derive| derive.rsplit("::").next() == Some(name))
+        })
+}
+
+fn doc_attributes(attributes: &[syn::Attribute]) -> Vec<ParsedAttribute> {
+    attributes
+        .iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .filter_map(|a| match a.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(doc),
+                ..
+            })) => Some(ParsedAttribute {
+                name: "doc".to_string(),
+                value: Some(doc.value()),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// the diesel sql type for values of a rust type (without `Option`); `newtypes` maps the names of newtype structs
+/// (like `--newtype-ids` keys) to the sql type they're declared with
+fn infer_sql_type(rust_type: &str, newtypes: &[(String, String)]) -> Option<String> {
+    let name = rust_type.rsplit("::").next().unwrap_or(rust_type);
+    if let Some((_, sql_type)) = newtypes.iter().find(|(newtype, _)| newtype == name) {
+        return Some(sql_type.clone());
+    }
+
+    let mut rust_type = rust_type.to_string();
+    for prefix in [
+        "chrono::",
+        "uuid::",
+        "serde_json::",
+        "bigdecimal::",
+        "std::string::",
+        "std::collections::",
+    ] {
+        rust_type = rust_type.replace(prefix, "");
+    }
+
+    let sql_type = match rust_type.as_str() {
+        "bool" => "Bool",
+        "i16" => "Int2",
+        "i32" => "Int4",
+        "i64" => "Int8",
+        "u16" => "Unsigned<Int2>",
+        "u32" => "Unsigned<Int4>",
+        "u64" => "Unsigned<Int8>",
+        "f32" => "Float4",
+        "f64" => "Float8",
+        "String" => "Text",
+        "Vec<u8>" => "Bytea",
+        "NaiveDate" => "Date",
+        "NaiveTime" => "Time",
+        "NaiveDateTime" => "Timestamp",
+        "DateTime<Utc>" => "Timestamptz",
+        "Uuid" => "Uuid",
+        "Value" => "Jsonb",
+        "BigDecimal" => "Numeric",
+        _ => {
+            if let Some(bounds) = rust_type
+                .strip_prefix("(Bound<")
+                .and_then(|t| t.strip_suffix(">)"))
+            {
+                let (start, end) = bounds.split_once(">,Bound<")?;
+                if start != end {
+                    return None;
+                }
+                return Some(format!("Range<{}>", infer_sql_type(start, newtypes)?));
+            }
+
+            let element = rust_type.strip_prefix("Vec<")?.strip_suffix('>')?;
+            let element = nullable_sql_type(element, newtypes)?.0;
+            return Some(match element.starts_with("Range<") {
+                true => format!(
+                    "Multirange<{}>",
+                    &element["Range<".len()..element.len() - 1]
+                ),
+                false => format!("Array<{element}>"),
+            });
+        }
+    };
+
+    Some(sql_type.to_string())
+}
+
+/// strips `Option<...>` from a rust type
+fn strip_option(rust_type: &str) -> Option<&str> {
+    rust_type
+        .strip_prefix("Option<")
+        .and_then(|t| t.strip_suffix('>'))
+}
+
+/// the diesel sql type of a rust type, wrapped in `Nullable<...>` for options, along with its nullability (the
+/// `Option<Option<T>>` of changeset structs is a nullable `T`, too)
+fn nullable_sql_type(rust_type: &str, newtypes: &[(String, String)]) -> Option<(String, bool)> {
+    match strip_option(rust_type) {
+        Some(inner) => {
+            let inner = strip_option(inner).unwrap_or(inner);
+            Some((
+                format!("Nullable<{}>", infer_sql_type(inner, newtypes)?),
+                true,
+            ))
+        }
+        None => Some((infer_sql_type(rust_type, newtypes)?, false)),
+    }
+}
+
+/// the name and sql type of a newtype struct declared with `#[diesel(sql_type = ...)]`, like `pub struct TodoId(pub i32)`
+fn parse_newtype(item: &syn::ItemStruct) -> Option<(String, String)> {
+    let syn::Fields::Unnamed(fields) = &item.fields else {
+        return None;
+    };
+    if fields.unnamed.len() != 1 {
+        return None;
+    }
+
+    let sql_type = diesel_args(&item.attrs)
+        .into_iter()
+        .find(|(name, _)| name == "sql_type")
+        .and_then(|(_, values)| values.into_iter().next())?;
+    let sql_type = sql_type
+        .strip_prefix("diesel::sql_types::")
+        .unwrap_or(&sql_type)
+        .to_string();
+
+    Some((item.ident.to_string(), sql_type))
+}
+
+fn parse_struct(item: &syn::ItemStruct) -> Option<ModelStruct<'_>> {
+    let args = diesel_args(&item.attrs);
+    let table_name = args
+        .iter()
+        .find(|(name, _)| name == "table_name")
+        .and_then(|(_, values)| values.first())?;
+    // `table_name = crate::schema::todos` refers to the `todos` table
+    let table_name = table_name
+        .rsplit("::")
+        .next()
+        .unwrap_or(table_name)
+        .to_string();
+
+    Some(ModelStruct {
+        name: item.ident.to_string(),
+        table_name,
+        is_selectable: derives(&item.attrs, "Selectable"),
+        item,
+    })
+}
+
+/// reads the table a model struct describes, along with its `belongs_to` associations as
+/// `(struct name, foreign key column)`
+fn parse_table(
+    model: &ModelStruct,
+    newtypes: &[(String, String)],
+) -> anyhow::Result<(ParsedTableMacro, Vec<(String, String)>)> {
+    let item = model.item;
+    let args = diesel_args(&item.attrs);
+    let struct_name = &model.name;
+
+    let syn::Fields::Named(fields) = &item.fields else {
+        bail!("Model struct '{struct_name}' doesn't have named fields");
+    };
+
+    let mut columns = vec![];
+    for field in fields.named.iter() {
+        let field_name = field
+            .ident
+            .as_ref()
+            .expect("named fields have names")
+            .to_string();
+        let field_args = diesel_args(&field.attrs);
+        let field_arg = |arg: &str| {
+            field_args
+                .iter()
+                .find(|(name, _)| name == arg)
+                .and_then(|(_, values)| values.first())
+                .cloned()
+        };
+
+        let rust_type = token_text(field.ty.to_token_stream());
+        let (sql_type, is_nullable) = match field_arg("sql_type") {
+            Some(sql_type) => {
+                let inner = sql_type
+                    .strip_prefix("Nullable<")
+                    .and_then(|t| t.strip_suffix('>'));
+                (sql_type.clone(), inner.is_some() || rust_type.starts_with("Option<"))
+            }
+            None => nullable_sql_type(&rust_type, newtypes).with_context(|| {
+                format!(
+                    "Could not infer the sql type of '{struct_name}.{field_name}' (`{rust_type}`); annotate it with `#[diesel(sql_type = ...)]`"
+                )
+            })?,
+        };
+        let sql_type = match is_nullable {
+            true => sql_type
+                .strip_prefix("Nullable<")
+                .and_then(|t| t.strip_suffix('>'))
+                .unwrap_or(&sql_type)
+                .to_string(),
+            false => sql_type,
+        };
+
+        let mut attributes = doc_attributes(&field.attrs);
+        let name = match field_arg("column_name") {
+            None => field_name,
+            // keywords are written as raw identifiers in schemas
+            Some(column_name) if syn::parse_str::<syn::Ident>(&column_name).is_ok() => column_name,
+            Some(column_name)
+                if syn::parse_str::<syn::Ident>(&format!("r#{column_name}")).is_ok() =>
+            {
+                format!("r#{column_name}")
+            }
+            Some(column_name) => {
+                attributes.push(ParsedAttribute {
+                    name: "sql_name".to_string(),
+                    value: Some(column_name),
+                });
+                field_name
+            }
+        };
+
+        columns.push(ParsedColumnMacro {
+            ty: rust_type,
+            type_imports: vec![],
+            serde_attributes: vec![],
+            sql_type,
+            name,
+            is_nullable,
+            is_unsigned: false,
+            attributes,
+        });
+    }
+
+    // diesel's default primary key is `id`
+    let primary_key_columns = args
+        .iter()
+        .find(|(name, _)| name == "primary_key")
+        .map(|(_, values)| values.clone())
+        .unwrap_or_else(|| vec!["id".to_string()]);
+
+    let belongs_to = args
+        .iter()
+        .filter(|(name, _)| name == "belongs_to")
+        .filter_map(|(_, values)| {
+            let parent = values.first()?;
+            let parent = parent.rsplit("::").next().unwrap_or(parent).to_string();
+            let foreign_key = values
+                .iter()
+                .find_map(|v| v.strip_prefix("foreign_key="))
+                .map(|fk| fk.to_string())
+                .unwrap_or_else(|| format!("{}_id", parent.to_snake_case()));
+
+            Some((parent, foreign_key))
+        })
+        .collect();
+
+    let table = ParsedTableMacro {
+        name: model.table_name.clone(),
+        struct_name: struct_name.clone(),
+        columns,
+        primary_key_columns,
+        foreign_keys: vec![],
+        same_query_tables: vec![],
+        attributes: doc_attributes(&item.attrs),
+        schema: None,
+        generated_code: String::new(),
+    };
+
+    Ok((table, belongs_to))
+}
+
+/// reads the tables described by annotated model structs: for every `#[diesel(table_name = ...)]`, the struct deriving
+/// `Selectable` (or the first one mentioning the table) provides the columns, its `primary_key(...)` the primary key and
+/// its `belongs_to(...)` associations the foreign keys; the table's other structs (like insertable or changeset structs)
+/// are ignored
+pub fn parse_models(models_file_contents: &[String]) -> anyhow::Result<Vec<ParsedTableMacro>> {
+    let files = models_file_contents
+        .iter()
+        .map(|contents| syn::parse_file(contents).context("Could not parse the model structs"))
+        .collect::<anyhow::Result<Vec<syn::File>>>()?;
+    let items = files
+        .iter()
+        .flat_map(|file| file.items.iter())
+        .filter_map(|item| match item {
+            syn::Item::Struct(item) => Some(item),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let newtypes = items
+        .iter()
+        .filter_map(|item| parse_newtype(item))
+        .collect::<Vec<_>>();
+
+    let mut models: Vec<ModelStruct> = vec![];
+    for model in items.iter().filter_map(|item| parse_struct(item)) {
+        match models.iter_mut().find(|m| m.table_name == model.table_name) {
+            Some(existing) if model.is_selectable && !existing.is_selectable => *existing = model,
+            Some(_) => {}
+            None => models.push(model),
+        }
+    }
+
+    let mut tables = vec![];
+    for model in models.iter() {
+        let (mut table, belongs_to) = parse_table(model, &newtypes)?;
+
+        for pk in table.primary_key_columns.iter() {
+            if table.column(pk).is_none() {
+                bail!(
+                    "Primary key column '{pk}' of table '{}' isn't a field of '{}'",
+                    table.name,
+                    model.name
+                );
+            }
+        }
+
+        for (parent, foreign_key) in belongs_to.iter() {
+            let parent_table = models
+                .iter()
+                .find(|m| &m.name == parent)
+                .map(|m| m.table_name.clone())
+                .unwrap_or_else(|| parent.to_snake_case().to_plural());
+            table.foreign_keys.push((parent_table, foreign_key.clone()));
+        }
+
+        tables.push(table);
+    }
+
+    Ok(tables)
+}
+
+/// renders the diesel schema (`table!`, `joinable!` and `allow_tables_to_appear_in_same_query!` macros) of tables
+pub fn schema_rs(tables: &[ParsedTableMacro]) -> String {
+    let mut buffer = format!("{FILE_SIGNATURE}\n");
+
+    for table in tables.iter() {
+        buffer.push_str("\ndiesel::table! {\n");
+        for doc_comment in table.doc_comments() {
+            buffer.push_str(&format!("    ///{doc_comment}\n"));
+        }
+        buffer.push_str(&format!(
+            "    {} ({}) {{\n",
+            table.name,
+            table.primary_key_columns.join(", ")
+        ));
+        for column in table.columns.iter() {
+            for doc_comment in column.doc_comments() {
+                buffer.push_str(&format!("        ///{doc_comment}\n"));
+            }
+            if let Some(sql_name) = column.sql_name() {
+                buffer.push_str(&format!("        #[sql_name = \"{sql_name}\"]\n"));
+            }
+            buffer.push_str(&format!(
+                "        {} -> {},\n",
+                column.name,
+                match column.is_nullable {
+                    true => format!("Nullable<{}>", column.sql_type),
+                    false => column.sql_type.clone(),
+                }
+            ));
+        }
+        buffer.push_str("    }\n}\n");
+    }
+
+    // diesel only allows a single `joinable!` between two tables, and none between a table and itself
+    let mut joined: Vec<(&str, &str)> = vec![];
+    let mut joinables = String::new();
+    for table in tables.iter() {
+        for (foreign_table_name, join_column) in table.foreign_keys.iter() {
+            let pair = (table.name.as_str(), foreign_table_name.as_str());
+            if pair.0 == pair.1 || joined.contains(&pair) || joined.contains(&(pair.1, pair.0)) {
+                continue;
+            }
+
+            joined.push(pair);
+            joinables.push_str(&format!(
+                "diesel::joinable!({} -> {foreign_table_name} ({join_column}));\n",
+                table.name
+            ));
+        }
+    }
+    if !joinables.is_empty() {
+        buffer.push_str(&format!("\n{joinables}"));
+    }
+
+    if tables.len() > 1 {
+        buffer.push_str(&format!(
+            "\ndiesel::allow_tables_to_appear_in_same_query!(\n{});\n",
+            tables
+                .iter()
+                .map(|t| format!("    {},\n", t.name))
+                .collect::<String>()
+        ));
+    }
+
+    buffer
+}
@@ -0,0 +1,209 @@
+use inflector::Inflector;
+use std::io::{self, Write};
+
+use crate::code::{
+    build_file_attributes, build_module_docs, column_type, model_module, struct_fields,
+    type_imports,
+};
+use crate::parser::{ParsedTableMacro, FILE_SIGNATURE};
+use crate::GenerationConfig;
+
+/// path of a table's module as seen from other models
+fn entity_module(
+    table_name: &str,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> String {
+    format!(
+        "{}::{}",
+        config.table(table_name).get_models_module(),
+        model_module(table_name, tables, config)
+    )
+}
+
+/// a relation of the entity, rendered as a variant of its `Relation` enum
+struct Relation {
+    variant: String,
+    /// the module of the related entity (`None` for self-references)
+    module: Option<String>,
+    attribute: String,
+}
+
+fn relations(
+    table: &ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> Vec<Relation> {
+    let mut relations = vec![];
+
+    let references = |foreign_table_name: &str| {
+        table
+            .foreign_keys
+            .iter()
+            .filter(|(t, _)| t == foreign_table_name)
+            .count()
+    };
+    for (foreign_table_name, join_column) in table.foreign_keys.iter() {
+        let column_variant = join_column.trim_start_matches("r#").to_pascal_case();
+        let Some(foreign_table) = tables.iter().find(|t| &t.name == foreign_table_name) else {
+            continue;
+        };
+        let Some(foreign_pk) = foreign_table.primary_key_columns.first() else {
+            continue;
+        };
+        let foreign_pk_variant = foreign_pk.trim_start_matches("r#").to_pascal_case();
+
+        if foreign_table_name == &table.name {
+            relations.push(Relation {
+                variant: "SelfRef".to_string(),
+                module: None,
+                attribute: format!(
+                    "belongs_to = \"Entity\", from = \"Column::{column_variant}\", to = \"Column::{foreign_pk_variant}\""
+                ),
+            });
+            continue;
+        }
+
+        let module = entity_module(foreign_table_name, tables, config);
+        relations.push(Relation {
+            // tables referenced through several columns get a variant per column
+            variant: match references(foreign_table_name) {
+                1 => foreign_table_name.to_pascal_case(),
+                _ => format!("{}{column_variant}", foreign_table_name.to_pascal_case()),
+            },
+            attribute: format!(
+                "belongs_to = \"{module}::Entity\", from = \"Column::{column_variant}\", to = \"{module}::Column::{foreign_pk_variant}\""
+            ),
+            module: Some(module),
+        });
+    }
+
+    for other in tables.iter().filter(|t| t.name != table.name) {
+        // `has_many` goes through the other entity's `Related` impl, which only exists if there's one way back
+        let references_back = other
+            .foreign_keys
+            .iter()
+            .filter(|(foreign_table_name, _)| foreign_table_name == &table.name)
+            .count();
+        if references_back != 1 {
+            continue;
+        }
+
+        let module = entity_module(&other.name, tables, config);
+        relations.push(Relation {
+            variant: other.name.to_pascal_case(),
+            attribute: format!("has_many = \"{module}::Entity\""),
+            module: Some(module),
+        });
+    }
+
+    relations
+}
+
+/// renders the SeaORM entity of a table: its `Model` (deriving `Entity`, `Column`, `PrimaryKey` and `ActiveModel`)
+/// and the `Relation`s following its foreign keys and the foreign keys referencing it (when there is just one)
+pub fn generate_for_table(
+    out: &mut impl Write,
+    table: ParsedTableMacro,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> io::Result<()> {
+    let (_, fields) = struct_fields(&table, config)
+        .into_iter()
+        .next()
+        .expect("Missing the Read struct");
+
+    // sea-orm only auto-increments single integer primary keys
+    let auto_increment = match table.primary_key_columns.as_slice() {
+        [pk] => table
+            .column(pk)
+            .map(|c| c.ty.starts_with('i'))
+            .unwrap_or_default(),
+        _ => false,
+    };
+
+    let mut model_fields = String::new();
+    for field in fields.iter() {
+        let column = table
+            .column(&field.column_name)
+            .expect("Struct field doesn't map to a column");
+
+        let mut attributes = vec![];
+        if table.primary_key_columns.contains(&column.name) {
+            attributes.push("primary_key".to_string());
+            if !auto_increment {
+                attributes.push("auto_increment = false".to_string());
+            }
+        }
+        let column_name = column
+            .sql_name()
+            .unwrap_or(column.name.trim_start_matches("r#"));
+        if field.name.trim_start_matches("r#") != column_name {
+            attributes.push(format!("column_name = \"{column_name}\""));
+        }
+        if column.is_nullable {
+            attributes.push("nullable".to_string());
+        }
+
+        for doc_comment in field.doc_comments.iter() {
+            model_fields.push_str(&format!("    ///{doc_comment}\n"));
+        }
+        if !attributes.is_empty() {
+            model_fields.push_str(&format!("    #[sea_orm({})]\n", attributes.join(", ")));
+        }
+        for attribute in column.serde_attributes.iter() {
+            model_fields.push_str(&format!("    {attribute}\n"));
+        }
+        model_fields.push_str(&format!(
+            "    pub {name}: {ty},\n",
+            name = field.name,
+            ty = column_type(column)
+        ));
+    }
+
+    let relations = relations(&table, tables, config);
+    let relation_variants = relations
+        .iter()
+        .map(|r| format!("    #[sea_orm({})]\n    {},\n", r.attribute, r.variant))
+        .collect::<String>();
+
+    // `Related` can only be implemented once per entity
+    let related = relations
+        .iter()
+        .filter_map(|r| r.module.as_ref().map(|module| (r, module)))
+        .filter(|(_, module)| relations.iter().filter(|r| r.module.as_ref() == Some(module)).count() == 1)
+        .map(|(r, module)| {
+            format!(
+                "\nimpl Related<{module}::Entity> for Entity {{\n    fn to() -> RelationDef {{\n        Relation::{}.def()\n    }}\n}}\n",
+                r.variant
+            )
+        })
+        .collect::<String>();
+
+    write!(
+        out,
+        r##"{FILE_SIGNATURE}
+
+{module_docs}{file_attributes}use sea_orm::entity::prelude::*;
+use serde::{{Deserialize, Serialize}};
+{type_imports}
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "{table_name}")]
+pub struct Model {{
+{model_fields}}}
+
+#[derive(Debug, Clone, Copy, EnumIter, DeriveRelation)]
+pub enum Relation {{
+{relation_variants}}}
+{related}
+impl ActiveModelBehavior for ActiveModel {{}}
+"##,
+        module_docs = build_module_docs(&table),
+        file_attributes = build_file_attributes(&table, config),
+        table_name = table.sql_name().unwrap_or(&table.name),
+        type_imports = type_imports(&table, tables, config)
+            .iter()
+            .map(|path| format!("use {path};\n"))
+            .collect::<String>(),
+    )
+}
@@ -0,0 +1,102 @@
+use crate::code::{build_connection_alias, struct_fields};
+use crate::migration::creation_order;
+use crate::parser::{ParsedTableMacro, FILE_SIGNATURE};
+use crate::{GeneratedStruct, GenerationConfig, Target};
+
+/// tables which can be seeded: diesel models of tables with seeding enabled which take rows to insert (materialized
+/// views and tables without insertable columns don't) from fixture files (which needs `Deserialize` Create structs)
+fn seeded_tables<'a>(
+    tables: &'a [ParsedTableMacro],
+    config: &GenerationConfig,
+) -> Vec<&'a ParsedTableMacro> {
+    creation_order(tables)
+        .into_iter()
+        .filter(|table| {
+            let table_options = config.table(&table.name);
+
+            table_options.get_seed()
+                && table_options.get_target() == Target::Diesel
+                && table_options
+                    .get_serde_derives(GeneratedStruct::Create)
+                    .deserialize()
+                && struct_fields(table, config)
+                    .get(1)
+                    .is_some_and(|(_, fields)| !fields.is_empty())
+        })
+        .collect()
+}
+
+/// the module inserting fixture files through the Create structs, if any table is seeded
+pub fn render(tables: &[ParsedTableMacro], config: &GenerationConfig) -> Option<String> {
+    let seeded_tables = seeded_tables(tables, config);
+    if seeded_tables.is_empty() {
+        return None;
+    }
+
+    let statements = seeded_tables
+        .iter()
+        .map(|table| {
+            let cfg_attribute = match config.table(&table.name).get_cfg_feature() {
+                Some(feature) => format!("        #[cfg(feature = \"{feature}\")]\n"),
+                None => "".to_string(),
+            };
+
+            format!(
+                "{cfg_attribute}        count += seed_table::<super::{module_name}::Create{struct_name}>(db, dir, \"{table_name}\", |db, item| super::{module_name}::{struct_name}::create(db, item).map(|_| ()))?;\n",
+                table_name = table.name,
+                module_name = match table.group(config) {
+                    Some(group) => format!("{group}::{}", table.module_name(config)),
+                    None => table.module_name(config),
+                },
+                struct_name = table.struct_name,
+            )
+        })
+        .collect::<String>();
+
+    Some(format!(
+        r##"{FILE_SIGNATURE}
+
+//! Inserts fixture files into the database (for example, to bootstrap development databases).
+
+use diesel::{{Connection as _, QueryResult}};
+use std::path::Path;
+
+{connection_alias}
+
+pub type SeedError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Inserts the rows of the fixture files in `dir` in a single transaction, returning the number of inserted rows.
+///
+/// Fixture files are named after tables (`<table>.json`) and hold a JSON array of the table's Create structs; tables
+/// without a fixture file are skipped. Referenced tables are seeded before the tables referencing them.
+pub fn seed(db: &mut Connection, dir: &Path) -> Result<usize, SeedError> {{
+    db.transaction(|db| {{
+        let mut count = 0;
+{statements}
+        Ok(count)
+    }})
+}}
+
+fn seed_table<T: serde::de::DeserializeOwned>(
+    db: &mut Connection,
+    dir: &Path,
+    table_name: &str,
+    create: impl Fn(&mut Connection, &T) -> QueryResult<()>,
+) -> Result<usize, SeedError> {{
+    let path = dir.join(format!("{{table_name}}.json"));
+    if !path.exists() {{
+        return Ok(0);
+    }}
+
+    let items: Vec<T> = serde_json::from_str(&std::fs::read_to_string(&path)?)
+        .map_err(|e| format!("Could not parse '{{}}': {{e}}", path.display()))?;
+    for item in items.iter() {{
+        create(db, item)?;
+    }}
+
+    Ok(items.len())
+}}
+"##,
+        connection_alias = build_connection_alias(config)
+    ))
+}
@@ -0,0 +1,222 @@
+use std::io::{self, Write};
+
+use crate::code::{
+    build_file_attributes, build_module_docs, column_type, struct_fields, type_imports, StructField,
+};
+use crate::parser::{ParsedTableMacro, FILE_SIGNATURE};
+use crate::GenerationConfig;
+
+/// SQL dialects differ in identifier quoting, bind parameters and `RETURNING` support
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+impl Dialect {
+    /// picks the dialect matching the backend of the configured connection type (defaults to postgres)
+    fn from_connection_type(connection_type: &str) -> Self {
+        if connection_type.contains("Mysql") {
+            Self::Mysql
+        } else if connection_type.contains("Sqlite") {
+            Self::Sqlite
+        } else {
+            Self::Postgres
+        }
+    }
+
+    fn quote(&self, identifier: &str) -> String {
+        match self {
+            Self::Mysql => format!("`{identifier}`"),
+            Self::Postgres | Self::Sqlite => format!("\"{identifier}\""),
+        }
+    }
+
+    /// the bind parameter at a 1-based index
+    fn param(&self, index: usize) -> String {
+        match self {
+            Self::Postgres => format!("${index}"),
+            Self::Mysql | Self::Sqlite => "?".to_string(),
+        }
+    }
+
+    fn supports_returning(&self) -> bool {
+        !matches!(self, Self::Mysql)
+    }
+}
+
+/// the database name of the column a field maps to
+fn column_name<'a>(table: &'a ParsedTableMacro, field: &StructField) -> &'a str {
+    let column = table
+        .column(&field.column_name)
+        .expect("Struct field doesn't map to a column");
+
+    column
+        .sql_name()
+        .unwrap_or(column.name.trim_start_matches("r#"))
+}
+
+fn struct_code(
+    table: &ParsedTableMacro,
+    name: &str,
+    fields: &[StructField],
+    is_row: bool,
+) -> String {
+    let mut buffer = format!(
+        "#[derive(Debug, Clone, Serialize, Deserialize{})]\npub struct {name} {{\n",
+        if is_row { ", sqlx::FromRow" } else { "" }
+    );
+
+    for field in fields.iter() {
+        let column = table
+            .column(&field.column_name)
+            .expect("Struct field doesn't map to a column");
+
+        for doc_comment in field.doc_comments.iter() {
+            buffer.push_str(&format!("    ///{doc_comment}\n"));
+        }
+        let column_name = column_name(table, field);
+        if is_row && field.name.trim_start_matches("r#") != column_name {
+            buffer.push_str(&format!("    #[sqlx(rename = \"{column_name}\")]\n"));
+        }
+        for attribute in column.serde_attributes.iter() {
+            buffer.push_str(&format!("    {attribute}\n"));
+        }
+        buffer.push_str(&format!(
+            "    pub {name}: {ty},\n",
+            name = field.name,
+            ty = column_type(column)
+        ));
+    }
+
+    buffer.push_str("}\n");
+
+    buffer
+}
+
+/// renders a plain struct deriving `sqlx::FromRow` for a table's rows, a struct for inserting rows and constants with
+/// the table's CRUD statements (in the dialect of the configured connection type)
+pub fn generate_for_table(
+    out: &mut impl Write,
+    table: ParsedTableMacro,
+    config: &GenerationConfig,
+) -> io::Result<()> {
+    let dialect = Dialect::from_connection_type(&config.connection_type);
+    let mut structs = struct_fields(&table, config).into_iter();
+    let (read_name, read_fields) = structs.next().expect("Missing the Read struct");
+    let (create_name, create_fields) = structs.next().expect("Missing the Create struct");
+    let (_, update_fields) = structs.next().expect("Missing the Update struct");
+
+    let table_name = dialect.quote(table.sql_name().unwrap_or(&table.name));
+    let columns = |fields: &[StructField]| {
+        fields
+            .iter()
+            .map(|field| dialect.quote(column_name(&table, field)))
+            .collect::<Vec<String>>()
+    };
+    let select_columns = columns(&read_fields).join(", ");
+    let returning = match dialect.supports_returning() {
+        true => format!(" RETURNING {select_columns}"),
+        false => "".to_string(),
+    };
+    let primary_keys = table
+        .primary_key_columns
+        .iter()
+        .map(|pk| {
+            let column = table.column(pk).expect("Primary key isn't a column");
+            dialect.quote(column.sql_name().unwrap_or(pk.trim_start_matches("r#")))
+        })
+        .collect::<Vec<String>>();
+    // conditions on the primary key, with bind parameters following the first `offset` ones
+    let pk_condition = |offset: usize| {
+        primary_keys
+            .iter()
+            .enumerate()
+            .map(|(index, pk)| format!("{pk} = {}", dialect.param(offset + index + 1)))
+            .collect::<Vec<String>>()
+            .join(" AND ")
+    };
+
+    let mut constants = vec![];
+    let pk_binds = table
+        .primary_key_columns
+        .iter()
+        .map(|pk| format!("`{}`", pk.trim_start_matches("r#")))
+        .collect::<Vec<String>>()
+        .join(", ");
+    if !primary_keys.is_empty() {
+        constants.push(format!(
+            "    /// Selects a row by primary key (binds: {pk_binds})\n    pub const SELECT: &'static str = r#\"SELECT {select_columns} FROM {table_name} WHERE {}\"#;\n",
+            pk_condition(0)
+        ));
+    }
+    constants.push(format!(
+        "    /// Selects a page of rows (binds: limit, offset)\n    pub const SELECT_PAGE: &'static str = r#\"SELECT {select_columns} FROM {table_name}{order} LIMIT {} OFFSET {}\"#;\n",
+        dialect.param(1),
+        dialect.param(2),
+        order = match primary_keys.is_empty() {
+            true => "".to_string(),
+            false => format!(" ORDER BY {}", primary_keys.join(", ")),
+        }
+    ));
+    if !create_fields.is_empty() {
+        constants.push(format!(
+            "    /// Inserts a row (binds: the fields of [`{create_name}`], in order)\n    pub const INSERT: &'static str = r#\"INSERT INTO {table_name} ({}) VALUES ({}){returning}\"#;\n",
+            columns(&create_fields).join(", "),
+            (1..=create_fields.len())
+                .map(|index| dialect.param(index))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    if !primary_keys.is_empty() && !update_fields.is_empty() {
+        let update_columns = columns(&update_fields);
+        constants.push(format!(
+            "    /// Updates every column of a row (binds: {}, then {pk_binds})\n    pub const UPDATE: &'static str = r#\"UPDATE {table_name} SET {} WHERE {}{returning}\"#;\n",
+            update_fields
+                .iter()
+                .map(|field| format!("`{}`", field.name.trim_start_matches("r#")))
+                .collect::<Vec<String>>()
+                .join(", "),
+            update_columns
+                .iter()
+                .enumerate()
+                .map(|(index, column)| format!("{column} = {}", dialect.param(index + 1)))
+                .collect::<Vec<String>>()
+                .join(", "),
+            pk_condition(update_columns.len())
+        ));
+    }
+    if !primary_keys.is_empty() {
+        constants.push(format!(
+            "    /// Deletes a row by primary key (binds: {pk_binds})\n    pub const DELETE: &'static str = r#\"DELETE FROM {table_name} WHERE {}\"#;\n",
+            pk_condition(0)
+        ));
+    }
+
+    write!(
+        out,
+        "{FILE_SIGNATURE}\n\n{module_docs}{file_attributes}use serde::{{Deserialize, Serialize}};\n{type_imports}\n{read_struct}",
+        module_docs = build_module_docs(&table),
+        file_attributes = build_file_attributes(&table, config),
+        type_imports = type_imports(&table, &[], config)
+            .iter()
+            .map(|path| format!("use {path};\n"))
+            .collect::<String>(),
+        read_struct = struct_code(&table, &read_name, &read_fields, true)
+    )?;
+    if !create_fields.is_empty() {
+        write!(
+            out,
+            "\n{}",
+            struct_code(&table, &create_name, &create_fields, false)
+        )?;
+    }
+
+    write!(
+        out,
+        "\nimpl {read_name} {{\n{constants}}}\n",
+        constants = constants.join("\n")
+    )
+}
@@ -0,0 +1,126 @@
+use crate::code::{build_connect, build_connection_alias};
+use crate::parser::{snake_case_identifier, ParsedTableMacro, FILE_SIGNATURE};
+use crate::{GenerationConfig, Target};
+
+/// the test support module (`tests/support/mod.rs`) of a standalone models crate: a postgres testcontainer shared by
+/// the tests of a test binary, with the migrations in `migrations_dir` applied
+pub fn render_support(migrations_dir: &str, config: &GenerationConfig) -> String {
+    format!(
+        r##"{FILE_SIGNATURE}
+
+//! Test support: starts a postgres testcontainer (once per test binary, so docker has to be running), applies the
+//! migrations in `{migrations_dir}` and hands out connections whose changes are rolled back.
+
+use diesel::Connection as _;
+use diesel_migrations::{{embed_migrations, EmbeddedMigrations, MigrationHarness}};
+use std::sync::OnceLock;
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::testcontainers::{{runners::SyncRunner, Container}};
+
+{connection_alias}
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("{migrations_dir}");
+
+/// the container (kept running until the test binary exits) and its database url
+static DATABASE: OnceLock<(Container<Postgres>, String)> = OnceLock::new();
+
+/// The url of the migrated database, starting the container on first use
+pub fn database_url() -> &'static str {{
+    &DATABASE.get_or_init(|| {{
+        let container = Postgres::default()
+            .start()
+            .expect("Could not start the postgres container");
+        let database_url = format!(
+            "postgres://postgres:postgres@{{}}:{{}}/postgres",
+            container.get_host().expect("Could not get the container's host"),
+            container
+                .get_host_port_ipv4(5432)
+                .expect("Could not get the container's port")
+        );
+
+        diesel::PgConnection::establish(&database_url)
+            .expect("Could not connect to the database")
+            .run_pending_migrations(MIGRATIONS)
+            .expect("Could not run the migrations");
+
+        (container, database_url)
+    }})
+    .1
+}}
+
+/// A connection to the migrated database inside a test transaction, so tests don't see each other's changes
+pub fn connection() -> Connection {{
+    let database_url = database_url();
+    let mut db: Connection = {connect};
+    db.begin_test_transaction()
+        .expect("Could not begin a test transaction");
+
+    db
+}}
+"##,
+        connection_alias = build_connection_alias(config),
+        connect = build_connect(config)
+    )
+}
+
+/// the integration tests (`tests/models.rs`) of a standalone models crate, checking that the diesel models of every
+/// table can query the migrated database (which catches models drifting from the migrations)
+pub fn render_tests(
+    crate_name: &str,
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> String {
+    let crate_name = crate_name.replace('-', "_");
+
+    let tests = tables
+        .iter()
+        .filter(|t| config.table(&t.name).get_target() == Target::Diesel)
+        .map(|table| {
+            let module = match table.group(config) {
+                Some(group) => format!("{group}::{}", table.module_name(config)),
+                None => table.module_name(config),
+            };
+
+            let fn_name = snake_case_identifier(&table.name);
+            let struct_name = &table.struct_name;
+            let model = format!("{crate_name}::models::{module}::{struct_name}");
+            // filters without conditions mustn't turn into `DELETE ... WHERE TRUE`
+            let delete_where_test = match config.table(&table.name).get_materialized_view() {
+                true => "".to_string(),
+                false => format!(
+                    r##"
+#[test]
+fn {fn_name}_delete_where_empty_filter() {{
+    let mut db = support::connection();
+    let total_items = {model}::paginate(&mut db, 0, 1, None).unwrap().total_items;
+
+    assert_eq!({model}::delete_where(&mut db, &{model}Filter::default()).unwrap(), 0);
+    assert_eq!({model}::paginate(&mut db, 0, 1, None).unwrap().total_items, total_items);
+}}
+"##
+                ),
+            };
+
+            format!(
+                r##"
+#[test]
+fn {fn_name}_paginate() {{
+    let mut db = support::connection();
+
+    {model}::paginate(&mut db, 0, 10, None).unwrap();
+}}
+{delete_where_test}"##
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r##"{FILE_SIGNATURE}
+
+//! Checks that the models of every table can query the migrated test database (see `support`), and that deleting
+//! with an empty filter deletes nothing.
+
+mod support;
+{tests}"##
+    )
+}
@@ -0,0 +1,76 @@
+use std::fmt::Debug;
+
+use crate::parser::schema_type_to_rust_type;
+
+/// Maps the diesel sql types of columns (like `Int4`, or custom types like `MoodEnum`) to rust types. The mappers of
+/// [`GenerationConfig::type_mappers`](crate::GenerationConfig::type_mappers) are asked in order, before the
+/// [`DieselTypeMapper`]; type overrides of the table options take precedence over all of them.
+pub trait TypeMapper: Debug + Send + Sync {
+    /// the rust type of the values of columns of the sql type (disregarding nullability), if the mapper knows it
+    fn map(&self, sql_type: &str) -> Option<TypeMapping>;
+}
+
+/// A rust type of column values, with what the generated code needs to use it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeMapping {
+    /// the rust type, like `chrono::NaiveDate` or `Mood`
+    pub rust_type: String,
+    /// paths the models import to use the type (like `crate::types::Mood`)
+    pub imports: Vec<String>,
+    /// serde attributes of the fields holding values of the type (like `#[serde(with = "crate::types::mood")]`),
+    /// which are added to the fields of every generated struct deriving serde's traits (where the values may be
+    /// wrapped in an `Option`)
+    pub serde_attributes: Vec<String>,
+}
+
+impl TypeMapping {
+    pub fn new(rust_type: impl Into<String>) -> Self {
+        Self {
+            rust_type: rust_type.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn import(mut self, path: impl Into<String>) -> Self {
+        self.imports.push(path.into());
+        self
+    }
+
+    pub fn serde_attribute(mut self, attribute: impl Into<String>) -> Self {
+        self.serde_attributes.push(attribute.into());
+        self
+    }
+}
+
+/// The default mapping of diesel's sql types (along with `diesel_full_text_search`'s `Tsvector`, and postgis geometries
+/// with the `postgis` feature)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DieselTypeMapper;
+
+impl TypeMapper for DieselTypeMapper {
+    fn map(&self, sql_type: &str) -> Option<TypeMapping> {
+        schema_type_to_rust_type(sql_type.to_string()).map(TypeMapping::new)
+    }
+}
+
+/// Maps a single sql type (named exactly as in the schema) to a rust type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeRule {
+    pub sql_type: String,
+    pub mapping: TypeMapping,
+}
+
+impl TypeRule {
+    pub fn new(sql_type: impl Into<String>, mapping: TypeMapping) -> Self {
+        Self {
+            sql_type: sql_type.into(),
+            mapping,
+        }
+    }
+}
+
+impl TypeMapper for TypeRule {
+    fn map(&self, sql_type: &str) -> Option<TypeMapping> {
+        (sql_type == self.sql_type).then(|| self.mapping.clone())
+    }
+}
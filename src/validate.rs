@@ -0,0 +1,223 @@
+use anyhow::bail;
+
+use crate::parser::ParsedTableMacro;
+use crate::{GenerationConfig, TableOptions, WriteResult};
+
+/// checks that the tables and columns named in the options exist in the schema: table options must name a table,
+/// columns in table options must exist in that table, and columns in the default table options must exist in at
+/// least one table
+pub fn validate(tables: &[ParsedTableMacro], config: &GenerationConfig) -> anyhow::Result<()> {
+    let mut offenders = vec![];
+    let find_table = |name: &str| tables.iter().find(|t| t.name == name);
+
+    let mut table_names = config.table_options.keys().collect::<Vec<_>>();
+    table_names.sort();
+    for table_name in table_names {
+        let options = &config.table_options[table_name];
+        // ignored tables aren't parsed
+        if options.ignore == Some(true) {
+            continue;
+        }
+        let Some(table) = find_table(table_name) else {
+            offenders.push(format!("`{table_name}` (table options) isn't a table"));
+            continue;
+        };
+
+        // columns of this table named in the options, as `(option, column)`
+        let mut columns = option_columns(options);
+        for projection in options.projections.iter() {
+            for column in projection.columns.iter() {
+                columns.push((format!("projection `{}`", projection.name), *column));
+            }
+            for (joined_table_name, column) in projection.joined_columns.iter() {
+                let exists = find_table(joined_table_name)
+                    .map(|t| t.column(column).is_some())
+                    .unwrap_or_default();
+                if !exists {
+                    offenders.push(format!(
+                        "`{joined_table_name}.{column}` (joined column of projection `{}` of `{table_name}`) isn't a column",
+                        projection.name
+                    ));
+                }
+            }
+        }
+
+        for (option, column) in columns {
+            if table.column(column).is_none() {
+                offenders.push(format!(
+                    "`{table_name}.{column}` (in {option}) isn't a column"
+                ));
+            }
+        }
+    }
+
+    for (option, column) in option_columns(&config.default_table_options) {
+        if !tables.iter().any(|t| t.column(column).is_some()) {
+            offenders.push(format!(
+                "`{column}` (in the default table options' {option}) isn't a column of any table"
+            ));
+        }
+    }
+
+    if !offenders.is_empty() {
+        bail!(
+            "The options refer to tables or columns which aren't in the schema:\n{}",
+            offenders
+                .iter()
+                .map(|o| format!("- {o}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+/// the columns named in the options (except for projections), as `(option, column)`; options are labeled by their
+/// field names, so per-table and default options read the same
+fn option_columns<'a>(options: &TableOptions<'a>) -> Vec<(String, &'a str)> {
+    let mut columns = vec![];
+    for column in options.autogenerated_columns.iter().flatten() {
+        columns.push(("`autogenerated_columns`".to_string(), *column));
+    }
+    for column in options.immutable_columns.iter().flatten() {
+        columns.push(("`immutable_columns`".to_string(), *column));
+    }
+    for column in options.sensitive_columns.iter().flatten() {
+        columns.push(("`sensitive_columns`".to_string(), *column));
+    }
+    for column in options.case_insensitive_columns.iter().flatten() {
+        columns.push(("`case_insensitive_columns`".to_string(), *column));
+    }
+    for (column, from) in options.excluded_columns.iter().flatten() {
+        columns.push((
+            format!("`excluded_columns` (of the {from:?} struct)"),
+            *column,
+        ));
+    }
+    if let Some((column, _)) = options.default_order {
+        columns.push(("`default_order`".to_string(), column));
+    }
+    if let Some(column) = options.full_text_search_column {
+        columns.push(("`full_text_search_column`".to_string(), column));
+    }
+
+    columns
+}
+
+/// checks the options which can't be generated: the columns of projections must be columns of their table, and their
+/// joined columns columns of tables which may appear in the same query as the table; page sizes must be at least 1,
+/// with the default page size within the max page size; MySQL connections can't return the written rows
+pub fn validate_generation(
+    tables: &[ParsedTableMacro],
+    config: &GenerationConfig,
+) -> anyhow::Result<()> {
+    let mut offenders = vec![];
+    let defaults = &config.default_table_options;
+    offenders.extend(page_size_offenders("the default table options", defaults));
+    for table in tables {
+        let options = config.table(&table.name);
+        // tables whose options don't change the page sizes fail with the default table options
+        let page_sizes =
+            |options: &TableOptions| (options.get_default_page_size(), options.get_max_page_size());
+        if config.table_options.contains_key(table.name.as_str())
+            && page_sizes(&options) != page_sizes(defaults)
+        {
+            offenders.extend(page_size_offenders(&format!("`{}`", table.name), &options));
+        }
+    }
+    for table in tables {
+        let table_name = &table.name;
+        for projection in config.table(table_name).get_projections() {
+            let name = projection.name;
+            for column in projection.columns.iter() {
+                if table.column(column).is_none() {
+                    offenders.push(format!(
+                        "`{table_name}.{column}` (column of projection `{name}`) isn't a column"
+                    ));
+                }
+            }
+            for (joined_table_name, column) in projection.joined_columns.iter() {
+                if !table
+                    .same_query_tables
+                    .iter()
+                    .any(|t| t == joined_table_name)
+                {
+                    offenders.push(format!(
+                        "`{joined_table_name}` (joined by projection `{name}` of `{table_name}`) isn't allowed to appear in the same query as `{table_name}`"
+                    ));
+                } else if tables
+                    .iter()
+                    .find(|t| t.name == *joined_table_name)
+                    .and_then(|t| t.column(column))
+                    .is_none()
+                {
+                    offenders.push(format!(
+                        "`{joined_table_name}.{column}` (joined column of projection `{name}` of `{table_name}`) isn't a column"
+                    ));
+                }
+            }
+        }
+    }
+
+    // MySQL has no `RETURNING`, so its models can't return the rows they write
+    let returning_tables = tables
+        .iter()
+        .filter(|t| {
+            let options = config.table(&t.name);
+            !options.get_materialized_view() && options.get_write_result() == WriteResult::Row
+        })
+        .map(|t| format!("`{}`", t.name))
+        .collect::<Vec<String>>();
+    let mut connection_types = vec![("the connection type".to_string(), &config.connection_type)];
+    for (feature, connection_type) in config.connection_features.iter() {
+        connection_types.push((
+            format!("the connection type of feature `{feature}`"),
+            connection_type,
+        ));
+    }
+    for (scope, connection_type) in connection_types {
+        if connection_type.contains("MysqlConnection") && !returning_tables.is_empty() {
+            offenders.push(format!(
+                "{scope} (`{connection_type}`) is a MySQL connection, which can't return the rows written by {} (MySQL has no `RETURNING`; write with the `count` or `nothing` write result instead)",
+                returning_tables.join(", ")
+            ));
+        }
+    }
+
+    if !offenders.is_empty() {
+        bail!(
+            "The options can't be generated:\n{}",
+            offenders
+                .iter()
+                .map(|o| format!("- {o}"))
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+/// the problems of the page sizes of table options (of `scope`): `max_page_size` clamps every page size (a max below 1
+/// leaves every page empty), including the `default_page_size` taking the place of page sizes below 1
+fn page_size_offenders(scope: &str, options: &TableOptions) -> Vec<String> {
+    let default = options.get_default_page_size();
+    let mut offenders = vec![];
+    if default < 1 {
+        offenders.push(format!(
+            "the default page size of {scope} ({default}) is less than 1"
+        ));
+    }
+    match options.get_max_page_size() {
+        Some(max) if max < 1 => offenders.push(format!(
+            "the max page size of {scope} ({max}) is less than 1"
+        )),
+        Some(max) if default > max => offenders.push(format!(
+            "the default page size of {scope} ({default}) is larger than its max page size ({max})"
+        )),
+        _ => {}
+    }
+
+    offenders
+}
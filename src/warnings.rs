@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use crate::parser::ParsedTableMacro;
+use crate::{GenerationConfig, Target};
+
+/// warnings about tables which generate code that likely doesn't compile, or which doesn't do what was intended:
+/// tables without primary keys, foreign keys to tables which aren't generated and tables whose structs get the same
+/// name (like `user_role` and `user_roles`)
+pub fn schema_warnings(tables: &[ParsedTableMacro], config: &GenerationConfig) -> Vec<String> {
+    let mut warnings = vec![];
+
+    for table in tables.iter() {
+        if table.primary_key_columns.is_empty() {
+            let consequence = match config.table(&table.name).get_target() {
+                Target::Diesel => "it gets no `read`, `update` and `delete` functions",
+                Target::SeaOrm => "the generated entity will not compile",
+                Target::Sqlx => "it gets no `SELECT`, `UPDATE` and `DELETE` statements",
+            };
+            warnings.push(format!(
+                "table '{}' has no primary key; {consequence}",
+                table.name
+            ));
+        }
+
+        for (foreign_table_name, join_column) in table.foreign_keys.iter() {
+            if tables.iter().any(|t| &t.name == foreign_table_name) {
+                continue;
+            }
+
+            let reason = match config.table(foreign_table_name).get_ignore() {
+                true => "is ignored",
+                false => "isn't in the schema",
+            };
+            warnings.push(format!(
+                "table '{}' references '{foreign_table_name}' (through `{join_column}`), which {reason}; the generated associations will not compile",
+                table.name
+            ));
+        }
+    }
+
+    let mut struct_names: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for table in tables.iter() {
+        struct_names
+            .entry(&table.struct_name)
+            .or_default()
+            .push(&table.name);
+    }
+    for (struct_name, table_names) in struct_names {
+        if let [first_names @ .., last_name] = table_names.as_slice() {
+            if first_names.is_empty() {
+                continue;
+            }
+
+            warnings.push(format!(
+                "tables '{}' and '{last_name}' generate structs named `{struct_name}`; code using several of them has to rename them on import (or give them a suffix)",
+                first_names.join("', '")
+            ));
+        }
+    }
+
+    warnings
+}
@@ -0,0 +1,72 @@
+use crate::code::struct_fields;
+use crate::parser::{ParsedColumnMacro, ParsedTableMacro};
+use crate::{BinaryFormat, GenerationConfig};
+
+/// the zod schema of a column's values (nullability and optionality are added by the caller)
+fn zod_type(column: &ParsedColumnMacro, binary_format: BinaryFormat) -> String {
+    let max_length = column
+        .attribute("max_length")
+        .and_then(|a| a.value.as_deref())
+        .map(|max| format!(".max({max})"))
+        .unwrap_or_default();
+
+    match column.ty.as_str() {
+        "bool" => "z.boolean()".to_string(),
+        "i8" | "i16" | "i32" | "i64" | "u32" => "z.number().int()".to_string(),
+        "f32" | "f64" => "z.number()".to_string(),
+        "String" => format!("z.string(){max_length}"),
+        "uuid::Uuid" => "z.string().uuid()".to_string(),
+        "chrono::DateTime<chrono::Utc>" => "z.string().datetime({ offset: true })".to_string(),
+        "Vec<u8>" if binary_format == BinaryFormat::Base64 => "z.string().base64()".to_string(),
+        "Vec<u8>" => "z.array(z.number().int())".to_string(),
+        "serde_json::Value" => "z.unknown()".to_string(),
+        // dates, times and decimals are serialized as strings
+        _ => "z.string()".to_string(),
+    }
+}
+
+/// renders zod schemas for the Create and Update structs of every table, for validating forms in frontends
+pub fn render(tables: &[ParsedTableMacro], config: &GenerationConfig) -> String {
+    let mut schemas = vec![];
+
+    for table in tables.iter() {
+        // the Read struct isn't submitted by frontends
+        for (struct_name, fields) in struct_fields(table, config).into_iter().skip(1) {
+            if fields.is_empty() {
+                continue;
+            }
+
+            let properties = fields
+                .iter()
+                .map(|field| {
+                    let column = table
+                        .column(&field.column_name)
+                        .expect("Struct field doesn't map to a column");
+
+                    format!(
+                        "  {name}: {ty}{nullable}{optional},\n",
+                        name = column
+                            .serde_rename()
+                            .unwrap_or(field.name.trim_start_matches("r#")),
+                        ty = zod_type(column, config.table(&table.name).get_binary_format()),
+                        nullable = if column.is_nullable {
+                            ".nullable()"
+                        } else {
+                            ""
+                        },
+                        optional = if field.is_optional { ".optional()" } else { "" },
+                    )
+                })
+                .collect::<String>();
+
+            schemas.push(format!(
+                "export const {struct_name}Schema = z.object({{\n{properties}}});\n"
+            ));
+        }
+    }
+
+    format!(
+        "// This file is generated and managed by dsync\n\nimport {{ z }} from \"zod\";\n\n{}",
+        schemas.join("\n")
+    )
+}
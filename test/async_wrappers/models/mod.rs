@@ -0,0 +1,4 @@
+pub mod todos;
+pub mod tags;
+pub mod sessions;
+pub mod todo_counts;
@@ -0,0 +1,3 @@
+pub mod todos;
+pub mod sessions;
+pub mod tags;
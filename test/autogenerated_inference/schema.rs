@@ -0,0 +1,24 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        completed -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    sessions (id) {
+        id -> Uuid,
+        token -> Text,
+        expires_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    tags (name) {
+        name -> Text,
+        created_at -> Timestamptz,
+    }
+}
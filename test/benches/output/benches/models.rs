@@ -0,0 +1,123 @@
+/* This file is generated and managed by dsync */
+
+//! Benchmarks of the generated `read`, `paginate` and `create` functions against the database at `DATABASE_URL`, run
+//! on the first row of each table (created rows are rolled back). Run them with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use diesel::Connection as _;
+use todo_models::models::users::*;
+use todo_models::models::todos::*;
+use todo_models::models::todo_tags::*;
+use todo_models::models::todo_counts::*;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+fn connect() -> Connection {
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set to run the benchmarks");
+    diesel::r2d2::Pool::builder()
+        .max_size(1)
+        .build(diesel::r2d2::ConnectionManager::new(database_url))
+        .expect("Could not connect to the database")
+        .get()
+        .expect("Could not connect to the database")
+}
+
+fn bench_users(c: &mut Criterion) {
+    let mut db = connect();
+    let Some(row) = User::paginate(&mut db, 0, 1, None).unwrap().items.pop() else {
+        eprintln!("skipping the `users` benchmarks: the table is empty");
+        return;
+    };
+
+    c.bench_function("users/read", |b| {
+        b.iter(|| User::read(&mut db, row.id.clone()).unwrap())
+    });
+
+    c.bench_function("users/paginate", |b| {
+        b.iter(|| User::paginate(&mut db, 0, 20, None).unwrap())
+    });
+
+    let mut i = 0;
+    c.bench_function("users/create", |b| {
+        b.iter(|| {
+            i += 1;
+            let _ = db.transaction::<(), diesel::result::Error, _>(|db| {
+                User::create(db, &CreateUser { email: format!("{} {i}", row.email), nickname: row.nickname.as_ref().map(|value| format!("{value} {i}")) })?;
+                Err(diesel::result::Error::RollbackTransaction)
+            });
+        })
+    });
+}
+
+fn bench_todos(c: &mut Criterion) {
+    let mut db = connect();
+    let Some(row) = Todo::paginate(&mut db, 0, 1, None).unwrap().items.pop() else {
+        eprintln!("skipping the `todos` benchmarks: the table is empty");
+        return;
+    };
+
+    c.bench_function("todos/read", |b| {
+        b.iter(|| Todo::read(&mut db, row.id.clone()).unwrap())
+    });
+
+    c.bench_function("todos/paginate", |b| {
+        b.iter(|| Todo::paginate(&mut db, 0, 20, None).unwrap())
+    });
+
+    let mut i = 0;
+    c.bench_function("todos/create", |b| {
+        b.iter(|| {
+            i += 1;
+            let _ = db.transaction::<(), diesel::result::Error, _>(|db| {
+                Todo::create(db, &CreateTodo { user_id: row.user_id.clone(), text: format!("{} {i}", row.text), completed: row.completed.clone() })?;
+                Err(diesel::result::Error::RollbackTransaction)
+            });
+        })
+    });
+}
+
+fn bench_todo_tags(c: &mut Criterion) {
+    let mut db = connect();
+    let Some(row) = TodoTag::paginate(&mut db, 0, 1, None).unwrap().items.pop() else {
+        eprintln!("skipping the `todo_tags` benchmarks: the table is empty");
+        return;
+    };
+
+    c.bench_function("todo_tags/read", |b| {
+        b.iter(|| TodoTag::read(&mut db, row.todo_id.clone(), row.tag.clone()).unwrap())
+    });
+
+    c.bench_function("todo_tags/paginate", |b| {
+        b.iter(|| TodoTag::paginate(&mut db, 0, 20, None).unwrap())
+    });
+
+    let mut i = 0;
+    c.bench_function("todo_tags/create", |b| {
+        b.iter(|| {
+            i += 1;
+            let _ = db.transaction::<(), diesel::result::Error, _>(|db| {
+                TodoTag::create(db, &CreateTodoTag { todo_id: row.todo_id.clone(), tag: format!("{} {i}", row.tag) })?;
+                Err(diesel::result::Error::RollbackTransaction)
+            });
+        })
+    });
+}
+
+fn bench_todo_counts(c: &mut Criterion) {
+    let mut db = connect();
+    let Some(row) = TodoCount::paginate(&mut db, 0, 1, None).unwrap().items.pop() else {
+        eprintln!("skipping the `todo_counts` benchmarks: the table is empty");
+        return;
+    };
+
+    c.bench_function("todo_counts/read", |b| {
+        b.iter(|| TodoCount::read(&mut db, row.user_id.clone()).unwrap())
+    });
+
+    c.bench_function("todo_counts/paginate", |b| {
+        b.iter(|| TodoCount::paginate(&mut db, 0, 20, None).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_users, bench_todos, bench_todo_tags, bench_todo_counts);
+criterion_main!(benches);
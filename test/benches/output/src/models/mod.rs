@@ -0,0 +1,4 @@
+pub mod users;
+pub mod todos;
+pub mod todo_tags;
+pub mod todo_counts;
@@ -0,0 +1,36 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        #[max_length = 64]
+        email -> Varchar,
+        nickname -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        user_id -> Int4,
+        text -> Text,
+        completed -> Bool,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    todo_tags (todo_id, tag) {
+        todo_id -> Int4,
+        tag -> Text,
+    }
+}
+
+diesel::table! {
+    todo_counts (user_id) {
+        user_id -> Int4,
+        count -> Int8,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
+diesel::joinable!(todo_tags -> todos (todo_id));
+diesel::allow_tables_to_appear_in_same_query!(todo_tags, todos, users);
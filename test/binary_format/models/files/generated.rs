@@ -0,0 +1,328 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `files` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `name` | `Text` | `String` |  |
+//! | `contents` | `Bytea` | `Vec<u8>` |  |
+//! | `thumbnail` | `Nullable<Bytea>` | `Option<Vec<u8>>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+/// (de)serializes binary values as base64 strings, rather than as arrays of numbers
+mod base64_serde {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub trait Base64: Sized {
+        type Repr: Serialize + for<'de> Deserialize<'de>;
+
+        fn encode(&self) -> Self::Repr;
+        fn decode(repr: Self::Repr) -> Result<Self, base64::DecodeError>;
+    }
+
+    impl Base64 for Vec<u8> {
+        type Repr = String;
+
+        fn encode(&self) -> Self::Repr {
+            STANDARD.encode(self)
+        }
+
+        fn decode(repr: Self::Repr) -> Result<Self, base64::DecodeError> {
+            STANDARD.decode(repr)
+        }
+    }
+
+    impl<T: Base64> Base64 for Option<T> {
+        type Repr = Option<T::Repr>;
+
+        fn encode(&self) -> Self::Repr {
+            self.as_ref().map(T::encode)
+        }
+
+        fn decode(repr: Self::Repr) -> Result<Self, base64::DecodeError> {
+            repr.map(T::decode).transpose()
+        }
+    }
+
+    pub fn serialize<T: Base64, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.encode().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Base64, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::decode(T::Repr::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=files, primary_key(id))]
+pub struct File {
+    pub id: i32,
+    pub name: String,
+    #[serde(with = "base64_serde")]
+    pub contents: Vec<u8>,
+    #[serde(default, with = "base64_serde")]
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=files)]
+pub struct CreateFile {
+    pub name: String,
+    #[serde(with = "base64_serde")]
+    pub contents: Vec<u8>,
+    #[serde(default, with = "base64_serde")]
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=files)]
+pub struct UpdateFile {
+    pub name: Option<String>,
+    #[serde(default, with = "base64_serde")]
+    pub contents: Option<Vec<u8>>,
+    #[serde(default, with = "base64_serde")]
+    pub thumbnail: Option<Option<Vec<u8>>>,
+}
+
+impl UpdateFile {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.contents.is_none()
+            && self.thumbnail.is_none()
+    }
+}
+
+/// Conditions on `files` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct FileFilter {
+    pub id: Option<i32>,
+    pub name: Option<String>,
+    #[serde(default, with = "base64_serde")]
+    pub contents: Option<Vec<u8>>,
+    #[serde(default, with = "base64_serde")]
+    pub thumbnail: Option<Option<Vec<u8>>>,
+}
+
+pub type FileFilterExpression = Box<dyn BoxableExpression<crate::schema::files::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl FileFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.name.is_none() && self.contents.is_none() && self.thumbnail.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> FileFilterExpression {
+        use crate::schema::files;
+
+        let mut expression: FileFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(files::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(files::name.eq(value.clone())));
+        }
+        if let Some(value) = &self.contents {
+            expression = Box::new(expression.and(files::contents.eq(value.clone())));
+        }
+        match &self.thumbnail {
+            Some(Some(value)) => expression = Box::new(expression.and(files::thumbnail.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(files::thumbnail.is_null())),
+            None => {}
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `files` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileColumn {
+    Id,
+    Name,
+    Contents,
+    Thumbnail,
+}
+
+impl File {
+
+    pub fn create(db: &mut Connection, item: &CreateFile) -> QueryResult<Self> {
+        use crate::schema::files;
+
+        insert_into(files::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::files;
+
+        files::table.filter(files::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`files::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(FileColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::files;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = files::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = files::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((FileColumn::Id, SortDirection::Asc)) => query.order(files::id.asc()),
+            Some((FileColumn::Id, SortDirection::Desc)) => query.order(files::id.desc()),
+            Some((FileColumn::Name, SortDirection::Asc)) => query.order((files::name.asc(), files::id.asc())),
+            Some((FileColumn::Name, SortDirection::Desc)) => query.order((files::name.desc(), files::id.asc())),
+            Some((FileColumn::Contents, SortDirection::Asc)) => query.order((files::contents.asc(), files::id.asc())),
+            Some((FileColumn::Contents, SortDirection::Desc)) => query.order((files::contents.desc(), files::id.asc())),
+            Some((FileColumn::Thumbnail, SortDirection::Asc)) => query.order((files::thumbnail.asc(), files::id.asc())),
+            Some((FileColumn::Thumbnail, SortDirection::Desc)) => query.order((files::thumbnail.desc(), files::id.asc())),
+            None => query.order(files::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::files;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = files::table.order(files::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::files;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = files::table.order(files::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(files::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateFile) -> QueryResult<Self> {
+        use crate::schema::files;
+
+        if item.is_empty() {
+            return files::table.filter(files::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(files::table.filter(files::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &FileFilter, changes: &UpdateFile) -> QueryResult<Vec<Self>> {
+        use crate::schema::files;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return files::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(files::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::files;
+
+        diesel::delete(files::table.filter(files::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &FileFilter) -> QueryResult<usize> {
+        use crate::schema::files;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(files::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+diesel::table! {
+    files (id) {
+        id -> Int4,
+        name -> Text,
+        contents -> Bytea,
+        thumbnail -> Nullable<Bytea>,
+    }
+}
@@ -0,0 +1,9 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        assignee -> Nullable<Text>,
+        due_at -> Nullable<Timestamptz>,
+        completed -> Bool,
+    }
+}
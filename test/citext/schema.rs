@@ -0,0 +1,8 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        email -> Citext,
+        username -> Text,
+        nickname -> Nullable<Citext>,
+    }
+}
@@ -1,5 +1,17 @@
 /* This file is generated and managed by dsync */
 
+//! Model for the `todos` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `text` | `Text` | `String` |  |
+//! | `completed` | `Bool` | `bool` |  |
+//! | `created_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+//! | `updated_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+
 use crate::diesel::*;
 use crate::schema::*;
 use diesel::QueryResult;
@@ -8,7 +20,7 @@ use serde::{Deserialize, Serialize};
 
 type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Insertable, AsChangeset, Selectable)]
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
 #[diesel(table_name=todos, primary_key(id))]
 pub struct Todo {
     pub id: i32,
@@ -18,7 +30,7 @@ pub struct Todo {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Insertable, AsChangeset)]
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
 #[diesel(table_name=todos)]
 pub struct CreateTodo {
     pub id: i32,
@@ -26,7 +38,7 @@ pub struct CreateTodo {
     pub completed: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Insertable, AsChangeset)]
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
 #[diesel(table_name=todos)]
 pub struct UpdateTodo {
     pub text: Option<String>,
@@ -35,6 +47,59 @@ pub struct UpdateTodo {
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl UpdateTodo {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none()
+            && self.completed.is_none()
+            && self.created_at.is_none()
+            && self.updated_at.is_none()
+    }
+}
+
+/// Conditions on `todos` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TodoFilter {
+    pub id: Option<i32>,
+    pub text: Option<String>,
+    pub completed: Option<bool>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub type TodoFilterExpression = Box<dyn BoxableExpression<crate::schema::todos::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl TodoFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.text.is_none() && self.completed.is_none() && self.created_at.is_none() && self.updated_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> TodoFilterExpression {
+        use crate::schema::todos;
+
+        let mut expression: TodoFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(todos::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.text {
+            expression = Box::new(expression.and(todos::text.eq(value.clone())));
+        }
+        if let Some(value) = &self.completed {
+            expression = Box::new(expression.and(todos::completed.eq(value.clone())));
+        }
+        if let Some(value) = &self.created_at {
+            expression = Box::new(expression.and(todos::created_at.eq(value.clone())));
+        }
+        if let Some(value) = &self.updated_at {
+            expression = Box::new(expression.and(todos::updated_at.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
 
 #[derive(Debug, Serialize)]
 pub struct PaginationResult<T> {
@@ -46,27 +111,71 @@ pub struct PaginationResult<T> {
     pub num_pages: i64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `todos` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoColumn {
+    Id,
+    Text,
+    Completed,
+    CreatedAt,
+    UpdatedAt,
+}
+
 impl Todo {
 
     pub fn create(db: &mut Connection, item: &CreateTodo) -> QueryResult<Self> {
-        use crate::schema::todos::dsl::*;
+        use crate::schema::todos;
 
-        insert_into(todos).values(item).get_result::<Self>(db)
+        insert_into(todos::table).values(item).get_result::<Self>(db)
     }
 
     pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
-        use crate::schema::todos::dsl::*;
+        use crate::schema::todos;
 
-        todos.filter(id.eq(param_id)).first::<Self>(db)
+        todos::table.filter(todos::id.eq(param_id)).first::<Self>(db)
     }
 
-    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page)
-    pub fn paginate(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<PaginationResult<Self>> {
-        use crate::schema::todos::dsl::*;
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`todos::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(TodoColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::todos;
 
         let page_size = if page_size < 1 { 1 } else { page_size };
-        let total_items = todos.count().get_result(db)?;
-        let items = todos.limit(page_size).offset(page * page_size).load::<Self>(db)?;
+        let total_items = todos::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = todos::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((TodoColumn::Id, SortDirection::Asc)) => query.order(todos::id.asc()),
+            Some((TodoColumn::Id, SortDirection::Desc)) => query.order(todos::id.desc()),
+            Some((TodoColumn::Text, SortDirection::Asc)) => query.order((todos::text.asc(), todos::id.asc())),
+            Some((TodoColumn::Text, SortDirection::Desc)) => query.order((todos::text.desc(), todos::id.asc())),
+            Some((TodoColumn::Completed, SortDirection::Asc)) => query.order((todos::completed.asc(), todos::id.asc())),
+            Some((TodoColumn::Completed, SortDirection::Desc)) => query.order((todos::completed.desc(), todos::id.asc())),
+            Some((TodoColumn::CreatedAt, SortDirection::Asc)) => query.order((todos::created_at.asc(), todos::id.asc())),
+            Some((TodoColumn::CreatedAt, SortDirection::Desc)) => query.order((todos::created_at.desc(), todos::id.asc())),
+            Some((TodoColumn::UpdatedAt, SortDirection::Asc)) => query.order((todos::updated_at.asc(), todos::id.asc())),
+            Some((TodoColumn::UpdatedAt, SortDirection::Desc)) => query.order((todos::updated_at.desc(), todos::id.asc())),
+            None => query.order(todos::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
 
         Ok(PaginationResult {
             items,
@@ -78,16 +187,96 @@ impl Todo {
         })
     }
 
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::todos;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = todos::table.order(todos::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::todos;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = todos::table.order(todos::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(todos::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
     pub fn update(db: &mut Connection, param_id: i32, item: &UpdateTodo) -> QueryResult<Self> {
-        use crate::schema::todos::dsl::*;
+        use crate::schema::todos;
+
+        if item.is_empty() {
+            return todos::table.filter(todos::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(todos::table.filter(todos::id.eq(param_id))).set(item).get_result(db)
+    }
 
-        diesel::update(todos.filter(id.eq(param_id))).set(item).get_result(db)
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &TodoFilter, changes: &UpdateTodo) -> QueryResult<Vec<Self>> {
+        use crate::schema::todos;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return todos::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(todos::table.filter(filter.expression())).set(changes).get_results(db)
     }
 
     pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
-        use crate::schema::todos::dsl::*;
+        use crate::schema::todos;
+
+        diesel::delete(todos::table.filter(todos::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &TodoFilter) -> QueryResult<usize> {
+        use crate::schema::todos;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
 
-        diesel::delete(todos.filter(id.eq(param_id))).execute(db)
+        diesel::delete(todos::table.filter(filter.expression())).execute(db)
     }
 
 }
\ No newline at end of file
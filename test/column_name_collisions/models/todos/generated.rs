@@ -0,0 +1,440 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `todos` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `param_id` | `Int4` | `i32` |  |
+//! | `page` | `Int4` | `i32` |  |
+//! | `page_size` | `Int4` | `i32` |  |
+//! | `db` | `Text` | `String` |  |
+//! | `item` | `Text` | `String` |  |
+//! | `query` | `Text` | `String` |  |
+//! | `filter` | `Text` | `String` |  |
+//! | `count` | `Int4` | `i32` |  |
+//! | `total_items` | `Int8` | `i64` |  |
+//! | `changes` | `Text` | `String` |  |
+//! | `items` | `Text` | `String` |  |
+//! | `num_pages` | `Int8` | `i64` |  |
+//! | `has_next` | `Bool` | `bool` |  |
+//! | `order` | `Text` | `String` |  |
+//! | `cursor` | `Text` | `String` |  |
+//! | `name` | `Text` | `String` |  |
+//! | `value` | `Text` | `String` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=todos, primary_key(id))]
+pub struct Todo {
+    pub id: i32,
+    pub param_id: i32,
+    pub page: i32,
+    pub page_size: i32,
+    pub db: String,
+    pub item: String,
+    pub query: String,
+    pub filter: String,
+    pub count: i32,
+    pub total_items: i64,
+    pub changes: String,
+    pub items: String,
+    pub num_pages: i64,
+    pub has_next: bool,
+    pub order: String,
+    pub cursor: String,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=todos)]
+pub struct CreateTodo {
+    pub id: i32,
+    pub param_id: i32,
+    pub page: i32,
+    pub page_size: i32,
+    pub db: String,
+    pub item: String,
+    pub query: String,
+    pub filter: String,
+    pub count: i32,
+    pub total_items: i64,
+    pub changes: String,
+    pub items: String,
+    pub num_pages: i64,
+    pub has_next: bool,
+    pub order: String,
+    pub cursor: String,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=todos)]
+pub struct UpdateTodo {
+    pub param_id: Option<i32>,
+    pub page: Option<i32>,
+    pub page_size: Option<i32>,
+    pub db: Option<String>,
+    pub item: Option<String>,
+    pub query: Option<String>,
+    pub filter: Option<String>,
+    pub count: Option<i32>,
+    pub total_items: Option<i64>,
+    pub changes: Option<String>,
+    pub items: Option<String>,
+    pub num_pages: Option<i64>,
+    pub has_next: Option<bool>,
+    pub order: Option<String>,
+    pub cursor: Option<String>,
+    pub name: Option<String>,
+    pub value: Option<String>,
+}
+
+impl UpdateTodo {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.param_id.is_none()
+            && self.page.is_none()
+            && self.page_size.is_none()
+            && self.db.is_none()
+            && self.item.is_none()
+            && self.query.is_none()
+            && self.filter.is_none()
+            && self.count.is_none()
+            && self.total_items.is_none()
+            && self.changes.is_none()
+            && self.items.is_none()
+            && self.num_pages.is_none()
+            && self.has_next.is_none()
+            && self.order.is_none()
+            && self.cursor.is_none()
+            && self.name.is_none()
+            && self.value.is_none()
+    }
+}
+
+/// Conditions on `todos` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TodoFilter {
+    pub id: Option<i32>,
+    pub param_id: Option<i32>,
+    pub page: Option<i32>,
+    pub page_size: Option<i32>,
+    pub db: Option<String>,
+    pub item: Option<String>,
+    pub query: Option<String>,
+    pub filter: Option<String>,
+    pub count: Option<i32>,
+    pub total_items: Option<i64>,
+    pub changes: Option<String>,
+    pub items: Option<String>,
+    pub num_pages: Option<i64>,
+    pub has_next: Option<bool>,
+    pub order: Option<String>,
+    pub cursor: Option<String>,
+    pub name: Option<String>,
+    pub value: Option<String>,
+}
+
+pub type TodoFilterExpression = Box<dyn BoxableExpression<crate::schema::todos::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl TodoFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.param_id.is_none() && self.page.is_none() && self.page_size.is_none() && self.db.is_none() && self.item.is_none() && self.query.is_none() && self.filter.is_none() && self.count.is_none() && self.total_items.is_none() && self.changes.is_none() && self.items.is_none() && self.num_pages.is_none() && self.has_next.is_none() && self.order.is_none() && self.cursor.is_none() && self.name.is_none() && self.value.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> TodoFilterExpression {
+        use crate::schema::todos;
+
+        let mut expression: TodoFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(todos::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.param_id {
+            expression = Box::new(expression.and(todos::param_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.page {
+            expression = Box::new(expression.and(todos::page.eq(value.clone())));
+        }
+        if let Some(value) = &self.page_size {
+            expression = Box::new(expression.and(todos::page_size.eq(value.clone())));
+        }
+        if let Some(value) = &self.db {
+            expression = Box::new(expression.and(todos::db.eq(value.clone())));
+        }
+        if let Some(value) = &self.item {
+            expression = Box::new(expression.and(todos::item.eq(value.clone())));
+        }
+        if let Some(value) = &self.query {
+            expression = Box::new(expression.and(todos::query.eq(value.clone())));
+        }
+        if let Some(value) = &self.filter {
+            expression = Box::new(expression.and(todos::filter.eq(value.clone())));
+        }
+        if let Some(value) = &self.count {
+            expression = Box::new(expression.and(todos::count.eq(value.clone())));
+        }
+        if let Some(value) = &self.total_items {
+            expression = Box::new(expression.and(todos::total_items.eq(value.clone())));
+        }
+        if let Some(value) = &self.changes {
+            expression = Box::new(expression.and(todos::changes.eq(value.clone())));
+        }
+        if let Some(value) = &self.items {
+            expression = Box::new(expression.and(todos::items.eq(value.clone())));
+        }
+        if let Some(value) = &self.num_pages {
+            expression = Box::new(expression.and(todos::num_pages.eq(value.clone())));
+        }
+        if let Some(value) = &self.has_next {
+            expression = Box::new(expression.and(todos::has_next.eq(value.clone())));
+        }
+        if let Some(value) = &self.order {
+            expression = Box::new(expression.and(todos::order.eq(value.clone())));
+        }
+        if let Some(value) = &self.cursor {
+            expression = Box::new(expression.and(todos::cursor.eq(value.clone())));
+        }
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(todos::name.eq(value.clone())));
+        }
+        if let Some(value) = &self.value {
+            expression = Box::new(expression.and(todos::value.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `todos` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoColumn {
+    Id,
+    ParamId,
+    Page,
+    PageSize,
+    Db,
+    Item,
+    Query,
+    Filter,
+    Count,
+    TotalItems,
+    Changes,
+    Items,
+    NumPages,
+    HasNext,
+    Order,
+    Cursor,
+    Name,
+    Value,
+}
+
+impl Todo {
+
+    pub fn create(db: &mut Connection, item: &CreateTodo) -> QueryResult<Self> {
+        use crate::schema::todos;
+
+        insert_into(todos::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::todos;
+
+        todos::table.filter(todos::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`todos::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(TodoColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::todos;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = todos::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = todos::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((TodoColumn::Id, SortDirection::Asc)) => query.order(todos::id.asc()),
+            Some((TodoColumn::Id, SortDirection::Desc)) => query.order(todos::id.desc()),
+            Some((TodoColumn::ParamId, SortDirection::Asc)) => query.order((todos::param_id.asc(), todos::id.asc())),
+            Some((TodoColumn::ParamId, SortDirection::Desc)) => query.order((todos::param_id.desc(), todos::id.asc())),
+            Some((TodoColumn::Page, SortDirection::Asc)) => query.order((todos::page.asc(), todos::id.asc())),
+            Some((TodoColumn::Page, SortDirection::Desc)) => query.order((todos::page.desc(), todos::id.asc())),
+            Some((TodoColumn::PageSize, SortDirection::Asc)) => query.order((todos::page_size.asc(), todos::id.asc())),
+            Some((TodoColumn::PageSize, SortDirection::Desc)) => query.order((todos::page_size.desc(), todos::id.asc())),
+            Some((TodoColumn::Db, SortDirection::Asc)) => query.order((todos::db.asc(), todos::id.asc())),
+            Some((TodoColumn::Db, SortDirection::Desc)) => query.order((todos::db.desc(), todos::id.asc())),
+            Some((TodoColumn::Item, SortDirection::Asc)) => query.order((todos::item.asc(), todos::id.asc())),
+            Some((TodoColumn::Item, SortDirection::Desc)) => query.order((todos::item.desc(), todos::id.asc())),
+            Some((TodoColumn::Query, SortDirection::Asc)) => query.order((todos::query.asc(), todos::id.asc())),
+            Some((TodoColumn::Query, SortDirection::Desc)) => query.order((todos::query.desc(), todos::id.asc())),
+            Some((TodoColumn::Filter, SortDirection::Asc)) => query.order((todos::filter.asc(), todos::id.asc())),
+            Some((TodoColumn::Filter, SortDirection::Desc)) => query.order((todos::filter.desc(), todos::id.asc())),
+            Some((TodoColumn::Count, SortDirection::Asc)) => query.order((todos::count.asc(), todos::id.asc())),
+            Some((TodoColumn::Count, SortDirection::Desc)) => query.order((todos::count.desc(), todos::id.asc())),
+            Some((TodoColumn::TotalItems, SortDirection::Asc)) => query.order((todos::total_items.asc(), todos::id.asc())),
+            Some((TodoColumn::TotalItems, SortDirection::Desc)) => query.order((todos::total_items.desc(), todos::id.asc())),
+            Some((TodoColumn::Changes, SortDirection::Asc)) => query.order((todos::changes.asc(), todos::id.asc())),
+            Some((TodoColumn::Changes, SortDirection::Desc)) => query.order((todos::changes.desc(), todos::id.asc())),
+            Some((TodoColumn::Items, SortDirection::Asc)) => query.order((todos::items.asc(), todos::id.asc())),
+            Some((TodoColumn::Items, SortDirection::Desc)) => query.order((todos::items.desc(), todos::id.asc())),
+            Some((TodoColumn::NumPages, SortDirection::Asc)) => query.order((todos::num_pages.asc(), todos::id.asc())),
+            Some((TodoColumn::NumPages, SortDirection::Desc)) => query.order((todos::num_pages.desc(), todos::id.asc())),
+            Some((TodoColumn::HasNext, SortDirection::Asc)) => query.order((todos::has_next.asc(), todos::id.asc())),
+            Some((TodoColumn::HasNext, SortDirection::Desc)) => query.order((todos::has_next.desc(), todos::id.asc())),
+            Some((TodoColumn::Order, SortDirection::Asc)) => query.order((todos::order.asc(), todos::id.asc())),
+            Some((TodoColumn::Order, SortDirection::Desc)) => query.order((todos::order.desc(), todos::id.asc())),
+            Some((TodoColumn::Cursor, SortDirection::Asc)) => query.order((todos::cursor.asc(), todos::id.asc())),
+            Some((TodoColumn::Cursor, SortDirection::Desc)) => query.order((todos::cursor.desc(), todos::id.asc())),
+            Some((TodoColumn::Name, SortDirection::Asc)) => query.order((todos::name.asc(), todos::id.asc())),
+            Some((TodoColumn::Name, SortDirection::Desc)) => query.order((todos::name.desc(), todos::id.asc())),
+            Some((TodoColumn::Value, SortDirection::Asc)) => query.order((todos::value.asc(), todos::id.asc())),
+            Some((TodoColumn::Value, SortDirection::Desc)) => query.order((todos::value.desc(), todos::id.asc())),
+            None => query.order(todos::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::todos;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = todos::table.order(todos::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::todos;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = todos::table.order(todos::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(todos::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateTodo) -> QueryResult<Self> {
+        use crate::schema::todos;
+
+        if item.is_empty() {
+            return todos::table.filter(todos::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(todos::table.filter(todos::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &TodoFilter, changes: &UpdateTodo) -> QueryResult<Vec<Self>> {
+        use crate::schema::todos;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return todos::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(todos::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::todos;
+
+        diesel::delete(todos::table.filter(todos::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &TodoFilter) -> QueryResult<usize> {
+        use crate::schema::todos;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(todos::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        param_id -> Int4,
+        page -> Int4,
+        page_size -> Int4,
+        db -> Text,
+        item -> Text,
+        query -> Text,
+        filter -> Text,
+        count -> Int4,
+        total_items -> Int8,
+        changes -> Text,
+        items -> Text,
+        num_pages -> Int8,
+        has_next -> Bool,
+        order -> Text,
+        cursor -> Text,
+        name -> Text,
+        value -> Text,
+    }
+}
@@ -0,0 +1,14 @@
+#!/bin/bash
+# type-checks the generated crate with its default features and with each of its connection features (needs the
+# backends' native libraries and network access, so it isn't run by `test_all.sh`)
+
+set -e
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR/output
+
+cargo check
+for FEATURE in $(sed -n '/^\[features\]/,/^\[/s/^\([a-z_]*\) = .*/\1/p' Cargo.toml); do
+    cargo check --features "$FEATURE"
+done
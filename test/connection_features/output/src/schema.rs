@@ -0,0 +1,9 @@
+/* This file is generated and managed by dsync */
+
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        completed -> Bool,
+    }
+}
@@ -0,0 +1,7 @@
+#!/bin/bash
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR
+
+cargo run -- -i schema.rs -o output --crate-name todo_models -g id -c "diesel::PgConnection" --connection-feature sqlite=diesel::SqliteConnection
\ No newline at end of file
@@ -0,0 +1,12 @@
+diesel::table! {
+    #[sql_name = "TodoItems"]
+    todos (todoId) {
+        #[sql_name = "TodoId"]
+        todoId -> Int4,
+        #[sql_name = "Type"]
+        r#type -> Text,
+        #[sql_name = "Text"]
+        text -> Text,
+        createdAt -> Timestamptz,
+    }
+}
@@ -0,0 +1,36 @@
+/* This file is generated and managed by dsync */
+
+//! Connects to the database configured by the `DATABASE_URL` environment variable.
+
+use crate::diesel::*;
+use diesel::QueryResult;
+
+pub type DbError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The url of the database, from the `DATABASE_URL` environment variable
+pub fn database_url() -> Result<String, DbError> {
+    std::env::var("DATABASE_URL").map_err(|_| "DATABASE_URL must be set".into())
+}
+
+/// Connects to the database at `DATABASE_URL`
+pub fn establish_connection() -> Result<diesel::PgConnection, DbError> {
+    Ok(<diesel::PgConnection as diesel::Connection>::establish(&database_url()?)?)
+}
+
+pub type Pool = diesel::r2d2::Pool<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+/// Creates a connection pool for the database at `DATABASE_URL`, with up to `DATABASE_POOL_SIZE` connections (r2d2's
+/// default of 10 if it isn't set)
+pub fn create_pool() -> Result<Pool, DbError> {
+    let mut builder = Pool::builder();
+    if let Ok(pool_size) = std::env::var("DATABASE_POOL_SIZE") {
+        builder = builder.max_size(pool_size.parse()?);
+    }
+
+    Ok(builder.build(diesel::r2d2::ConnectionManager::new(database_url()?))?)
+}
+
+/// Checks that the database answers queries (pooled connections can be passed too), for health check endpoints
+pub fn ping(db: &mut diesel::PgConnection) -> QueryResult<()> {
+    diesel::sql_query("SELECT 1").execute(db).map(|_| ())
+}
@@ -0,0 +1,267 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `comments` table.
+//!
+//! - primary key: `id`
+//! - foreign key: `todo_id` references `todos`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `todo_id` | `Int4` | `i32` |  |
+//! | `body` | `Text` | `String` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::models::todos::Todo;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Selectable)]
+#[diesel(table_name=comments, primary_key(id), belongs_to(Todo, foreign_key=todo_id))]
+pub struct Comment {
+    pub id: i32,
+    pub todo_id: i32,
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=comments)]
+pub struct CreateComment {
+    pub todo_id: i32,
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=comments)]
+pub struct UpdateComment {
+    pub todo_id: Option<i32>,
+    pub body: Option<String>,
+}
+
+impl UpdateComment {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.todo_id.is_none()
+            && self.body.is_none()
+    }
+}
+
+/// Conditions on `comments` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct CommentFilter {
+    pub id: Option<i32>,
+    pub todo_id: Option<i32>,
+    pub body: Option<String>,
+}
+
+pub type CommentFilterExpression = Box<dyn BoxableExpression<crate::schema::comments::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl CommentFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.todo_id.is_none() && self.body.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> CommentFilterExpression {
+        use crate::schema::comments;
+
+        let mut expression: CommentFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(comments::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.todo_id {
+            expression = Box::new(expression.and(comments::todo_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.body {
+            expression = Box::new(expression.and(comments::body.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `comments` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentColumn {
+    Id,
+    TodoId,
+    Body,
+}
+
+impl Comment {
+
+    pub fn create(db: &mut Connection, item: &CreateComment) -> QueryResult<Self> {
+        use crate::schema::comments;
+
+        insert_into(comments::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::comments;
+
+        comments::table.filter(comments::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`comments::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(CommentColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::comments;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = comments::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = comments::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((CommentColumn::Id, SortDirection::Asc)) => query.order(comments::id.asc()),
+            Some((CommentColumn::Id, SortDirection::Desc)) => query.order(comments::id.desc()),
+            Some((CommentColumn::TodoId, SortDirection::Asc)) => query.order((comments::todo_id.asc(), comments::id.asc())),
+            Some((CommentColumn::TodoId, SortDirection::Desc)) => query.order((comments::todo_id.desc(), comments::id.asc())),
+            Some((CommentColumn::Body, SortDirection::Asc)) => query.order((comments::body.asc(), comments::id.asc())),
+            Some((CommentColumn::Body, SortDirection::Desc)) => query.order((comments::body.desc(), comments::id.asc())),
+            None => query.order(comments::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::comments;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = comments::table.order(comments::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::comments;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = comments::table.order(comments::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(comments::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateComment) -> QueryResult<Self> {
+        use crate::schema::comments;
+
+        if item.is_empty() {
+            return comments::table.filter(comments::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(comments::table.filter(comments::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &CommentFilter, changes: &UpdateComment) -> QueryResult<Vec<Self>> {
+        use crate::schema::comments;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return comments::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(comments::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::comments;
+
+        diesel::delete(comments::table.filter(comments::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &CommentFilter) -> QueryResult<usize> {
+        use crate::schema::comments;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(comments::table.filter(filter.expression())).execute(db)
+    }
+
+    /// Loads rows along with their associated `Todo` (inner join on `todo_id`)
+    pub fn with_todo(db: &mut Connection) -> QueryResult<Vec<(Self, Todo)>> {
+        use crate::schema::{comments, todos};
+
+        comments::table.inner_join(todos::table).select((Self::as_select(), Todo::as_select())).load::<(Self, Todo)>(db)
+    }
+
+}
\ No newline at end of file
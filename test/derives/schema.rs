@@ -0,0 +1,21 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+    }
+}
+
+diesel::table! {
+    comments (id) {
+        id -> Int4,
+        todo_id -> Int4,
+        body -> Text,
+    }
+}
+
+diesel::joinable!(comments -> todos (todo_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    comments,
+    todos,
+);
@@ -0,0 +1,3 @@
+pub mod users;
+pub mod teams;
+pub mod todos;
@@ -0,0 +1,23 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        team_id -> Int4,
+    }
+}
+diesel::table! {
+    teams (id) {
+        id -> Int4,
+        best_todo_id -> Int4,
+    }
+}
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        user_id -> Int4,
+        parent_id -> Nullable<Int4>,
+    }
+}
+diesel::joinable!(todos -> users (user_id));
+diesel::joinable!(users -> teams (team_id));
+diesel::joinable!(teams -> todos (best_todo_id));
+diesel::allow_tables_to_appear_in_same_query!(todos, users, teams);
@@ -0,0 +1,22 @@
+/* This file is generated and managed by dsync */
+
+//! Embeds the migrations in `migrations` into the crate, so databases can be migrated (for example, on startup)
+//! without the diesel CLI.
+
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+/// The migrations in `migrations` (relative to the crate), read at compile time
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+pub type MigrationError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Runs the migrations which haven't been applied yet, returning the versions of the migrations it ran
+pub fn run_pending_migrations(db: &mut Connection) -> Result<Vec<String>, MigrationError> {
+    Ok(db
+        .run_pending_migrations(MIGRATIONS)?
+        .iter()
+        .map(|version| version.to_string())
+        .collect())
+}
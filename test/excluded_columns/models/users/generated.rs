@@ -0,0 +1,279 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `users` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `email` | `Text` | `String` |  |
+//! | `password_hash` | `Text` | `String` |  |
+//! | `created_by` | `Nullable<Int4>` | `Option<i32>` |  |
+//! | `created_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=users, primary_key(id))]
+pub struct User {
+    pub id: i32,
+    pub email: String,
+    pub created_by: Option<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=users)]
+pub struct CreateUser {
+    pub email: String,
+    pub password_hash: String,
+    pub created_by: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=users)]
+pub struct UpdateUser {
+    pub email: Option<String>,
+    pub password_hash: Option<String>,
+}
+
+impl UpdateUser {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.email.is_none()
+            && self.password_hash.is_none()
+    }
+}
+
+/// Conditions on `users` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct UserFilter {
+    pub id: Option<i32>,
+    pub email: Option<String>,
+    pub password_hash: Option<String>,
+    pub created_by: Option<Option<i32>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub type UserFilterExpression = Box<dyn BoxableExpression<crate::schema::users::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl UserFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.email.is_none() && self.password_hash.is_none() && self.created_by.is_none() && self.created_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> UserFilterExpression {
+        use crate::schema::users;
+
+        let mut expression: UserFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(users::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.email {
+            expression = Box::new(expression.and(users::email.eq(value.clone())));
+        }
+        if let Some(value) = &self.password_hash {
+            expression = Box::new(expression.and(users::password_hash.eq(value.clone())));
+        }
+        match &self.created_by {
+            Some(Some(value)) => expression = Box::new(expression.and(users::created_by.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(users::created_by.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.created_at {
+            expression = Box::new(expression.and(users::created_at.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `users` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserColumn {
+    Id,
+    Email,
+    PasswordHash,
+    CreatedBy,
+    CreatedAt,
+}
+
+impl User {
+
+    pub fn create(db: &mut Connection, item: &CreateUser) -> QueryResult<Self> {
+        use crate::schema::users;
+
+        insert_into(users::table).values(item).returning(Self::as_returning()).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::users;
+
+        users::table.filter(users::id.eq(param_id)).select(Self::as_select()).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`users::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(UserColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::users;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = users::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = users::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((UserColumn::Id, SortDirection::Asc)) => query.order(users::id.asc()),
+            Some((UserColumn::Id, SortDirection::Desc)) => query.order(users::id.desc()),
+            Some((UserColumn::Email, SortDirection::Asc)) => query.order((users::email.asc(), users::id.asc())),
+            Some((UserColumn::Email, SortDirection::Desc)) => query.order((users::email.desc(), users::id.asc())),
+            Some((UserColumn::PasswordHash, SortDirection::Asc)) => query.order((users::password_hash.asc(), users::id.asc())),
+            Some((UserColumn::PasswordHash, SortDirection::Desc)) => query.order((users::password_hash.desc(), users::id.asc())),
+            Some((UserColumn::CreatedBy, SortDirection::Asc)) => query.order((users::created_by.asc(), users::id.asc())),
+            Some((UserColumn::CreatedBy, SortDirection::Desc)) => query.order((users::created_by.desc(), users::id.asc())),
+            Some((UserColumn::CreatedAt, SortDirection::Asc)) => query.order((users::created_at.asc(), users::id.asc())),
+            Some((UserColumn::CreatedAt, SortDirection::Desc)) => query.order((users::created_at.desc(), users::id.asc())),
+            None => query.order(users::id.asc()),
+        };
+        let items = query.select(Self::as_select()).load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::users;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = users::table.order(users::id.asc()).limit(page_size.saturating_add(1)).offset(offset).select(Self::as_select()).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::users;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = users::table.order(users::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(users::id.gt(&last.id));
+            }
+            let items = query.select(Self::as_select()).load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateUser) -> QueryResult<Self> {
+        use crate::schema::users;
+
+        if item.is_empty() {
+            return users::table.filter(users::id.eq(param_id)).select(Self::as_select()).first::<Self>(db);
+        }
+
+        diesel::update(users::table.filter(users::id.eq(param_id))).set(item).returning(Self::as_returning()).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &UserFilter, changes: &UpdateUser) -> QueryResult<Vec<Self>> {
+        use crate::schema::users;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return users::table.filter(filter.expression()).select(Self::as_select()).load::<Self>(db);
+        }
+
+        diesel::update(users::table.filter(filter.expression())).set(changes).returning(Self::as_returning()).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::users;
+
+        diesel::delete(users::table.filter(users::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &UserFilter) -> QueryResult<usize> {
+        use crate::schema::users;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(users::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+#!/bin/bash
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR
+
+cargo run -- explain > options.txt
+cargo run -- explain save > save.txt
+cargo run -- --explain default-order > default_order.txt
\ No newline at end of file
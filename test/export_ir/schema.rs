@@ -0,0 +1,22 @@
+diesel::table! {
+    /// People who can be assigned todos
+    users (id) {
+        id -> Int4,
+        /// Display name (first | last)
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    #[sql_name = "TodoItems"]
+    todos (id) {
+        id -> Int4,
+        user_id -> Int4,
+        /// What needs to be done
+        text -> Text,
+        due_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
+diesel::allow_tables_to_appear_in_same_query!(todos, users);
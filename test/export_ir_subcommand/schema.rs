@@ -0,0 +1,22 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+        email -> Text,
+        age -> Nullable<Int8>,
+        about -> Text,
+    }
+}
+
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 200]
+        title -> Varchar,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
+
+diesel::allow_tables_to_appear_in_same_query!(todos, users,);
@@ -0,0 +1,273 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `tags` table.
+//!
+//! - primary key: `slug`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `name` | `Text` | `String` |  |
+//! | `slug` | `Text` | `String` |  |
+//! | `color` | `Nullable<Text>` | `Option<String>` |  |
+//! | `created_at` | `Timestamp` | `chrono::NaiveDateTime` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=tags, primary_key(slug))]
+pub struct Tag {
+    pub color: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=tags)]
+pub struct CreateTag {
+    pub color: Option<String>,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=tags)]
+pub struct UpdateTag {
+    pub color: Option<Option<String>>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub name: Option<String>,
+}
+
+impl UpdateTag {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none()
+            && self.created_at.is_none()
+            && self.name.is_none()
+    }
+}
+
+/// Conditions on `tags` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TagFilter {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub color: Option<Option<String>>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+pub type TagFilterExpression = Box<dyn BoxableExpression<crate::schema::tags::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl TagFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.slug.is_none() && self.color.is_none() && self.created_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> TagFilterExpression {
+        use crate::schema::tags;
+
+        let mut expression: TagFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(tags::name.eq(value.clone())));
+        }
+        if let Some(value) = &self.slug {
+            expression = Box::new(expression.and(tags::slug.eq(value.clone())));
+        }
+        match &self.color {
+            Some(Some(value)) => expression = Box::new(expression.and(tags::color.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(tags::color.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.created_at {
+            expression = Box::new(expression.and(tags::created_at.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `tags` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagColumn {
+    Name,
+    Slug,
+    Color,
+    CreatedAt,
+}
+
+impl Tag {
+
+    pub fn create(db: &mut Connection, item: &CreateTag) -> QueryResult<Self> {
+        use crate::schema::tags;
+
+        insert_into(tags::table).values(item).returning(Self::as_returning()).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_slug: String) -> QueryResult<Self> {
+        use crate::schema::tags;
+
+        tags::table.filter(tags::slug.eq(param_slug)).select(Self::as_select()).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`tags::slug.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(TagColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::tags;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = tags::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = tags::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((TagColumn::Name, SortDirection::Asc)) => query.order((tags::name.asc(), tags::slug.asc())),
+            Some((TagColumn::Name, SortDirection::Desc)) => query.order((tags::name.desc(), tags::slug.asc())),
+            Some((TagColumn::Slug, SortDirection::Asc)) => query.order(tags::slug.asc()),
+            Some((TagColumn::Slug, SortDirection::Desc)) => query.order(tags::slug.desc()),
+            Some((TagColumn::Color, SortDirection::Asc)) => query.order((tags::color.asc(), tags::slug.asc())),
+            Some((TagColumn::Color, SortDirection::Desc)) => query.order((tags::color.desc(), tags::slug.asc())),
+            Some((TagColumn::CreatedAt, SortDirection::Asc)) => query.order((tags::created_at.asc(), tags::slug.asc())),
+            Some((TagColumn::CreatedAt, SortDirection::Desc)) => query.order((tags::created_at.desc(), tags::slug.asc())),
+            None => query.order(tags::slug.asc()),
+        };
+        let items = query.select(Self::as_select()).load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::tags;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = tags::table.order(tags::slug.asc()).limit(page_size.saturating_add(1)).offset(offset).select(Self::as_select()).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::tags;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = tags::table.order(tags::slug.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(tags::slug.gt(&last.slug));
+            }
+            let items = query.select(Self::as_select()).load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_slug: String, item: &UpdateTag) -> QueryResult<Self> {
+        use crate::schema::tags;
+
+        if item.is_empty() {
+            return tags::table.filter(tags::slug.eq(param_slug)).select(Self::as_select()).first::<Self>(db);
+        }
+
+        diesel::update(tags::table.filter(tags::slug.eq(param_slug))).set(item).returning(Self::as_returning()).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &TagFilter, changes: &UpdateTag) -> QueryResult<Vec<Self>> {
+        use crate::schema::tags;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return tags::table.filter(filter.expression()).select(Self::as_select()).load::<Self>(db);
+        }
+
+        diesel::update(tags::table.filter(filter.expression())).set(changes).returning(Self::as_returning()).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_slug: String) -> QueryResult<usize> {
+        use crate::schema::tags;
+
+        diesel::delete(tags::table.filter(tags::slug.eq(param_slug))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &TagFilter) -> QueryResult<usize> {
+        use crate::schema::tags;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(tags::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+diesel::table! {
+    tags (slug) {
+        name -> Text,
+        slug -> Text,
+        color -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
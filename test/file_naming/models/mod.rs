@@ -0,0 +1,2 @@
+pub mod TodoItem;
+pub mod User;
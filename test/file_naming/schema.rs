@@ -0,0 +1,18 @@
+diesel::table! {
+    todo_items (id) {
+        id -> Int4,
+        user_id -> Int4,
+        text -> Text,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::joinable!(todo_items -> users (user_id));
+
+diesel::allow_tables_to_appear_in_same_query!(todo_items, users);
@@ -0,0 +1,10 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        done -> Bool,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
@@ -0,0 +1,276 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `posts` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `title` | `Text` | `String` |  |
+//! | `body` | `Text` | `String` |  |
+//! | `search_vector` | `Tsvector` | `diesel_full_text_search::PgTsVector` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=posts, primary_key(id))]
+pub struct Post {
+    pub id: i32,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=posts)]
+pub struct CreatePost {
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=posts)]
+pub struct UpdatePost {
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+impl UpdatePost {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.body.is_none()
+    }
+}
+
+/// Conditions on `posts` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct PostFilter {
+    pub id: Option<i32>,
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+pub type PostFilterExpression = Box<dyn BoxableExpression<crate::schema::posts::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl PostFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.title.is_none() && self.body.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> PostFilterExpression {
+        use crate::schema::posts;
+
+        let mut expression: PostFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(posts::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.title {
+            expression = Box::new(expression.and(posts::title.eq(value.clone())));
+        }
+        if let Some(value) = &self.body {
+            expression = Box::new(expression.and(posts::body.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `posts` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostColumn {
+    Id,
+    Title,
+    Body,
+}
+
+impl Post {
+
+    pub fn create(db: &mut Connection, item: &CreatePost) -> QueryResult<Self> {
+        use crate::schema::posts;
+
+        insert_into(posts::table).values(item).returning(Self::as_returning()).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::posts;
+
+        posts::table.filter(posts::id.eq(param_id)).select(Self::as_select()).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`posts::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(PostColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::posts;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = posts::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = posts::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((PostColumn::Id, SortDirection::Asc)) => query.order(posts::id.asc()),
+            Some((PostColumn::Id, SortDirection::Desc)) => query.order(posts::id.desc()),
+            Some((PostColumn::Title, SortDirection::Asc)) => query.order((posts::title.asc(), posts::id.asc())),
+            Some((PostColumn::Title, SortDirection::Desc)) => query.order((posts::title.desc(), posts::id.asc())),
+            Some((PostColumn::Body, SortDirection::Asc)) => query.order((posts::body.asc(), posts::id.asc())),
+            Some((PostColumn::Body, SortDirection::Desc)) => query.order((posts::body.desc(), posts::id.asc())),
+            None => query.order(posts::id.asc()),
+        };
+        let items = query.select(Self::as_select()).load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::posts;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = posts::table.order(posts::id.asc()).limit(page_size.saturating_add(1)).offset(offset).select(Self::as_select()).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::posts;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = posts::table.order(posts::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(posts::id.gt(&last.id));
+            }
+            let items = query.select(Self::as_select()).load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Full-text search on `search_vector` using `websearch_to_tsquery`, ordered by rank (page is a 0-based index)
+    pub fn search(db: &mut Connection, query: &str, page: i64, page_size: i64) -> QueryResult<Vec<Self>> {
+        use crate::schema::posts;
+        use diesel_full_text_search::{ts_rank, websearch_to_tsquery, TsVectorExtensions};
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        posts::table
+            .filter(posts::search_vector.matches(websearch_to_tsquery(query)))
+            .order((ts_rank(posts::search_vector, websearch_to_tsquery(query)).desc(), posts::id.asc()))
+            .limit(page_size)
+            .offset(offset)
+            .select(Self::as_select())
+            .load::<Self>(db)
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdatePost) -> QueryResult<Self> {
+        use crate::schema::posts;
+
+        if item.is_empty() {
+            return posts::table.filter(posts::id.eq(param_id)).select(Self::as_select()).first::<Self>(db);
+        }
+
+        diesel::update(posts::table.filter(posts::id.eq(param_id))).set(item).returning(Self::as_returning()).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &PostFilter, changes: &UpdatePost) -> QueryResult<Vec<Self>> {
+        use crate::schema::posts;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return posts::table.filter(filter.expression()).select(Self::as_select()).load::<Self>(db);
+        }
+
+        diesel::update(posts::table.filter(filter.expression())).set(changes).returning(Self::as_returning()).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::posts;
+
+        diesel::delete(posts::table.filter(posts::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &PostFilter) -> QueryResult<usize> {
+        use crate::schema::posts;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(posts::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
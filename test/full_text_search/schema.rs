@@ -0,0 +1,11 @@
+diesel::table! {
+    use diesel::sql_types::*;
+    use diesel_full_text_search::Tsvector;
+
+    posts (id) {
+        id -> Int4,
+        title -> Text,
+        body -> Text,
+        search_vector -> Tsvector,
+    }
+}
@@ -0,0 +1,267 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `invoices` table.
+//!
+//! - primary key: `id`
+//! - foreign key: `user_id` references `users`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `user_id` | `Int4` | `i32` |  |
+//! | `total_cents` | `Int8` | `i64` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::models::users::User;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Associations, Selectable)]
+#[diesel(table_name=invoices, primary_key(id), belongs_to(User, foreign_key=user_id))]
+pub struct Invoice {
+    pub id: i32,
+    pub user_id: i32,
+    pub total_cents: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=invoices)]
+pub struct CreateInvoice {
+    pub user_id: i32,
+    pub total_cents: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=invoices)]
+pub struct UpdateInvoice {
+    pub user_id: Option<i32>,
+    pub total_cents: Option<i64>,
+}
+
+impl UpdateInvoice {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.user_id.is_none()
+            && self.total_cents.is_none()
+    }
+}
+
+/// Conditions on `invoices` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct InvoiceFilter {
+    pub id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub total_cents: Option<i64>,
+}
+
+pub type InvoiceFilterExpression = Box<dyn BoxableExpression<crate::schema::invoices::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl InvoiceFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.user_id.is_none() && self.total_cents.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> InvoiceFilterExpression {
+        use crate::schema::invoices;
+
+        let mut expression: InvoiceFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(invoices::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.user_id {
+            expression = Box::new(expression.and(invoices::user_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.total_cents {
+            expression = Box::new(expression.and(invoices::total_cents.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `invoices` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceColumn {
+    Id,
+    UserId,
+    TotalCents,
+}
+
+impl Invoice {
+
+    pub fn create(db: &mut Connection, item: &CreateInvoice) -> QueryResult<Self> {
+        use crate::schema::invoices;
+
+        insert_into(invoices::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::invoices;
+
+        invoices::table.filter(invoices::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`invoices::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(InvoiceColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::invoices;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = invoices::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = invoices::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((InvoiceColumn::Id, SortDirection::Asc)) => query.order(invoices::id.asc()),
+            Some((InvoiceColumn::Id, SortDirection::Desc)) => query.order(invoices::id.desc()),
+            Some((InvoiceColumn::UserId, SortDirection::Asc)) => query.order((invoices::user_id.asc(), invoices::id.asc())),
+            Some((InvoiceColumn::UserId, SortDirection::Desc)) => query.order((invoices::user_id.desc(), invoices::id.asc())),
+            Some((InvoiceColumn::TotalCents, SortDirection::Asc)) => query.order((invoices::total_cents.asc(), invoices::id.asc())),
+            Some((InvoiceColumn::TotalCents, SortDirection::Desc)) => query.order((invoices::total_cents.desc(), invoices::id.asc())),
+            None => query.order(invoices::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::invoices;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = invoices::table.order(invoices::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::invoices;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = invoices::table.order(invoices::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(invoices::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateInvoice) -> QueryResult<Self> {
+        use crate::schema::invoices;
+
+        if item.is_empty() {
+            return invoices::table.filter(invoices::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(invoices::table.filter(invoices::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &InvoiceFilter, changes: &UpdateInvoice) -> QueryResult<Vec<Self>> {
+        use crate::schema::invoices;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return invoices::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(invoices::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::invoices;
+
+        diesel::delete(invoices::table.filter(invoices::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &InvoiceFilter) -> QueryResult<usize> {
+        use crate::schema::invoices;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(invoices::table.filter(filter.expression())).execute(db)
+    }
+
+    /// Loads rows along with their associated `User` (inner join on `user_id`)
+    pub fn with_user(db: &mut Connection) -> QueryResult<Vec<(Self, User)>> {
+        use crate::schema::{invoices, users};
+
+        invoices::table.inner_join(users::table).select((Self::as_select(), User::as_select())).load::<(Self, User)>(db)
+    }
+
+}
\ No newline at end of file
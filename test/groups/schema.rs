@@ -0,0 +1,27 @@
+diesel::table! {
+    invoices (id) {
+        id -> Int4,
+        user_id -> Int4,
+        total_cents -> Int8,
+    }
+}
+
+diesel::table! {
+    payments (id) {
+        id -> Int4,
+        invoice_id -> Int4,
+        amount_cents -> Int8,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::joinable!(invoices -> users (user_id));
+diesel::joinable!(payments -> invoices (invoice_id));
+
+diesel::allow_tables_to_appear_in_same_query!(invoices, payments, users);
@@ -0,0 +1,6 @@
+diesel::table! {
+    events (id) {
+        id -> Text,
+        name -> Text,
+    }
+}
@@ -0,0 +1,8 @@
+diesel::table! {
+    documents (id) {
+        id -> Int4,
+        owner_id -> Int4,
+        title -> Text,
+        body -> Text,
+    }
+}
@@ -0,0 +1,15 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        done -> Bool,
+        due_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    todo_counts (done) {
+        done -> Bool,
+        count -> Int8,
+    }
+}
@@ -0,0 +1,261 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `projects` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `title` | `Text` | `String` |  |
+//! | `archived_at` | `Nullable<Timestamptz>` | `Option<chrono::DateTime<chrono::Utc>>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=projects, primary_key(id))]
+pub struct Project {
+    pub id: i32,
+    pub title: String,
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=projects)]
+pub struct CreateProject {
+    pub title: String,
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=projects)]
+pub struct UpdateProject {
+    pub title: Option<String>,
+    pub archived_at: Option<Option<chrono::DateTime<chrono::Utc>>>,
+}
+
+impl UpdateProject {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.archived_at.is_none()
+    }
+}
+
+/// Conditions on `projects` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ProjectFilter {
+    pub id: Option<i32>,
+    pub title: Option<String>,
+    pub archived_at: Option<Option<chrono::DateTime<chrono::Utc>>>,
+}
+
+pub type ProjectFilterExpression = Box<dyn BoxableExpression<crate::schema::projects::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl ProjectFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.title.is_none() && self.archived_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> ProjectFilterExpression {
+        use crate::schema::projects;
+
+        let mut expression: ProjectFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(projects::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.title {
+            expression = Box::new(expression.and(projects::title.eq(value.clone())));
+        }
+        match &self.archived_at {
+            Some(Some(value)) => expression = Box::new(expression.and(projects::archived_at.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(projects::archived_at.is_null())),
+            None => {}
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `projects` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectColumn {
+    Id,
+    Title,
+    ArchivedAt,
+}
+
+impl Project {
+
+    pub fn create(db: &mut Connection, item: &CreateProject) -> QueryResult<Self> {
+        use crate::schema::projects;
+
+        insert_into(projects::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::projects;
+
+        projects::table.filter(projects::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`projects::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(ProjectColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::projects;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = projects::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = projects::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((ProjectColumn::Id, SortDirection::Asc)) => query.order(projects::id.asc()),
+            Some((ProjectColumn::Id, SortDirection::Desc)) => query.order(projects::id.desc()),
+            Some((ProjectColumn::Title, SortDirection::Asc)) => query.order((projects::title.asc(), projects::id.asc())),
+            Some((ProjectColumn::Title, SortDirection::Desc)) => query.order((projects::title.desc(), projects::id.asc())),
+            Some((ProjectColumn::ArchivedAt, SortDirection::Asc)) => query.order((projects::archived_at.asc(), projects::id.asc())),
+            Some((ProjectColumn::ArchivedAt, SortDirection::Desc)) => query.order((projects::archived_at.desc(), projects::id.asc())),
+            None => query.order(projects::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::projects;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = projects::table.order(projects::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::projects;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = projects::table.order(projects::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(projects::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateProject) -> QueryResult<Self> {
+        use crate::schema::projects;
+
+        if item.is_empty() {
+            return projects::table.filter(projects::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(projects::table.filter(projects::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &ProjectFilter, changes: &UpdateProject) -> QueryResult<Vec<Self>> {
+        use crate::schema::projects;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return projects::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(projects::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::projects;
+
+        diesel::delete(projects::table.filter(projects::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &ProjectFilter) -> QueryResult<usize> {
+        use crate::schema::projects;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(projects::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,270 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `tasks` table.
+//!
+//! - primary key: `id`
+//! - foreign key: `project_id` references `projects`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `project_id` | `Int4` | `i32` |  |
+//! | `summary` | `Text` | `String` | What needs to be done |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::models::projects::Project;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Associations, Selectable)]
+#[diesel(table_name=tasks, primary_key(id), belongs_to(Project, foreign_key=project_id))]
+pub struct ProjectTask {
+    pub id: i32,
+    pub project_id: i32,
+    /// What needs to be done
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=tasks)]
+pub struct CreateProjectTask {
+    pub project_id: i32,
+    /// What needs to be done
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=tasks)]
+pub struct UpdateProjectTask {
+    pub project_id: Option<i32>,
+    /// What needs to be done
+    pub summary: Option<String>,
+}
+
+impl UpdateProjectTask {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.project_id.is_none()
+            && self.summary.is_none()
+    }
+}
+
+/// Conditions on `tasks` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ProjectTaskFilter {
+    pub id: Option<i32>,
+    pub project_id: Option<i32>,
+    pub summary: Option<String>,
+}
+
+pub type ProjectTaskFilterExpression = Box<dyn BoxableExpression<crate::schema::tasks::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl ProjectTaskFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.project_id.is_none() && self.summary.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> ProjectTaskFilterExpression {
+        use crate::schema::tasks;
+
+        let mut expression: ProjectTaskFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(tasks::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.project_id {
+            expression = Box::new(expression.and(tasks::project_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.summary {
+            expression = Box::new(expression.and(tasks::summary.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `tasks` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectTaskColumn {
+    Id,
+    ProjectId,
+    Summary,
+}
+
+impl ProjectTask {
+
+    pub fn create(db: &mut Connection, item: &CreateProjectTask) -> QueryResult<Self> {
+        use crate::schema::tasks;
+
+        insert_into(tasks::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::tasks;
+
+        tasks::table.filter(tasks::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`tasks::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(ProjectTaskColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::tasks;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = tasks::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = tasks::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((ProjectTaskColumn::Id, SortDirection::Asc)) => query.order(tasks::id.asc()),
+            Some((ProjectTaskColumn::Id, SortDirection::Desc)) => query.order(tasks::id.desc()),
+            Some((ProjectTaskColumn::ProjectId, SortDirection::Asc)) => query.order((tasks::project_id.asc(), tasks::id.asc())),
+            Some((ProjectTaskColumn::ProjectId, SortDirection::Desc)) => query.order((tasks::project_id.desc(), tasks::id.asc())),
+            Some((ProjectTaskColumn::Summary, SortDirection::Asc)) => query.order((tasks::summary.asc(), tasks::id.asc())),
+            Some((ProjectTaskColumn::Summary, SortDirection::Desc)) => query.order((tasks::summary.desc(), tasks::id.asc())),
+            None => query.order(tasks::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::tasks;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = tasks::table.order(tasks::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::tasks;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = tasks::table.order(tasks::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(tasks::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateProjectTask) -> QueryResult<Self> {
+        use crate::schema::tasks;
+
+        if item.is_empty() {
+            return tasks::table.filter(tasks::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(tasks::table.filter(tasks::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &ProjectTaskFilter, changes: &UpdateProjectTask) -> QueryResult<Vec<Self>> {
+        use crate::schema::tasks;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return tasks::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(tasks::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::tasks;
+
+        diesel::delete(tasks::table.filter(tasks::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &ProjectTaskFilter) -> QueryResult<usize> {
+        use crate::schema::tasks;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(tasks::table.filter(filter.expression())).execute(db)
+    }
+
+    /// Loads rows along with their associated `Project` (inner join on `project_id`)
+    pub fn with_project(db: &mut Connection) -> QueryResult<Vec<(Self, Project)>> {
+        use crate::schema::{tasks, projects};
+
+        tasks::table.inner_join(projects::table).select((Self::as_select(), Project::as_select())).load::<(Self, Project)>(db)
+    }
+
+}
\ No newline at end of file
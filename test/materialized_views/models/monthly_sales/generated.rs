@@ -0,0 +1,200 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `monthly_sales` table.
+//!
+//! - primary key: `month`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `month` | `Date` | `chrono::NaiveDate` |  |
+//! | `order_count` | `Int8` | `i64` |  |
+//! | `revenue` | `Int8` | `i64` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=monthly_sales, primary_key(month))]
+pub struct MonthlySale {
+    pub month: chrono::NaiveDate,
+    pub order_count: i64,
+    pub revenue: i64,
+}
+
+
+
+/// Conditions on `monthly_sales` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct MonthlySaleFilter {
+    pub month: Option<chrono::NaiveDate>,
+    pub order_count: Option<i64>,
+    pub revenue: Option<i64>,
+}
+
+pub type MonthlySaleFilterExpression = Box<dyn BoxableExpression<crate::schema::monthly_sales::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl MonthlySaleFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.month.is_none() && self.order_count.is_none() && self.revenue.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> MonthlySaleFilterExpression {
+        use crate::schema::monthly_sales;
+
+        let mut expression: MonthlySaleFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.month {
+            expression = Box::new(expression.and(monthly_sales::month.eq(value.clone())));
+        }
+        if let Some(value) = &self.order_count {
+            expression = Box::new(expression.and(monthly_sales::order_count.eq(value.clone())));
+        }
+        if let Some(value) = &self.revenue {
+            expression = Box::new(expression.and(monthly_sales::revenue.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `monthly_sales` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonthlySaleColumn {
+    Month,
+    OrderCount,
+    Revenue,
+}
+
+impl MonthlySale {
+
+    /// Recomputes the materialized view with `REFRESH MATERIALIZED VIEW`, which blocks reads of the view until it's done
+    pub fn refresh(db: &mut Connection) -> QueryResult<usize> {
+        diesel::sql_query("REFRESH MATERIALIZED VIEW \"monthly_sales\"").execute(db)
+    }
+
+    /// Like `refresh` but without blocking reads (`REFRESH MATERIALIZED VIEW CONCURRENTLY`), which needs a unique index
+    /// on the view
+    pub fn refresh_concurrently(db: &mut Connection) -> QueryResult<usize> {
+        diesel::sql_query("REFRESH MATERIALIZED VIEW CONCURRENTLY \"monthly_sales\"").execute(db)
+    }
+
+    pub fn read(db: &mut Connection, param_month: chrono::NaiveDate) -> QueryResult<Self> {
+        use crate::schema::monthly_sales;
+
+        monthly_sales::table.filter(monthly_sales::month.eq(param_month)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`monthly_sales::month.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(MonthlySaleColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::monthly_sales;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = monthly_sales::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = monthly_sales::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((MonthlySaleColumn::Month, SortDirection::Asc)) => query.order(monthly_sales::month.asc()),
+            Some((MonthlySaleColumn::Month, SortDirection::Desc)) => query.order(monthly_sales::month.desc()),
+            Some((MonthlySaleColumn::OrderCount, SortDirection::Asc)) => query.order((monthly_sales::order_count.asc(), monthly_sales::month.asc())),
+            Some((MonthlySaleColumn::OrderCount, SortDirection::Desc)) => query.order((monthly_sales::order_count.desc(), monthly_sales::month.asc())),
+            Some((MonthlySaleColumn::Revenue, SortDirection::Asc)) => query.order((monthly_sales::revenue.asc(), monthly_sales::month.asc())),
+            Some((MonthlySaleColumn::Revenue, SortDirection::Desc)) => query.order((monthly_sales::revenue.desc(), monthly_sales::month.asc())),
+            None => query.order(monthly_sales::month.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::monthly_sales;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = monthly_sales::table.order(monthly_sales::month.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::monthly_sales;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = monthly_sales::table.order(monthly_sales::month.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(monthly_sales::month.gt(&last.month));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,283 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `orders` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `customer_id` | `Int4` | `i32` |  |
+//! | `total` | `Int8` | `i64` |  |
+//! | `created_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=orders, primary_key(id))]
+pub struct Order {
+    pub id: i32,
+    pub customer_id: i32,
+    pub total: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=orders)]
+pub struct CreateOrder {
+    pub customer_id: i32,
+    pub total: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=orders)]
+pub struct UpdateOrder {
+    pub customer_id: Option<i32>,
+    pub total: Option<i64>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl UpdateOrder {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.customer_id.is_none()
+            && self.total.is_none()
+            && self.created_at.is_none()
+    }
+}
+
+/// Conditions on `orders` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct OrderFilter {
+    pub id: Option<i32>,
+    pub customer_id: Option<i32>,
+    pub total: Option<i64>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub type OrderFilterExpression = Box<dyn BoxableExpression<crate::schema::orders::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl OrderFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.customer_id.is_none() && self.total.is_none() && self.created_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> OrderFilterExpression {
+        use crate::schema::orders;
+
+        let mut expression: OrderFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(orders::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.customer_id {
+            expression = Box::new(expression.and(orders::customer_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.total {
+            expression = Box::new(expression.and(orders::total.eq(value.clone())));
+        }
+        if let Some(value) = &self.created_at {
+            expression = Box::new(expression.and(orders::created_at.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `orders` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderColumn {
+    Id,
+    CustomerId,
+    Total,
+    CreatedAt,
+}
+
+impl Order {
+
+    pub fn create(db: &mut Connection, item: &CreateOrder) -> QueryResult<Self> {
+        use crate::schema::orders;
+
+        insert_into(orders::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::orders;
+
+        orders::table.filter(orders::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`orders::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(OrderColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::orders;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = orders::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = orders::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((OrderColumn::Id, SortDirection::Asc)) => query.order(orders::id.asc()),
+            Some((OrderColumn::Id, SortDirection::Desc)) => query.order(orders::id.desc()),
+            Some((OrderColumn::CustomerId, SortDirection::Asc)) => query.order((orders::customer_id.asc(), orders::id.asc())),
+            Some((OrderColumn::CustomerId, SortDirection::Desc)) => query.order((orders::customer_id.desc(), orders::id.asc())),
+            Some((OrderColumn::Total, SortDirection::Asc)) => query.order((orders::total.asc(), orders::id.asc())),
+            Some((OrderColumn::Total, SortDirection::Desc)) => query.order((orders::total.desc(), orders::id.asc())),
+            Some((OrderColumn::CreatedAt, SortDirection::Asc)) => query.order((orders::created_at.asc(), orders::id.asc())),
+            Some((OrderColumn::CreatedAt, SortDirection::Desc)) => query.order((orders::created_at.desc(), orders::id.asc())),
+            None => query.order(orders::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::orders;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = orders::table.order(orders::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::orders;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = orders::table.order(orders::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(orders::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateOrder) -> QueryResult<Self> {
+        use crate::schema::orders;
+
+        if item.is_empty() {
+            return orders::table.filter(orders::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(orders::table.filter(orders::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &OrderFilter, changes: &UpdateOrder) -> QueryResult<Vec<Self>> {
+        use crate::schema::orders;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return orders::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(orders::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::orders;
+
+        diesel::delete(orders::table.filter(orders::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows (`delete_all` deletes every row)
+    pub fn delete_where(db: &mut Connection, filter: &OrderFilter) -> QueryResult<usize> {
+        use crate::schema::orders;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(orders::table.filter(filter.expression())).execute(db)
+    }
+
+    /// Deletes every row of the table, returning the number of deleted rows
+    pub fn delete_all(db: &mut Connection) -> QueryResult<usize> {
+        use crate::schema::orders;
+
+        diesel::delete(orders::table).execute(db)
+    }
+
+    /// Empties the table with `TRUNCATE`, which is faster than `delete_all` but doesn't fire `ON DELETE` triggers
+    pub fn truncate(db: &mut Connection) -> QueryResult<usize> {
+        diesel::sql_query("TRUNCATE TABLE \"orders\"").execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+diesel::table! {
+    orders (id) {
+        id -> Int4,
+        customer_id -> Int4,
+        total -> Int8,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    monthly_sales (month) {
+        month -> Date,
+        order_count -> Int8,
+        revenue -> Int8,
+    }
+}
@@ -0,0 +1,7 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text
+        completed -> Bool,
+    }
+}
@@ -0,0 +1,7 @@
+/* This file is generated and managed by dsync */
+
+// the generated models refer to diesel as `crate::diesel`
+extern crate diesel;
+
+pub mod models;
+pub mod schema;
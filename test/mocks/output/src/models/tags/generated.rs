@@ -0,0 +1,356 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `tags` table.
+//!
+//! - primary key: `todo_id`, `name`
+//! - foreign key: `todo_id` references `todos`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `todo_id` | `Int4` | `i32` |  |
+//! | `name` | `Text` | `String` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::models::todos::Todo;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+type Pool = diesel::r2d2::Pool<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Associations, Selectable)]
+#[diesel(table_name=tags, primary_key(todo_id,name), belongs_to(Todo, foreign_key=todo_id))]
+pub struct Tag {
+    pub todo_id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=tags)]
+pub struct CreateTag {
+    pub todo_id: i32,
+    pub name: String,
+}
+
+
+/// Conditions on `tags` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TagFilter {
+    pub todo_id: Option<i32>,
+    pub name: Option<String>,
+}
+
+pub type TagFilterExpression = Box<dyn BoxableExpression<crate::schema::tags::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl TagFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.todo_id.is_none() && self.name.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> TagFilterExpression {
+        use crate::schema::tags;
+
+        let mut expression: TagFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.todo_id {
+            expression = Box::new(expression.and(tags::todo_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(tags::name.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `tags` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagColumn {
+    TodoId,
+    Name,
+}
+
+/// The composite primary key of `tags` rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TagKey {
+    pub todo_id: i32,
+    pub name: String,
+}
+
+impl Tag {
+
+    pub fn create(db: &mut Connection, item: &CreateTag) -> QueryResult<Self> {
+        use crate::schema::tags;
+
+        insert_into(tags::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_todo_id: i32, param_name: String) -> QueryResult<Self> {
+        use crate::schema::tags;
+
+        tags::table.filter(tags::todo_id.eq(param_todo_id)).filter(tags::name.eq(param_name)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`(tags::todo_id.asc(), tags::name.asc())` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(TagColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::tags;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = tags::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = tags::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((TagColumn::TodoId, SortDirection::Asc)) => query.order((tags::todo_id.asc(), tags::name.asc())),
+            Some((TagColumn::TodoId, SortDirection::Desc)) => query.order((tags::todo_id.desc(), tags::name.asc())),
+            Some((TagColumn::Name, SortDirection::Asc)) => query.order((tags::name.asc(), tags::todo_id.asc())),
+            Some((TagColumn::Name, SortDirection::Desc)) => query.order((tags::name.desc(), tags::todo_id.asc())),
+            None => query.order((tags::todo_id.asc(), tags::name.asc())),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::tags;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = tags::table.order((tags::todo_id.asc(), tags::name.asc())).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::tags;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = tags::table.order((tags::todo_id.asc(), tags::name.asc())).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(tags::todo_id.gt(&last.todo_id).or(tags::todo_id.eq(&last.todo_id).and(tags::name.gt(&last.name))));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn delete(db: &mut Connection, param_todo_id: i32, param_name: String) -> QueryResult<usize> {
+        use crate::schema::tags;
+
+        diesel::delete(tags::table.filter(tags::todo_id.eq(param_todo_id)).filter(tags::name.eq(param_name))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &TagFilter) -> QueryResult<usize> {
+        use crate::schema::tags;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(tags::table.filter(filter.expression())).execute(db)
+    }
+
+    /// The composite primary key of this row
+    pub fn key(&self) -> TagKey {
+        TagKey { todo_id: self.todo_id.clone(), name: self.name.clone() }
+    }
+
+    pub fn read_by_key(db: &mut Connection, key: &TagKey) -> QueryResult<Self> {
+        Self::read(db, key.todo_id.clone(), key.name.clone())
+    }
+
+    pub fn delete_by_key(db: &mut Connection, key: &TagKey) -> QueryResult<usize> {
+        Self::delete(db, key.todo_id.clone(), key.name.clone())
+    }
+
+    /// Runs `create` on a connection of `pool`, in tokio's blocking thread pool
+    pub async fn create_async(pool: &Pool, item: CreateTag) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut db = pool.get()?;
+            Ok(Self::create(&mut db, &item)?)
+        })
+        .await?
+    }
+
+    /// Runs `read` on a connection of `pool`, in tokio's blocking thread pool
+    pub async fn read_async(pool: &Pool, param_todo_id: i32, param_name: String) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut db = pool.get()?;
+            Ok(Self::read(&mut db, param_todo_id, param_name)?)
+        })
+        .await?
+    }
+
+    /// Runs `paginate` on a connection of `pool`, in tokio's blocking thread pool
+    pub async fn paginate_async(pool: &Pool, page: i64, page_size: i64, order: Option<(TagColumn, SortDirection)>) -> Result<PaginationResult<Self>, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut db = pool.get()?;
+            Ok(Self::paginate(&mut db, page, page_size, order)?)
+        })
+        .await?
+    }
+
+    /// Runs `delete` on a connection of `pool`, in tokio's blocking thread pool
+    pub async fn delete_async(pool: &Pool, param_todo_id: i32, param_name: String) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut db = pool.get()?;
+            Ok(Self::delete(&mut db, param_todo_id, param_name)?)
+        })
+        .await?
+    }
+
+    /// Loads rows along with their associated `Todo` (inner join on `todo_id`)
+    pub fn with_todo(db: &mut Connection) -> QueryResult<Vec<(Self, Todo)>> {
+        use crate::schema::{tags, todos};
+
+        tags::table.inner_join(todos::table).select((Self::as_select(), Todo::as_select())).load::<(Self, Todo)>(db)
+    }
+
+}
+
+/// Async CRUD on `tags` over a connection pool, so handlers can hold a single (cloneable) service per table
+#[derive(Clone)]
+pub struct TagService {
+    pool: Pool,
+}
+
+impl TagService {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, item: CreateTag) -> Result<Tag, Box<dyn std::error::Error + Send + Sync>> {
+        Tag::create_async(&self.pool, item).await
+    }
+
+    pub async fn read(&self, param_todo_id: i32, param_name: String) -> Result<Tag, Box<dyn std::error::Error + Send + Sync>> {
+        Tag::read_async(&self.pool, param_todo_id, param_name).await
+    }
+
+    pub async fn paginate(&self, page: i64, page_size: i64, order: Option<(TagColumn, SortDirection)>) -> Result<PaginationResult<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+        Tag::paginate_async(&self.pool, page, page_size, order).await
+    }
+
+    pub async fn delete(&self, param_todo_id: i32, param_name: String) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        Tag::delete_async(&self.pool, param_todo_id, param_name).await
+    }
+}
+
+/// The async CRUD of `tags`, implemented by `TagService`; with the `mock` feature, mockall generates
+/// `MockTagRepository` to unit test handlers without a database
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait TagRepository: Send + Sync {
+    fn create(&self, item: CreateTag) -> impl std::future::Future<Output = Result<Tag, Box<dyn std::error::Error + Send + Sync>>> + Send;
+    fn read(&self, param_todo_id: i32, param_name: String) -> impl std::future::Future<Output = Result<Tag, Box<dyn std::error::Error + Send + Sync>>> + Send;
+    fn paginate(&self, page: i64, page_size: i64, order: Option<(TagColumn, SortDirection)>) -> impl std::future::Future<Output = Result<PaginationResult<Tag>, Box<dyn std::error::Error + Send + Sync>>> + Send;
+    fn delete(&self, param_todo_id: i32, param_name: String) -> impl std::future::Future<Output = Result<usize, Box<dyn std::error::Error + Send + Sync>>> + Send;
+}
+
+impl TagRepository for TagService {
+    fn create(&self, item: CreateTag) -> impl std::future::Future<Output = Result<Tag, Box<dyn std::error::Error + Send + Sync>>> + Send {
+        TagService::create(self, item)
+    }
+
+    fn read(&self, param_todo_id: i32, param_name: String) -> impl std::future::Future<Output = Result<Tag, Box<dyn std::error::Error + Send + Sync>>> + Send {
+        TagService::read(self, param_todo_id, param_name)
+    }
+
+    fn paginate(&self, page: i64, page_size: i64, order: Option<(TagColumn, SortDirection)>) -> impl std::future::Future<Output = Result<PaginationResult<Tag>, Box<dyn std::error::Error + Send + Sync>>> + Send {
+        TagService::paginate(self, page, page_size, order)
+    }
+
+    fn delete(&self, param_todo_id: i32, param_name: String) -> impl std::future::Future<Output = Result<usize, Box<dyn std::error::Error + Send + Sync>>> + Send {
+        TagService::delete(self, param_todo_id, param_name)
+    }
+}
+
+#[cfg(feature = "mock")]
+impl MockTagRepository {
+    /// A mock whose `read` returns (a clone of) `row`
+    pub fn reading(row: Tag) -> Self {
+        let mut mock = Self::new();
+        mock.expect_read().returning(move |_, _| {
+            let row = row.clone();
+            Box::pin(async move { Ok(row) })
+        });
+        mock
+    }
+
+    /// A mock whose `read`, `update` and `delete` fail with diesel's `NotFound` error
+    pub fn not_found() -> Self {
+        let mut mock = Self::new();
+        mock.expect_read().returning(|_, _| Box::pin(async { Err(diesel::result::Error::NotFound.into()) }));
+        mock.expect_delete().returning(|_, _| Box::pin(async { Err(diesel::result::Error::NotFound.into()) }));
+        mock
+    }
+}
\ No newline at end of file
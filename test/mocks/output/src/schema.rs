@@ -0,0 +1,19 @@
+/* This file is generated and managed by dsync */
+
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        completed -> Bool,
+    }
+}
+
+diesel::table! {
+    tags (todo_id, name) {
+        todo_id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::joinable!(tags -> todos (todo_id));
+diesel::allow_tables_to_appear_in_same_query!(tags, todos);
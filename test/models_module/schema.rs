@@ -0,0 +1,21 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        user_id -> Int4,
+        text -> Text,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    todos,
+    users,
+);
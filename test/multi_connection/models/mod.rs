@@ -0,0 +1,3 @@
+pub mod todos;
+pub mod posts;
+pub mod todo_counts;
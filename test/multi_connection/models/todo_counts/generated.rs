@@ -0,0 +1,194 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `todo_counts` table.
+//!
+//! - primary key: `completed`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `completed` | `Bool` | `bool` |  |
+//! | `count` | `Int8` | `i64` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = crate::AnyConnection;
+
+/// materialized view
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=todo_counts, primary_key(completed))]
+pub struct TodoCount {
+    pub completed: bool,
+    pub count: i64,
+}
+
+
+
+/// Conditions on `todo_counts` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TodoCountFilter {
+    pub completed: Option<bool>,
+    pub count: Option<i64>,
+}
+
+pub type TodoCountFilterExpression = Box<dyn BoxableExpression<crate::schema::todo_counts::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl TodoCountFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.completed.is_none() && self.count.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> TodoCountFilterExpression {
+        use crate::schema::todo_counts;
+
+        let mut expression: TodoCountFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.completed {
+            expression = Box::new(expression.and(todo_counts::completed.eq(value.clone())));
+        }
+        if let Some(value) = &self.count {
+            expression = Box::new(expression.and(todo_counts::count.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `todo_counts` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoCountColumn {
+    Completed,
+    Count,
+}
+
+impl TodoCount {
+
+    /// Recomputes the materialized view with `REFRESH MATERIALIZED VIEW`, which blocks reads of the view until it's done
+    #[cfg(feature = "postgres")]
+    pub fn refresh(db: &mut diesel::PgConnection) -> QueryResult<usize> {
+        diesel::sql_query("REFRESH MATERIALIZED VIEW \"todo_counts\"").execute(db)
+    }
+
+    /// Like `refresh` but without blocking reads (`REFRESH MATERIALIZED VIEW CONCURRENTLY`), which needs a unique index
+    /// on the view
+    #[cfg(feature = "postgres")]
+    pub fn refresh_concurrently(db: &mut diesel::PgConnection) -> QueryResult<usize> {
+        diesel::sql_query("REFRESH MATERIALIZED VIEW CONCURRENTLY \"todo_counts\"").execute(db)
+    }
+
+    pub fn read(db: &mut Connection, param_completed: bool) -> QueryResult<Self> {
+        use crate::schema::todo_counts;
+
+        todo_counts::table.filter(todo_counts::completed.eq(param_completed)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`todo_counts::completed.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(TodoCountColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::todo_counts;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = todo_counts::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = todo_counts::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((TodoCountColumn::Completed, SortDirection::Asc)) => query.order(todo_counts::completed.asc()),
+            Some((TodoCountColumn::Completed, SortDirection::Desc)) => query.order(todo_counts::completed.desc()),
+            Some((TodoCountColumn::Count, SortDirection::Asc)) => query.order((todo_counts::count.asc(), todo_counts::completed.asc())),
+            Some((TodoCountColumn::Count, SortDirection::Desc)) => query.order((todo_counts::count.desc(), todo_counts::completed.asc())),
+            None => query.order(todo_counts::completed.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::todo_counts;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = todo_counts::table.order(todo_counts::completed.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::todo_counts;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = todo_counts::table.order(todo_counts::completed.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(todo_counts::completed.gt(&last.completed));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        completed -> Bool,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    posts (id) {
+        id -> Int4,
+        title -> Text,
+        search_vector -> diesel_full_text_search::TsVector,
+    }
+}
+
+diesel::table! {
+    /// materialized view
+    todo_counts (completed) {
+        completed -> Bool,
+        count -> Int8,
+    }
+}
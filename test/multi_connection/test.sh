@@ -0,0 +1,7 @@
+#!/bin/bash
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR
+
+cargo run -- -i schema.rs -o models -g id --dangerous-helpers --full-text-search-column search_vector --materialized-views todo_counts --multi-connection -c "crate::AnyConnection"
\ No newline at end of file
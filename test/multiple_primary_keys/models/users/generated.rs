@@ -1,5 +1,15 @@
 /* This file is generated and managed by dsync */
 
+//! Model for the `users` table.
+//!
+//! - primary key: `name`, `address`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `name` | `Text` | `String` |  |
+//! | `address` | `Text` | `String` |  |
+//! | `secret` | `Text` | `String` |  |
+
 use crate::diesel::*;
 use crate::schema::*;
 use diesel::QueryResult;
@@ -8,7 +18,7 @@ use serde::{Deserialize, Serialize};
 
 type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Insertable, AsChangeset, Selectable)]
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
 #[diesel(table_name=users, primary_key(name,address))]
 pub struct User {
     pub name: String,
@@ -16,7 +26,7 @@ pub struct User {
     pub secret: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Insertable, AsChangeset)]
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
 #[diesel(table_name=users)]
 pub struct CreateUser {
     pub name: String,
@@ -24,12 +34,54 @@ pub struct CreateUser {
     pub secret: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Insertable, AsChangeset)]
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
 #[diesel(table_name=users)]
 pub struct UpdateUser {
     pub secret: Option<String>,
 }
 
+impl UpdateUser {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.secret.is_none()
+    }
+}
+
+/// Conditions on `users` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct UserFilter {
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub secret: Option<String>,
+}
+
+pub type UserFilterExpression = Box<dyn BoxableExpression<crate::schema::users::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl UserFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.address.is_none() && self.secret.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> UserFilterExpression {
+        use crate::schema::users;
+
+        let mut expression: UserFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(users::name.eq(value.clone())));
+        }
+        if let Some(value) = &self.address {
+            expression = Box::new(expression.and(users::address.eq(value.clone())));
+        }
+        if let Some(value) = &self.secret {
+            expression = Box::new(expression.and(users::secret.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
 
 #[derive(Debug, Serialize)]
 pub struct PaginationResult<T> {
@@ -41,27 +93,72 @@ pub struct PaginationResult<T> {
     pub num_pages: i64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `users` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserColumn {
+    Name,
+    Address,
+    Secret,
+}
+
+/// The composite primary key of `users` rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UserKey {
+    pub name: String,
+    pub address: String,
+}
+
 impl User {
 
     pub fn create(db: &mut Connection, item: &CreateUser) -> QueryResult<Self> {
-        use crate::schema::users::dsl::*;
+        use crate::schema::users;
 
-        insert_into(users).values(item).get_result::<Self>(db)
+        insert_into(users::table).values(item).get_result::<Self>(db)
     }
 
     pub fn read(db: &mut Connection, param_name: String, param_address: String) -> QueryResult<Self> {
-        use crate::schema::users::dsl::*;
+        use crate::schema::users;
 
-        users.filter(name.eq(param_name)).filter(address.eq(param_address)).first::<Self>(db)
+        users::table.filter(users::name.eq(param_name)).filter(users::address.eq(param_address)).first::<Self>(db)
     }
 
-    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page)
-    pub fn paginate(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<PaginationResult<Self>> {
-        use crate::schema::users::dsl::*;
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`(users::name.asc(), users::address.asc())` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(UserColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::users;
 
         let page_size = if page_size < 1 { 1 } else { page_size };
-        let total_items = users.count().get_result(db)?;
-        let items = users.limit(page_size).offset(page * page_size).load::<Self>(db)?;
+        let total_items = users::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = users::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((UserColumn::Name, SortDirection::Asc)) => query.order((users::name.asc(), users::address.asc())),
+            Some((UserColumn::Name, SortDirection::Desc)) => query.order((users::name.desc(), users::address.asc())),
+            Some((UserColumn::Address, SortDirection::Asc)) => query.order((users::address.asc(), users::name.asc())),
+            Some((UserColumn::Address, SortDirection::Desc)) => query.order((users::address.desc(), users::name.asc())),
+            Some((UserColumn::Secret, SortDirection::Asc)) => query.order((users::secret.asc(), users::name.asc(), users::address.asc())),
+            Some((UserColumn::Secret, SortDirection::Desc)) => query.order((users::secret.desc(), users::name.asc(), users::address.asc())),
+            None => query.order((users::name.asc(), users::address.asc())),
+        };
+        let items = query.load::<Self>(db)?;
 
         Ok(PaginationResult {
             items,
@@ -73,16 +170,113 @@ impl User {
         })
     }
 
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::users;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = users::table.order((users::name.asc(), users::address.asc())).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::users;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = users::table.order((users::name.asc(), users::address.asc())).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(users::name.gt(&last.name).or(users::name.eq(&last.name).and(users::address.gt(&last.address))));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
     pub fn update(db: &mut Connection, param_name: String, param_address: String, item: &UpdateUser) -> QueryResult<Self> {
-        use crate::schema::users::dsl::*;
+        use crate::schema::users;
+
+        if item.is_empty() {
+            return users::table.filter(users::name.eq(param_name)).filter(users::address.eq(param_address)).first::<Self>(db);
+        }
+
+        diesel::update(users::table.filter(users::name.eq(param_name)).filter(users::address.eq(param_address))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &UserFilter, changes: &UpdateUser) -> QueryResult<Vec<Self>> {
+        use crate::schema::users;
 
-        diesel::update(users.filter(name.eq(param_name)).filter(address.eq(param_address))).set(item).get_result(db)
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return users::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(users::table.filter(filter.expression())).set(changes).get_results(db)
     }
 
     pub fn delete(db: &mut Connection, param_name: String, param_address: String) -> QueryResult<usize> {
-        use crate::schema::users::dsl::*;
+        use crate::schema::users;
+
+        diesel::delete(users::table.filter(users::name.eq(param_name)).filter(users::address.eq(param_address))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &UserFilter) -> QueryResult<usize> {
+        use crate::schema::users;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(users::table.filter(filter.expression())).execute(db)
+    }
+
+    /// The composite primary key of this row
+    pub fn key(&self) -> UserKey {
+        UserKey { name: self.name.clone(), address: self.address.clone() }
+    }
+
+    pub fn read_by_key(db: &mut Connection, key: &UserKey) -> QueryResult<Self> {
+        Self::read(db, key.name.clone(), key.address.clone())
+    }
+
+    pub fn update_by_key(db: &mut Connection, key: &UserKey, item: &UpdateUser) -> QueryResult<Self> {
+        Self::update(db, key.name.clone(), key.address.clone(), item)
+    }
 
-        diesel::delete(users.filter(name.eq(param_name)).filter(address.eq(param_address))).execute(db)
+    pub fn delete_by_key(db: &mut Connection, key: &UserKey) -> QueryResult<usize> {
+        Self::delete(db, key.name.clone(), key.address.clone())
     }
 
 }
\ No newline at end of file
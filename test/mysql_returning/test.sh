@@ -0,0 +1,7 @@
+#!/bin/bash
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR
+
+cargo run -q -- -i schema.rs -o models -g id -c "diesel::PgConnection" --connection-feature mysql=diesel::MysqlConnection 2> error.txt
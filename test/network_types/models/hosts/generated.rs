@@ -0,0 +1,432 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `hosts` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `address` | `Inet` | `ipnetwork::IpNetwork` |  |
+//! | `network` | `Nullable<Cidr>` | `Option<ipnetwork::IpNetwork>` |  |
+//! | `mac` | `Macaddr` | `[u8; 6]` |  |
+//! | `mac8` | `Nullable<Macaddr8>` | `Option<[u8; 8]>` |  |
+//! | `price` | `Money` | `diesel::data_types::PgMoney` |  |
+//! | `discount` | `Nullable<Money>` | `Option<diesel::data_types::PgMoney>` |  |
+//! | `uptime` | `Interval` | `diesel::data_types::PgInterval` |  |
+//! | `ports` | `Int4range` | `(std::collections::Bound<i32>, std::collections::Bound<i32>)` |  |
+//! | `maintenance` | `Nullable<Tstzrange>` | `Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>` |  |
+//! | `windows` | `Tstzmultirange` | `Vec<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+/// (de)serializes diesel's `PgMoney` (as its amount in the smallest currency unit) and `PgInterval` (as
+/// `[microseconds, days, months]`) values, which don't implement serde's traits
+mod pg_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub trait Repr: Sized {
+        type Repr: Serialize + for<'de> Deserialize<'de>;
+
+        fn to_repr(&self) -> Self::Repr;
+        fn from_repr(repr: Self::Repr) -> Self;
+    }
+
+    impl Repr for diesel::data_types::PgMoney {
+        type Repr = i64;
+
+        fn to_repr(&self) -> Self::Repr {
+            self.0
+        }
+
+        fn from_repr(repr: Self::Repr) -> Self {
+            Self(repr)
+        }
+    }
+
+    impl Repr for diesel::data_types::PgInterval {
+        type Repr = (i64, i32, i32);
+
+        fn to_repr(&self) -> Self::Repr {
+            (self.microseconds, self.days, self.months)
+        }
+
+        fn from_repr((microseconds, days, months): Self::Repr) -> Self {
+            Self { microseconds, days, months }
+        }
+    }
+
+    impl<T: Repr> Repr for Option<T> {
+        type Repr = Option<T::Repr>;
+
+        fn to_repr(&self) -> Self::Repr {
+            self.as_ref().map(T::to_repr)
+        }
+
+        fn from_repr(repr: Self::Repr) -> Self {
+            repr.map(T::from_repr)
+        }
+    }
+
+    pub fn serialize<T: Repr, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        value.to_repr().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Repr, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        T::Repr::deserialize(deserializer).map(T::from_repr)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=hosts, primary_key(id))]
+pub struct Host {
+    pub id: i32,
+    pub address: ipnetwork::IpNetwork,
+    pub network: Option<ipnetwork::IpNetwork>,
+    pub mac: [u8; 6],
+    pub mac8: Option<[u8; 8]>,
+    #[serde(with = "pg_serde")]
+    pub price: diesel::data_types::PgMoney,
+    #[serde(default, with = "pg_serde")]
+    pub discount: Option<diesel::data_types::PgMoney>,
+    #[serde(with = "pg_serde")]
+    pub uptime: diesel::data_types::PgInterval,
+    pub ports: (std::collections::Bound<i32>, std::collections::Bound<i32>),
+    pub maintenance: Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>,
+    pub windows: Vec<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=hosts)]
+pub struct CreateHost {
+    pub address: ipnetwork::IpNetwork,
+    pub network: Option<ipnetwork::IpNetwork>,
+    pub mac: [u8; 6],
+    pub mac8: Option<[u8; 8]>,
+    #[serde(with = "pg_serde")]
+    pub price: diesel::data_types::PgMoney,
+    #[serde(default, with = "pg_serde")]
+    pub discount: Option<diesel::data_types::PgMoney>,
+    #[serde(with = "pg_serde")]
+    pub uptime: diesel::data_types::PgInterval,
+    pub ports: (std::collections::Bound<i32>, std::collections::Bound<i32>),
+    pub maintenance: Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>,
+    pub windows: Vec<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=hosts)]
+pub struct UpdateHost {
+    pub address: Option<ipnetwork::IpNetwork>,
+    pub network: Option<Option<ipnetwork::IpNetwork>>,
+    pub mac: Option<[u8; 6]>,
+    pub mac8: Option<Option<[u8; 8]>>,
+    #[serde(default, with = "pg_serde")]
+    pub price: Option<diesel::data_types::PgMoney>,
+    #[serde(default, with = "pg_serde")]
+    pub discount: Option<Option<diesel::data_types::PgMoney>>,
+    #[serde(default, with = "pg_serde")]
+    pub uptime: Option<diesel::data_types::PgInterval>,
+    pub ports: Option<(std::collections::Bound<i32>, std::collections::Bound<i32>)>,
+    pub maintenance: Option<Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>>,
+    pub windows: Option<Vec<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>>,
+}
+
+impl UpdateHost {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.address.is_none()
+            && self.network.is_none()
+            && self.mac.is_none()
+            && self.mac8.is_none()
+            && self.price.is_none()
+            && self.discount.is_none()
+            && self.uptime.is_none()
+            && self.ports.is_none()
+            && self.maintenance.is_none()
+            && self.windows.is_none()
+    }
+}
+
+/// Conditions on `hosts` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct HostFilter {
+    pub id: Option<i32>,
+    pub address: Option<ipnetwork::IpNetwork>,
+    pub network: Option<Option<ipnetwork::IpNetwork>>,
+    pub mac: Option<[u8; 6]>,
+    pub mac8: Option<Option<[u8; 8]>>,
+    #[serde(default, with = "pg_serde")]
+    pub price: Option<diesel::data_types::PgMoney>,
+    #[serde(default, with = "pg_serde")]
+    pub discount: Option<Option<diesel::data_types::PgMoney>>,
+    #[serde(default, with = "pg_serde")]
+    pub uptime: Option<diesel::data_types::PgInterval>,
+    pub ports: Option<(std::collections::Bound<i32>, std::collections::Bound<i32>)>,
+    pub maintenance: Option<Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>>,
+    pub windows: Option<Vec<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>>,
+}
+
+pub type HostFilterExpression = Box<dyn BoxableExpression<crate::schema::hosts::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl HostFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.address.is_none() && self.network.is_none() && self.mac.is_none() && self.mac8.is_none() && self.price.is_none() && self.discount.is_none() && self.uptime.is_none() && self.ports.is_none() && self.maintenance.is_none() && self.windows.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> HostFilterExpression {
+        use crate::schema::hosts;
+
+        let mut expression: HostFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(hosts::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.address {
+            expression = Box::new(expression.and(hosts::address.eq(value.clone())));
+        }
+        match &self.network {
+            Some(Some(value)) => expression = Box::new(expression.and(hosts::network.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(hosts::network.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.mac {
+            expression = Box::new(expression.and(hosts::mac.eq(value.clone())));
+        }
+        match &self.mac8 {
+            Some(Some(value)) => expression = Box::new(expression.and(hosts::mac8.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(hosts::mac8.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.price {
+            expression = Box::new(expression.and(hosts::price.eq(value.clone())));
+        }
+        match &self.discount {
+            Some(Some(value)) => expression = Box::new(expression.and(hosts::discount.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(hosts::discount.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.uptime {
+            expression = Box::new(expression.and(hosts::uptime.eq(value.clone())));
+        }
+        if let Some(value) = &self.ports {
+            expression = Box::new(expression.and(hosts::ports.eq(value.clone())));
+        }
+        match &self.maintenance {
+            Some(Some(value)) => expression = Box::new(expression.and(hosts::maintenance.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(hosts::maintenance.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.windows {
+            expression = Box::new(expression.and(hosts::windows.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `hosts` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostColumn {
+    Id,
+    Address,
+    Network,
+    Mac,
+    Mac8,
+    Price,
+    Discount,
+    Uptime,
+    Ports,
+    Maintenance,
+    Windows,
+}
+
+impl Host {
+
+    pub fn create(db: &mut Connection, item: &CreateHost) -> QueryResult<Self> {
+        use crate::schema::hosts;
+
+        insert_into(hosts::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::hosts;
+
+        hosts::table.filter(hosts::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`hosts::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(HostColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::hosts;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = hosts::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = hosts::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((HostColumn::Id, SortDirection::Asc)) => query.order(hosts::id.asc()),
+            Some((HostColumn::Id, SortDirection::Desc)) => query.order(hosts::id.desc()),
+            Some((HostColumn::Address, SortDirection::Asc)) => query.order((hosts::address.asc(), hosts::id.asc())),
+            Some((HostColumn::Address, SortDirection::Desc)) => query.order((hosts::address.desc(), hosts::id.asc())),
+            Some((HostColumn::Network, SortDirection::Asc)) => query.order((hosts::network.asc(), hosts::id.asc())),
+            Some((HostColumn::Network, SortDirection::Desc)) => query.order((hosts::network.desc(), hosts::id.asc())),
+            Some((HostColumn::Mac, SortDirection::Asc)) => query.order((hosts::mac.asc(), hosts::id.asc())),
+            Some((HostColumn::Mac, SortDirection::Desc)) => query.order((hosts::mac.desc(), hosts::id.asc())),
+            Some((HostColumn::Mac8, SortDirection::Asc)) => query.order((hosts::mac8.asc(), hosts::id.asc())),
+            Some((HostColumn::Mac8, SortDirection::Desc)) => query.order((hosts::mac8.desc(), hosts::id.asc())),
+            Some((HostColumn::Price, SortDirection::Asc)) => query.order((hosts::price.asc(), hosts::id.asc())),
+            Some((HostColumn::Price, SortDirection::Desc)) => query.order((hosts::price.desc(), hosts::id.asc())),
+            Some((HostColumn::Discount, SortDirection::Asc)) => query.order((hosts::discount.asc(), hosts::id.asc())),
+            Some((HostColumn::Discount, SortDirection::Desc)) => query.order((hosts::discount.desc(), hosts::id.asc())),
+            Some((HostColumn::Uptime, SortDirection::Asc)) => query.order((hosts::uptime.asc(), hosts::id.asc())),
+            Some((HostColumn::Uptime, SortDirection::Desc)) => query.order((hosts::uptime.desc(), hosts::id.asc())),
+            Some((HostColumn::Ports, SortDirection::Asc)) => query.order((hosts::ports.asc(), hosts::id.asc())),
+            Some((HostColumn::Ports, SortDirection::Desc)) => query.order((hosts::ports.desc(), hosts::id.asc())),
+            Some((HostColumn::Maintenance, SortDirection::Asc)) => query.order((hosts::maintenance.asc(), hosts::id.asc())),
+            Some((HostColumn::Maintenance, SortDirection::Desc)) => query.order((hosts::maintenance.desc(), hosts::id.asc())),
+            Some((HostColumn::Windows, SortDirection::Asc)) => query.order((hosts::windows.asc(), hosts::id.asc())),
+            Some((HostColumn::Windows, SortDirection::Desc)) => query.order((hosts::windows.desc(), hosts::id.asc())),
+            None => query.order(hosts::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::hosts;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = hosts::table.order(hosts::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::hosts;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = hosts::table.order(hosts::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(hosts::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateHost) -> QueryResult<Self> {
+        use crate::schema::hosts;
+
+        if item.is_empty() {
+            return hosts::table.filter(hosts::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(hosts::table.filter(hosts::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &HostFilter, changes: &UpdateHost) -> QueryResult<Vec<Self>> {
+        use crate::schema::hosts;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return hosts::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(hosts::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::hosts;
+
+        diesel::delete(hosts::table.filter(hosts::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &HostFilter) -> QueryResult<usize> {
+        use crate::schema::hosts;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(hosts::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+diesel::table! {
+    hosts (id) {
+        id -> Int4,
+        address -> Inet,
+        network -> Nullable<Cidr>,
+        mac -> Macaddr,
+        mac8 -> Nullable<Macaddr8>,
+        price -> Money,
+        discount -> Nullable<Money>,
+        uptime -> Interval,
+        ports -> Int4range,
+        maintenance -> Nullable<Tstzrange>,
+        windows -> Tstzmultirange,
+    }
+}
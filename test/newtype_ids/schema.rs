@@ -0,0 +1,20 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    todos (id) {
+        id -> Uuid,
+        user_id -> Int4,
+        reviewer_id -> Nullable<Int4>,
+        parent_id -> Nullable<Uuid>,
+        text -> Text,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
+
+diesel::allow_tables_to_appear_in_same_query!(todos, users);
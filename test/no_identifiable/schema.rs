@@ -0,0 +1,6 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+    }
+}
@@ -0,0 +1,269 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `events` table.
+//!
+//! - primary key: none
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `name` | `Text` | `String` |  |
+//! | `happened_at` | `Timestamp` | `chrono::NaiveDateTime` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+type Pool = diesel::r2d2::Pool<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Selectable)]
+#[diesel(table_name=events, primary_key())]
+pub struct Event {
+    pub id: i32,
+    pub name: String,
+    pub happened_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=events)]
+pub struct CreateEvent {
+    pub name: String,
+    pub happened_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=events)]
+pub struct UpdateEvent {
+    pub id: Option<i32>,
+    pub name: Option<String>,
+    pub happened_at: Option<chrono::NaiveDateTime>,
+}
+
+impl UpdateEvent {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none()
+            && self.name.is_none()
+            && self.happened_at.is_none()
+    }
+}
+
+/// Conditions on `events` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct EventFilter {
+    pub id: Option<i32>,
+    pub name: Option<String>,
+    pub happened_at: Option<chrono::NaiveDateTime>,
+}
+
+pub type EventFilterExpression = Box<dyn BoxableExpression<crate::schema::events::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl EventFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.name.is_none() && self.happened_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> EventFilterExpression {
+        use crate::schema::events;
+
+        let mut expression: EventFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(events::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(events::name.eq(value.clone())));
+        }
+        if let Some(value) = &self.happened_at {
+            expression = Box::new(expression.and(events::happened_at.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `events` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventColumn {
+    Id,
+    Name,
+    HappenedAt,
+}
+
+impl Event {
+
+    pub fn create(db: &mut Connection, item: &CreateEvent) -> QueryResult<Self> {
+        use crate::schema::events;
+
+        insert_into(events::table).values(item).get_result::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (unordered if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(EventColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::events;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = events::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = events::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((EventColumn::Id, SortDirection::Asc)) => query.order(events::id.asc()),
+            Some((EventColumn::Id, SortDirection::Desc)) => query.order(events::id.desc()),
+            Some((EventColumn::Name, SortDirection::Asc)) => query.order(events::name.asc()),
+            Some((EventColumn::Name, SortDirection::Desc)) => query.order(events::name.desc()),
+            Some((EventColumn::HappenedAt, SortDirection::Asc)) => query.order(events::happened_at.asc()),
+            Some((EventColumn::HappenedAt, SortDirection::Desc)) => query.order(events::happened_at.desc()),
+            None => query,
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::events;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = events::table.limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &EventFilter, changes: &UpdateEvent) -> QueryResult<Vec<Self>> {
+        use crate::schema::events;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return events::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(events::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &EventFilter) -> QueryResult<usize> {
+        use crate::schema::events;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(events::table.filter(filter.expression())).execute(db)
+    }
+
+    /// Runs `create` on a connection of `pool`, in tokio's blocking thread pool
+    pub async fn create_async(pool: &Pool, item: CreateEvent) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut db = pool.get()?;
+            Ok(Self::create(&mut db, &item)?)
+        })
+        .await?
+    }
+
+    /// Runs `paginate` on a connection of `pool`, in tokio's blocking thread pool
+    pub async fn paginate_async(pool: &Pool, page: i64, page_size: i64, order: Option<(EventColumn, SortDirection)>) -> Result<PaginationResult<Self>, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut db = pool.get()?;
+            Ok(Self::paginate(&mut db, page, page_size, order)?)
+        })
+        .await?
+    }
+
+}
+
+/// Async CRUD on `events` over a connection pool, so handlers can hold a single (cloneable) service per table
+#[derive(Clone)]
+pub struct EventService {
+    pool: Pool,
+}
+
+impl EventService {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, item: CreateEvent) -> Result<Event, Box<dyn std::error::Error + Send + Sync>> {
+        Event::create_async(&self.pool, item).await
+    }
+
+    pub async fn paginate(&self, page: i64, page_size: i64, order: Option<(EventColumn, SortDirection)>) -> Result<PaginationResult<Event>, Box<dyn std::error::Error + Send + Sync>> {
+        Event::paginate_async(&self.pool, page, page_size, order).await
+    }
+}
+
+/// The async CRUD of `events`, implemented by `EventService`; with the `mock` feature, mockall generates
+/// `MockEventRepository` to unit test handlers without a database
+#[cfg_attr(feature = "mock", mockall::automock)]
+pub trait EventRepository: Send + Sync {
+    fn create(&self, item: CreateEvent) -> impl std::future::Future<Output = Result<Event, Box<dyn std::error::Error + Send + Sync>>> + Send;
+    fn paginate(&self, page: i64, page_size: i64, order: Option<(EventColumn, SortDirection)>) -> impl std::future::Future<Output = Result<PaginationResult<Event>, Box<dyn std::error::Error + Send + Sync>>> + Send;
+}
+
+impl EventRepository for EventService {
+    fn create(&self, item: CreateEvent) -> impl std::future::Future<Output = Result<Event, Box<dyn std::error::Error + Send + Sync>>> + Send {
+        EventService::create(self, item)
+    }
+
+    fn paginate(&self, page: i64, page_size: i64, order: Option<(EventColumn, SortDirection)>) -> impl std::future::Future<Output = Result<PaginationResult<Event>, Box<dyn std::error::Error + Send + Sync>>> + Send {
+        EventService::paginate(self, page, page_size, order)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+diesel::table! {
+    events {
+        id -> Int4,
+        name -> Text,
+        happened_at -> Timestamp,
+    }
+}
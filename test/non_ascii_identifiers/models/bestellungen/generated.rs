@@ -0,0 +1,281 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `bestellungen` table.
+//!
+//! - primary key: `id`
+//! - foreign key: `café_id` references `cafés`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `café_id` | `Int4` | `i32` |  |
+//! | `名前` | `Text` | `String` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::models::cafes::Cafe;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Associations, Selectable)]
+#[diesel(table_name=bestellungen, primary_key(id), belongs_to(Cafe, foreign_key=café_id))]
+pub struct Bestellungen {
+    pub id: i32,
+    #[diesel(column_name = café_id)]
+    #[serde(rename = "café_id")]
+    pub cafe_id: i32,
+    #[diesel(column_name = 名前)]
+    #[serde(rename = "名前")]
+    pub u540d_u524d: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=bestellungen)]
+pub struct CreateBestellungen {
+    #[diesel(column_name = café_id)]
+    #[serde(rename = "café_id")]
+    pub cafe_id: i32,
+    #[diesel(column_name = 名前)]
+    #[serde(rename = "名前")]
+    pub u540d_u524d: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=bestellungen)]
+pub struct UpdateBestellungen {
+    #[diesel(column_name = café_id)]
+    #[serde(rename = "café_id")]
+    pub cafe_id: Option<i32>,
+    #[diesel(column_name = 名前)]
+    #[serde(rename = "名前")]
+    pub u540d_u524d: Option<String>,
+}
+
+impl UpdateBestellungen {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.cafe_id.is_none()
+            && self.u540d_u524d.is_none()
+    }
+}
+
+/// Conditions on `bestellungen` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct BestellungenFilter {
+    pub id: Option<i32>,
+    #[serde(rename = "café_id")]
+    pub cafe_id: Option<i32>,
+    #[serde(rename = "名前")]
+    pub u540d_u524d: Option<String>,
+}
+
+pub type BestellungenFilterExpression = Box<dyn BoxableExpression<crate::schema::bestellungen::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl BestellungenFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.cafe_id.is_none() && self.u540d_u524d.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> BestellungenFilterExpression {
+        use crate::schema::bestellungen;
+
+        let mut expression: BestellungenFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(bestellungen::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.cafe_id {
+            expression = Box::new(expression.and(bestellungen::café_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.u540d_u524d {
+            expression = Box::new(expression.and(bestellungen::名前.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `bestellungen` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BestellungenColumn {
+    Id,
+    CafeId,
+    U540DU524D,
+}
+
+impl Bestellungen {
+
+    pub fn create(db: &mut Connection, item: &CreateBestellungen) -> QueryResult<Self> {
+        use crate::schema::bestellungen;
+
+        insert_into(bestellungen::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::bestellungen;
+
+        bestellungen::table.filter(bestellungen::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`bestellungen::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(BestellungenColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::bestellungen;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = bestellungen::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = bestellungen::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((BestellungenColumn::Id, SortDirection::Asc)) => query.order(bestellungen::id.asc()),
+            Some((BestellungenColumn::Id, SortDirection::Desc)) => query.order(bestellungen::id.desc()),
+            Some((BestellungenColumn::CafeId, SortDirection::Asc)) => query.order((bestellungen::café_id.asc(), bestellungen::id.asc())),
+            Some((BestellungenColumn::CafeId, SortDirection::Desc)) => query.order((bestellungen::café_id.desc(), bestellungen::id.asc())),
+            Some((BestellungenColumn::U540DU524D, SortDirection::Asc)) => query.order((bestellungen::名前.asc(), bestellungen::id.asc())),
+            Some((BestellungenColumn::U540DU524D, SortDirection::Desc)) => query.order((bestellungen::名前.desc(), bestellungen::id.asc())),
+            None => query.order(bestellungen::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::bestellungen;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = bestellungen::table.order(bestellungen::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::bestellungen;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = bestellungen::table.order(bestellungen::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(bestellungen::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateBestellungen) -> QueryResult<Self> {
+        use crate::schema::bestellungen;
+
+        if item.is_empty() {
+            return bestellungen::table.filter(bestellungen::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(bestellungen::table.filter(bestellungen::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &BestellungenFilter, changes: &UpdateBestellungen) -> QueryResult<Vec<Self>> {
+        use crate::schema::bestellungen;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return bestellungen::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(bestellungen::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::bestellungen;
+
+        diesel::delete(bestellungen::table.filter(bestellungen::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &BestellungenFilter) -> QueryResult<usize> {
+        use crate::schema::bestellungen;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(bestellungen::table.filter(filter.expression())).execute(db)
+    }
+
+    /// Loads rows along with their associated `Cafe` (inner join on `café_id`)
+    pub fn with_cafe(db: &mut Connection) -> QueryResult<Vec<(Self, Cafe)>> {
+        use crate::schema::{bestellungen, cafés};
+
+        bestellungen::table.inner_join(cafés::table).select((Self::as_select(), Cafe::as_select())).load::<(Self, Cafe)>(db)
+    }
+
+}
\ No newline at end of file
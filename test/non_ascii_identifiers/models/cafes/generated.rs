@@ -0,0 +1,294 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `cafés` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `prénom` | `Text` | `String` |  |
+//! | `größe` | `Nullable<Int4>` | `Option<i32>` |  |
+//! | `Straße` | `Text` | `String` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=cafés, primary_key(id))]
+pub struct Cafe {
+    pub id: i32,
+    #[diesel(column_name = prénom)]
+    #[serde(rename = "prénom")]
+    pub prenom: String,
+    #[diesel(column_name = größe)]
+    #[serde(rename = "größe")]
+    pub groesse: Option<i32>,
+    #[diesel(column_name = Straße)]
+    #[serde(rename = "Straße")]
+    pub strasse: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=cafés)]
+pub struct CreateCafe {
+    #[diesel(column_name = prénom)]
+    #[serde(rename = "prénom")]
+    pub prenom: String,
+    #[diesel(column_name = größe)]
+    #[serde(rename = "größe")]
+    pub groesse: Option<i32>,
+    #[diesel(column_name = Straße)]
+    #[serde(rename = "Straße")]
+    pub strasse: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=cafés)]
+pub struct UpdateCafe {
+    #[diesel(column_name = prénom)]
+    #[serde(rename = "prénom")]
+    pub prenom: Option<String>,
+    #[diesel(column_name = größe)]
+    #[serde(rename = "größe")]
+    pub groesse: Option<Option<i32>>,
+    #[diesel(column_name = Straße)]
+    #[serde(rename = "Straße")]
+    pub strasse: Option<String>,
+}
+
+impl UpdateCafe {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.prenom.is_none()
+            && self.groesse.is_none()
+            && self.strasse.is_none()
+    }
+}
+
+/// Conditions on `cafés` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct CafeFilter {
+    pub id: Option<i32>,
+    #[serde(rename = "prénom")]
+    pub prenom: Option<String>,
+    #[serde(rename = "größe")]
+    pub groesse: Option<Option<i32>>,
+    #[serde(rename = "Straße")]
+    pub strasse: Option<String>,
+}
+
+pub type CafeFilterExpression = Box<dyn BoxableExpression<crate::schema::cafés::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl CafeFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.prenom.is_none() && self.groesse.is_none() && self.strasse.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> CafeFilterExpression {
+        use crate::schema::cafés;
+
+        let mut expression: CafeFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(cafés::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.prenom {
+            expression = Box::new(expression.and(cafés::prénom.eq(value.clone())));
+        }
+        match &self.groesse {
+            Some(Some(value)) => expression = Box::new(expression.and(cafés::größe.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(cafés::größe.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.strasse {
+            expression = Box::new(expression.and(cafés::Straße.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `cafés` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CafeColumn {
+    Id,
+    Prenom,
+    Groesse,
+    Strasse,
+}
+
+impl Cafe {
+
+    pub fn create(db: &mut Connection, item: &CreateCafe) -> QueryResult<Self> {
+        use crate::schema::cafés;
+
+        insert_into(cafés::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::cafés;
+
+        cafés::table.filter(cafés::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`cafés::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(CafeColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::cafés;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = cafés::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = cafés::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((CafeColumn::Id, SortDirection::Asc)) => query.order(cafés::id.asc()),
+            Some((CafeColumn::Id, SortDirection::Desc)) => query.order(cafés::id.desc()),
+            Some((CafeColumn::Prenom, SortDirection::Asc)) => query.order((cafés::prénom.asc(), cafés::id.asc())),
+            Some((CafeColumn::Prenom, SortDirection::Desc)) => query.order((cafés::prénom.desc(), cafés::id.asc())),
+            Some((CafeColumn::Groesse, SortDirection::Asc)) => query.order((cafés::größe.asc(), cafés::id.asc())),
+            Some((CafeColumn::Groesse, SortDirection::Desc)) => query.order((cafés::größe.desc(), cafés::id.asc())),
+            Some((CafeColumn::Strasse, SortDirection::Asc)) => query.order((cafés::Straße.asc(), cafés::id.asc())),
+            Some((CafeColumn::Strasse, SortDirection::Desc)) => query.order((cafés::Straße.desc(), cafés::id.asc())),
+            None => query.order(cafés::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::cafés;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = cafés::table.order(cafés::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::cafés;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = cafés::table.order(cafés::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(cafés::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateCafe) -> QueryResult<Self> {
+        use crate::schema::cafés;
+
+        if item.is_empty() {
+            return cafés::table.filter(cafés::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(cafés::table.filter(cafés::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &CafeFilter, changes: &UpdateCafe) -> QueryResult<Vec<Self>> {
+        use crate::schema::cafés;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return cafés::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(cafés::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::cafés;
+
+        diesel::delete(cafés::table.filter(cafés::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &CafeFilter) -> QueryResult<usize> {
+        use crate::schema::cafés;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(cafés::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,2 @@
+pub mod cafes;
+pub mod bestellungen;
@@ -0,0 +1,20 @@
+diesel::table! {
+    cafés (id) {
+        id -> Int4,
+        prénom -> Text,
+        größe -> Nullable<Int4>,
+        Straße -> Text,
+    }
+}
+
+diesel::table! {
+    bestellungen (id) {
+        id -> Int4,
+        café_id -> Int4,
+        名前 -> Text,
+    }
+}
+
+diesel::joinable!(bestellungen -> cafés (café_id));
+
+diesel::allow_tables_to_appear_in_same_query!(bestellungen, cafés);
@@ -0,0 +1,275 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `authors` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `name` | `Varchar` | `String` | the name displayed on posts |
+//! | `bio` | `Nullable<Text>` | `Option<String>` |  |
+//! | `created_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=authors, primary_key(id))]
+pub struct Author {
+    pub id: i32,
+    /// the name displayed on posts
+    pub name: String,
+    pub bio: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=authors)]
+pub struct CreateAuthor {
+    /// the name displayed on posts
+    pub name: String,
+    pub bio: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=authors)]
+pub struct UpdateAuthor {
+    /// the name displayed on posts
+    pub name: Option<String>,
+    pub bio: Option<Option<String>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl UpdateAuthor {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.bio.is_none()
+            && self.created_at.is_none()
+    }
+}
+
+/// Conditions on `authors` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct AuthorFilter {
+    pub id: Option<i32>,
+    pub name: Option<String>,
+    pub bio: Option<Option<String>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub type AuthorFilterExpression = Box<dyn BoxableExpression<crate::schema::authors::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl AuthorFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.name.is_none() && self.bio.is_none() && self.created_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> AuthorFilterExpression {
+        use crate::schema::authors;
+
+        let mut expression: AuthorFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(authors::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(authors::name.eq(value.clone())));
+        }
+        match &self.bio {
+            Some(Some(value)) => expression = Box::new(expression.and(authors::bio.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(authors::bio.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.created_at {
+            expression = Box::new(expression.and(authors::created_at.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `authors` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthorColumn {
+    Id,
+    Name,
+    Bio,
+    CreatedAt,
+}
+
+impl Author {
+
+    pub fn create(db: &mut Connection, item: &CreateAuthor) -> QueryResult<Self> {
+        use crate::schema::authors;
+
+        insert_into(authors::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::authors;
+
+        authors::table.filter(authors::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`authors::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(AuthorColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::authors;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = authors::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = authors::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((AuthorColumn::Id, SortDirection::Asc)) => query.order(authors::id.asc()),
+            Some((AuthorColumn::Id, SortDirection::Desc)) => query.order(authors::id.desc()),
+            Some((AuthorColumn::Name, SortDirection::Asc)) => query.order((authors::name.asc(), authors::id.asc())),
+            Some((AuthorColumn::Name, SortDirection::Desc)) => query.order((authors::name.desc(), authors::id.asc())),
+            Some((AuthorColumn::Bio, SortDirection::Asc)) => query.order((authors::bio.asc(), authors::id.asc())),
+            Some((AuthorColumn::Bio, SortDirection::Desc)) => query.order((authors::bio.desc(), authors::id.asc())),
+            Some((AuthorColumn::CreatedAt, SortDirection::Asc)) => query.order((authors::created_at.asc(), authors::id.asc())),
+            Some((AuthorColumn::CreatedAt, SortDirection::Desc)) => query.order((authors::created_at.desc(), authors::id.asc())),
+            None => query.order(authors::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::authors;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = authors::table.order(authors::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::authors;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = authors::table.order(authors::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(authors::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateAuthor) -> QueryResult<Self> {
+        use crate::schema::authors;
+
+        if item.is_empty() {
+            return authors::table.filter(authors::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(authors::table.filter(authors::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &AuthorFilter, changes: &UpdateAuthor) -> QueryResult<Vec<Self>> {
+        use crate::schema::authors;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return authors::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(authors::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::authors;
+
+        diesel::delete(authors::table.filter(authors::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &AuthorFilter) -> QueryResult<usize> {
+        use crate::schema::authors;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(authors::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
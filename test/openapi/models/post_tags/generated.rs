@@ -0,0 +1,230 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `post_tags` table.
+//!
+//! - primary key: `post_id`, `tag`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `post_id` | `Uuid` | `uuid::Uuid` |  |
+//! | `tag` | `Text` | `String` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=post_tags, primary_key(post_id,tag))]
+pub struct PostTag {
+    pub post_id: uuid::Uuid,
+    pub tag: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=post_tags)]
+pub struct CreatePostTag {
+    pub post_id: uuid::Uuid,
+    pub tag: String,
+}
+
+
+/// Conditions on `post_tags` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct PostTagFilter {
+    pub post_id: Option<uuid::Uuid>,
+    pub tag: Option<String>,
+}
+
+pub type PostTagFilterExpression = Box<dyn BoxableExpression<crate::schema::post_tags::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl PostTagFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.post_id.is_none() && self.tag.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> PostTagFilterExpression {
+        use crate::schema::post_tags;
+
+        let mut expression: PostTagFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.post_id {
+            expression = Box::new(expression.and(post_tags::post_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.tag {
+            expression = Box::new(expression.and(post_tags::tag.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `post_tags` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostTagColumn {
+    PostId,
+    Tag,
+}
+
+/// The composite primary key of `post_tags` rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PostTagKey {
+    pub post_id: uuid::Uuid,
+    pub tag: String,
+}
+
+impl PostTag {
+
+    pub fn create(db: &mut Connection, item: &CreatePostTag) -> QueryResult<Self> {
+        use crate::schema::post_tags;
+
+        insert_into(post_tags::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_post_id: uuid::Uuid, param_tag: String) -> QueryResult<Self> {
+        use crate::schema::post_tags;
+
+        post_tags::table.filter(post_tags::post_id.eq(param_post_id)).filter(post_tags::tag.eq(param_tag)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`(post_tags::post_id.asc(), post_tags::tag.asc())` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(PostTagColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::post_tags;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = post_tags::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = post_tags::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((PostTagColumn::PostId, SortDirection::Asc)) => query.order((post_tags::post_id.asc(), post_tags::tag.asc())),
+            Some((PostTagColumn::PostId, SortDirection::Desc)) => query.order((post_tags::post_id.desc(), post_tags::tag.asc())),
+            Some((PostTagColumn::Tag, SortDirection::Asc)) => query.order((post_tags::tag.asc(), post_tags::post_id.asc())),
+            Some((PostTagColumn::Tag, SortDirection::Desc)) => query.order((post_tags::tag.desc(), post_tags::post_id.asc())),
+            None => query.order((post_tags::post_id.asc(), post_tags::tag.asc())),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::post_tags;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = post_tags::table.order((post_tags::post_id.asc(), post_tags::tag.asc())).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::post_tags;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = post_tags::table.order((post_tags::post_id.asc(), post_tags::tag.asc())).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(post_tags::post_id.gt(&last.post_id).or(post_tags::post_id.eq(&last.post_id).and(post_tags::tag.gt(&last.tag))));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn delete(db: &mut Connection, param_post_id: uuid::Uuid, param_tag: String) -> QueryResult<usize> {
+        use crate::schema::post_tags;
+
+        diesel::delete(post_tags::table.filter(post_tags::post_id.eq(param_post_id)).filter(post_tags::tag.eq(param_tag))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &PostTagFilter) -> QueryResult<usize> {
+        use crate::schema::post_tags;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(post_tags::table.filter(filter.expression())).execute(db)
+    }
+
+    /// The composite primary key of this row
+    pub fn key(&self) -> PostTagKey {
+        PostTagKey { post_id: self.post_id.clone(), tag: self.tag.clone() }
+    }
+
+    pub fn read_by_key(db: &mut Connection, key: &PostTagKey) -> QueryResult<Self> {
+        Self::read(db, key.post_id.clone(), key.tag.clone())
+    }
+
+    pub fn delete_by_key(db: &mut Connection, key: &PostTagKey) -> QueryResult<usize> {
+        Self::delete(db, key.post_id.clone(), key.tag.clone())
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+diesel::table! {
+    authors (id) {
+        id -> Int4,
+        /// the name displayed on posts
+        #[max_length = 80]
+        name -> Varchar,
+        bio -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    post_tags (post_id, tag) {
+        post_id -> Uuid,
+        tag -> Text,
+    }
+}
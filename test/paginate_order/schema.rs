@@ -0,0 +1,14 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    tags (todo_id, name) {
+        todo_id -> Int4,
+        name -> Text,
+    }
+}
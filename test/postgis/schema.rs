@@ -0,0 +1,11 @@
+diesel::table! {
+    use diesel::sql_types::*;
+    use postgis_diesel::sql_types::*;
+
+    places (id) {
+        id -> Int4,
+        name -> Text,
+        location -> Geometry,
+        area -> Nullable<Geography>,
+    }
+}
@@ -0,0 +1,495 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `todos` table.
+//!
+//! - primary key: `id`
+//! - foreign key: `user_id` references `users`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `user_id` | `Int4` | `i32` |  |
+//! | `text` | `Text` | `String` |  |
+//! | `completed` | `Bool` | `bool` |  |
+//! | `priority` | `Int2` | `i16` |  |
+//! | `estimate` | `Nullable<Float8>` | `Option<f64>` |  |
+//! | `due_at` | `Nullable<Timestamp>` | `Option<chrono::NaiveDateTime>` |  |
+//! | `remind_at` | `Nullable<Time>` | `Option<chrono::NaiveTime>` |  |
+//! | `a` | `Int4` | `i32` |  |
+//! | `b` | `Int4` | `i32` |  |
+//! | `c` | `Int4` | `i32` |  |
+//! | `d` | `Int4` | `i32` |  |
+//! | `e` | `Int4` | `i32` |  |
+//! | `f` | `Int4` | `i32` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::models::users::User;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+/// The primary key of `todos` rows, which can't be mixed up with keys of other tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, diesel::AsExpression, diesel::FromSqlRow)]
+#[diesel(sql_type = diesel::sql_types::Int4)]
+#[serde(transparent)]
+pub struct TodoId(pub i32);
+
+impl<DB: diesel::backend::Backend> diesel::serialize::ToSql<diesel::sql_types::Int4, DB> for TodoId
+where
+    i32: diesel::serialize::ToSql<diesel::sql_types::Int4, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, DB>) -> diesel::serialize::Result {
+        <i32 as diesel::serialize::ToSql<diesel::sql_types::Int4, DB>>::to_sql(&self.0, out)
+    }
+}
+
+impl<DB: diesel::backend::Backend> diesel::deserialize::FromSql<diesel::sql_types::Int4, DB> for TodoId
+where
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Int4, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        <i32 as diesel::deserialize::FromSql<diesel::sql_types::Int4, DB>>::from_sql(bytes).map(Self)
+    }
+}
+
+impl std::fmt::Display for TodoId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<i32> for TodoId {
+    fn from(id: i32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<TodoId> for i32 {
+    fn from(id: TodoId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Associations, Selectable)]
+#[diesel(table_name=todos, primary_key(id), belongs_to(User, foreign_key=user_id))]
+pub struct Todo {
+    pub id: TodoId,
+    pub user_id: crate::models::users::UserId,
+    pub text: String,
+    pub completed: bool,
+    pub priority: i16,
+    pub estimate: Option<f64>,
+    pub due_at: Option<chrono::NaiveDateTime>,
+    pub remind_at: Option<chrono::NaiveTime>,
+    pub a: i32,
+    pub b: i32,
+    pub c: i32,
+    pub d: i32,
+    pub e: i32,
+    pub f: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=todos)]
+pub struct CreateTodo {
+    pub user_id: crate::models::users::UserId,
+    pub text: String,
+    pub completed: bool,
+    pub priority: i16,
+    pub estimate: Option<f64>,
+    pub due_at: Option<chrono::NaiveDateTime>,
+    pub remind_at: Option<chrono::NaiveTime>,
+    pub a: i32,
+    pub b: i32,
+    pub c: i32,
+    pub d: i32,
+    pub e: i32,
+    pub f: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=todos)]
+pub struct UpdateTodo {
+    pub user_id: Option<crate::models::users::UserId>,
+    pub text: Option<String>,
+    pub completed: Option<bool>,
+    pub priority: Option<i16>,
+    pub estimate: Option<Option<f64>>,
+    pub due_at: Option<Option<chrono::NaiveDateTime>>,
+    pub remind_at: Option<Option<chrono::NaiveTime>>,
+    pub a: Option<i32>,
+    pub b: Option<i32>,
+    pub c: Option<i32>,
+    pub d: Option<i32>,
+    pub e: Option<i32>,
+    pub f: Option<i32>,
+}
+
+impl UpdateTodo {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.user_id.is_none()
+            && self.text.is_none()
+            && self.completed.is_none()
+            && self.priority.is_none()
+            && self.estimate.is_none()
+            && self.due_at.is_none()
+            && self.remind_at.is_none()
+            && self.a.is_none()
+            && self.b.is_none()
+            && self.c.is_none()
+            && self.d.is_none()
+            && self.e.is_none()
+            && self.f.is_none()
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl CreateTodo {
+    /// A proptest strategy generating `CreateTodo`s with values fitting the columns' types (and max lengths)
+    pub fn strategy() -> impl proptest::strategy::Strategy<Value = Self> {
+        use proptest::prelude::*;
+
+        (
+            (any::<i32>().prop_map(crate::models::users::UserId), "\\PC*", any::<bool>(), any::<i16>(), proptest::option::of(any::<f64>()), proptest::option::of((0i64..4_102_444_800).prop_map(|secs| chrono::DateTime::from_timestamp(secs, 0).unwrap()).prop_map(|t| t.naive_utc())), proptest::option::of((0u32..86_400).prop_map(|secs| chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs, 0).unwrap())), any::<i32>(), any::<i32>(), any::<i32>(), any::<i32>(), any::<i32>()),
+            (any::<i32>(),),
+        )
+            .prop_map(|((user_id, text, completed, priority, estimate, due_at, remind_at, a, b, c, d, e), (f,))| Self { user_id, text, completed, priority, estimate, due_at, remind_at, a, b, c, d, e, f })
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for CreateTodo {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        proptest::strategy::Strategy::boxed(Self::strategy())
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl UpdateTodo {
+    /// A proptest strategy generating `UpdateTodo`s with values fitting the columns' types (and max lengths)
+    pub fn strategy() -> impl proptest::strategy::Strategy<Value = Self> {
+        use proptest::prelude::*;
+
+        (
+            (proptest::option::of(any::<i32>().prop_map(crate::models::users::UserId)), proptest::option::of("\\PC*"), proptest::option::of(any::<bool>()), proptest::option::of(any::<i16>()), proptest::option::of(proptest::option::of(any::<f64>())), proptest::option::of(proptest::option::of((0i64..4_102_444_800).prop_map(|secs| chrono::DateTime::from_timestamp(secs, 0).unwrap()).prop_map(|t| t.naive_utc()))), proptest::option::of(proptest::option::of((0u32..86_400).prop_map(|secs| chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs, 0).unwrap()))), proptest::option::of(any::<i32>()), proptest::option::of(any::<i32>()), proptest::option::of(any::<i32>()), proptest::option::of(any::<i32>()), proptest::option::of(any::<i32>())),
+            (proptest::option::of(any::<i32>()),),
+        )
+            .prop_map(|((user_id, text, completed, priority, estimate, due_at, remind_at, a, b, c, d, e), (f,))| Self { user_id, text, completed, priority, estimate, due_at, remind_at, a, b, c, d, e, f })
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for UpdateTodo {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        proptest::strategy::Strategy::boxed(Self::strategy())
+    }
+}
+
+/// Conditions on `todos` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct TodoFilter {
+    pub id: Option<TodoId>,
+    pub user_id: Option<crate::models::users::UserId>,
+    pub text: Option<String>,
+    pub completed: Option<bool>,
+    pub priority: Option<i16>,
+    pub estimate: Option<Option<f64>>,
+    pub due_at: Option<Option<chrono::NaiveDateTime>>,
+    pub remind_at: Option<Option<chrono::NaiveTime>>,
+    pub a: Option<i32>,
+    pub b: Option<i32>,
+    pub c: Option<i32>,
+    pub d: Option<i32>,
+    pub e: Option<i32>,
+    pub f: Option<i32>,
+}
+
+pub type TodoFilterExpression = Box<dyn BoxableExpression<crate::schema::todos::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl TodoFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.user_id.is_none() && self.text.is_none() && self.completed.is_none() && self.priority.is_none() && self.estimate.is_none() && self.due_at.is_none() && self.remind_at.is_none() && self.a.is_none() && self.b.is_none() && self.c.is_none() && self.d.is_none() && self.e.is_none() && self.f.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> TodoFilterExpression {
+        use crate::schema::todos;
+
+        let mut expression: TodoFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(todos::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.user_id {
+            expression = Box::new(expression.and(todos::user_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.text {
+            expression = Box::new(expression.and(todos::text.eq(value.clone())));
+        }
+        if let Some(value) = &self.completed {
+            expression = Box::new(expression.and(todos::completed.eq(value.clone())));
+        }
+        if let Some(value) = &self.priority {
+            expression = Box::new(expression.and(todos::priority.eq(value.clone())));
+        }
+        match &self.estimate {
+            Some(Some(value)) => expression = Box::new(expression.and(todos::estimate.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(todos::estimate.is_null())),
+            None => {}
+        }
+        match &self.due_at {
+            Some(Some(value)) => expression = Box::new(expression.and(todos::due_at.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(todos::due_at.is_null())),
+            None => {}
+        }
+        match &self.remind_at {
+            Some(Some(value)) => expression = Box::new(expression.and(todos::remind_at.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(todos::remind_at.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.a {
+            expression = Box::new(expression.and(todos::a.eq(value.clone())));
+        }
+        if let Some(value) = &self.b {
+            expression = Box::new(expression.and(todos::b.eq(value.clone())));
+        }
+        if let Some(value) = &self.c {
+            expression = Box::new(expression.and(todos::c.eq(value.clone())));
+        }
+        if let Some(value) = &self.d {
+            expression = Box::new(expression.and(todos::d.eq(value.clone())));
+        }
+        if let Some(value) = &self.e {
+            expression = Box::new(expression.and(todos::e.eq(value.clone())));
+        }
+        if let Some(value) = &self.f {
+            expression = Box::new(expression.and(todos::f.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `todos` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoColumn {
+    Id,
+    UserId,
+    Text,
+    Completed,
+    Priority,
+    Estimate,
+    DueAt,
+    RemindAt,
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+}
+
+impl Todo {
+
+    pub fn create(db: &mut Connection, item: &CreateTodo) -> QueryResult<Self> {
+        use crate::schema::todos;
+
+        insert_into(todos::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: TodoId) -> QueryResult<Self> {
+        use crate::schema::todos;
+
+        todos::table.filter(todos::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`todos::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(TodoColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::todos;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = todos::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = todos::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((TodoColumn::Id, SortDirection::Asc)) => query.order(todos::id.asc()),
+            Some((TodoColumn::Id, SortDirection::Desc)) => query.order(todos::id.desc()),
+            Some((TodoColumn::UserId, SortDirection::Asc)) => query.order((todos::user_id.asc(), todos::id.asc())),
+            Some((TodoColumn::UserId, SortDirection::Desc)) => query.order((todos::user_id.desc(), todos::id.asc())),
+            Some((TodoColumn::Text, SortDirection::Asc)) => query.order((todos::text.asc(), todos::id.asc())),
+            Some((TodoColumn::Text, SortDirection::Desc)) => query.order((todos::text.desc(), todos::id.asc())),
+            Some((TodoColumn::Completed, SortDirection::Asc)) => query.order((todos::completed.asc(), todos::id.asc())),
+            Some((TodoColumn::Completed, SortDirection::Desc)) => query.order((todos::completed.desc(), todos::id.asc())),
+            Some((TodoColumn::Priority, SortDirection::Asc)) => query.order((todos::priority.asc(), todos::id.asc())),
+            Some((TodoColumn::Priority, SortDirection::Desc)) => query.order((todos::priority.desc(), todos::id.asc())),
+            Some((TodoColumn::Estimate, SortDirection::Asc)) => query.order((todos::estimate.asc(), todos::id.asc())),
+            Some((TodoColumn::Estimate, SortDirection::Desc)) => query.order((todos::estimate.desc(), todos::id.asc())),
+            Some((TodoColumn::DueAt, SortDirection::Asc)) => query.order((todos::due_at.asc(), todos::id.asc())),
+            Some((TodoColumn::DueAt, SortDirection::Desc)) => query.order((todos::due_at.desc(), todos::id.asc())),
+            Some((TodoColumn::RemindAt, SortDirection::Asc)) => query.order((todos::remind_at.asc(), todos::id.asc())),
+            Some((TodoColumn::RemindAt, SortDirection::Desc)) => query.order((todos::remind_at.desc(), todos::id.asc())),
+            Some((TodoColumn::A, SortDirection::Asc)) => query.order((todos::a.asc(), todos::id.asc())),
+            Some((TodoColumn::A, SortDirection::Desc)) => query.order((todos::a.desc(), todos::id.asc())),
+            Some((TodoColumn::B, SortDirection::Asc)) => query.order((todos::b.asc(), todos::id.asc())),
+            Some((TodoColumn::B, SortDirection::Desc)) => query.order((todos::b.desc(), todos::id.asc())),
+            Some((TodoColumn::C, SortDirection::Asc)) => query.order((todos::c.asc(), todos::id.asc())),
+            Some((TodoColumn::C, SortDirection::Desc)) => query.order((todos::c.desc(), todos::id.asc())),
+            Some((TodoColumn::D, SortDirection::Asc)) => query.order((todos::d.asc(), todos::id.asc())),
+            Some((TodoColumn::D, SortDirection::Desc)) => query.order((todos::d.desc(), todos::id.asc())),
+            Some((TodoColumn::E, SortDirection::Asc)) => query.order((todos::e.asc(), todos::id.asc())),
+            Some((TodoColumn::E, SortDirection::Desc)) => query.order((todos::e.desc(), todos::id.asc())),
+            Some((TodoColumn::F, SortDirection::Asc)) => query.order((todos::f.asc(), todos::id.asc())),
+            Some((TodoColumn::F, SortDirection::Desc)) => query.order((todos::f.desc(), todos::id.asc())),
+            None => query.order(todos::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::todos;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = todos::table.order(todos::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::todos;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = todos::table.order(todos::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(todos::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: TodoId, item: &UpdateTodo) -> QueryResult<Self> {
+        use crate::schema::todos;
+
+        if item.is_empty() {
+            return todos::table.filter(todos::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(todos::table.filter(todos::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &TodoFilter, changes: &UpdateTodo) -> QueryResult<Vec<Self>> {
+        use crate::schema::todos;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return todos::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(todos::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: TodoId) -> QueryResult<usize> {
+        use crate::schema::todos;
+
+        diesel::delete(todos::table.filter(todos::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &TodoFilter) -> QueryResult<usize> {
+        use crate::schema::todos;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(todos::table.filter(filter.expression())).execute(db)
+    }
+
+    /// Loads rows along with their associated `User` (inner join on `user_id`)
+    pub fn with_user(db: &mut Connection) -> QueryResult<Vec<(Self, User)>> {
+        use crate::schema::{todos, users};
+
+        todos::table.inner_join(users::table).select((Self::as_select(), User::as_select())).load::<(Self, User)>(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,425 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `users` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `name` | `Varchar` | `String` |  |
+//! | `bio` | `Nullable<Text>` | `Option<String>` |  |
+//! | `external_id` | `Uuid` | `uuid::Uuid` |  |
+//! | `avatar` | `Nullable<Bytea>` | `Option<Vec<u8>>` |  |
+//! | `settings` | `Jsonb` | `serde_json::Value` |  |
+//! | `born_on` | `Nullable<Date>` | `Option<chrono::NaiveDate>` |  |
+//! | `created_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+/// The primary key of `users` rows, which can't be mixed up with keys of other tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, diesel::AsExpression, diesel::FromSqlRow)]
+#[diesel(sql_type = diesel::sql_types::Int4)]
+#[serde(transparent)]
+pub struct UserId(pub i32);
+
+impl<DB: diesel::backend::Backend> diesel::serialize::ToSql<diesel::sql_types::Int4, DB> for UserId
+where
+    i32: diesel::serialize::ToSql<diesel::sql_types::Int4, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, DB>) -> diesel::serialize::Result {
+        <i32 as diesel::serialize::ToSql<diesel::sql_types::Int4, DB>>::to_sql(&self.0, out)
+    }
+}
+
+impl<DB: diesel::backend::Backend> diesel::deserialize::FromSql<diesel::sql_types::Int4, DB> for UserId
+where
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Int4, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        <i32 as diesel::deserialize::FromSql<diesel::sql_types::Int4, DB>>::from_sql(bytes).map(Self)
+    }
+}
+
+impl std::fmt::Display for UserId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<i32> for UserId {
+    fn from(id: i32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<UserId> for i32 {
+    fn from(id: UserId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=users, primary_key(id))]
+pub struct User {
+    pub id: UserId,
+    pub name: String,
+    pub bio: Option<String>,
+    pub external_id: uuid::Uuid,
+    pub avatar: Option<Vec<u8>>,
+    pub settings: serde_json::Value,
+    pub born_on: Option<chrono::NaiveDate>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=users)]
+pub struct CreateUser {
+    pub name: String,
+    pub bio: Option<String>,
+    pub external_id: uuid::Uuid,
+    pub avatar: Option<Vec<u8>>,
+    pub settings: serde_json::Value,
+    pub born_on: Option<chrono::NaiveDate>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=users)]
+pub struct UpdateUser {
+    pub name: Option<String>,
+    pub bio: Option<Option<String>>,
+    pub external_id: Option<uuid::Uuid>,
+    pub avatar: Option<Option<Vec<u8>>>,
+    pub settings: Option<serde_json::Value>,
+    pub born_on: Option<Option<chrono::NaiveDate>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl UpdateUser {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.bio.is_none()
+            && self.external_id.is_none()
+            && self.avatar.is_none()
+            && self.settings.is_none()
+            && self.born_on.is_none()
+            && self.created_at.is_none()
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl CreateUser {
+    /// A proptest strategy generating `CreateUser`s with values fitting the columns' types (and max lengths)
+    pub fn strategy() -> impl proptest::strategy::Strategy<Value = Self> {
+        use proptest::prelude::*;
+
+        (
+            "\\PC{0,32}",
+            proptest::option::of("\\PC*"),
+            any::<u128>().prop_map(uuid::Uuid::from_u128),
+            proptest::option::of(proptest::collection::vec(any::<u8>(), 0..64)),
+            "\\PC*".prop_map(serde_json::Value::String),
+            proptest::option::of((0i64..4_102_444_800).prop_map(|secs| chrono::DateTime::from_timestamp(secs, 0).unwrap()).prop_map(|t| t.date_naive())),
+            (0i64..4_102_444_800).prop_map(|secs| chrono::DateTime::from_timestamp(secs, 0).unwrap()),
+        )
+            .prop_map(|(name, bio, external_id, avatar, settings, born_on, created_at)| Self { name, bio, external_id, avatar, settings, born_on, created_at })
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for CreateUser {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        proptest::strategy::Strategy::boxed(Self::strategy())
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl UpdateUser {
+    /// A proptest strategy generating `UpdateUser`s with values fitting the columns' types (and max lengths)
+    pub fn strategy() -> impl proptest::strategy::Strategy<Value = Self> {
+        use proptest::prelude::*;
+
+        (
+            proptest::option::of("\\PC{0,32}"),
+            proptest::option::of(proptest::option::of("\\PC*")),
+            proptest::option::of(any::<u128>().prop_map(uuid::Uuid::from_u128)),
+            proptest::option::of(proptest::option::of(proptest::collection::vec(any::<u8>(), 0..64))),
+            proptest::option::of("\\PC*".prop_map(serde_json::Value::String)),
+            proptest::option::of(proptest::option::of((0i64..4_102_444_800).prop_map(|secs| chrono::DateTime::from_timestamp(secs, 0).unwrap()).prop_map(|t| t.date_naive()))),
+            proptest::option::of((0i64..4_102_444_800).prop_map(|secs| chrono::DateTime::from_timestamp(secs, 0).unwrap())),
+        )
+            .prop_map(|(name, bio, external_id, avatar, settings, born_on, created_at)| Self { name, bio, external_id, avatar, settings, born_on, created_at })
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for UpdateUser {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        proptest::strategy::Strategy::boxed(Self::strategy())
+    }
+}
+
+/// Conditions on `users` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct UserFilter {
+    pub id: Option<UserId>,
+    pub name: Option<String>,
+    pub bio: Option<Option<String>>,
+    pub external_id: Option<uuid::Uuid>,
+    pub avatar: Option<Option<Vec<u8>>>,
+    pub settings: Option<serde_json::Value>,
+    pub born_on: Option<Option<chrono::NaiveDate>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub type UserFilterExpression = Box<dyn BoxableExpression<crate::schema::users::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl UserFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.name.is_none() && self.bio.is_none() && self.external_id.is_none() && self.avatar.is_none() && self.settings.is_none() && self.born_on.is_none() && self.created_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> UserFilterExpression {
+        use crate::schema::users;
+
+        let mut expression: UserFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(users::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(users::name.eq(value.clone())));
+        }
+        match &self.bio {
+            Some(Some(value)) => expression = Box::new(expression.and(users::bio.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(users::bio.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.external_id {
+            expression = Box::new(expression.and(users::external_id.eq(value.clone())));
+        }
+        match &self.avatar {
+            Some(Some(value)) => expression = Box::new(expression.and(users::avatar.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(users::avatar.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.settings {
+            expression = Box::new(expression.and(users::settings.eq(value.clone())));
+        }
+        match &self.born_on {
+            Some(Some(value)) => expression = Box::new(expression.and(users::born_on.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(users::born_on.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.created_at {
+            expression = Box::new(expression.and(users::created_at.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `users` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserColumn {
+    Id,
+    Name,
+    Bio,
+    ExternalId,
+    Avatar,
+    Settings,
+    BornOn,
+    CreatedAt,
+}
+
+impl User {
+
+    pub fn create(db: &mut Connection, item: &CreateUser) -> QueryResult<Self> {
+        use crate::schema::users;
+
+        insert_into(users::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: UserId) -> QueryResult<Self> {
+        use crate::schema::users;
+
+        users::table.filter(users::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`users::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(UserColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::users;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = users::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = users::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((UserColumn::Id, SortDirection::Asc)) => query.order(users::id.asc()),
+            Some((UserColumn::Id, SortDirection::Desc)) => query.order(users::id.desc()),
+            Some((UserColumn::Name, SortDirection::Asc)) => query.order((users::name.asc(), users::id.asc())),
+            Some((UserColumn::Name, SortDirection::Desc)) => query.order((users::name.desc(), users::id.asc())),
+            Some((UserColumn::Bio, SortDirection::Asc)) => query.order((users::bio.asc(), users::id.asc())),
+            Some((UserColumn::Bio, SortDirection::Desc)) => query.order((users::bio.desc(), users::id.asc())),
+            Some((UserColumn::ExternalId, SortDirection::Asc)) => query.order((users::external_id.asc(), users::id.asc())),
+            Some((UserColumn::ExternalId, SortDirection::Desc)) => query.order((users::external_id.desc(), users::id.asc())),
+            Some((UserColumn::Avatar, SortDirection::Asc)) => query.order((users::avatar.asc(), users::id.asc())),
+            Some((UserColumn::Avatar, SortDirection::Desc)) => query.order((users::avatar.desc(), users::id.asc())),
+            Some((UserColumn::Settings, SortDirection::Asc)) => query.order((users::settings.asc(), users::id.asc())),
+            Some((UserColumn::Settings, SortDirection::Desc)) => query.order((users::settings.desc(), users::id.asc())),
+            Some((UserColumn::BornOn, SortDirection::Asc)) => query.order((users::born_on.asc(), users::id.asc())),
+            Some((UserColumn::BornOn, SortDirection::Desc)) => query.order((users::born_on.desc(), users::id.asc())),
+            Some((UserColumn::CreatedAt, SortDirection::Asc)) => query.order((users::created_at.asc(), users::id.asc())),
+            Some((UserColumn::CreatedAt, SortDirection::Desc)) => query.order((users::created_at.desc(), users::id.asc())),
+            None => query.order(users::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::users;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = users::table.order(users::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::users;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = users::table.order(users::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(users::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: UserId, item: &UpdateUser) -> QueryResult<Self> {
+        use crate::schema::users;
+
+        if item.is_empty() {
+            return users::table.filter(users::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(users::table.filter(users::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &UserFilter, changes: &UpdateUser) -> QueryResult<Vec<Self>> {
+        use crate::schema::users;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return users::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(users::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: UserId) -> QueryResult<usize> {
+        use crate::schema::users;
+
+        diesel::delete(users::table.filter(users::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &UserFilter) -> QueryResult<usize> {
+        use crate::schema::users;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(users::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
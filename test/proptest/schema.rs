@@ -0,0 +1,35 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        #[max_length = 32]
+        name -> Varchar,
+        bio -> Nullable<Text>,
+        external_id -> Uuid,
+        avatar -> Nullable<Bytea>,
+        settings -> Jsonb,
+        born_on -> Nullable<Date>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        user_id -> Int4,
+        text -> Text,
+        completed -> Bool,
+        priority -> Int2,
+        estimate -> Nullable<Float8>,
+        due_at -> Nullable<Timestamp>,
+        remind_at -> Nullable<Time>,
+        a -> Int4,
+        b -> Int4,
+        c -> Int4,
+        d -> Int4,
+        e -> Int4,
+        f -> Int4,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
+diesel::allow_tables_to_appear_in_same_query!(todos, users);
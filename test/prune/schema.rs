@@ -0,0 +1,9 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+        email -> Text,
+        age -> Nullable<Int8>,
+        about -> Text,
+    }
+}
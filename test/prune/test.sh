@@ -0,0 +1,12 @@
+#!/bin/bash
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR
+
+# start from the models of the previous schema
+rm -rf models
+cp -r previous_models models
+
+cargo run -- check -i schema.rs -g id
+cargo run -- prune -i schema.rs -o models -g id
\ No newline at end of file
@@ -0,0 +1,8 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        dueDate -> Nullable<Timestamptz>,
+        completed -> Bool,
+    }
+}
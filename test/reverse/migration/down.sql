@@ -0,0 +1,5 @@
+/* This file is generated and managed by dsync */
+
+DROP TABLE "todo_tags";
+DROP TABLE "todos";
+DROP TABLE "users";
@@ -0,0 +1,26 @@
+/* This file is generated and managed by dsync */
+
+CREATE TABLE "users" (
+    "id" SERIAL NOT NULL,
+    "name" TEXT NOT NULL,
+    "email" TEXT,
+    "handle" VARCHAR NOT NULL,
+    "created_at" TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY ("id")
+);
+
+CREATE TABLE "todos" (
+    "id" UUID NOT NULL,
+    "owner_id" INTEGER NOT NULL REFERENCES "users" ("id"),
+    "type" TEXT NOT NULL,
+    "tags" TEXT[] NOT NULL,
+    "due date" DATE,
+    "metadata" JSONB,
+    PRIMARY KEY ("id")
+);
+
+CREATE TABLE "todo_tags" (
+    "todo_id" UUID NOT NULL REFERENCES "todos" ("id"),
+    "tag" TEXT NOT NULL,
+    PRIMARY KEY ("todo_id", "tag")
+);
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// someone who can log in
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = crate::schema::users)]
+pub struct User {
+    pub id: i32,
+    /// shown on the profile page
+    pub name: String,
+    pub email: Option<String>,
+    #[diesel(sql_type = Varchar)]
+    pub handle: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name = crate::schema::users)]
+pub struct CreateUser {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Selectable, Identifiable, Associations)]
+#[diesel(table_name = todos, belongs_to(User, foreign_key = owner_id))]
+pub struct Todo {
+    pub id: uuid::Uuid,
+    pub owner_id: i32,
+    #[diesel(column_name = "type")]
+    pub kind: String,
+    pub tags: Vec<Option<String>>,
+    #[diesel(column_name = "due date")]
+    pub due_date: Option<chrono::NaiveDate>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Associations)]
+#[diesel(table_name = todo_tags, primary_key(todo_id, tag), belongs_to(Todo))]
+pub struct TodoTag {
+    pub todo_id: uuid::Uuid,
+    pub tag: String,
+}
@@ -0,0 +1,41 @@
+/* This file is generated and managed by dsync */
+
+diesel::table! {
+    /// someone who can log in
+    users (id) {
+        id -> Int4,
+        /// shown on the profile page
+        name -> Text,
+        email -> Nullable<Text>,
+        handle -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    todos (id) {
+        id -> Uuid,
+        owner_id -> Int4,
+        r#type -> Text,
+        tags -> Array<Nullable<Text>>,
+        #[sql_name = "due date"]
+        due_date -> Nullable<Date>,
+        metadata -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    todo_tags (todo_id, tag) {
+        todo_id -> Uuid,
+        tag -> Text,
+    }
+}
+
+diesel::joinable!(todos -> users (owner_id));
+diesel::joinable!(todo_tags -> todos (todo_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    users,
+    todos,
+    todo_tags,
+);
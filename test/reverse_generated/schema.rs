@@ -0,0 +1,25 @@
+/* This file is generated and managed by dsync */
+
+diesel::table! {
+    todos (id) {
+        id -> Uuid,
+        user_id -> Int4,
+        reviewer_id -> Nullable<Int4>,
+        parent_id -> Nullable<Uuid>,
+        text -> Text,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    todos,
+    users,
+);
@@ -0,0 +1,8 @@
+#!/bin/bash
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR
+
+# reads the models dsync generates for the `newtype_ids` test back into a schema
+cargo run -- --models-input ../newtype_ids/models -o schema.rs
@@ -0,0 +1,16 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        completed -> Bool,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    tags (todo_id, name) {
+        todo_id -> Int4,
+        name -> Text,
+        color -> Nullable<Text>,
+    }
+}
@@ -0,0 +1,12 @@
+diesel::table! {
+    use diesel::sql_types::*;
+
+    /// Things that need to get done
+    todos (id) {
+        id -> Int4,
+        /// A short description of the todo
+        #[max_length = 255]
+        text -> Varchar,
+        completed -> Bool,
+    }
+}
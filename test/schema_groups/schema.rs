@@ -0,0 +1,27 @@
+diesel::table! {
+    billing.invoices (id) {
+        id -> Int4,
+        user_id -> Int4,
+        total -> Int4,
+    }
+}
+
+diesel::table! {
+    billing.payments (id) {
+        id -> Int4,
+        invoice_id -> Int4,
+        amount -> Int4,
+    }
+}
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::joinable!(invoices -> users (user_id));
+diesel::joinable!(payments -> invoices (invoice_id));
+
+diesel::allow_tables_to_appear_in_same_query!(invoices, payments, users,);
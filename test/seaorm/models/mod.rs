@@ -0,0 +1,3 @@
+pub mod users;
+pub mod todos;
+pub mod todo_tags;
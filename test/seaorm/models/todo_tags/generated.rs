@@ -0,0 +1,37 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `todo_tags` table.
+//!
+//! - primary key: `todo_id`, `tag`
+//! - foreign key: `todo_id` references `todos`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `todo_id` | `Uuid` | `uuid::Uuid` |  |
+//! | `tag` | `Text` | `String` |  |
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "todo_tags")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub todo_id: uuid::Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, Copy, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(belongs_to = "crate::models::todos::Entity", from = "Column::TodoId", to = "crate::models::todos::Column::Id")]
+    Todos,
+}
+
+impl Related<crate::models::todos::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Todos.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,54 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `todos` table.
+//!
+//! - primary key: `id`
+//! - foreign key: `owner_id` references `users`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Uuid` | `uuid::Uuid` |  |
+//! | `owner_id` | `Int4` | `i32` |  |
+//! | `assignee_id` | `Nullable<Int4>` | `Option<i32>` |  |
+//! | `kind` | `Text` | `String` |  |
+//! | `done` | `Bool` | `bool` |  |
+//! | `metadata` | `Jsonb` | `serde_json::Value` |  |
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "todos")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: uuid::Uuid,
+    pub owner_id: i32,
+    #[sea_orm(nullable)]
+    pub assignee_id: Option<i32>,
+    #[sea_orm(column_name = "type")]
+    pub kind: String,
+    pub done: bool,
+    pub metadata: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(belongs_to = "crate::models::users::Entity", from = "Column::OwnerId", to = "crate::models::users::Column::Id")]
+    Users,
+    #[sea_orm(has_many = "crate::models::todo_tags::Entity")]
+    TodoTags,
+}
+
+impl Related<crate::models::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl Related<crate::models::todo_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TodoTags.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
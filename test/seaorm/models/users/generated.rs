@@ -0,0 +1,44 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `users` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `name` | `Text` | `String` | shown on the profile page |
+//! | `email` | `Nullable<Text>` | `Option<String>` |  |
+//! | `invited_by` | `Nullable<Int4>` | `Option<i32>` |  |
+//! | `created_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "users")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// shown on the profile page
+    pub name: String,
+    #[sea_orm(nullable)]
+    pub email: Option<String>,
+    #[sea_orm(nullable)]
+    pub invited_by: Option<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Copy, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "crate::models::todos::Entity")]
+    Todos,
+}
+
+impl Related<crate::models::todos::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Todos.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
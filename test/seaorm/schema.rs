@@ -0,0 +1,34 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        /// shown on the profile page
+        name -> Text,
+        email -> Nullable<Text>,
+        invited_by -> Nullable<Int4>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    todos (id) {
+        id -> Uuid,
+        owner_id -> Int4,
+        assignee_id -> Nullable<Int4>,
+        #[sql_name = "type"]
+        kind -> Text,
+        done -> Bool,
+        metadata -> Jsonb,
+    }
+}
+
+diesel::table! {
+    todo_tags (todo_id, tag) {
+        todo_id -> Uuid,
+        tag -> Text,
+    }
+}
+
+diesel::joinable!(todos -> users (owner_id));
+diesel::joinable!(todo_tags -> todos (todo_id));
+
+diesel::allow_tables_to_appear_in_same_query!(todo_tags, todos, users,);
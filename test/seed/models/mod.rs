@@ -0,0 +1,3 @@
+pub mod todos;
+pub mod users;
+pub mod seed;
@@ -0,0 +1,8 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        email -> Text,
+        password_hash -> Text,
+        recovery_code -> Nullable<Text>,
+    }
+}
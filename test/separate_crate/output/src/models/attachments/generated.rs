@@ -0,0 +1,279 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `attachments` table.
+//!
+//! - primary key: `id`
+//! - foreign key: `todo_id` references `todos`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Uuid` | `uuid::Uuid` |  |
+//! | `todo_id` | `Int4` | `i32` |  |
+//! | `metadata` | `Jsonb` | `serde_json::Value` |  |
+//! | `created_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::models::todos::Todo;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Associations, Selectable)]
+#[diesel(table_name=attachments, primary_key(id), belongs_to(Todo, foreign_key=todo_id))]
+pub struct Attachment {
+    pub id: uuid::Uuid,
+    pub todo_id: i32,
+    pub metadata: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=attachments)]
+pub struct CreateAttachment {
+    pub todo_id: i32,
+    pub metadata: serde_json::Value,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=attachments)]
+pub struct UpdateAttachment {
+    pub todo_id: Option<i32>,
+    pub metadata: Option<serde_json::Value>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl UpdateAttachment {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.todo_id.is_none()
+            && self.metadata.is_none()
+            && self.created_at.is_none()
+    }
+}
+
+/// Conditions on `attachments` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct AttachmentFilter {
+    pub id: Option<uuid::Uuid>,
+    pub todo_id: Option<i32>,
+    pub metadata: Option<serde_json::Value>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub type AttachmentFilterExpression = Box<dyn BoxableExpression<crate::schema::attachments::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl AttachmentFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.todo_id.is_none() && self.metadata.is_none() && self.created_at.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> AttachmentFilterExpression {
+        use crate::schema::attachments;
+
+        let mut expression: AttachmentFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(attachments::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.todo_id {
+            expression = Box::new(expression.and(attachments::todo_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.metadata {
+            expression = Box::new(expression.and(attachments::metadata.eq(value.clone())));
+        }
+        if let Some(value) = &self.created_at {
+            expression = Box::new(expression.and(attachments::created_at.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `attachments` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentColumn {
+    Id,
+    TodoId,
+    Metadata,
+    CreatedAt,
+}
+
+impl Attachment {
+
+    pub fn create(db: &mut Connection, item: &CreateAttachment) -> QueryResult<Self> {
+        use crate::schema::attachments;
+
+        insert_into(attachments::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: uuid::Uuid) -> QueryResult<Self> {
+        use crate::schema::attachments;
+
+        attachments::table.filter(attachments::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`attachments::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(AttachmentColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::attachments;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = attachments::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = attachments::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((AttachmentColumn::Id, SortDirection::Asc)) => query.order(attachments::id.asc()),
+            Some((AttachmentColumn::Id, SortDirection::Desc)) => query.order(attachments::id.desc()),
+            Some((AttachmentColumn::TodoId, SortDirection::Asc)) => query.order((attachments::todo_id.asc(), attachments::id.asc())),
+            Some((AttachmentColumn::TodoId, SortDirection::Desc)) => query.order((attachments::todo_id.desc(), attachments::id.asc())),
+            Some((AttachmentColumn::Metadata, SortDirection::Asc)) => query.order((attachments::metadata.asc(), attachments::id.asc())),
+            Some((AttachmentColumn::Metadata, SortDirection::Desc)) => query.order((attachments::metadata.desc(), attachments::id.asc())),
+            Some((AttachmentColumn::CreatedAt, SortDirection::Asc)) => query.order((attachments::created_at.asc(), attachments::id.asc())),
+            Some((AttachmentColumn::CreatedAt, SortDirection::Desc)) => query.order((attachments::created_at.desc(), attachments::id.asc())),
+            None => query.order(attachments::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::attachments;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = attachments::table.order(attachments::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::attachments;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = attachments::table.order(attachments::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(attachments::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: uuid::Uuid, item: &UpdateAttachment) -> QueryResult<Self> {
+        use crate::schema::attachments;
+
+        if item.is_empty() {
+            return attachments::table.filter(attachments::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(attachments::table.filter(attachments::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &AttachmentFilter, changes: &UpdateAttachment) -> QueryResult<Vec<Self>> {
+        use crate::schema::attachments;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return attachments::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(attachments::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: uuid::Uuid) -> QueryResult<usize> {
+        use crate::schema::attachments;
+
+        diesel::delete(attachments::table.filter(attachments::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &AttachmentFilter) -> QueryResult<usize> {
+        use crate::schema::attachments;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(attachments::table.filter(filter.expression())).execute(db)
+    }
+
+    /// Loads rows along with their associated `Todo` (inner join on `todo_id`)
+    pub fn with_todo(db: &mut Connection) -> QueryResult<Vec<(Self, Todo)>> {
+        use crate::schema::{attachments, todos};
+
+        attachments::table.inner_join(todos::table).select((Self::as_select(), Todo::as_select())).load::<(Self, Todo)>(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        text -> Text,
+        assignee -> Nullable<Text>,
+        due_at -> Nullable<Timestamptz>,
+        completed -> Bool,
+    }
+}
+
+diesel::table! {
+    attachments (id) {
+        id -> Uuid,
+        todo_id -> Int4,
+        metadata -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(attachments -> todos (todo_id));
+diesel::allow_tables_to_appear_in_same_query!(attachments, todos);
@@ -0,0 +1,43 @@
+/* This file is generated and managed by dsync */
+
+//! Inserts fixture files into the database (for example, to bootstrap development databases).
+
+use diesel::{Connection as _, QueryResult};
+use std::path::Path;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+pub type SeedError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Inserts the rows of the fixture files in `dir` in a single transaction, returning the number of inserted rows.
+///
+/// Fixture files are named after tables (`<table>.json`) and hold a JSON array of the table's Create structs; tables
+/// without a fixture file are skipped. Referenced tables are seeded before the tables referencing them.
+pub fn seed(db: &mut Connection, dir: &Path) -> Result<usize, SeedError> {
+    db.transaction(|db| {
+        let mut count = 0;
+        count += seed_table::<super::users::CreateUser>(db, dir, "users", |db, item| super::users::User::create(db, item).map(|_| ()))?;
+
+        Ok(count)
+    })
+}
+
+fn seed_table<T: serde::de::DeserializeOwned>(
+    db: &mut Connection,
+    dir: &Path,
+    table_name: &str,
+    create: impl Fn(&mut Connection, &T) -> QueryResult<()>,
+) -> Result<usize, SeedError> {
+    let path = dir.join(format!("{table_name}.json"));
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    let items: Vec<T> = serde_json::from_str(&std::fs::read_to_string(&path)?)
+        .map_err(|e| format!("Could not parse '{}': {e}", path.display()))?;
+    for item in items.iter() {
+        create(db, item)?;
+    }
+
+    Ok(items.len())
+}
@@ -0,0 +1,8 @@
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Text,
+        password_hash -> Text,
+        avatar -> Nullable<Bytea>,
+    }
+}
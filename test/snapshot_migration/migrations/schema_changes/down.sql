@@ -0,0 +1,15 @@
+-- generated by dsync from the schema changes since the last snapshot; review it before running it
+
+CREATE TABLE "sessions" (
+    "id" UUID NOT NULL,
+    "user_id" INTEGER NOT NULL REFERENCES "users" ("id"),
+    PRIMARY KEY ("id")
+);
+
+ALTER TABLE "users" DROP COLUMN "email";
+ALTER TABLE "users" RENAME COLUMN "about" TO "bio";
+ALTER TABLE "users" ALTER COLUMN "age" TYPE INTEGER;
+ALTER TABLE "users" ALTER COLUMN "age" SET NOT NULL;
+ALTER TABLE "users" ADD COLUMN "nickname" TEXT;
+
+DROP TABLE "todos";
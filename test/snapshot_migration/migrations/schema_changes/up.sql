@@ -0,0 +1,18 @@
+-- generated by dsync from the schema changes since the last snapshot; review it before running it
+
+CREATE TABLE "todos" (
+    "id" SERIAL NOT NULL,
+    "user_id" INTEGER NOT NULL REFERENCES "users" ("id"),
+    "title" VARCHAR(200) NOT NULL,
+    PRIMARY KEY ("id")
+);
+
+-- existing rows need a value for this column (add a DEFAULT if the table isn't empty)
+ALTER TABLE "users" ADD COLUMN "email" TEXT NOT NULL;
+-- looks like a rename (same type and position); use DROP COLUMN and ADD COLUMN if it isn't
+ALTER TABLE "users" RENAME COLUMN "bio" TO "about";
+ALTER TABLE "users" ALTER COLUMN "age" TYPE BIGINT;
+ALTER TABLE "users" ALTER COLUMN "age" DROP NOT NULL;
+ALTER TABLE "users" DROP COLUMN "nickname";
+
+DROP TABLE "sessions";
@@ -0,0 +1,13 @@
+#!/bin/bash
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR
+
+# start from the snapshot of the previous schema, and give the migration a stable name
+rm -rf migrations
+cp previous_snapshot.json snapshot.json
+
+cargo run -- -i schema.rs -o models --snapshot snapshot.json --migrations-dir migrations -g bio -c "diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>"
+
+mv migrations/*_dsync_schema_changes migrations/schema_changes
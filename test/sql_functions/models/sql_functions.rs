@@ -0,0 +1,28 @@
+/* This file is generated and managed by dsync */
+
+//! Typed wrappers for database functions and procedures.
+
+use crate::diesel::*;
+use diesel::QueryResult;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+/// Calls the `next_invoice_number` database function
+pub fn next_invoice_number(db: &mut Connection, customer_id: i32) -> QueryResult<i64> {
+    diesel::select(diesel::dsl::sql::<diesel::sql_types::Int8>("next_invoice_number(").bind::<diesel::sql_types::Int4, _>(customer_id).sql(")")).get_result::<i64>(db)
+}
+
+/// Calls the `billing.format_invoice` database function
+pub fn format_invoice(db: &mut Connection, number: i64, prefix: Option<&str>) -> QueryResult<String> {
+    diesel::select(diesel::dsl::sql::<diesel::sql_types::Text>("billing.format_invoice(").bind::<diesel::sql_types::Int8, _>(number).sql(", ").bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(prefix).sql(")")).get_result::<String>(db)
+}
+
+/// Calls the `current_fiscal_year` database function
+pub fn current_fiscal_year(db: &mut Connection) -> QueryResult<Option<i32>> {
+    diesel::select(diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Int4>>("current_fiscal_year()")).get_result::<Option<i32>>(db)
+}
+
+/// Invokes the `archive_invoices` procedure (with `CALL`)
+pub fn archive_invoices(db: &mut Connection, before: chrono::NaiveDateTime, note: &str) -> QueryResult<usize> {
+    diesel::sql_query("CALL archive_invoices($1, $2)").bind::<diesel::sql_types::Timestamp, _>(before).bind::<diesel::sql_types::Text, _>(note).execute(db)
+}
@@ -0,0 +1,8 @@
+diesel::table! {
+    invoices (id) {
+        id -> Int4,
+        customer_id -> Int4,
+        number -> Int8,
+        note -> Nullable<Text>,
+    }
+}
@@ -0,0 +1,7 @@
+#!/bin/bash
+
+SCRIPT_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" &> /dev/null && pwd )"
+
+cd $SCRIPT_DIR
+
+cargo run -- -i schema.rs -o models -g id --sql-function 'next_invoice_number(customer_id: Int4) -> Int8' 'billing.format_invoice(number: Int8, prefix: Nullable<Text>) -> Text' 'current_fiscal_year() -> Nullable<Int4>' 'archive_invoices(before: Timestamp, note: Text)' -c "diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>"
\ No newline at end of file
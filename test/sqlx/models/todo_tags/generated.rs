@@ -0,0 +1,39 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `todo_tags` table.
+//!
+//! - primary key: `todo_id`, `tag`
+//! - foreign key: `todo_id` references `todos`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `todo_id` | `Uuid` | `uuid::Uuid` |  |
+//! | `tag` | `Text` | `String` |  |
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TodoTag {
+    pub todo_id: uuid::Uuid,
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTodoTag {
+    pub todo_id: uuid::Uuid,
+    pub tag: String,
+}
+
+impl TodoTag {
+    /// Selects a row by primary key (binds: `todo_id`, `tag`)
+    pub const SELECT: &'static str = r#"SELECT "todo_id", "tag" FROM "todo_tags" WHERE "todo_id" = $1 AND "tag" = $2"#;
+
+    /// Selects a page of rows (binds: limit, offset)
+    pub const SELECT_PAGE: &'static str = r#"SELECT "todo_id", "tag" FROM "todo_tags" ORDER BY "todo_id", "tag" LIMIT $1 OFFSET $2"#;
+
+    /// Inserts a row (binds: the fields of [`CreateTodoTag`], in order)
+    pub const INSERT: &'static str = r#"INSERT INTO "todo_tags" ("todo_id", "tag") VALUES ($1, $2) RETURNING "todo_id", "tag""#;
+
+    /// Deletes a row by primary key (binds: `todo_id`, `tag`)
+    pub const DELETE: &'static str = r#"DELETE FROM "todo_tags" WHERE "todo_id" = $1 AND "tag" = $2"#;
+}
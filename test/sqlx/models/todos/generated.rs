@@ -0,0 +1,54 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `todos` table.
+//!
+//! - primary key: `id`
+//! - foreign key: `owner_id` references `users`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Uuid` | `uuid::Uuid` |  |
+//! | `owner_id` | `Int4` | `i32` |  |
+//! | `assignee_id` | `Nullable<Int4>` | `Option<i32>` |  |
+//! | `kind` | `Text` | `String` |  |
+//! | `done` | `Bool` | `bool` |  |
+//! | `metadata` | `Jsonb` | `serde_json::Value` |  |
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Todo {
+    pub id: uuid::Uuid,
+    pub owner_id: i32,
+    pub assignee_id: Option<i32>,
+    #[sqlx(rename = "type")]
+    pub kind: String,
+    pub done: bool,
+    pub metadata: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTodo {
+    pub owner_id: i32,
+    pub assignee_id: Option<i32>,
+    pub kind: String,
+    pub done: bool,
+    pub metadata: serde_json::Value,
+}
+
+impl Todo {
+    /// Selects a row by primary key (binds: `id`)
+    pub const SELECT: &'static str = r#"SELECT "id", "owner_id", "assignee_id", "type", "done", "metadata" FROM "todos" WHERE "id" = $1"#;
+
+    /// Selects a page of rows (binds: limit, offset)
+    pub const SELECT_PAGE: &'static str = r#"SELECT "id", "owner_id", "assignee_id", "type", "done", "metadata" FROM "todos" ORDER BY "id" LIMIT $1 OFFSET $2"#;
+
+    /// Inserts a row (binds: the fields of [`CreateTodo`], in order)
+    pub const INSERT: &'static str = r#"INSERT INTO "todos" ("owner_id", "assignee_id", "type", "done", "metadata") VALUES ($1, $2, $3, $4, $5) RETURNING "id", "owner_id", "assignee_id", "type", "done", "metadata""#;
+
+    /// Updates every column of a row (binds: `owner_id`, `assignee_id`, `kind`, `done`, `metadata`, then `id`)
+    pub const UPDATE: &'static str = r#"UPDATE "todos" SET "owner_id" = $1, "assignee_id" = $2, "type" = $3, "done" = $4, "metadata" = $5 WHERE "id" = $6 RETURNING "id", "owner_id", "assignee_id", "type", "done", "metadata""#;
+
+    /// Deletes a row by primary key (binds: `id`)
+    pub const DELETE: &'static str = r#"DELETE FROM "todos" WHERE "id" = $1"#;
+}
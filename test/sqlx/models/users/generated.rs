@@ -0,0 +1,50 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `users` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `name` | `Text` | `String` | shown on the profile page |
+//! | `email` | `Nullable<Text>` | `Option<String>` |  |
+//! | `invited_by` | `Nullable<Int4>` | `Option<i32>` |  |
+//! | `created_at` | `Timestamptz` | `chrono::DateTime<chrono::Utc>` |  |
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct User {
+    pub id: i32,
+    /// shown on the profile page
+    pub name: String,
+    pub email: Option<String>,
+    pub invited_by: Option<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUser {
+    /// shown on the profile page
+    pub name: String,
+    pub email: Option<String>,
+    pub invited_by: Option<i32>,
+}
+
+impl User {
+    /// Selects a row by primary key (binds: `id`)
+    pub const SELECT: &'static str = r#"SELECT "id", "name", "email", "invited_by", "created_at" FROM "users" WHERE "id" = $1"#;
+
+    /// Selects a page of rows (binds: limit, offset)
+    pub const SELECT_PAGE: &'static str = r#"SELECT "id", "name", "email", "invited_by", "created_at" FROM "users" ORDER BY "id" LIMIT $1 OFFSET $2"#;
+
+    /// Inserts a row (binds: the fields of [`CreateUser`], in order)
+    pub const INSERT: &'static str = r#"INSERT INTO "users" ("name", "email", "invited_by") VALUES ($1, $2, $3) RETURNING "id", "name", "email", "invited_by", "created_at""#;
+
+    /// Updates every column of a row (binds: `name`, `email`, `invited_by`, `created_at`, then `id`)
+    pub const UPDATE: &'static str = r#"UPDATE "users" SET "name" = $1, "email" = $2, "invited_by" = $3, "created_at" = $4 WHERE "id" = $5 RETURNING "id", "name", "email", "invited_by", "created_at""#;
+
+    /// Deletes a row by primary key (binds: `id`)
+    pub const DELETE: &'static str = r#"DELETE FROM "users" WHERE "id" = $1"#;
+}
@@ -0,0 +1,2 @@
+pub mod user_role;
+pub mod user_roles;
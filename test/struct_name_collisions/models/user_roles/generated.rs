@@ -0,0 +1,238 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `user_roles` table.
+//!
+//! - primary key: `user_id`, `role_id`
+//! - foreign key: `role_id` references `user_role`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `user_id` | `Int4` | `i32` |  |
+//! | `role_id` | `Int4` | `i32` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::models::user_role::UserRole;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Associations, Selectable)]
+#[diesel(table_name=user_roles, primary_key(user_id,role_id), belongs_to(UserRole, foreign_key=role_id))]
+pub struct UserRoleLink {
+    pub user_id: i32,
+    pub role_id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=user_roles)]
+pub struct CreateUserRoleLink {
+    pub user_id: i32,
+    pub role_id: i32,
+}
+
+
+/// Conditions on `user_roles` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct UserRoleLinkFilter {
+    pub user_id: Option<i32>,
+    pub role_id: Option<i32>,
+}
+
+pub type UserRoleLinkFilterExpression = Box<dyn BoxableExpression<crate::schema::user_roles::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl UserRoleLinkFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.user_id.is_none() && self.role_id.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> UserRoleLinkFilterExpression {
+        use crate::schema::user_roles;
+
+        let mut expression: UserRoleLinkFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.user_id {
+            expression = Box::new(expression.and(user_roles::user_id.eq(value.clone())));
+        }
+        if let Some(value) = &self.role_id {
+            expression = Box::new(expression.and(user_roles::role_id.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `user_roles` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserRoleLinkColumn {
+    UserId,
+    RoleId,
+}
+
+/// The composite primary key of `user_roles` rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UserRoleLinkKey {
+    pub user_id: i32,
+    pub role_id: i32,
+}
+
+impl UserRoleLink {
+
+    pub fn create(db: &mut Connection, item: &CreateUserRoleLink) -> QueryResult<Self> {
+        use crate::schema::user_roles;
+
+        insert_into(user_roles::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_user_id: i32, param_role_id: i32) -> QueryResult<Self> {
+        use crate::schema::user_roles;
+
+        user_roles::table.filter(user_roles::user_id.eq(param_user_id)).filter(user_roles::role_id.eq(param_role_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`(user_roles::user_id.asc(), user_roles::role_id.asc())` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(UserRoleLinkColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::user_roles;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = user_roles::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = user_roles::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((UserRoleLinkColumn::UserId, SortDirection::Asc)) => query.order((user_roles::user_id.asc(), user_roles::role_id.asc())),
+            Some((UserRoleLinkColumn::UserId, SortDirection::Desc)) => query.order((user_roles::user_id.desc(), user_roles::role_id.asc())),
+            Some((UserRoleLinkColumn::RoleId, SortDirection::Asc)) => query.order((user_roles::role_id.asc(), user_roles::user_id.asc())),
+            Some((UserRoleLinkColumn::RoleId, SortDirection::Desc)) => query.order((user_roles::role_id.desc(), user_roles::user_id.asc())),
+            None => query.order((user_roles::user_id.asc(), user_roles::role_id.asc())),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::user_roles;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = user_roles::table.order((user_roles::user_id.asc(), user_roles::role_id.asc())).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::user_roles;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = user_roles::table.order((user_roles::user_id.asc(), user_roles::role_id.asc())).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(user_roles::user_id.gt(&last.user_id).or(user_roles::user_id.eq(&last.user_id).and(user_roles::role_id.gt(&last.role_id))));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn delete(db: &mut Connection, param_user_id: i32, param_role_id: i32) -> QueryResult<usize> {
+        use crate::schema::user_roles;
+
+        diesel::delete(user_roles::table.filter(user_roles::user_id.eq(param_user_id)).filter(user_roles::role_id.eq(param_role_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &UserRoleLinkFilter) -> QueryResult<usize> {
+        use crate::schema::user_roles;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(user_roles::table.filter(filter.expression())).execute(db)
+    }
+
+    /// The composite primary key of this row
+    pub fn key(&self) -> UserRoleLinkKey {
+        UserRoleLinkKey { user_id: self.user_id.clone(), role_id: self.role_id.clone() }
+    }
+
+    pub fn read_by_key(db: &mut Connection, key: &UserRoleLinkKey) -> QueryResult<Self> {
+        Self::read(db, key.user_id.clone(), key.role_id.clone())
+    }
+
+    pub fn delete_by_key(db: &mut Connection, key: &UserRoleLinkKey) -> QueryResult<usize> {
+        Self::delete(db, key.user_id.clone(), key.role_id.clone())
+    }
+
+    /// Loads rows along with their associated `UserRole` (inner join on `role_id`)
+    pub fn with_user_role(db: &mut Connection) -> QueryResult<Vec<(Self, UserRole)>> {
+        use crate::schema::{user_roles, user_role};
+
+        user_roles::table.inner_join(user_role::table).select((Self::as_select(), UserRole::as_select())).load::<(Self, UserRole)>(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+diesel::table! {
+    user_role (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    user_roles (user_id, role_id) {
+        user_id -> Int4,
+        role_id -> Int4,
+    }
+}
+
+diesel::joinable!(user_roles -> user_role (role_id));
+
+diesel::allow_tables_to_appear_in_same_query!(user_role, user_roles,);
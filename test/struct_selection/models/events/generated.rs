@@ -0,0 +1,237 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `events` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `kind` | `Text` | `String` |  |
+//! | `payload` | `Text` | `String` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=events, primary_key(id))]
+pub struct Event {
+    pub id: i32,
+    pub kind: String,
+    pub payload: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=events)]
+pub struct CreateEvent {
+    pub kind: String,
+    pub payload: String,
+}
+
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EventForm {
+    pub id: i32,
+    pub kind: Option<String>,
+    pub payload: Option<String>,
+}
+
+impl TryFrom<EventForm> for CreateEvent {
+    type Error = &'static str;
+
+    fn try_from(form: EventForm) -> Result<Self, Self::Error> {
+        Ok(Self {
+            kind: form.kind.ok_or("the `kind` field is required")?,
+            payload: form.payload.ok_or("the `payload` field is required")?,
+        })
+    }
+}
+
+/// Conditions on `events` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct EventFilter {
+    pub id: Option<i32>,
+    pub kind: Option<String>,
+    pub payload: Option<String>,
+}
+
+pub type EventFilterExpression = Box<dyn BoxableExpression<crate::schema::events::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl EventFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.kind.is_none() && self.payload.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> EventFilterExpression {
+        use crate::schema::events;
+
+        let mut expression: EventFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(events::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.kind {
+            expression = Box::new(expression.and(events::kind.eq(value.clone())));
+        }
+        if let Some(value) = &self.payload {
+            expression = Box::new(expression.and(events::payload.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `events` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventColumn {
+    Id,
+    Kind,
+    Payload,
+}
+
+impl Event {
+
+    pub fn create(db: &mut Connection, item: &CreateEvent) -> QueryResult<Self> {
+        use crate::schema::events;
+
+        insert_into(events::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::events;
+
+        events::table.filter(events::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`events::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(EventColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::events;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = events::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = events::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((EventColumn::Id, SortDirection::Asc)) => query.order(events::id.asc()),
+            Some((EventColumn::Id, SortDirection::Desc)) => query.order(events::id.desc()),
+            Some((EventColumn::Kind, SortDirection::Asc)) => query.order((events::kind.asc(), events::id.asc())),
+            Some((EventColumn::Kind, SortDirection::Desc)) => query.order((events::kind.desc(), events::id.asc())),
+            Some((EventColumn::Payload, SortDirection::Asc)) => query.order((events::payload.asc(), events::id.asc())),
+            Some((EventColumn::Payload, SortDirection::Desc)) => query.order((events::payload.desc(), events::id.asc())),
+            None => query.order(events::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::events;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = events::table.order(events::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::events;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = events::table.order(events::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(events::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::events;
+
+        diesel::delete(events::table.filter(events::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &EventFilter) -> QueryResult<usize> {
+        use crate::schema::events;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(events::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,2 @@
+pub mod countries;
+pub mod events;
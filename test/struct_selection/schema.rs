@@ -0,0 +1,14 @@
+diesel::table! {
+    countries (code) {
+        code -> Text,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    events (id) {
+        id -> Int4,
+        kind -> Text,
+        payload -> Text,
+    }
+}
@@ -0,0 +1,23 @@
+/* This file is generated and managed by dsync */
+
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        #[max_length = 64]
+        email -> Varchar,
+        nickname -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        user_id -> Int4,
+        text -> Text,
+        completed -> Bool,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
+diesel::allow_tables_to_appear_in_same_query!(todos, users);
@@ -0,0 +1,38 @@
+/* This file is generated and managed by dsync */
+
+//! Checks that the models of every table can query the migrated test database (see `support`), and that deleting
+//! with an empty filter deletes nothing.
+
+mod support;
+
+#[test]
+fn users_paginate() {
+    let mut db = support::connection();
+
+    todo_models::models::users::User::paginate(&mut db, 0, 10, None).unwrap();
+}
+
+#[test]
+fn users_delete_where_empty_filter() {
+    let mut db = support::connection();
+    let total_items = todo_models::models::users::User::paginate(&mut db, 0, 1, None).unwrap().total_items;
+
+    assert_eq!(todo_models::models::users::User::delete_where(&mut db, &todo_models::models::users::UserFilter::default()).unwrap(), 0);
+    assert_eq!(todo_models::models::users::User::paginate(&mut db, 0, 1, None).unwrap().total_items, total_items);
+}
+
+#[test]
+fn todos_paginate() {
+    let mut db = support::connection();
+
+    todo_models::models::todos::Todo::paginate(&mut db, 0, 10, None).unwrap();
+}
+
+#[test]
+fn todos_delete_where_empty_filter() {
+    let mut db = support::connection();
+    let total_items = todo_models::models::todos::Todo::paginate(&mut db, 0, 1, None).unwrap().total_items;
+
+    assert_eq!(todo_models::models::todos::Todo::delete_where(&mut db, &todo_models::models::todos::TodoFilter::default()).unwrap(), 0);
+    assert_eq!(todo_models::models::todos::Todo::paginate(&mut db, 0, 1, None).unwrap().total_items, total_items);
+}
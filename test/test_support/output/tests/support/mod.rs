@@ -0,0 +1,56 @@
+/* This file is generated and managed by dsync */
+
+//! Test support: starts a postgres testcontainer (once per test binary, so docker has to be running), applies the
+//! migrations in `migrations` and hands out connections whose changes are rolled back.
+
+use diesel::Connection as _;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use std::sync::OnceLock;
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::testcontainers::{runners::SyncRunner, Container};
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// the container (kept running until the test binary exits) and its database url
+static DATABASE: OnceLock<(Container<Postgres>, String)> = OnceLock::new();
+
+/// The url of the migrated database, starting the container on first use
+pub fn database_url() -> &'static str {
+    &DATABASE.get_or_init(|| {
+        let container = Postgres::default()
+            .start()
+            .expect("Could not start the postgres container");
+        let database_url = format!(
+            "postgres://postgres:postgres@{}:{}/postgres",
+            container.get_host().expect("Could not get the container's host"),
+            container
+                .get_host_port_ipv4(5432)
+                .expect("Could not get the container's port")
+        );
+
+        diesel::PgConnection::establish(&database_url)
+            .expect("Could not connect to the database")
+            .run_pending_migrations(MIGRATIONS)
+            .expect("Could not run the migrations");
+
+        (container, database_url)
+    })
+    .1
+}
+
+/// A connection to the migrated database inside a test transaction, so tests don't see each other's changes
+pub fn connection() -> Connection {
+    let database_url = database_url();
+    let mut db: Connection = diesel::r2d2::Pool::builder()
+        .max_size(1)
+        .build(diesel::r2d2::ConnectionManager::new(database_url))
+        .expect("Could not connect to the database")
+        .get()
+        .expect("Could not connect to the database");
+    db.begin_test_transaction()
+        .expect("Could not begin a test transaction");
+
+    db
+}
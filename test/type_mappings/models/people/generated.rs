@@ -0,0 +1,281 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `people` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `name` | `Text` | `String` |  |
+//! | `mood` | `Mood` | `Mood` |  |
+//! | `previous_mood` | `Nullable<Mood>` | `Option<Mood>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+use crate::types::Mood;
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=people, primary_key(id))]
+pub struct People {
+    pub id: i32,
+    pub name: String,
+    #[serde(with = "crate::types::mood_serde")]
+    pub mood: Mood,
+    #[serde(with = "crate::types::mood_serde")]
+    pub previous_mood: Option<Mood>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=people)]
+pub struct CreatePeople {
+    pub name: String,
+    #[serde(with = "crate::types::mood_serde")]
+    pub mood: Mood,
+    #[serde(with = "crate::types::mood_serde")]
+    pub previous_mood: Option<Mood>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=people)]
+pub struct UpdatePeople {
+    pub name: Option<String>,
+    #[serde(with = "crate::types::mood_serde")]
+    pub mood: Option<Mood>,
+    #[serde(with = "crate::types::mood_serde")]
+    pub previous_mood: Option<Option<Mood>>,
+}
+
+impl UpdatePeople {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.mood.is_none()
+            && self.previous_mood.is_none()
+    }
+}
+
+/// Conditions on `people` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct PeopleFilter {
+    pub id: Option<i32>,
+    pub name: Option<String>,
+    #[serde(with = "crate::types::mood_serde")]
+    pub mood: Option<Mood>,
+    #[serde(with = "crate::types::mood_serde")]
+    pub previous_mood: Option<Option<Mood>>,
+}
+
+pub type PeopleFilterExpression = Box<dyn BoxableExpression<crate::schema::people::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl PeopleFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.name.is_none() && self.mood.is_none() && self.previous_mood.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> PeopleFilterExpression {
+        use crate::schema::people;
+
+        let mut expression: PeopleFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(people::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.name {
+            expression = Box::new(expression.and(people::name.eq(value.clone())));
+        }
+        if let Some(value) = &self.mood {
+            expression = Box::new(expression.and(people::mood.eq(value.clone())));
+        }
+        match &self.previous_mood {
+            Some(Some(value)) => expression = Box::new(expression.and(people::previous_mood.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(people::previous_mood.is_null())),
+            None => {}
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `people` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeopleColumn {
+    Id,
+    Name,
+    Mood,
+    PreviousMood,
+}
+
+impl People {
+
+    pub fn create(db: &mut Connection, item: &CreatePeople) -> QueryResult<Self> {
+        use crate::schema::people;
+
+        insert_into(people::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::people;
+
+        people::table.filter(people::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`people::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(PeopleColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::people;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = people::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = people::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((PeopleColumn::Id, SortDirection::Asc)) => query.order(people::id.asc()),
+            Some((PeopleColumn::Id, SortDirection::Desc)) => query.order(people::id.desc()),
+            Some((PeopleColumn::Name, SortDirection::Asc)) => query.order((people::name.asc(), people::id.asc())),
+            Some((PeopleColumn::Name, SortDirection::Desc)) => query.order((people::name.desc(), people::id.asc())),
+            Some((PeopleColumn::Mood, SortDirection::Asc)) => query.order((people::mood.asc(), people::id.asc())),
+            Some((PeopleColumn::Mood, SortDirection::Desc)) => query.order((people::mood.desc(), people::id.asc())),
+            Some((PeopleColumn::PreviousMood, SortDirection::Asc)) => query.order((people::previous_mood.asc(), people::id.asc())),
+            Some((PeopleColumn::PreviousMood, SortDirection::Desc)) => query.order((people::previous_mood.desc(), people::id.asc())),
+            None => query.order(people::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::people;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = people::table.order(people::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::people;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = people::table.order(people::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(people::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdatePeople) -> QueryResult<Self> {
+        use crate::schema::people;
+
+        if item.is_empty() {
+            return people::table.filter(people::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(people::table.filter(people::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &PeopleFilter, changes: &UpdatePeople) -> QueryResult<Vec<Self>> {
+        use crate::schema::people;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return people::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(people::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::people;
+
+        diesel::delete(people::table.filter(people::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &PeopleFilter) -> QueryResult<usize> {
+        use crate::schema::people;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(people::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
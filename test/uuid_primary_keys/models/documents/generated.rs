@@ -0,0 +1,247 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `documents` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Uuid` | `uuid::Uuid` |  |
+//! | `title` | `Text` | `String` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=documents, primary_key(id))]
+pub struct Document {
+    pub id: uuid::Uuid,
+    pub title: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=documents)]
+pub struct CreateDocument {
+    pub title: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=documents)]
+pub struct UpdateDocument {
+    pub title: Option<String>,
+}
+
+impl UpdateDocument {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+    }
+}
+
+/// Conditions on `documents` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct DocumentFilter {
+    pub id: Option<uuid::Uuid>,
+    pub title: Option<String>,
+}
+
+pub type DocumentFilterExpression = Box<dyn BoxableExpression<crate::schema::documents::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl DocumentFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.title.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> DocumentFilterExpression {
+        use crate::schema::documents;
+
+        let mut expression: DocumentFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(documents::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.title {
+            expression = Box::new(expression.and(documents::title.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `documents` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentColumn {
+    Id,
+    Title,
+}
+
+impl Document {
+
+    pub fn create(db: &mut Connection, item: &CreateDocument) -> QueryResult<Self> {
+        use crate::schema::documents;
+
+        insert_into(documents::table).values((documents::id.eq(uuid::Uuid::now_v7()), item)).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: impl Into<uuid::Uuid>) -> QueryResult<Self> {
+        use crate::schema::documents;
+
+        documents::table.filter(documents::id.eq(Into::<uuid::Uuid>::into(param_id))).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`documents::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(DocumentColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::documents;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = documents::table.count().get_result(db)?;
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let query = documents::table.limit(page_size).offset(offset).into_boxed();
+        let query = match order {
+            Some((DocumentColumn::Id, SortDirection::Asc)) => query.order(documents::id.asc()),
+            Some((DocumentColumn::Id, SortDirection::Desc)) => query.order(documents::id.desc()),
+            Some((DocumentColumn::Title, SortDirection::Asc)) => query.order((documents::title.asc(), documents::id.asc())),
+            Some((DocumentColumn::Title, SortDirection::Desc)) => query.order((documents::title.desc(), documents::id.asc())),
+            None => query.order(documents::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::documents;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let offset = page.checked_mul(page_size).ok_or_else(|| diesel::result::Error::QueryBuilderError(format!("page {page} of {page_size} rows is out of range").into()))?;
+        let mut items = documents::table.order(documents::id.asc()).limit(page_size.saturating_add(1)).offset(offset).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::documents;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = documents::table.order(documents::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(documents::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: impl Into<uuid::Uuid>, item: &UpdateDocument) -> QueryResult<Self> {
+        use crate::schema::documents;
+
+        if item.is_empty() {
+            return documents::table.filter(documents::id.eq(Into::<uuid::Uuid>::into(param_id))).first::<Self>(db);
+        }
+
+        diesel::update(documents::table.filter(documents::id.eq(Into::<uuid::Uuid>::into(param_id)))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &DocumentFilter, changes: &UpdateDocument) -> QueryResult<Vec<Self>> {
+        use crate::schema::documents;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return documents::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(documents::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: impl Into<uuid::Uuid>) -> QueryResult<usize> {
+        use crate::schema::documents;
+
+        diesel::delete(documents::table.filter(documents::id.eq(Into::<uuid::Uuid>::into(param_id)))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &DocumentFilter) -> QueryResult<usize> {
+        use crate::schema::documents;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(documents::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+diesel::table! {
+    documents (id) {
+        id -> Uuid,
+        title -> Text,
+    }
+}
+
+diesel::table! {
+    tokens (id) {
+        id -> Uuid,
+    }
+}
@@ -0,0 +1,23 @@
+diesel::table! {
+    todos (id) {
+        id -> Int4,
+        user_id -> Int4,
+        text -> Text,
+    }
+}
+
+diesel::table! {
+    user_role (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    user_roles (user_id, role_id) {
+        user_id -> Int4,
+        role_id -> Int4,
+    }
+}
+
+diesel::joinable!(todos -> users (user_id));
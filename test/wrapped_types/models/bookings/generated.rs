@@ -0,0 +1,309 @@
+/* This file is generated and managed by dsync */
+
+//! Model for the `bookings` table.
+//!
+//! - primary key: `id`
+//!
+//! | column | sql type | rust type | description |
+//! |--------|----------|-----------|-------------|
+//! | `id` | `Int4` | `i32` |  |
+//! | `tags` | `Array<Text>` | `Vec<String>` |  |
+//! | `scores` | `Array<Nullable<Int4>>` | `Vec<Option<i32>>` |  |
+//! | `aliases` | `Nullable<Array<Text>>` | `Option<Vec<String>>` |  |
+//! | `seats` | `Range<Int4>` | `(std::collections::Bound<i32>, std::collections::Bound<i32>)` |  |
+//! | `stay` | `Nullable<Range<Timestamptz>>` | `Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>` |  |
+//! | `blocked` | `Multirange<Int8>` | `Vec<(std::collections::Bound<i64>, std::collections::Bound<i64>)>` |  |
+
+use crate::diesel::*;
+use crate::schema::*;
+use diesel::QueryResult;
+use serde::{Deserialize, Serialize};
+
+
+type Connection = diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=bookings, primary_key(id))]
+pub struct Booking {
+    pub id: i32,
+    pub tags: Vec<String>,
+    pub scores: Vec<Option<i32>>,
+    pub aliases: Option<Vec<String>>,
+    pub seats: (std::collections::Bound<i32>, std::collections::Bound<i32>),
+    pub stay: Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>,
+    pub blocked: Vec<(std::collections::Bound<i64>, std::collections::Bound<i64>)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name=bookings)]
+pub struct CreateBooking {
+    pub tags: Vec<String>,
+    pub scores: Vec<Option<i32>>,
+    pub aliases: Option<Vec<String>>,
+    pub seats: (std::collections::Bound<i32>, std::collections::Bound<i32>),
+    pub stay: Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>,
+    pub blocked: Vec<(std::collections::Bound<i64>, std::collections::Bound<i64>)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset)]
+#[diesel(table_name=bookings)]
+pub struct UpdateBooking {
+    pub tags: Option<Vec<String>>,
+    pub scores: Option<Vec<Option<i32>>>,
+    pub aliases: Option<Option<Vec<String>>>,
+    pub seats: Option<(std::collections::Bound<i32>, std::collections::Bound<i32>)>,
+    pub stay: Option<Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>>,
+    pub blocked: Option<Vec<(std::collections::Bound<i64>, std::collections::Bound<i64>)>>,
+}
+
+impl UpdateBooking {
+    /// whether the update leaves every column unchanged (all of its fields are `None`)
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_none()
+            && self.scores.is_none()
+            && self.aliases.is_none()
+            && self.seats.is_none()
+            && self.stay.is_none()
+            && self.blocked.is_none()
+    }
+}
+
+/// Conditions on `bookings` rows; fields which are `None` match every row
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct BookingFilter {
+    pub id: Option<i32>,
+    pub tags: Option<Vec<String>>,
+    pub scores: Option<Vec<Option<i32>>>,
+    pub aliases: Option<Option<Vec<String>>>,
+    pub seats: Option<(std::collections::Bound<i32>, std::collections::Bound<i32>)>,
+    pub stay: Option<Option<(std::collections::Bound<chrono::DateTime<chrono::Utc>>, std::collections::Bound<chrono::DateTime<chrono::Utc>>)>>,
+    pub blocked: Option<Vec<(std::collections::Bound<i64>, std::collections::Bound<i64>)>>,
+}
+
+pub type BookingFilterExpression = Box<dyn BoxableExpression<crate::schema::bookings::table, <Connection as diesel::Connection>::Backend, SqlType = diesel::sql_types::Bool>>;
+
+impl BookingFilter {
+    /// whether the filter has no conditions (and matches every row)
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.tags.is_none() && self.scores.is_none() && self.aliases.is_none() && self.seats.is_none() && self.stay.is_none() && self.blocked.is_none()
+    }
+
+    /// the filter's conditions combined with `AND`
+    pub fn expression(&self) -> BookingFilterExpression {
+        use crate::schema::bookings;
+
+        let mut expression: BookingFilterExpression = Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"));
+        if let Some(value) = &self.id {
+            expression = Box::new(expression.and(bookings::id.eq(value.clone())));
+        }
+        if let Some(value) = &self.tags {
+            expression = Box::new(expression.and(bookings::tags.eq(value.clone())));
+        }
+        if let Some(value) = &self.scores {
+            expression = Box::new(expression.and(bookings::scores.eq(value.clone())));
+        }
+        match &self.aliases {
+            Some(Some(value)) => expression = Box::new(expression.and(bookings::aliases.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(bookings::aliases.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.seats {
+            expression = Box::new(expression.and(bookings::seats.eq(value.clone())));
+        }
+        match &self.stay {
+            Some(Some(value)) => expression = Box::new(expression.and(bookings::stay.eq(value.clone()).assume_not_null())),
+            Some(None) => expression = Box::new(expression.and(bookings::stay.is_null())),
+            None => {}
+        }
+        if let Some(value) = &self.blocked {
+            expression = Box::new(expression.and(bookings::blocked.eq(value.clone())));
+        }
+
+        expression
+    }
+}
+
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResult<T> {
+    pub items: Vec<T>,
+    pub total_items: i64,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    pub num_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FastPaginationResult<T> {
+    pub items: Vec<T>,
+    /// 0-based index
+    pub page: i64,
+    pub page_size: i64,
+    /// whether there is at least one more page after this one
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Columns of `bookings` which `paginate` can order by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookingColumn {
+    Id,
+    Tags,
+    Scores,
+    Aliases,
+    Seats,
+    Stay,
+    Blocked,
+}
+
+impl Booking {
+
+    pub fn create(db: &mut Connection, item: &CreateBooking) -> QueryResult<Self> {
+        use crate::schema::bookings;
+
+        insert_into(bookings::table).values(item).get_result::<Self>(db)
+    }
+
+    pub fn read(db: &mut Connection, param_id: i32) -> QueryResult<Self> {
+        use crate::schema::bookings;
+
+        bookings::table.filter(bookings::id.eq(param_id)).first::<Self>(db)
+    }
+
+    /// Paginates through the table where page is a 0-based index (i.e. page 0 is the first page), ordered by `order`
+    /// (`bookings::id.asc()` if no ordering is given)
+    pub fn paginate(db: &mut Connection, page: i64, page_size: i64, order: Option<(BookingColumn, SortDirection)>) -> QueryResult<PaginationResult<Self>> {
+        use crate::schema::bookings;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let total_items = bookings::table.count().get_result(db)?;
+        let query = bookings::table.limit(page_size).offset(page * page_size).into_boxed();
+        let query = match order {
+            Some((BookingColumn::Id, SortDirection::Asc)) => query.order(bookings::id.asc()),
+            Some((BookingColumn::Id, SortDirection::Desc)) => query.order(bookings::id.desc()),
+            Some((BookingColumn::Tags, SortDirection::Asc)) => query.order((bookings::tags.asc(), bookings::id.asc())),
+            Some((BookingColumn::Tags, SortDirection::Desc)) => query.order((bookings::tags.desc(), bookings::id.asc())),
+            Some((BookingColumn::Scores, SortDirection::Asc)) => query.order((bookings::scores.asc(), bookings::id.asc())),
+            Some((BookingColumn::Scores, SortDirection::Desc)) => query.order((bookings::scores.desc(), bookings::id.asc())),
+            Some((BookingColumn::Aliases, SortDirection::Asc)) => query.order((bookings::aliases.asc(), bookings::id.asc())),
+            Some((BookingColumn::Aliases, SortDirection::Desc)) => query.order((bookings::aliases.desc(), bookings::id.asc())),
+            Some((BookingColumn::Seats, SortDirection::Asc)) => query.order((bookings::seats.asc(), bookings::id.asc())),
+            Some((BookingColumn::Seats, SortDirection::Desc)) => query.order((bookings::seats.desc(), bookings::id.asc())),
+            Some((BookingColumn::Stay, SortDirection::Asc)) => query.order((bookings::stay.asc(), bookings::id.asc())),
+            Some((BookingColumn::Stay, SortDirection::Desc)) => query.order((bookings::stay.desc(), bookings::id.asc())),
+            Some((BookingColumn::Blocked, SortDirection::Asc)) => query.order((bookings::blocked.asc(), bookings::id.asc())),
+            Some((BookingColumn::Blocked, SortDirection::Desc)) => query.order((bookings::blocked.desc(), bookings::id.asc())),
+            None => query.order(bookings::id.asc()),
+        };
+        let items = query.load::<Self>(db)?;
+
+        Ok(PaginationResult {
+            items,
+            total_items,
+            page,
+            page_size,
+            /* ceiling division of integers */
+            num_pages: total_items / page_size + i64::from(total_items % page_size != 0)
+        })
+    }
+
+    /// Like `paginate` but without counting the table's rows (which is slow on big tables); one extra row is fetched
+    /// to determine whether there's a next page. Page is a 0-based index (i.e. page 0 is the first page), ordered by
+    /// primary key
+    pub fn paginate_fast(db: &mut Connection, page: i64, page_size: i64) -> QueryResult<FastPaginationResult<Self>> {
+        use crate::schema::bookings;
+
+        let page_size = if page_size < 1 { 1 } else { page_size };
+        let mut items = bookings::table.order(bookings::id.asc()).limit(page_size + 1).offset(page * page_size).load::<Self>(db)?;
+        let has_next = items.len() as i64 > page_size;
+        items.truncate(page_size as usize);
+
+        Ok(FastPaginationResult {
+            items,
+            page,
+            page_size,
+            has_next,
+        })
+    }
+
+    /// Calls `f` with consecutive chunks of (at most) `chunk_size` rows ordered by primary key, so batch jobs
+    /// don't have to load the whole table into memory
+    pub fn for_each_chunk<F>(db: &mut Connection, chunk_size: i64, mut f: F) -> QueryResult<()>
+    where
+        F: FnMut(Vec<Self>) -> QueryResult<()>,
+    {
+        use crate::schema::bookings;
+
+        let chunk_size = if chunk_size < 1 { 1 } else { chunk_size };
+        let mut last: Option<Self> = None;
+        loop {
+            let mut query = bookings::table.order(bookings::id.asc()).limit(chunk_size).into_boxed();
+            if let Some(last) = &last {
+                query = query.filter(bookings::id.gt(&last.id));
+            }
+            let items = query.load::<Self>(db)?;
+            let is_last_chunk = (items.len() as i64) < chunk_size;
+            last = items.last().cloned();
+
+            if !items.is_empty() {
+                f(items)?;
+            }
+            if is_last_chunk {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies `item` to the row, returning the updated row (or the row as it is, if `item` is empty)
+    pub fn update(db: &mut Connection, param_id: i32, item: &UpdateBooking) -> QueryResult<Self> {
+        use crate::schema::bookings;
+
+        if item.is_empty() {
+            return bookings::table.filter(bookings::id.eq(param_id)).first::<Self>(db);
+        }
+
+        diesel::update(bookings::table.filter(bookings::id.eq(param_id))).set(item).get_result(db)
+    }
+
+    /// Applies `changes` to every row matching `filter` in a single statement, returning the updated rows (or the
+    /// matching rows as they are, if `changes` is empty); an empty `filter` updates no rows
+    pub fn update_where(db: &mut Connection, filter: &BookingFilter, changes: &UpdateBooking) -> QueryResult<Vec<Self>> {
+        use crate::schema::bookings;
+
+        if filter.is_empty() {
+            return Ok(vec![]);
+        }
+        if changes.is_empty() {
+            return bookings::table.filter(filter.expression()).load::<Self>(db);
+        }
+
+        diesel::update(bookings::table.filter(filter.expression())).set(changes).get_results(db)
+    }
+
+    pub fn delete(db: &mut Connection, param_id: i32) -> QueryResult<usize> {
+        use crate::schema::bookings;
+
+        diesel::delete(bookings::table.filter(bookings::id.eq(param_id))).execute(db)
+    }
+
+    /// Deletes every row matching `filter` in a single statement, returning the number of deleted rows; an empty
+    /// `filter` deletes no rows
+    pub fn delete_where(db: &mut Connection, filter: &BookingFilter) -> QueryResult<usize> {
+        use crate::schema::bookings;
+
+        if filter.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::delete(bookings::table.filter(filter.expression())).execute(db)
+    }
+
+}
\ No newline at end of file
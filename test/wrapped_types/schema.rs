@@ -0,0 +1,11 @@
+diesel::table! {
+    bookings (id) {
+        id -> Int4,
+        tags -> Array<Text>,
+        scores -> Array<Nullable<Int4>>,
+        aliases -> Nullable<Array<Text>>,
+        seats -> Range<Int4>,
+        stay -> Nullable<Range<Timestamptz>>,
+        blocked -> Multirange<Int8>,
+    }
+}
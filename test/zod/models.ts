@@ -0,0 +1,20 @@
+// This file is generated and managed by dsync
+
+import { z } from "zod";
+
+export const CreateTodoSchema = z.object({
+  title: z.string().max(120),
+  notes: z.string().nullable(),
+  priority: z.number().int(),
+  done: z.boolean(),
+  due_at: z.string().datetime({ offset: true }).nullable(),
+});
+
+export const UpdateTodoSchema = z.object({
+  title: z.string().max(120).optional(),
+  notes: z.string().nullable().optional(),
+  priority: z.number().int().optional(),
+  done: z.boolean().optional(),
+  due_at: z.string().datetime({ offset: true }).nullable().optional(),
+  created_at: z.string().datetime({ offset: true }).optional(),
+});
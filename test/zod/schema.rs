@@ -0,0 +1,12 @@
+diesel::table! {
+    todos (id) {
+        id -> Uuid,
+        #[max_length = 120]
+        title -> Varchar,
+        notes -> Nullable<Text>,
+        priority -> Int4,
+        done -> Bool,
+        due_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}